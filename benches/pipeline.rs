@@ -0,0 +1,228 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Benchmarks of the message pipeline.
+//!
+//! Run them with:
+//!
+//! ``` Shell
+//! cargo bench --features bench
+//! ```
+//!
+//! The benchmarks requiring widgets are skipped when GTK+ cannot be initialized, e.g. when there
+//! is no display, so that the core benchmarks can run headless on a CI server.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use glib::MainContext;
+use gtk::{ContainerExt, Label, LabelExt, WidgetExt};
+use relm::{ContainerWidget, DisplayVariant, EventStream, Relm, Update, UpdateNew, Widget};
+
+const BURST_SIZES: &[usize] = &[1, 100, 10_000];
+
+#[derive(Clone)]
+enum Msg {
+    Increment,
+}
+
+impl DisplayVariant for Msg {
+    fn display_variant(&self) -> &'static str {
+        "Increment"
+    }
+}
+
+/// Non-widget component, to measure the cost of the update() dispatch.
+struct Counter {
+    count: Rc<Cell<usize>>,
+}
+
+impl Update for Counter {
+    type Model = Rc<Cell<usize>>;
+    type ModelParam = Rc<Cell<usize>>;
+    type Msg = Msg;
+
+    fn model(_: &Relm<Self>, count: Rc<Cell<usize>>) -> Rc<Cell<usize>> {
+        count
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Msg::Increment => self.count.set(self.count.get() + 1),
+        }
+    }
+}
+
+impl UpdateNew for Counter {
+    fn new(_: &Relm<Self>, count: Rc<Cell<usize>>) -> Self {
+        Counter {
+            count,
+        }
+    }
+}
+
+/// Widget component whose update() refreshes a label, like the code generated for a binding.
+struct CounterLabel {
+    count: usize,
+    label: Label,
+}
+
+impl Update for CounterLabel {
+    type Model = ();
+    type ModelParam = ();
+    type Msg = Msg;
+
+    fn model(_: &Relm<Self>, _: ()) {
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Msg::Increment => {
+                self.count += 1;
+                self.label.set_text(&self.count.to_string());
+            },
+        }
+    }
+}
+
+impl Widget for CounterLabel {
+    type Root = Label;
+
+    fn root(&self) -> Label {
+        self.label.clone()
+    }
+
+    fn view(_: &Relm<Self>, _: ()) -> Self {
+        let label = Label::new(Some("0"));
+        label.show();
+        CounterLabel {
+            count: 0,
+            label,
+        }
+    }
+}
+
+fn wait_until(count: &Cell<usize>, expected: usize) {
+    let context = MainContext::default();
+    while count.get() < expected {
+        let _ = context.iteration(false);
+    }
+}
+
+fn emit_throughput(c: &mut Criterion) {
+    let mut group = c.benchmark_group("emit");
+    for &size in BURST_SIZES {
+        let _ = group.throughput(Throughput::Elements(size as u64));
+        let _ = group.bench_with_input(BenchmarkId::new("callback", size), &size, |b, &size| {
+            let stream = EventStream::new();
+            let count = Rc::new(Cell::new(0));
+            {
+                let count = count.clone();
+                stream.set_callback(move |_: Msg| count.set(count.get() + 1));
+            }
+            b.iter(|| {
+                count.set(0);
+                for _ in 0..size {
+                    stream.emit(Msg::Increment);
+                }
+                wait_until(&count, size);
+            });
+        });
+        let _ = group.bench_with_input(BenchmarkId::new("observers", size), &size, |b, &size| {
+            let stream = EventStream::new();
+            let count = Rc::new(Cell::new(0));
+            for _ in 0..10 {
+                stream.observe(|_: &Msg| ());
+            }
+            {
+                let count = count.clone();
+                stream.set_callback(move |_: Msg| count.set(count.get() + 1));
+            }
+            b.iter(|| {
+                count.set(0);
+                for _ in 0..size {
+                    stream.emit(Msg::Increment);
+                }
+                wait_until(&count, size);
+            });
+        });
+        let _ = group.bench_with_input(BenchmarkId::new("update", size), &size, |b, &size| {
+            let count = Rc::new(Cell::new(0));
+            let stream = relm::execute::<Counter>(count.clone());
+            b.iter(|| {
+                count.set(0);
+                for _ in 0..size {
+                    stream.emit(Msg::Increment);
+                }
+                wait_until(&count, size);
+            });
+        });
+    }
+    group.finish();
+}
+
+fn component_spawn(c: &mut Criterion) {
+    let container = gtk::Box::new(gtk::Orientation::Vertical, 0);
+    let mut group = c.benchmark_group("component");
+    let _ = group.bench_function("spawn_teardown", |b| {
+        b.iter(|| {
+            let component = container.add_widget::<CounterLabel>(());
+            container.remove_widget(component);
+        });
+    });
+    group.finish();
+}
+
+fn view_refresh(c: &mut Criterion) {
+    let window = gtk::Window::new(gtk::WindowType::Toplevel);
+    let component = window.add_widget::<CounterLabel>(());
+    window.show_all();
+    let label = component.widget().clone();
+    let mut group = c.benchmark_group("view");
+    let _ = group.throughput(Throughput::Elements(100));
+    let _ = group.bench_function("binding_refresh", |b| {
+        b.iter(|| {
+            let expected = label.get_text().parse::<usize>().expect("number") + 100;
+            for _ in 0..100 {
+                component.emit(Msg::Increment);
+            }
+            let context = MainContext::default();
+            while label.get_text().parse::<usize>().expect("number") < expected {
+                let _ = context.iteration(false);
+            }
+        });
+    });
+    group.finish();
+    window.remove(&label);
+}
+
+fn widget_benches(c: &mut Criterion) {
+    if gtk::init().is_err() {
+        eprintln!("Cannot initialize GTK+: skipping the widget benchmarks");
+        return;
+    }
+    component_spawn(c);
+    view_refresh(c);
+}
+
+criterion_group!(benches, emit_throughput, widget_benches);
+criterion_main!(benches);
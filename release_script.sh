@@ -13,3 +13,7 @@ git push
 cd ./relm-test
 cargo release --no-dev-version
 cd ..
+
+cd ./relm-plot
+cargo release --no-dev-version
+cd ..
@@ -22,6 +22,9 @@
 //! The adder adds the calls to set_property() or emit(Msg) whenever we assign to an attribute of
 //! the model.
 
+use std::collections::HashSet;
+
+use proc_macro2::TokenStream;
 use quote::{quote, quote_spanned, TokenStreamExt};
 use syn::{
     Expr,
@@ -40,15 +43,19 @@ use syn::fold::{Fold, fold_expr};
 use syn::Member::Named;
 
 use super::{MsgModelMap, PropertyModelMap};
+use super::walker::uses_model_method;
 
 pub struct Adder<'a> {
+    // Whether the properties of the gtk widgets are only set in after_update().
+    defer_properties: bool,
     msg_map: &'a MsgModelMap,
     property_map: &'a PropertyModelMap,
 }
 
 impl<'a> Adder<'a> {
-    pub fn new(property_map: &'a PropertyModelMap, msg_map: &'a MsgModelMap) -> Self {
+    pub fn new(property_map: &'a PropertyModelMap, msg_map: &'a MsgModelMap, defer_properties: bool) -> Self {
         Adder {
+            defer_properties,
             msg_map,
             property_map,
         }
@@ -61,7 +68,7 @@ impl<'a> Adder<'a> {
         let new_statements =
             if let Field(ExprField { ref base, member: Named(ref ident), .. }) = lhs {
                 if is_model_path(base) {
-                    Some(create_stmts(ident, self.property_map, self.msg_map))
+                    Some(create_stmts(ident, self.property_map, self.msg_map, self.defer_properties))
                 }
                 else {
                     None
@@ -107,13 +114,16 @@ pub struct Message {
 pub struct Property {
     pub expr: Expr,
     pub is_relm_widget: bool,
+    pub is_text_comparable: bool,
     pub name: Ident,
     pub widget_name: Ident,
 }
 
-fn create_stmts(ident: &Ident, property_map: &PropertyModelMap, msg_map: &MsgModelMap) -> Vec<Stmt> {
+fn create_stmts(ident: &Ident, property_map: &PropertyModelMap, msg_map: &MsgModelMap, defer_properties: bool)
+    -> Vec<Stmt>
+{
     let mut stmts = vec![];
-    stmts.append(&mut create_stmts_for_props(ident, property_map));
+    stmts.append(&mut create_stmts_for_props(ident, property_map, defer_properties));
     stmts.append(&mut create_stmts_for_msgs(ident, msg_map));
     stmts
 }
@@ -139,17 +149,25 @@ fn create_stmts_for_msgs(ident: &Ident, msg_map: &MsgModelMap) -> Vec<Stmt> {
     stmts
 }
 
-fn create_stmts_for_props(ident: &Ident, property_map: &PropertyModelMap) -> Vec<Stmt> {
+fn create_stmts_for_props(ident: &Ident, property_map: &PropertyModelMap, defer_properties: bool) -> Vec<Stmt> {
     let mut stmts = vec![];
     if let Some(properties) = property_map.get(ident) {
         for property in properties {
+            if defer_properties && !property.is_relm_widget {
+                continue;
+            }
             let widget_name = &property.widget_name;
             let prop_name = Ident::new(&format!("set_{}", property.name), property.name.span());
             let mut tokens = quote! {};
             tokens.append_all(&[&property.expr]);
             let stmt =
-                quote_spanned! { ident.span() =>
-                    { self.widgets.#widget_name.#prop_name(#tokens); }
+                if property.is_relm_widget {
+                    quote_spanned! { ident.span() =>
+                        { self.widgets.#widget_name.#prop_name(#tokens); }
+                    }
+                }
+                else {
+                    gen_checked_set(ident, property, &prop_name, tokens)
                 };
             let expr: Expr = parse(stmt.into()).expect("parse() in create_stmts");
             if let Block(ExprBlock { ref block, .. }) = expr {
@@ -160,6 +178,73 @@ fn create_stmts_for_props(ident: &Ident, property_map: &PropertyModelMap) -> Vec
     stmts
 }
 
+/*
+ * Re-evaluate the visible and sensitive properties bound to the model, since they can depend on the
+ * model in ways not caught by the assignments, like a method call, as well as the properties calling
+ * a method on the model, like self.model.validate().
+ * When the properties are deferred, all the properties of the gtk widgets are re-evaluated, since
+ * they are not set in update().
+ */
+pub fn create_refresh_stmts(property_map: &PropertyModelMap, defer_properties: bool) -> Vec<Stmt> {
+    let mut properties: Vec<_> = property_map.values()
+        .flatten()
+        .filter(|property| property.name == "visible" || property.name == "sensitive" ||
+            (!property.is_relm_widget && (defer_properties || uses_model_method(&property.expr))))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    properties.sort_by_key(|property| (property.widget_name.to_string(), property.name.to_string()));
+    let mut stmts = vec![];
+    for property in properties {
+        let prop_name = Ident::new(&format!("set_{}", property.name), property.name.span());
+        let mut tokens = quote! {};
+        tokens.append_all(&[&property.expr]);
+        let stmt = gen_checked_set(&property.name, property, &prop_name, tokens);
+        let expr: Expr = parse(stmt.into()).expect("parse() in create_refresh_stmts");
+        if let Block(ExprBlock { ref block, .. }) = expr {
+            stmts.push(block.stmts[0].clone());
+        }
+    }
+    stmts
+}
+
+/*
+ * Skip the call to the setter when the widget already has this value, to avoid spurious relayouts
+ * and changed signals feeding back into the update() method.
+ * This is only done for the properties whose getter is known to return a comparable value.
+ * For the text, this depends on the widget: some of them (like gtk::ProgressBar) take and return
+ * an Option, so the setter is called unconditionally for those.
+ */
+fn gen_checked_set(ident: &Ident, property: &Property, setter: &Ident, value: TokenStream) -> TokenStream {
+    let widget_name = &property.widget_name;
+    let name = &property.name;
+    let getter = Ident::new(&format!("get_{}", name), name.span());
+    match name.to_string().as_ref() {
+        "text" if property.is_text_comparable =>
+            quote_spanned! { ident.span() =>
+                {{
+                    let value = #value;
+                    if !::relm::same_text(&self.widgets.#widget_name.#getter(), &value) {
+                        self.widgets.#widget_name.#setter(value);
+                    }
+                }}
+            },
+        "active" | "sensitive" | "visible" =>
+            quote_spanned! { ident.span() =>
+                {{
+                    let value = #value;
+                    if self.widgets.#widget_name.#getter() != value {
+                        self.widgets.#widget_name.#setter(value);
+                    }
+                }}
+            },
+        _ =>
+            quote_spanned! { ident.span() =>
+                { self.widgets.#widget_name.#setter(#value); }
+            },
+    }
+}
+
 fn is_model_path(expr: &Expr) -> bool {
     if let Field(ExprField { ref base, ref member, .. }) = *expr {
         if let Expr::Path(ExprPath { path: Path { ref segments, .. }, ..}) = **base {
@@ -45,7 +45,7 @@ use super::parser::EventValue::{CurrentWidget, ForeignWidget, NoEventValue};
 use super::parser::EventValueReturn::{CallReturn, Return, WithoutReturn};
 use super::parser::EitherWidget::{Gtk, Relm};
 use super::transformer::Transformer;
-use super::{Driver, MODEL_IDENT};
+use super::{Driver, MODEL_IDENT, gen_phantom_init};
 
 use self::WidgetType::*;
 use self::WithParentheses::{WithParens, WithoutParens};
@@ -83,7 +83,15 @@ pub fn gen(name: &Ident, widgets: &[Widget], driver: &mut Driver) -> (TokenStrea
     let component_widgets: Vec<_> = generator.widget_names.iter()
         .filter(|ident| generator.relm_components.contains_key(ident) && ident != root_widget_name)
         .collect();
-    let component_widgets2 = &component_widgets;
+    // The lazy components are represented by their placeholder until their view is created.
+    let component_widget_values = component_widgets.iter()
+        .map(|name|
+            if generator.lazy_components.contains(*name) {
+                quote! { #name.placeholder().clone() }
+            }
+            else {
+                quote! { #name.widget().clone() }
+            });
 
     let widget_names: Vec<_> = generator.widget_names.iter()
         .filter(|ident| (idents.contains(ident) || generator.relm_widgets.contains_key(ident)) && !component_widgets.contains(ident) && ident != root_widget_name)
@@ -91,6 +99,8 @@ pub fn gen(name: &Ident, widgets: &[Widget], driver: &mut Driver) -> (TokenStrea
 
     let events = &generator.events;
     let properties = &generator.properties;
+    // The size groups can be declared after their members, so add the members once every widget is created.
+    let size_group_members = &generator.size_group_members;
     let model_ident = Ident::new(MODEL_IDENT, Span::call_site());
     let components_name = Ident::new(&format!("__{}Components", name), Span::call_site());
     let widgets_name = Ident::new(&format!("__{}Widgets", name), Span::call_site());
@@ -112,8 +122,11 @@ pub fn gen(name: &Ident, widgets: &[Widget], driver: &mut Driver) -> (TokenStrea
             quote! {}
         };
 
+    let phantom = gen_phantom_init(driver.generic_types.as_ref().expect("generic types"));
+
     let code = quote_spanned! { name.span() =>
         #widget_tokens
+        #(#size_group_members)*
 
         #(#events)*
         #(#properties)*
@@ -121,14 +134,17 @@ pub fn gen(name: &Ident, widgets: &[Widget], driver: &mut Driver) -> (TokenStrea
         #name {
             streams: #streams_name {
                 #(#stream_names: #component_streams,)*
+                #phantom
             },
             widgets: #widgets_name {
                 #root_widget_name #root_widget_expr,
                 #(#widget_names,)*
-                #(#component_widgets: #component_widgets2.widget().clone(),)*
+                #(#component_widgets: #component_widget_values,)*
+                #phantom
             },
             components: #components_name {
                 #(#component_names,)*
+                #phantom
             },
             model: #model_ident,
         }
@@ -141,9 +157,13 @@ struct Generator<'a> {
     container_names: HashMap<Option<String>, (Ident, Path)>,
     driver: Option<&'a mut Driver>,
     events: Vec<TokenStream>,
+    lazy_components: HashSet<Ident>,
+    // Nested views are created without a parent, but are never the root widget.
+    nested_view_depth: usize,
     properties: Vec<TokenStream>,
     relm_components: HashMap<Ident, Path>,
     relm_widgets: HashMap<Ident, Path>,
+    size_group_members: Vec<TokenStream>,
     streams_to_save: HashSet<Ident>,
     widget_names: Vec<Ident>,
 }
@@ -154,9 +174,12 @@ impl<'a> Generator<'a> {
             container_names: HashMap::new(),
             driver: Some(driver),
             events: vec![],
+            lazy_components: HashSet::new(),
+            nested_view_depth: 0,
             properties: vec![],
             relm_components: HashMap::new(),
             relm_widgets: HashMap::new(),
+            size_group_members: vec![],
             streams_to_save: HashSet::new(),
             widget_names: vec![],
         }
@@ -168,8 +191,18 @@ impl<'a> Generator<'a> {
         let widget_name = &widget.name;
         if let Some(name) = parent {
             if parent_widget_type == IsGtk {
-                quote_spanned! { widget_name.span() =>
-                    ::gtk::ContainerExt::add(&#name, &#widget_name);
+                if widget.is_overlay {
+                    quote_spanned! { widget_name.span() =>
+                        ::gtk::OverlayExt::add_overlay(&#name, &#widget_name);
+                    }
+                }
+                else {
+                    quote_spanned! { widget_name.span() =>
+                        {
+                            use ::relm::{GtkContainerDispatch as _, RelmContainerDispatch as _};
+                            (&::relm::ChildDispatch(&#name)).relm_add_child(::relm::Cast::upcast_ref::<::gtk::Widget>(&#widget_name));
+                        }
+                    }
                 }
             }
             else {
@@ -180,8 +213,9 @@ impl<'a> Generator<'a> {
         }
         else {
             let struct_name = &widget.typ;
+            let nested = self.nested_view_depth > 0;
             let driver = self.driver.as_mut().expect("driver");
-            if driver.root_widget_expr.is_none() {
+            if driver.root_widget_expr.is_none() && !nested {
                 driver.root_widget_type = Some(quote! {
                     #struct_name
                 });
@@ -196,11 +230,27 @@ impl<'a> Generator<'a> {
     }
 
     fn add_or_create_widget(&mut self, parent: Option<&Ident>, parent_widget_type: WidgetType, widget_name: &Ident,
-        widget_type_ident: &Path, init_parameters: &[Expr], is_container: bool) -> TokenStream
+        widget_type_ident: &Path, init_parameters: &[Expr], is_container: bool, is_overlay: bool) -> TokenStream
     {
         let init_parameters = gen_model_param(init_parameters, WithParens);
         if let Some(parent) = parent {
-            if parent_widget_type == IsGtk {
+            if parent_widget_type == IsGtk && is_overlay {
+                let create_method =
+                    if is_container {
+                        quote! { create_container }
+                    }
+                    else {
+                        quote! { create_component }
+                    };
+                quote_spanned! { widget_name.span() =>
+                    let #widget_name = {
+                        let component = ::relm::#create_method::<#widget_type_ident>(#init_parameters);
+                        ::gtk::OverlayExt::add_overlay(&#parent, component.widget());
+                        component
+                    };
+                }
+            }
+            else if parent_widget_type == IsGtk {
                 let add_method =
                     if is_container {
                         quote! { add_container }
@@ -223,8 +273,9 @@ impl<'a> Generator<'a> {
             }
         }
         else {
+            let nested = self.nested_view_depth > 0;
             let driver = self.driver.as_mut().expect("driver");
-            if driver.root_widget_expr.is_none() {
+            if driver.root_widget_expr.is_none() && !nested {
                 driver.root_widget_type = Some(quote_spanned! { widget_name.span() =>
                     <#widget_type_ident as ::relm::Widget>::Root
                 });
@@ -247,6 +298,69 @@ impl<'a> Generator<'a> {
         }
     }
 
+    fn add_to_size_group(&mut self, widget: &Widget, gtk_widget: TokenStream) {
+        if let Some(ref size_group) = widget.size_group {
+            self.size_group_members.push(quote_spanned! { size_group.span() =>
+                ::gtk::SizeGroupExt::add_widget(&#size_group, #gtk_widget);
+            });
+        }
+    }
+
+    /// Create the widgets declared with `view!` as child property values, like a notebook tab label,
+    /// and set them with the parent's child property setter.
+    fn child_nested_views(&mut self, widget: &Widget, parent: Option<&Ident>, parent_widget_type: WidgetType,
+        widget_type: WidgetType) -> Vec<TokenStream>
+    {
+        let widget_name = &widget.name;
+        let mut calls = vec![];
+        if let Some(parent) = parent {
+            let parent =
+                if parent_widget_type == IsGtk {
+                    quote! {
+                        #parent
+                    }
+                }
+                else {
+                    quote! {
+                        #parent.container
+                    }
+                };
+            let child =
+                if widget_type == IsGtk {
+                    quote! {
+                        &#widget_name
+                    }
+                }
+                else if widget.is_lazy {
+                    quote! {
+                        #widget_name.placeholder()
+                    }
+                }
+                else {
+                    quote! {
+                        #widget_name.widget()
+                    }
+                };
+            for (&(ref ident, ref key), value) in &widget.child_nested_views {
+                let name = &value.name;
+                let name =
+                    match value.widget {
+                        Gtk(_) => quote! { #name },
+                        Relm(_) => quote! {
+                            #name.widget().clone()
+                        },
+                    };
+                let nested_widget = self.nested_view(value);
+                let property_func = Ident::new(&format!("set_{}_{}", ident, key), key.span());
+                calls.push(quote! {
+                    #nested_widget
+                    #parent.#property_func(#child, ::std::convert::Into::into(&#name));
+                });
+            }
+        }
+        calls
+    }
+
     fn collect_event(&mut self, widget_name: TokenStream, name: &Ident, event: &Event) {
         let event_ident = Ident::new(&format!("connect_{}", name), name.span());
         let event_params = &event.params;
@@ -266,7 +380,12 @@ impl<'a> Generator<'a> {
                     #shared_values
                     relm::connect!(relm, #widget_name, #event_ident(#(#event_params),*), return (#event_value, #return_value));
                 }},
-                ForeignWidget(_, Return(_, _)) | ForeignWidget(_, CallReturn(_)) => unreachable!(),
+                ForeignWidget(ref foreign_widget_name, Return(ref event_value, ref return_value)) => quote! {{
+                    #shared_values
+                    relm::connect!(#widget_name, #event_ident(#(#event_params),*), #foreign_widget_name,
+                        return (#event_value, #return_value));
+                }},
+                ForeignWidget(_, CallReturn(_)) => unreachable!(),
                 CurrentWidget(CallReturn(ref func)) => quote_spanned! { widget_name.span() => {
                     #shared_values
                     relm::connect!(relm, #widget_name, #event_ident(#(#event_params),*), #metadata #func);
@@ -334,6 +453,15 @@ impl<'a> Generator<'a> {
             let mut remover = Transformer::new(MODEL_IDENT);
             let new_value = remover.fold_expr(value.clone());
             let property_func = Ident::new(&format!("set_{}", key), key.span());
+            #[cfg(feature = "i18n")]
+            {
+                if let Some(msgid) = translatable_literal(&new_value) {
+                    properties.push(quote! {
+                        ::relm::i18n::bind(&#ident, #msgid, |widget, text| widget.#property_func(text));
+                    });
+                    continue;
+                }
+            }
             let property = quote! {
                 #ident.#property_func(#new_value);
             };
@@ -353,7 +481,7 @@ impl<'a> Generator<'a> {
                         #name.widget().clone()
                     },
                 };
-            let widget = self.widget(value, None, IsGtk, true);
+            let widget = self.nested_view(value);
             let property_func = Ident::new(&format!("set_{}", key), key.span());
             let property = quote! {
                 #widget
@@ -379,6 +507,7 @@ impl<'a> Generator<'a> {
 
         let construct_widget = gen_construct_widget(widget, gtk_widget);
         self.collect_events(widget, gtk_widget);
+        self.add_to_size_group(widget, quote! { &#widget_name });
 
         let children: Vec<_> = widget.children.iter()
             .map(|child| self.widget(child, Some(widget_name), IsGtk, true))
@@ -387,7 +516,8 @@ impl<'a> Generator<'a> {
         let add_child_or_show_all = self.add_child_or_show_all(widget, parent, parent_widget_type);
         let ident = quote! { #widget_name };
         let (properties, visible_properties) = self.gtk_set_prop_calls(widget, ident);
-        let child_properties = gen_set_child_prop_calls(widget, parent, parent_widget_type, IsGtk);
+        let mut child_properties = gen_set_child_prop_calls(widget, parent, parent_widget_type, IsGtk);
+        child_properties.append(&mut self.child_nested_views(widget, parent, parent_widget_type, IsGtk));
 
         let show =
             if show {
@@ -416,27 +546,61 @@ impl<'a> Generator<'a> {
         self.widget_names.push(widget.name.clone());
         let widget_name = &widget.name;
         let widget_type_ident = &widget.typ;
+        if widget.is_lazy {
+            let supported = parent.is_some() && parent_widget_type == IsGtk && !widget.is_container && !widget.is_overlay
+                && widget.children.is_empty() && widget.child_events.is_empty() && relm_widget.gtk_events.is_empty();
+            if !supported {
+                panic!("#[lazy] is only supported on a relm widget without children nor gtk events, added to a gtk \
+                    container");
+            }
+            self.lazy_components.insert(widget_name.clone());
+        }
         self.set_container(widget, widget_name, widget_type_ident, true);
-        let relm_component_type = gen_relm_component_type(widget.is_container, widget_type_ident);
+        let relm_component_type =
+            if widget.is_lazy {
+                gen_lazy_component_type(widget_type_ident)
+            }
+            else {
+                gen_relm_component_type(widget.is_container, widget_type_ident)
+            };
         self.relm_components.insert(widget.name.clone(), relm_component_type);
+        // The widget added to the parent.
+        let root =
+            if widget.is_lazy {
+                quote! { #widget_name.placeholder() }
+            }
+            else {
+                quote! { #widget_name.widget() }
+            };
 
         if widget.save {
             self.streams_to_save.insert(widget_name.clone());
         }
 
         self.collect_relm_events(widget, relm_widget);
+        self.add_to_size_group(widget, root.clone());
 
         let children: Vec<_> = widget.children.iter()
             .map(|child| self.widget(child, Some(widget_name), IsRelm, true))
             .collect();
-        let ident = quote! { #widget_name.widget() };
-        let (mut properties, mut visible_properties) = self.gtk_set_prop_calls(widget, ident);
+        let (mut properties, mut visible_properties) = self.gtk_set_prop_calls(widget, root.clone());
         self.properties.append(&mut properties);
         self.properties.append(&mut visible_properties);
 
-        let add_or_create_widget = self.add_or_create_widget(
-            parent, parent_widget_type, widget_name, widget_type_ident, &widget.init_parameters, widget.is_container);
-        let child_properties = gen_set_child_prop_calls(widget, parent, parent_widget_type, IsRelm);
+        let add_or_create_widget =
+            match parent {
+                Some(parent) if widget.is_lazy => {
+                    let init_parameters = gen_model_param(&widget.init_parameters, WithParens);
+                    quote_spanned! { widget_name.span() =>
+                        let #widget_name = ::relm::ContainerWidget::add_widget_lazy::<#widget_type_ident>(&#parent,
+                            #init_parameters);
+                    }
+                },
+                _ => self.add_or_create_widget(parent, parent_widget_type, widget_name, widget_type_ident,
+                    &widget.init_parameters, widget.is_container, widget.is_overlay),
+            };
+        let mut child_properties = gen_set_child_prop_calls(widget, parent, parent_widget_type, IsRelm);
+        child_properties.append(&mut self.child_nested_views(widget, parent, parent_widget_type, IsRelm));
         let messages = self.messages(widget, relm_widget);
 
         quote! {
@@ -484,6 +648,13 @@ impl<'a> Generator<'a> {
         }
     }
 
+    fn nested_view(&mut self, widget: &Widget) -> TokenStream {
+        self.nested_view_depth += 1;
+        let tokens = self.widget(widget, None, IsGtk, true);
+        self.nested_view_depth -= 1;
+        tokens
+    }
+
     fn widget(&mut self, widget: &Widget, parent: Option<&Ident>, parent_widget_type: WidgetType, show: bool) -> TokenStream {
         match widget.widget {
             Gtk(ref gtk_widget) => self.gtk_widget(widget, gtk_widget, parent, parent_widget_type, show),
@@ -565,6 +736,17 @@ fn gen_construct_widget(widget: &Widget, gtk_widget: &GtkWidget) -> TokenStream
     }
 }
 
+/// Get the string literal of a property value, which is translated when the `i18n` feature is
+/// enabled.
+#[cfg(feature = "i18n")]
+fn translatable_literal(value: &Expr) -> Option<&syn::LitStr> {
+    match *value {
+        Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(ref literal), .. }) => Some(literal),
+        Expr::Reference(syn::ExprReference { ref expr, .. }) => translatable_literal(expr),
+        _ => None,
+    }
+}
+
 fn gen_event_metadata(event: &Event) -> TokenStream {
     if let CurrentWidget(CallReturn(_)) = event.value {
         quote! {
@@ -826,6 +1008,13 @@ fn gen_relm_component_type(is_container: bool, name: &Path) -> Path {
     parse(tokens.into()).expect("gen_relm_component_type is a Path")
 }
 
+fn gen_lazy_component_type(name: &Path) -> Path {
+    let tokens = quote_spanned! { name.span() =>
+        ::relm::LazyComponent<#name>
+    };
+    parse(tokens.into()).expect("gen_lazy_component_type is a Path")
+}
+
 fn gen_set_child_prop_calls(widget: &Widget, parent: Option<&Ident>, parent_widget_type: WidgetType,
     widget_type: WidgetType) -> Vec<TokenStream>
 {
@@ -851,6 +1040,11 @@ fn gen_set_child_prop_calls(widget: &Widget, parent: Option<&Ident>, parent_widg
                         #parent.#property_func(&#widget_name, #value);
                     }
                 }
+                else if widget.is_lazy {
+                    quote! {
+                        #parent.#property_func(#widget_name.placeholder(), #value);
+                    }
+                }
                 else {
                     quote! {
                         #parent.#property_func(#widget_name.widget(), #value);
@@ -37,6 +37,7 @@ use std::collections::{HashMap, HashSet};
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, quote_spanned};
 use syn::{
+    GenericArgument,
     Generics,
     Ident,
     ImplItem,
@@ -60,7 +61,7 @@ use syn::spanned::Spanned;
 use syn::Type;
 use syn::visit::Visit;
 
-use self::adder::{Adder, Message, Property};
+use self::adder::{Adder, Message, Property, create_refresh_stmts};
 pub use self::gen::gen_where_clause;
 use self::parser::EitherWidget::{Gtk, Relm};
 use self::parser::{Widget, parse_widgets};
@@ -71,14 +72,24 @@ const MODEL_IDENT: &str = "__relm_model";
 type MsgModelMap = HashMap<Ident, HashSet<Message>>;
 type PropertyModelMap = HashMap<Ident, HashSet<Property>>;
 
+/// The options given as arguments of the `#[widget]` attribute.
+#[derive(Debug, Default)]
+pub struct WidgetOptions {
+    /// The name under which the geometry of the window is saved.
+    pub persist_geometry: Option<String>,
+}
+
 #[derive(Debug)]
 pub struct Driver {
+    after_update_method: Option<ImplItem>,
     data_method: Option<ImplItem>,
+    frame_synchronized: bool,
     generic_types: Option<Generics>,
     model_type: Option<ImplItem>,
     model_param_type: Option<ImplItem>,
     msg_model_map: Option<MsgModelMap>,
     msg_type: Option<ImplItem>,
+    options: WidgetOptions,
     other_methods: Vec<ImplItem>,
     properties_model_map: Option<PropertyModelMap>,
     root_method: Option<ImplItem>,
@@ -107,14 +118,17 @@ struct View {
 }
 
 impl Driver {
-    fn new() -> Self {
+    fn new(options: WidgetOptions) -> Self {
         Driver {
+            after_update_method: None,
             data_method: None,
+            frame_synchronized: false,
             generic_types: None,
             model_type: None,
             model_param_type: None,
             msg_model_map: None,
             msg_type: None,
+            options,
             other_methods: vec![],
             properties_model_map: None,
             root_method: None,
@@ -132,11 +146,11 @@ impl Driver {
         }
     }
 
-    fn add_set_property_to_method(&self, func: &mut ImplItem) {
+    fn add_set_property_to_method(&self, func: &mut ImplItem, defer_properties: bool) {
         if let Method(ImplItemMethod { ref mut block, .. }) = *func {
             let msg_map = self.msg_model_map.as_ref().expect("update method");
             let property_map = self.properties_model_map.as_ref().expect("update method");
-            let mut adder = Adder::new(property_map, msg_map);
+            let mut adder = Adder::new(property_map, msg_map, defer_properties);
             *block = adder.fold_block(block.clone());
         }
     }
@@ -150,6 +164,10 @@ impl Driver {
             self.collect_bindings(nested_view, msg_model_map, properties_model_map);
         }
 
+        for (_, nested_view) in &widget.child_nested_views {
+            self.collect_bindings(nested_view, msg_model_map, properties_model_map);
+        }
+
         for child in &widget.children {
             self.collect_bindings(child, msg_model_map, properties_model_map);
         }
@@ -185,13 +203,19 @@ impl Driver {
         let components_name = Ident::new(&format!("__{}Components", get_name(&typ)), Span::call_site());
         let widgets_name = Ident::new(&format!("__{}Widgets", get_name(&typ)), Span::call_site());
         let streams_name = Ident::new(&format!("__{}Streams", get_name(&typ)), Span::call_site());
+        // The structures holding the widgets are generic when the widget is, since the types of
+        // its widgets can depend on the type parameters.
+        let phantom = gen_phantom_field(generics);
+        let (_, ty_generics, _) = generics.split_for_impl();
+        let struct_generics = if generics.params.is_empty() { None } else { Some(generics) };
         let components = {
             let components = relm_components.iter()
                 .map(|(ident, tokens)| (ident.clone(), tokens));
             let (idents, types): (Vec<Ident>, Vec<_>) = components.unzip();
             quote! {
-                pub struct #components_name {
+                pub struct #components_name #struct_generics #where_clause {
                     #(pub #idents: #types,)*
+                    #phantom
                 }
             }
         };
@@ -199,25 +223,36 @@ impl Driver {
         let component_root_types = relm_components.values();
         let component_root_types: Vec<_> = component_root_types
             .map(|path| {
-                if let PathArguments::AngleBracketed(ref arguments) = path.segments.last().expect("component").arguments {
+                let segment = path.segments.last().expect("component");
+                // A lazy component is represented by its placeholder in the widgets.
+                if segment.ident == "LazyComponent" {
+                    return quote! { ::gtk::Box };
+                }
+                if let PathArguments::AngleBracketed(ref arguments) = segment.arguments {
                     let first_arg = arguments.args.first();
                     let arg = first_arg.as_ref().expect("argument");
-                    return arg.clone();
+                    return quote! { <#arg as ::relm::Widget>::Root };
                 }
                 panic!("Not a component type");
             })
             .collect();
         let widgets = {
-            let relm_idents = relm_widgets.keys();
+            let relm_idents: Vec<_> = relm_widgets.keys().collect();
             let relm_types = relm_widgets.values();
 
-            let component_idents = relm_components.keys();
+            let component_idents: Vec<_> = relm_components.keys().collect();
+            let fields: Vec<_> = component_idents.iter().cloned()
+                .chain(idents.iter())
+                .chain(relm_idents.iter().cloned())
+                .collect();
+            let clone = gen_clone(&widgets_name, &fields, generics);
             quote! {
-                #[derive(Clone)]
-                pub struct #widgets_name {
-                    #(#component_idents: <#component_root_types as ::relm::Widget>::Root,)*
+                #clone
+                pub struct #widgets_name #struct_generics #where_clause {
+                    #(#component_idents: #component_root_types,)*
                     #(#idents: #types,)*
                     #(#relm_idents: #relm_types,)*
+                    #phantom
                 }
             }
         };
@@ -233,19 +268,22 @@ impl Driver {
                     panic!("Not a component type");
                 })
                 .unzip();
+            let clone = gen_clone(&streams_name, &component_idents, generics);
             quote! {
-                #[derive(Clone)]
-                pub struct #streams_name {
+                #clone
+                pub struct #streams_name #struct_generics #where_clause {
                     #(#component_idents: ::relm::StreamHandle<<#component_root_types as ::relm::Update>::Msg>,)*
+                    #phantom
                 }
             }
         };
+        let name = get_name(&typ);
         quote_spanned! { typ.span() =>
             #[allow(dead_code, missing_docs)]
-            pub struct #typ #where_clause {
-                streams: #streams_name,
-                components: #components_name,
-                widgets: #widgets_name,
+            pub struct #name #struct_generics #where_clause {
+                streams: #streams_name #ty_generics,
+                components: #components_name #ty_generics,
+                widgets: #widgets_name #ty_generics,
                 model: #widget_model_type,
             }
 
@@ -266,6 +304,8 @@ impl Driver {
             let name = get_name(&self_ty);
             let mut new_items = vec![];
             let mut update_items = vec![];
+            let mut has_model = false;
+            let mut has_try_model = false;
             for item in items {
                 let mut i = item.clone();
                 match item {
@@ -273,15 +313,28 @@ impl Driver {
                     ImplItem::Macro(mac) => self.view_macro = Some(mac.mac),
                     Method(ImplItemMethod { sig, .. }) => {
                         match sig.ident.to_string().as_ref() {
+                            "after_update" => self.after_update_method = Some(i),
                             "parent_id" => self.data_method = Some(i),
                             "root" => self.root_method = Some(i),
                             "model" => {
+                                has_model = true;
                                 self.widget_model_type = Some(get_return_type(sig));
                                 add_model_param(&mut i, &mut self.model_param_type);
                                 update_items.push(i);
                             },
+                            "try_model" => {
+                                has_try_model = true;
+                                self.widget_model_type = Some(get_result_ok_type(get_return_type(sig)));
+                                add_model_param(&mut i, &mut self.model_param_type);
+                                update_items.push(i);
+                            },
                             "subscriptions" => update_items.push(i),
-                            "init_view" | "on_add" => new_items.push(i),
+                            "frame_synchronized" => {
+                                self.frame_synchronized = true;
+                                new_items.push(i);
+                            },
+                            "init_messages" | "init_view" | "on_add" | "priority" | "shutdown_msg" =>
+                                new_items.push(i),
                             "update" => {
                                 self.widget_msg_type = Some(get_second_param_type(&sig));
                                 self.update_method = Some(i)
@@ -302,6 +355,9 @@ impl Driver {
                     __Nonexhaustive => panic!("Unexpected item"),
                 }
             }
+            if has_try_model && !has_model {
+                update_items.push(gen_model_from_try_model());
+            }
             let view =
                 match self.get_view(&name, &self_ty) {
                     Ok(view) => view,
@@ -343,6 +399,25 @@ impl Driver {
         }
     }
 
+    fn get_after_update(&mut self) -> Option<ImplItem> {
+        let property_map = self.properties_model_map.as_ref().expect("update method");
+        let refresh_stmts = create_refresh_stmts(property_map, self.frame_synchronized);
+        let mut func =
+            match self.after_update_method.take() {
+                Some(func) => func,
+                None if refresh_stmts.is_empty() => return None,
+                None => block_to_impl_item(quote! {
+                    fn after_update(&mut self) {
+                    }
+                }),
+            };
+        self.add_set_property_to_method(&mut func, false);
+        if let Method(ImplItemMethod { ref mut block, .. }) = func {
+            block.stmts.extend(refresh_stmts);
+        }
+        Some(func)
+    }
+
     fn get_data_method(&mut self) -> Option<ImplItem> {
         self.data_method.take().or_else(|| {
             if let Some(ref parent_id) = self.widget_parent_id {
@@ -388,7 +463,7 @@ impl Driver {
         let mut other_methods: Vec<_> = self.other_methods.drain(..).collect();
         let where_clause = gen_where_clause(generics);
         for method in &mut other_methods {
-            self.add_set_property_to_method(method);
+            self.add_set_property_to_method(method, false);
         }
         quote! {
             impl #generics #typ #where_clause {
@@ -423,7 +498,9 @@ impl Driver {
      */
     fn get_update(&mut self) -> ImplItem {
         let mut func = self.update_method.take().expect("update method");
-        self.add_set_property_to_method(&mut func);
+        // The view of a frame-synchronized widget is only refreshed in after_update(), once per
+        // batch of messages.
+        self.add_set_property_to_method(&mut func, self.frame_synchronized);
         // TODO: consider gtk::main_quit() as return.
         func
     }
@@ -493,6 +570,17 @@ impl Driver {
 
         let (view, relm_widgets, relm_components, streams_to_save, container_impl) = gen::gen(name, &widgets, self);
         let model_ident = Ident::new(MODEL_IDENT, Span::call_site()); // TODO: maybe need to set Span here.
+        let view =
+            match self.options.persist_geometry {
+                Some(ref window_name) => quote! {
+                    let widget: Self = {
+                        #view
+                    };
+                    ::relm::persist_geometry(&::relm::Widget::root(&widget), #window_name);
+                    widget
+                },
+                None => view,
+            };
         let code = quote_spanned! { name.span() =>
             #[allow(unused_variables,clippy::all)] // Necessary to avoid warnings in case the parameters are unused.
             fn view(relm: &::relm::Relm<Self>, #model_ident: Self::Model) -> Self {
@@ -519,6 +607,7 @@ impl Driver {
         let msg = self.get_msg_type();
         let model_param = self.get_model_param_type();
         let update = self.get_update();
+        let after_update = self.get_after_update();
         let model = self.get_model_type();
         quote_spanned! { typ.span() =>
             impl #generics ::relm::Update for #typ #where_clause {
@@ -526,6 +615,7 @@ impl Driver {
                 #model
                 #model_param
                 #update
+                #after_update
                 #(#items)*
             }
         }
@@ -535,17 +625,18 @@ impl Driver {
         let streams_name = Ident::new(&format!("__{}Streams", get_name(&typ)), Span::call_site());
         let name = Ident::new(&format!("__{}Widgets", get_name(&typ)), Span::call_site());
         let where_clause = gen_where_clause(generics);
+        let (_, ty_generics, _) = generics.split_for_impl();
         quote_spanned! { typ.span() =>
             #[cfg(test)]
             impl #generics ::relm::WidgetTest for #typ #where_clause {
-                type Streams = #streams_name;
-                type Widgets = #name;
+                type Streams = #streams_name #ty_generics;
+                type Widgets = #name #ty_generics;
 
-                fn get_streams(&self) -> #streams_name {
+                fn get_streams(&self) -> #streams_name #ty_generics {
                     self.streams.clone()
                 }
 
-                fn get_widgets(&self) -> #name {
+                fn get_widgets(&self) -> #name #ty_generics {
                     self.widgets.clone()
                 }
             }
@@ -553,8 +644,8 @@ impl Driver {
     }
 }
 
-pub fn gen_widget(input: TokenStream) -> TokenStream {
-    let mut driver = Driver::new();
+pub fn gen_widget(input: TokenStream, options: WidgetOptions) -> TokenStream {
+    let mut driver = Driver::new(options);
     driver.gen_widget(input)
 }
 
@@ -595,6 +686,53 @@ fn block_to_impl_item(tokens: TokenStream) -> ImplItem {
     }
 }
 
+/// Generate `Clone` for a structure holding widgets.
+/// `#[derive(Clone)]` is not used because it would require the type parameters to be `Clone`.
+fn gen_clone(name: &Ident, fields: &[&Ident], generics: &Generics) -> TokenStream {
+    if generics.params.is_empty() {
+        return quote! {
+            #[derive(Clone)]
+        };
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics Clone for #name #ty_generics #where_clause {
+            fn clone(&self) -> Self {
+                #name {
+                    #(#fields: self.#fields.clone(),)*
+                    __relm_phantom: ::std::marker::PhantomData,
+                }
+            }
+        }
+    }
+}
+
+/// Generate the field using the type parameters in the structures holding the widgets, if the
+/// widget is generic.
+fn gen_phantom_field(generics: &Generics) -> TokenStream {
+    if generics.params.is_empty() {
+        return quote! {};
+    }
+    let lifetimes = generics.lifetimes().map(|lifetime| &lifetime.lifetime);
+    let type_params = generics.type_params().map(|param| &param.ident);
+    quote! {
+        #[doc(hidden)]
+        pub __relm_phantom: ::std::marker::PhantomData<fn() -> (#(&#lifetimes (),)* #(#type_params,)*)>,
+    }
+}
+
+/// Generate the initialization of the field generated by `gen_phantom_field()`.
+fn gen_phantom_init(generics: &Generics) -> TokenStream {
+    if generics.params.is_empty() {
+        quote! {}
+    }
+    else {
+        quote! {
+            __relm_phantom: ::std::marker::PhantomData,
+        }
+    }
+}
+
 fn get_name(typ: &Type) -> Ident {
     if let Type::Path(TypePath { ref path, .. }) = *typ {
         let mut parts = vec![];
@@ -649,6 +787,7 @@ fn get_map(widget: &Widget, map: &mut PropertyModelMap, is_relm: bool) {
             set.insert(Property {
                 expr: expr.clone(),
                 is_relm_widget: is_relm,
+                is_text_comparable: has_str_text(&widget.typ),
                 name: name.clone(),
                 widget_name: widget.name.clone(),
             });
@@ -656,6 +795,21 @@ fn get_map(widget: &Widget, map: &mut PropertyModelMap, is_relm: bool) {
     }
 }
 
+/*
+ * The widgets whose text getter returns a GString and whose text setter takes a &str.
+ */
+fn has_str_text(typ: &Path) -> bool {
+    match typ.segments.last() {
+        Some(segment) => {
+            match segment.ident.to_string().as_ref() {
+                "AccelLabel" | "Entry" | "Label" | "SearchEntry" | "SpinButton" => true,
+                _ => false,
+            }
+        },
+        None => false,
+    }
+}
+
 fn get_return_type(sig: Signature) -> Type {
     if let ReturnType::Type(_, ty) = sig.output {
         *ty
@@ -668,6 +822,30 @@ fn get_return_type(sig: Signature) -> Type {
     }
 }
 
+fn gen_model_from_try_model() -> ImplItem {
+    parse(quote! {
+        fn model(relm: &::relm::Relm<Self>, param: Self::ModelParam) -> Self::Model {
+            match Self::try_model(relm, param) {
+                Ok(model) => model,
+                Err(error) => panic!("Cannot create the model: {}", error),
+            }
+        }
+    }.into()).expect("parse model() in gen_model_from_try_model()")
+}
+
+fn get_result_ok_type(typ: Type) -> Type {
+    if let Type::Path(TypePath { ref path, .. }) = typ {
+        if let Some(segment) = path.segments.last() {
+            if let PathArguments::AngleBracketed(ref arguments) = segment.arguments {
+                if let Some(GenericArgument::Type(ref ok_type)) = arguments.args.first() {
+                    return ok_type.clone();
+                }
+            }
+        }
+    }
+    panic!("try_model() must return a Result");
+}
+
 fn get_second_param_type(sig: &Signature) -> Type {
     if let Typed(PatType { ref ty, .. }) = sig.inputs[1] {
         *ty.clone()
@@ -684,7 +862,7 @@ fn gen_set_child_prop_calls(widget: &Widget) -> Option<ImplItem> {
         let property_func = Ident::new(&format!("set_{}_{}", ident, key), key.span());
         tokens = quote_spanned! { widget_name.span() =>
             #tokens
-            parent.#property_func(&self.widgets.#widget_name, #value);
+            parent.#property_func(&::relm::direct_child(&parent, &self.widgets.#widget_name), #value);
         };
     }
     if !widget.child_properties.is_empty() {
@@ -719,7 +897,7 @@ mod tests {
             ExprKind::Mac(mac) => mac,
             _ => panic!("Expected ExprKind::Mac(mac), found {:#?}", parsed_expr),
         };
-        let mut driver = Driver::new();
+        let mut driver = Driver::new(WidgetOptions::default());
         driver.view_macro = Some(mac);
         driver.view_validation_before_impl();
     }
@@ -735,7 +913,7 @@ mod tests {
             ExprKind::Mac(mac) => mac,
             _ => panic!("Expected ExprKind::Mac(mac), found {:#?}", parsed_expr),
         };
-        let mut driver = Driver::new();
+        let mut driver = Driver::new(WidgetOptions::default());
         driver.view_macro = Some(mac);
         driver.view_validation_before_impl();
     }
@@ -753,7 +931,7 @@ mod tests {
             ExprKind::Mac(mac) => mac,
             _ => panic!("Expected ExprKind::Mac(mac), found {:#?}", parsed_expr),
         };
-        let mut driver = Driver::new();
+        let mut driver = Driver::new(WidgetOptions::default());
         driver.view_macro = Some(mac);
         driver.view_validation_before_impl();
     }
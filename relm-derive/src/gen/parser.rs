@@ -74,6 +74,7 @@ macro_rules! catch_return {
 }
 
 type ChildEvents = HashMap<(Ident, Ident), Event>;
+type ChildNestedViews = HashMap<(Ident, Ident), Widget>;
 type ChildProperties = HashMap<(Ident, Ident), Expr>;
 
 #[derive(PartialEq)]
@@ -118,16 +119,20 @@ impl Event {
 #[derive(Debug)]
 pub struct Widget {
     pub child_events: ChildEvents, // TODO: does it make sense for a relm widget?
+    pub child_nested_views: ChildNestedViews,
     pub child_properties: ChildProperties, // TODO: does it make sense for a relm widget?
     pub children: Vec<Widget>,
     pub container_type: Option<Option<String>>, // TODO: Why two Options?
     pub init_parameters: Vec<Expr>,
     pub is_container: bool,
+    pub is_lazy: bool,
+    pub is_overlay: bool,
     pub name: Ident,
     pub nested_views: HashMap<Ident, Widget>,
     pub parent_id: Option<String>,
     pub properties: HashMap<Ident, Expr>,
     pub save: bool,
+    pub size_group: Option<Ident>,
     pub typ: Path,
     pub widget: EitherWidget,
 }
@@ -135,21 +140,25 @@ pub struct Widget {
 impl Widget {
     fn new_gtk(widget: GtkWidget, typ: Path, init_parameters: Vec<Expr>, children: Vec<Widget>,
         properties: HashMap<Ident, Expr>, child_properties: ChildProperties, child_events: ChildEvents,
-        nested_views: HashMap<Ident, Widget>) -> Self
+        child_nested_views: ChildNestedViews, nested_views: HashMap<Ident, Widget>) -> Self
     {
         let name = gen_widget_name(&typ);
         Widget {
             child_events,
+            child_nested_views,
             child_properties,
             children,
             container_type: None,
             init_parameters,
             is_container: false,
+            is_lazy: false,
+            is_overlay: false,
             name,
             nested_views,
             parent_id: None,
             properties,
             save: false,
+            size_group: None,
             typ,
             widget: Gtk(widget),
         }
@@ -157,7 +166,7 @@ impl Widget {
 
     fn new_relm(widget: RelmWidget, typ: Path, init_parameters: Vec<Expr>, children: Vec<Widget>,
         properties: HashMap<Ident, Expr>, child_properties: ChildProperties, child_events: ChildEvents,
-        nested_views: HashMap<Ident, Widget>) -> Self
+        child_nested_views: ChildNestedViews, nested_views: HashMap<Ident, Widget>) -> Self
     {
         let mut name = gen_widget_name(&typ);
         // Relm widgets are not used in the update() method; they are only saved to avoid dropping
@@ -166,16 +175,20 @@ impl Widget {
         name = Ident::new(&format!("_{}", name), name.span());
         Widget {
             child_events,
+            child_nested_views,
             child_properties,
             children,
             container_type: None,
             init_parameters,
             is_container: false,
+            is_lazy: false,
+            is_overlay: false,
             name,
             nested_views,
             parent_id: None,
             properties,
             save: false,
+            size_group: None,
             typ,
             widget: Relm(widget),
         }
@@ -521,6 +534,7 @@ impl GtkWidgetParser {
         let mut children = vec![];
         let mut properties = HashMap::new();
         let mut child_events = HashMap::new();
+        let mut child_nested_views = HashMap::new();
         let mut child_properties = HashMap::new();
         let mut nested_views = HashMap::new();
         for item in child_items.into_iter() {
@@ -531,7 +545,12 @@ impl GtkWidgetParser {
                 },
                 ItemChildProperties(child_props) => {
                     for (key, value) in child_props {
-                        child_properties.insert(key, value);
+                        if let Some(tokens) = nested_view_tokens(&value) {
+                            child_nested_views.insert(key, parse2(tokens)?);
+                        }
+                        else {
+                            child_properties.insert(key, value);
+                        }
                     }
                 },
                 ItemEvent(ident, event) => { let _ = gtk_widget.events.insert(ident, event); },
@@ -548,7 +567,7 @@ impl GtkWidgetParser {
         }
         Ok(GtkWidgetParser {
             gtk_widget: ChildWidget(Widget::new_gtk(gtk_widget, typ, init_parameters, children, properties,
-                            child_properties, child_events, nested_views)),
+                            child_properties, child_events, child_nested_views, nested_views)),
         })
     }
 }
@@ -603,6 +622,7 @@ impl RelmWidgetParser {
                 let mut children = vec![];
                 let mut child_properties = HashMap::new();
                 let mut child_events = HashMap::new();
+                let mut child_nested_views = HashMap::new();
                 let mut properties = HashMap::new();
                 let mut nested_views = HashMap::new();
                 for item in child_items {
@@ -614,7 +634,12 @@ impl RelmWidgetParser {
                         ItemEvent(ident, event) => { let _ = relm_widget.gtk_events.insert(ident, event); },
                         ItemChildProperties(child_props) => {
                             for (key, value) in child_props {
-                                child_properties.insert(key, value);
+                                if let Some(tokens) = nested_view_tokens(&value) {
+                                    child_nested_views.insert(key, parse2(tokens)?);
+                                }
+                                else {
+                                    child_properties.insert(key, value);
+                                }
                             }
                         },
                         NestedView(ident, widget) => { let _ = nested_views.insert(ident, widget); },
@@ -627,12 +652,12 @@ impl RelmWidgetParser {
                     }
                 }
                 ChildWidget(Widget::new_relm(relm_widget, typ.clone(), init_parameters, children, properties,
-                    child_properties, child_events, nested_views))
+                    child_properties, child_events, child_nested_views, nested_views))
             }
             else {
                 let init_parameters = init_parameters.unwrap_or_else(Vec::new);
                 ChildWidget(Widget::new_relm(RelmWidget::new(), typ, init_parameters, vec![], HashMap::new(),
-                    HashMap::new(), HashMap::new(), HashMap::new()))
+                    HashMap::new(), HashMap::new(), HashMap::new(), HashMap::new()))
             };
         Ok(RelmWidgetParser {
             relm_widget,
@@ -755,13 +780,7 @@ impl ValueOrChildProperties {
             }
             else {
                 let value = Value::parse(input)?;
-                let mut nested_view = None;
-                if let Expr::Macro(ExprMacro { mac: Macro { ref path, ref tokens, .. }, .. }) = value.value {
-                    if path.is_ident(&dummy_ident("view")) {
-                        nested_view = Some(tokens.clone());
-                    }
-                }
-                if let Some(tokens) = nested_view {
+                if let Some(tokens) = nested_view_tokens(&value.value) {
                     let widget: Widget = parse2(tokens)?;
                     NestedView(ident.clone(), widget)
                 }
@@ -775,6 +794,15 @@ impl ValueOrChildProperties {
     }
 }
 
+fn nested_view_tokens(value: &Expr) -> Option<TokenStream> {
+    if let Expr::Macro(ExprMacro { mac: Macro { ref path, ref tokens, .. }, .. }) = *value {
+        if path.is_ident(&dummy_ident("view")) {
+            return Some(tokens.clone());
+        }
+    }
+    None
+}
+
 struct Tag;
 
 impl Tag {
@@ -1136,6 +1164,10 @@ fn adjust_widget_with_attributes(mut widget: ChildItem, attributes: &HashMap<Str
             }
             widget.is_container = !widget.children.is_empty();
             widget.container_type = container_type;
+            widget.is_lazy = attributes.contains_key("lazy");
+            widget.is_overlay = attributes.contains_key("overlay");
+            widget.size_group = attributes.get("size_group")
+                .and_then(|group| group.as_ref().map(|lit| Ident::new(&lit.value(), lit.span())));
             parent_id = attributes.get("parent").and_then(|opt_str| opt_str.as_ref().map(|lit| lit.value()));
         },
         _ => panic!("Expecting widget"),
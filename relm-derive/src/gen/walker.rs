@@ -24,6 +24,7 @@
 use syn::{
     Expr,
     ExprField,
+    ExprMethodCall,
     ExprPath,
     Ident,
 };
@@ -46,6 +47,13 @@ impl ModelVariableVisitor {
 
 impl<'ast> Visit<'ast> for ModelVariableVisitor {
     fn visit_expr(&mut self, expr: &'ast Expr) {
+        // A method called on the model, like self.model.validate(), is recorded with its name so
+        // that the property is bound and refreshed after each update.
+        if let Expr::MethodCall(ExprMethodCall { ref receiver, ref method, .. }) = *expr {
+            if is_model(receiver) {
+                self.idents.push(method.clone());
+            }
+        }
         if let Expr::Field(ExprField { base: ref obj, member: ref field, .. }) = *expr {
             if let Expr::Field(ExprField { base: ref expr, member: ref model_ident, .. }) = **obj {
                 if let Expr::Path(ExprPath { ref path, .. }) = **expr {
@@ -62,3 +70,37 @@ impl<'ast> Visit<'ast> for ModelVariableVisitor {
         visit_expr(self, expr);
     }
 }
+
+/// Check whether `expr` calls a method on the model, in which case its value can change after
+/// any update.
+pub fn uses_model_method(expr: &Expr) -> bool {
+    let mut visitor = ModelMethodVisitor {
+        found: false,
+    };
+    visitor.visit_expr(expr);
+    visitor.found
+}
+
+struct ModelMethodVisitor {
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for ModelMethodVisitor {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if let Expr::MethodCall(ExprMethodCall { ref receiver, .. }) = *expr {
+            if is_model(receiver) {
+                self.found = true;
+            }
+        }
+        visit_expr(self, expr);
+    }
+}
+
+fn is_model(expr: &Expr) -> bool {
+    if let Expr::Field(ExprField { ref base, member: Named(ref ident), .. }) = *expr {
+        if let Expr::Path(ExprPath { ref path, .. }) = **base {
+            return path.is_ident(&dummy_ident("self")) && ident == "model";
+        }
+    }
+    false
+}
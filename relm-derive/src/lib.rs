@@ -32,42 +32,152 @@ mod gen;
 use quote::{quote, quote_spanned};
 use proc_macro2::TokenStream;
 use syn::{
+    Attribute,
+    AttributeArgs,
     GenericParam,
     Generics,
     Ident,
     Item,
+    ItemFn,
     LifetimeDef,
+    Lit,
+    LitInt,
+    LitStr,
+    Meta,
+    MetaNameValue,
+    NestedMeta,
     TypeParam,
     parse,
+    parse_macro_input,
 };
 use syn::spanned::Spanned;
 
-use gen::{gen_widget, gen_where_clause, parser::dummy_ident};
+use gen::{WidgetOptions, gen_widget, gen_where_clause, parser::dummy_ident};
 
-#[proc_macro_derive(Msg)]
+#[proc_macro_derive(GridRow, attributes(column))]
+pub fn grid_row(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast: Item = parse(input).expect("grid_row > parse failed");
+    let gen = derive_grid_row(&ast, Ident::new("relm", ast.span()));
+    gen.into()
+}
+
+#[proc_macro_derive(Msg, attributes(action, msg))]
 pub fn msg(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let ast: Item = parse(input).expect("msg > parse failed");
     let gen = impl_msg(&ast, Ident::new("relm", ast.span()));
     gen.into()
 }
 
+#[proc_macro_derive(Settings, attributes(setting))]
+pub fn settings(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast: Item = parse(input).expect("settings > parse failed");
+    let gen = derive_settings_model(&ast, Ident::new("relm", ast.span()));
+    gen.into()
+}
+
+#[proc_macro_derive(PersistentModel, attributes(persist))]
+pub fn persistent_model(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast: Item = parse(input).expect("persistent_model > parse failed");
+    let gen = derive_persistent_model(&ast, Ident::new("relm", ast.span()));
+    gen.into()
+}
+
+#[proc_macro_derive(Preferences, attributes(preference))]
+pub fn preferences(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast: Item = parse(input).expect("preferences > parse failed");
+    let gen = derive_preferences(&ast, Ident::new("relm", ast.span()));
+    gen.into()
+}
+
+#[proc_macro_derive(PreferenceChoice, attributes(preference))]
+pub fn preference_choice(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast: Item = parse(input).expect("preference_choice > parse failed");
+    let gen = derive_preference_choice(&ast, Ident::new("relm", ast.span()));
+    gen.into()
+}
+
+#[proc_macro_derive(Validate, attributes(validate))]
+pub fn validate(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast: Item = parse(input).expect("validate > parse failed");
+    let gen = derive_validate(&ast, Ident::new("relm", ast.span()));
+    gen.into()
+}
+
+#[proc_macro_derive(FromBuilder, attributes(builder))]
+pub fn from_builder(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast: Item = parse(input).expect("from_builder > parse failed");
+    let gen = derive_from_builder(&ast, Ident::new("relm", ast.span()));
+    gen.into()
+}
+
 #[proc_macro_attribute]
-pub fn widget(_attributes: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+pub fn widget(attributes: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let attributes = parse_macro_input!(attributes as AttributeArgs);
     let ast: Item = parse(input).expect("widget.parse failed");
+    let options =
+        match parse_widget_options(&attributes) {
+            Ok(options) => options,
+            Err(error) => return error.into(),
+        };
     let tokens = quote! {
         #ast
     };
-    let expanded = gen_widget(tokens);
+    let expanded = gen_widget(tokens, options);
+    expanded.into()
+}
+
+/// Parse the arguments of the `#[widget]` attribute, like
+/// `#[widget(persist_geometry = "main-window")]`.
+fn parse_widget_options(attributes: &[NestedMeta]) -> Result<WidgetOptions, TokenStream> {
+    let mut options = WidgetOptions::default();
+    for attribute in attributes {
+        match *attribute {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue { ref path, lit: Lit::Str(ref value), .. }))
+                if path.is_ident("persist_geometry") =>
+            {
+                options.persist_geometry = Some(value.value());
+            },
+            _ => return Err(quote_spanned! { attribute.span() =>
+                compile_error!("expected #[widget] or #[widget(persist_geometry = \"window-name\")]");
+            }),
+        }
+    }
+    Ok(options)
+}
+
+/// Run a test on the GTK thread shared by all the tests of the process, after the previous
+/// tests finished. This requires the `relm-test` crate.
+/// The test can return a `Result`, which fails the test when it is an `Err`, like with `#[test]`.
+#[proc_macro_attribute]
+pub fn relm_test(_attributes: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let function: ItemFn = parse(input).expect("relm_test > parse failed");
+    let attrs = &function.attrs;
+    let visibility = &function.vis;
+    let signature = &function.sig;
+    let block = &function.block;
+    // Give the closure the return type of the test so that the ? operator can be used in it.
+    let output = &signature.output;
+    let expanded = quote! {
+        #[test]
+        #(#attrs)*
+        #visibility #signature {
+            ::relm_test::run_serialized(move || #output #block)
+        }
+    };
     expanded.into()
 }
 
 fn impl_msg(ast: &Item, krate: Ident) -> TokenStream {
     let display = derive_display_variant(ast, &krate);
     let into_option = derive_into_option(ast, &krate);
+    let actions = derive_actions(ast, &krate);
+    let versioned = derive_versioned(ast, &krate);
 
     quote! {
         #display
         #into_option
+        #actions
+        #versioned
     }
 }
 
@@ -82,15 +192,19 @@ fn derive_display_variant(ast: &Item, krate: &Ident) -> TokenStream {
 
         let variant_patterns = enum_item.variants.iter().map(|variant| {
             let doc_ident = dummy_ident("doc");
-            let attrs = variant.attrs.iter().filter(|attr| !attr.path.is_ident(&doc_ident));
+            let action_ident = dummy_ident("action");
+            let attrs = variant.attrs.iter()
+                .filter(|attr| !attr.path.is_ident(&doc_ident) && !attr.path.is_ident(&action_ident));
             let ident = &variant.ident;
             quote! {
                 #(#attrs)* #name::#ident { .. }
             }
         });
-        let variant_names = enum_item.variants.iter().map(|variant| {
+        let variant_patterns: Vec<_> = variant_patterns.collect();
+        let variant_names: Vec<_> = enum_item.variants.iter().map(|variant| {
             variant.ident.to_string()
-        });
+        }).collect();
+        let variant_indexes = 0..variant_names.len();
         let where_clause = gen_where_clause(generics);
 
         quote_spanned! { krate.span() =>
@@ -101,6 +215,17 @@ fn derive_display_variant(ast: &Item, krate: &Ident) -> TokenStream {
                         #(#variant_patterns => #variant_names,)*
                     }
                 }
+
+                #[allow(unused_qualifications)]
+                fn variant_index(&self) -> usize {
+                    match *self {
+                        #(#variant_patterns => #variant_indexes,)*
+                    }
+                }
+
+                fn variant_names() -> &'static [&'static str] {
+                    &[#(#variant_names),*]
+                }
             }
         }
     }
@@ -132,6 +257,832 @@ fn derive_into_option(ast: &Item, krate: &Ident) -> TokenStream {
     }
 }
 
+/*
+ * Generate the Actions implementation for the variants having an attribute like:
+ * #[action(name = "win.save", accel = "<Ctrl>S")]
+ */
+fn derive_actions(ast: &Item, krate: &Ident) -> TokenStream {
+    if let Item::Enum(ref enum_item) = *ast {
+        let action_ident = dummy_ident("action");
+        let name = &enum_item.ident;
+        let mut action_names = vec![];
+        let mut accels = vec![];
+        let mut variants = vec![];
+        for variant in &enum_item.variants {
+            for attr in variant.attrs.iter().filter(|attr| attr.path.is_ident(&action_ident)) {
+                if !variant.fields.is_empty() {
+                    return quote_spanned! { variant.span() =>
+                        compile_error!("#[action] can only be used on variants without parameters");
+                    };
+                }
+                let (action_name, accel) =
+                    match parse_action_attribute(attr) {
+                        Some(action) => action,
+                        None => return quote_spanned! { attr.span() =>
+                            compile_error!("expected #[action(name = \"scope.action\", accel = \"<Ctrl>A\")]");
+                        },
+                    };
+                action_names.push(action_name);
+                accels.push(match accel {
+                    Some(accel) => quote! { Some(#accel) },
+                    None => quote! { None },
+                });
+                variants.push(&variant.ident);
+            }
+        }
+        if variants.is_empty() {
+            return quote! {};
+        }
+
+        let generics = &enum_item.generics;
+        let generics_without_bound = remove_generic_bounds(generics);
+        let typ = quote! {
+            #name #generics_without_bound
+        };
+        let where_clause = gen_where_clause(generics);
+        let action_names2 = action_names.clone();
+        let variants2 = variants.clone();
+
+        quote_spanned! { krate.span() =>
+            impl #generics ::#krate::Actions for #typ #where_clause {
+                fn actions() -> Vec<::#krate::Action<Self>> {
+                    vec![#(::#krate::Action {
+                        accel: #accels,
+                        constructor: || #name::#variants,
+                        name: #action_names,
+                    },)*]
+                }
+
+                #[allow(unreachable_patterns)]
+                fn action_name(&self) -> Option<&'static str> {
+                    match *self {
+                        #(#name::#variants2 => Some(#action_names2),)*
+                        _ => None,
+                    }
+                }
+            }
+        }
+    }
+    else {
+        panic!("Expected enum");
+    }
+}
+
+fn parse_action_attribute(attr: &Attribute) -> Option<(LitStr, Option<LitStr>)> {
+    let mut name = None;
+    let mut accel = None;
+    if let Ok(Meta::List(list)) = attr.parse_meta() {
+        for nested in list.nested {
+            if let NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit: Lit::Str(value), .. })) = nested {
+                if path.is_ident(&dummy_ident("name")) {
+                    name = Some(value);
+                }
+                else if path.is_ident(&dummy_ident("accel")) {
+                    accel = Some(value);
+                }
+                else {
+                    return None;
+                }
+            }
+            else {
+                return None;
+            }
+        }
+    }
+    name.map(|name| (name, accel))
+}
+
+/*
+ * Generate the Versioned implementation for the enums having an attribute like:
+ * #[msg(version = 2, migrate = "migrate_msg")]
+ */
+fn derive_versioned(ast: &Item, krate: &Ident) -> TokenStream {
+    if let Item::Enum(ref enum_item) = *ast {
+        let msg_ident = dummy_ident("msg");
+        let attr =
+            match enum_item.attrs.iter().find(|attr| attr.path.is_ident(&msg_ident)) {
+                Some(attr) => attr,
+                None => return quote! {},
+            };
+        let (version, migrate) =
+            match parse_msg_attribute(attr) {
+                Some(msg) => msg,
+                None => return quote_spanned! { attr.span() =>
+                    compile_error!("expected #[msg(version = 2, migrate = \"migrate_function\")]");
+                },
+            };
+        let migrate =
+            match migrate {
+                Some(migrate) => {
+                    let path: syn::Path =
+                        match migrate.parse() {
+                            Ok(path) => path,
+                            Err(_) => return quote_spanned! { migrate.span() =>
+                                compile_error!("expected the path of the migration function");
+                            },
+                        };
+                    quote! {
+                        fn migrate(version: u32, value: ::#krate::debug::Value) -> Result<::#krate::debug::Value, String> {
+                            #path(version, value)
+                        }
+                    }
+                },
+                None => quote! {},
+            };
+
+        let name = &enum_item.ident;
+        let generics = &enum_item.generics;
+        let generics_without_bound = remove_generic_bounds(generics);
+        let where_clause = gen_where_clause(generics);
+
+        quote_spanned! { krate.span() =>
+            impl #generics ::#krate::debug::Versioned for #name #generics_without_bound #where_clause {
+                const VERSION: u32 = #version;
+
+                #migrate
+            }
+        }
+    }
+    else {
+        panic!("Expected enum");
+    }
+}
+
+fn parse_msg_attribute(attr: &Attribute) -> Option<(LitInt, Option<LitStr>)> {
+    let mut version = None;
+    let mut migrate = None;
+    if let Ok(Meta::List(list)) = attr.parse_meta() {
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue { ref path, lit: Lit::Int(ref value), .. }))
+                    if path.is_ident(&dummy_ident("version")) => version = Some(value.clone()),
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue { ref path, lit: Lit::Str(ref value), .. }))
+                    if path.is_ident(&dummy_ident("migrate")) => migrate = Some(value.clone()),
+                _ => return None,
+            }
+        }
+    }
+    version.map(|version| (version, migrate))
+}
+
+/*
+ * Generate the SettingsModel implementation for the structs having fields with an attribute like:
+ * #[setting(schema = "org.example.App", key = "window-width")]
+ */
+fn derive_settings_model(ast: &Item, krate: &Ident) -> TokenStream {
+    if let Item::Struct(ref struct_item) = *ast {
+        let setting_ident = dummy_ident("setting");
+        let name = &struct_item.ident;
+        let mut initializers = vec![];
+        let mut setting_fields = vec![];
+        for field in &struct_item.fields {
+            let field_name =
+                match field.ident {
+                    Some(ref ident) => ident,
+                    None => return quote_spanned! { field.span() =>
+                        compile_error!("#[derive(Settings)] requires named fields");
+                    },
+                };
+            let attr = field.attrs.iter().find(|attr| attr.path.is_ident(&setting_ident));
+            match attr {
+                Some(attr) => {
+                    let (schema, key) =
+                        match parse_setting_attribute(attr) {
+                            Some(setting) => setting,
+                            None => return quote_spanned! { attr.span() =>
+                                compile_error!("expected #[setting(schema = \"org.example.App\", key = \"key-name\")]");
+                            },
+                        };
+                    initializers.push(quote! {
+                        #field_name: ::#krate::Setting::new(#schema, #key)
+                    });
+                    setting_fields.push(field_name);
+                },
+                None => initializers.push(quote! {
+                    #field_name: ::std::default::Default::default()
+                }),
+            }
+        }
+
+        let generics = &struct_item.generics;
+        let generics_without_bound = remove_generic_bounds(generics);
+        let where_clause = gen_where_clause(generics);
+
+        quote_spanned! { krate.span() =>
+            impl #generics ::#krate::SettingsModel for #name #generics_without_bound #where_clause {
+                fn load_settings() -> Self {
+                    #name {
+                        #(#initializers,)*
+                    }
+                }
+
+                fn watch_settings<F, MSG>(&self, stream: &::#krate::StreamHandle<MSG>, constructor: F)
+                    where F: Fn(&'static str) -> MSG + Clone + 'static,
+                          MSG: 'static,
+                {
+                    #(self.#setting_fields.watch(stream, constructor.clone());)*
+                }
+            }
+        }
+    }
+    else {
+        panic!("Expected struct");
+    }
+}
+
+fn parse_setting_attribute(attr: &Attribute) -> Option<(LitStr, LitStr)> {
+    let mut schema = None;
+    let mut key = None;
+    if let Ok(Meta::List(list)) = attr.parse_meta() {
+        for nested in list.nested {
+            if let NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit: Lit::Str(value), .. })) = nested {
+                if path.is_ident(&dummy_ident("schema")) {
+                    schema = Some(value);
+                }
+                else if path.is_ident(&dummy_ident("key")) {
+                    key = Some(value);
+                }
+                else {
+                    return None;
+                }
+            }
+            else {
+                return None;
+            }
+        }
+    }
+    match (schema, key) {
+        (Some(schema), Some(key)) => Some((schema, key)),
+        _ => None,
+    }
+}
+
+/*
+ * Generate the PersistentModel implementation, using the file name of an attribute like:
+ * #[persist(file = "state.json")]
+ */
+fn derive_persistent_model(ast: &Item, krate: &Ident) -> TokenStream {
+    let (name, generics, attrs) =
+        match *ast {
+            Item::Enum(ref item) => (&item.ident, &item.generics, &item.attrs),
+            Item::Struct(ref item) => (&item.ident, &item.generics, &item.attrs),
+            _ => panic!("Expected struct or enum"),
+        };
+    let persist_ident = dummy_ident("persist");
+    let file_name =
+        match attrs.iter().find(|attr| attr.path.is_ident(&persist_ident)) {
+            Some(attr) =>
+                match parse_persist_attribute(attr) {
+                    Some(file_name) => file_name.value(),
+                    None => return quote_spanned! { attr.span() =>
+                        compile_error!("expected #[persist(file = \"state.json\")]");
+                    },
+                },
+            None => format!("{}.json", to_snake_case(&name.to_string())),
+        };
+
+    let generics_without_bound = remove_generic_bounds(generics);
+    let where_clause = gen_where_clause(generics);
+
+    quote_spanned! { krate.span() =>
+        impl #generics ::#krate::persist::PersistentModel for #name #generics_without_bound #where_clause {
+            fn file_name() -> &'static str {
+                #file_name
+            }
+        }
+    }
+}
+
+fn parse_persist_attribute(attr: &Attribute) -> Option<LitStr> {
+    let mut file_name = None;
+    if let Ok(Meta::List(list)) = attr.parse_meta() {
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue { ref path, lit: Lit::Str(ref value), .. }))
+                    if path.is_ident(&dummy_ident("file")) => file_name = Some(value.clone()),
+                _ => return None,
+            }
+        }
+    }
+    file_name
+}
+
+struct PreferenceAttribute {
+    label: Option<LitStr>,
+    range: Option<(Lit, Lit, Option<Lit>)>,
+}
+
+/*
+ * Generate the Preferences implementation for the structs having fields with an attribute like:
+ * #[preference(label = "Font size", range(min = 6, max = 72, step = 1))]
+ */
+fn derive_preferences(ast: &Item, krate: &Ident) -> TokenStream {
+    if let Item::Struct(ref struct_item) = *ast {
+        let preference_ident = dummy_ident("preference");
+        let mut fields = vec![];
+        let mut getters = vec![];
+        let mut setters = vec![];
+        for field in &struct_item.fields {
+            let attr =
+                match field.attrs.iter().find(|attr| attr.path.is_ident(&preference_ident)) {
+                    Some(attr) => attr,
+                    None => continue,
+                };
+            let field_name =
+                match field.ident {
+                    Some(ref ident) => ident,
+                    None => return quote_spanned! { field.span() =>
+                        compile_error!("#[derive(Preferences)] requires named fields");
+                    },
+                };
+            let preference =
+                match parse_preference_attribute(attr) {
+                    Some(preference) => preference,
+                    None => return quote_spanned! { attr.span() =>
+                        compile_error!("expected #[preference(label = \"Label\", range(min = 0, max = 100, step = 1))]");
+                    },
+                };
+            let name = field_name.to_string();
+            let label = preference.label
+                .map(|label| label.value())
+                .unwrap_or_else(|| name.clone());
+            let typ = &field.ty;
+            let kind =
+                match preference.range {
+                    Some((min, max, step)) => {
+                        let step = step.map(|step| quote! { #step }).unwrap_or_else(|| quote! { 1 });
+                        quote! {
+                            <#typ as ::#krate::preferences::PreferenceType>::kind()
+                                .with_range(#min as f64, #max as f64, #step as f64)
+                        }
+                    },
+                    None => quote! {
+                        <#typ as ::#krate::preferences::PreferenceType>::kind()
+                    },
+                };
+            fields.push(quote! {
+                ::#krate::preferences::PreferenceField {
+                    kind: #kind,
+                    label: #label,
+                    name: #name,
+                }
+            });
+            getters.push(quote! {
+                #name => Some(::#krate::preferences::PreferenceType::to_value(&self.#field_name))
+            });
+            setters.push(quote! {
+                #name =>
+                    match <#typ as ::#krate::preferences::PreferenceType>::from_value(value) {
+                        Some(value) => {
+                            self.#field_name = value;
+                            true
+                        },
+                        None => false,
+                    }
+            });
+        }
+
+        let name = &struct_item.ident;
+        let generics = &struct_item.generics;
+        let generics_without_bound = remove_generic_bounds(generics);
+        let where_clause = gen_where_clause(generics);
+
+        quote_spanned! { krate.span() =>
+            impl #generics ::#krate::preferences::Preferences for #name #generics_without_bound #where_clause {
+                fn fields() -> Vec<::#krate::preferences::PreferenceField> {
+                    vec![#(#fields,)*]
+                }
+
+                fn get(&self, name: &str) -> Option<::#krate::preferences::PreferenceValue> {
+                    match name {
+                        #(#getters,)*
+                        _ => None,
+                    }
+                }
+
+                fn set(&mut self, name: &str, value: ::#krate::preferences::PreferenceValue) -> bool {
+                    match name {
+                        #(#setters,)*
+                        _ => {
+                            let _ = value;
+                            false
+                        },
+                    }
+                }
+            }
+        }
+    }
+    else {
+        panic!("Expected struct");
+    }
+}
+
+/*
+ * Generate the PreferenceChoice implementation for the enums with unit variants, whose labels
+ * can be changed with an attribute like:
+ * #[preference(label = "Compact layout")]
+ */
+fn derive_preference_choice(ast: &Item, krate: &Ident) -> TokenStream {
+    if let Item::Enum(ref enum_item) = *ast {
+        let preference_ident = dummy_ident("preference");
+        let name = &enum_item.ident;
+        let mut labels = vec![];
+        let mut variants = vec![];
+        for variant in &enum_item.variants {
+            if !variant.fields.is_empty() {
+                return quote_spanned! { variant.span() =>
+                    compile_error!("#[derive(PreferenceChoice)] can only be used on enums without parameters");
+                };
+            }
+            let label =
+                match variant.attrs.iter().find(|attr| attr.path.is_ident(&preference_ident)) {
+                    Some(attr) =>
+                        match parse_preference_attribute(attr).and_then(|preference| preference.label) {
+                            Some(label) => label.value(),
+                            None => return quote_spanned! { attr.span() =>
+                                compile_error!("expected #[preference(label = \"Label\")]");
+                            },
+                        },
+                    None => variant.ident.to_string(),
+                };
+            labels.push(label);
+            variants.push(&variant.ident);
+        }
+        let indexes: Vec<_> = (0..variants.len()).collect();
+        let indexes2 = indexes.clone();
+        let variants2 = variants.clone();
+
+        quote_spanned! { krate.span() =>
+            impl ::#krate::preferences::PreferenceChoice for #name {
+                fn choices() -> &'static [&'static str] {
+                    &[#(#labels),*]
+                }
+
+                fn choice_index(&self) -> usize {
+                    match *self {
+                        #(#name::#variants => #indexes,)*
+                    }
+                }
+
+                fn from_choice_index(index: usize) -> Option<Self> {
+                    match index {
+                        #(#indexes2 => Some(#name::#variants2),)*
+                        _ => None,
+                    }
+                }
+            }
+
+            impl ::#krate::preferences::PreferenceType for #name {
+                fn kind() -> ::#krate::preferences::PreferenceKind {
+                    ::#krate::preferences::PreferenceKind::Choice(
+                        <Self as ::#krate::preferences::PreferenceChoice>::choices())
+                }
+
+                fn to_value(&self) -> ::#krate::preferences::PreferenceValue {
+                    ::#krate::preferences::PreferenceValue::Choice(
+                        ::#krate::preferences::PreferenceChoice::choice_index(self))
+                }
+
+                fn from_value(value: ::#krate::preferences::PreferenceValue) -> Option<Self> {
+                    match value {
+                        ::#krate::preferences::PreferenceValue::Choice(index) =>
+                            <Self as ::#krate::preferences::PreferenceChoice>::from_choice_index(index),
+                        _ => None,
+                    }
+                }
+            }
+        }
+    }
+    else {
+        panic!("Expected enum");
+    }
+}
+
+fn parse_preference_attribute(attr: &Attribute) -> Option<PreferenceAttribute> {
+    let mut preference = PreferenceAttribute {
+        label: None,
+        range: None,
+    };
+    if let Ok(Meta::List(list)) = attr.parse_meta() {
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue { ref path, lit: Lit::Str(ref value), .. }))
+                    if path.is_ident(&dummy_ident("label")) => preference.label = Some(value.clone()),
+                NestedMeta::Meta(Meta::List(ref range)) if range.path.is_ident(&dummy_ident("range")) => {
+                    let mut min = None;
+                    let mut max = None;
+                    let mut step = None;
+                    for nested in &range.nested {
+                        match *nested {
+                            NestedMeta::Meta(Meta::NameValue(MetaNameValue { ref path, ref lit, .. })) => {
+                                if path.is_ident(&dummy_ident("min")) {
+                                    min = Some(lit.clone());
+                                }
+                                else if path.is_ident(&dummy_ident("max")) {
+                                    max = Some(lit.clone());
+                                }
+                                else if path.is_ident(&dummy_ident("step")) {
+                                    step = Some(lit.clone());
+                                }
+                                else {
+                                    return None;
+                                }
+                            },
+                            _ => return None,
+                        }
+                    }
+                    preference.range = Some((min?, max?, step));
+                },
+                _ => return None,
+            }
+        }
+    }
+    Some(preference)
+}
+
+/*
+ * Generate the Validate implementation for the structs having fields with an attribute like:
+ * #[validate(length(min = 1, max = 20), email, message = "Invalid email")]
+ */
+fn derive_validate(ast: &Item, krate: &Ident) -> TokenStream {
+    if let Item::Struct(ref struct_item) = *ast {
+        let validate_ident = dummy_ident("validate");
+        let mut checks = vec![];
+        for field in &struct_item.fields {
+            for attr in field.attrs.iter().filter(|attr| attr.path.is_ident(&validate_ident)) {
+                let field_name =
+                    match field.ident {
+                        Some(ref ident) => ident,
+                        None => return quote_spanned! { field.span() =>
+                            compile_error!("#[derive(Validate)] requires named fields");
+                        },
+                    };
+                let (validators, message) =
+                    match parse_validate_attribute(attr, &field_name, krate) {
+                        Some(validate) => validate,
+                        None => return quote_spanned! { attr.span() =>
+                            compile_error!("expected #[validate(length(min = 1, max = 20), range(min = 0, max = 100), email, custom = \"function\", message = \"message\")]");
+                        },
+                    };
+                let name = field_name.to_string();
+                for validator in validators {
+                    checks.push(match message {
+                        Some(ref message) => quote! {
+                            if #validator.is_err() {
+                                errors.add(#name, #message.to_string());
+                            }
+                        },
+                        None => quote! {
+                            if let Err(message) = #validator {
+                                errors.add(#name, message);
+                            }
+                        },
+                    });
+                }
+            }
+        }
+
+        let name = &struct_item.ident;
+        let generics = &struct_item.generics;
+        let generics_without_bound = remove_generic_bounds(generics);
+        let where_clause = gen_where_clause(generics);
+
+        quote_spanned! { krate.span() =>
+            impl #generics ::#krate::validation::Validate for #name #generics_without_bound #where_clause {
+                fn validate(&self) -> ::#krate::validation::ValidationErrors {
+                    #[allow(unused_mut)]
+                    let mut errors = ::#krate::validation::ValidationErrors::new();
+                    #(#checks)*
+                    errors
+                }
+            }
+        }
+    }
+    else {
+        panic!("Expected struct");
+    }
+}
+
+fn parse_validate_attribute(attr: &Attribute, field: &Ident, krate: &Ident) -> Option<(Vec<TokenStream>, Option<LitStr>)> {
+    let mut validators = vec![];
+    let mut message = None;
+    let bounds = |list: &syn::MetaList| -> Option<(TokenStream, TokenStream)> {
+        let mut min = quote! { None };
+        let mut max = quote! { None };
+        for nested in &list.nested {
+            match *nested {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue { ref path, ref lit, .. })) => {
+                    if path.is_ident(&dummy_ident("min")) {
+                        min = quote! { Some(#lit) };
+                    }
+                    else if path.is_ident(&dummy_ident("max")) {
+                        max = quote! { Some(#lit) };
+                    }
+                    else {
+                        return None;
+                    }
+                },
+                _ => return None,
+            }
+        }
+        Some((min, max))
+    };
+    if let Ok(Meta::List(list)) = attr.parse_meta() {
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident(&dummy_ident("email")) =>
+                    validators.push(quote! { ::#krate::validation::email(&self.#field) }),
+                NestedMeta::Meta(Meta::List(ref list)) if list.path.is_ident(&dummy_ident("length")) => {
+                    let (min, max) = bounds(list)?;
+                    validators.push(quote! { ::#krate::validation::length(&self.#field, #min, #max) });
+                },
+                NestedMeta::Meta(Meta::List(ref list)) if list.path.is_ident(&dummy_ident("range")) => {
+                    let (min, max) = bounds(list)?;
+                    validators.push(quote! { ::#krate::validation::range(&self.#field, #min, #max) });
+                },
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue { ref path, lit: Lit::Str(ref value), .. }))
+                    if path.is_ident(&dummy_ident("custom")) =>
+                {
+                    let function: syn::Path = value.parse().ok()?;
+                    validators.push(quote! { #function(&self.#field) });
+                },
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue { ref path, lit: Lit::Str(ref value), .. }))
+                    if path.is_ident(&dummy_ident("message")) => message = Some(value.clone()),
+                _ => return None,
+            }
+        }
+    }
+    else {
+        return None;
+    }
+    Some((validators, message))
+}
+
+/*
+ * Generate the GridRow implementation for the structs having fields with an attribute like:
+ * #[column(title = "Name", editable)]
+ */
+fn derive_grid_row(ast: &Item, krate: &Ident) -> TokenStream {
+    if let Item::Struct(ref struct_item) = *ast {
+        let column_ident = dummy_ident("column");
+        let mut columns = vec![];
+        let mut values = vec![];
+        for field in &struct_item.fields {
+            let attr =
+                match field.attrs.iter().find(|attr| attr.path.is_ident(&column_ident)) {
+                    Some(attr) => attr,
+                    None => continue,
+                };
+            let field_name =
+                match field.ident {
+                    Some(ref ident) => ident,
+                    None => return quote_spanned! { field.span() =>
+                        compile_error!("#[derive(GridRow)] requires named fields");
+                    },
+                };
+            let (title, editable) =
+                match parse_column_attribute(attr) {
+                    Some(column) => column,
+                    None => return quote_spanned! { attr.span() =>
+                        compile_error!("expected #[column(title = \"Title\", editable)]");
+                    },
+                };
+            let name = field_name.to_string();
+            let title = title.map(|title| title.value()).unwrap_or_else(|| name.clone());
+            let typ = &field.ty;
+            columns.push(quote! {
+                ::#krate::data_grid::GridColumn {
+                    editable: #editable,
+                    field: #name,
+                    title: #title,
+                    typ: <#typ as ::#krate::StaticType>::static_type(),
+                }
+            });
+            values.push(quote! {
+                ::#krate::ToValue::to_value(&self.#field_name)
+            });
+        }
+
+        let name = &struct_item.ident;
+        let generics = &struct_item.generics;
+        let generics_without_bound = remove_generic_bounds(generics);
+        let where_clause = gen_where_clause(generics);
+
+        quote_spanned! { krate.span() =>
+            impl #generics ::#krate::data_grid::GridRow for #name #generics_without_bound #where_clause {
+                fn columns() -> Vec<::#krate::data_grid::GridColumn> {
+                    vec![#(#columns,)*]
+                }
+
+                fn values(&self) -> Vec<::#krate::Value> {
+                    vec![#(#values,)*]
+                }
+            }
+        }
+    }
+    else {
+        panic!("Expected struct");
+    }
+}
+
+fn parse_column_attribute(attr: &Attribute) -> Option<(Option<LitStr>, bool)> {
+    let mut title = None;
+    let mut editable = false;
+    if let Ok(Meta::List(list)) = attr.parse_meta() {
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue { ref path, lit: Lit::Str(ref value), .. }))
+                    if path.is_ident(&dummy_ident("title")) => title = Some(value.clone()),
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident(&dummy_ident("editable")) => editable = true,
+                _ => return None,
+            }
+        }
+    }
+    Some((title, editable))
+}
+
+/*
+ * Generate the FromBuilder implementation getting each field from the builder object having the
+ * name of the field as id, or the id of an attribute like:
+ * #[builder(id = "object_id")]
+ */
+fn derive_from_builder(ast: &Item, krate: &Ident) -> TokenStream {
+    if let Item::Struct(ref struct_item) = *ast {
+        let builder_ident = dummy_ident("builder");
+        let mut children = vec![];
+        for field in &struct_item.fields {
+            let field_name =
+                match field.ident {
+                    Some(ref ident) => ident,
+                    None => return quote_spanned! { field.span() =>
+                        compile_error!("#[derive(FromBuilder)] requires named fields");
+                    },
+                };
+            let id =
+                match field.attrs.iter().find(|attr| attr.path.is_ident(&builder_ident)) {
+                    Some(attr) =>
+                        match parse_builder_attribute(attr) {
+                            Some(id) => id.value(),
+                            None => return quote_spanned! { attr.span() =>
+                                compile_error!("expected #[builder(id = \"object_id\")]");
+                            },
+                        },
+                    None => field_name.to_string(),
+                };
+            children.push(quote! {
+                #field_name: ::#krate::builder::builder_object(builder, #id)?
+            });
+        }
+
+        let name = &struct_item.ident;
+        let generics = &struct_item.generics;
+        let generics_without_bound = remove_generic_bounds(generics);
+        let where_clause = gen_where_clause(generics);
+
+        quote_spanned! { krate.span() =>
+            impl #generics ::#krate::builder::FromBuilder for #name #generics_without_bound #where_clause {
+                fn from_builder(builder: &::gtk::Builder) -> Result<Self, ::#krate::builder::BuilderError> {
+                    Ok(#name {
+                        #(#children,)*
+                    })
+                }
+            }
+        }
+    }
+    else {
+        panic!("Expected struct");
+    }
+}
+
+fn parse_builder_attribute(attr: &Attribute) -> Option<LitStr> {
+    if let Ok(Meta::List(list)) = attr.parse_meta() {
+        if let Some(NestedMeta::Meta(Meta::NameValue(MetaNameValue { ref path, lit: Lit::Str(ref value), .. }))) =
+            list.nested.first()
+        {
+            if list.nested.len() == 1 && path.is_ident(&dummy_ident("id")) {
+                return Some(value.clone());
+            }
+        }
+    }
+    None
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut result = String::new();
+    for (index, character) in name.chars().enumerate() {
+        if character.is_uppercase() {
+            if index > 0 {
+                result.push('_');
+            }
+            result.extend(character.to_lowercase());
+        }
+        else {
+            result.push(character);
+        }
+    }
+    result
+}
+
 fn remove_generic_bounds(generics: &Generics) -> Generics {
     let mut generics = generics.clone();
     for param in generics.params.iter_mut() {
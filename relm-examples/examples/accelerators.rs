@@ -0,0 +1,99 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    ContainerExt,
+    Inhibit,
+    Label,
+    LabelExt,
+    WidgetExt,
+    Window,
+    WindowType,
+};
+use relm_derive::Msg;
+use relm::{Accelerators, Relm, Update, Widget, accel, connect};
+
+#[derive(Msg)]
+enum Msg {
+    New,
+    Quit,
+    Reset,
+}
+
+struct Win {
+    // Keep the accelerators for as long as the window lives.
+    _accelerators: Accelerators,
+    count: u32,
+    label: Label,
+    window: Window,
+}
+
+impl Update for Win {
+    type Model = ();
+    type ModelParam = ();
+    type Msg = Msg;
+
+    fn model(_: &Relm<Self>, _: ()) -> () {
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Msg::New => self.count += 1,
+            Msg::Quit => gtk::main_quit(),
+            Msg::Reset => self.count = 0,
+        }
+        self.label.set_text(&format!("{} documents", self.count));
+    }
+}
+
+impl Widget for Win {
+    type Root = Window;
+
+    fn root(&self) -> Self::Root {
+        self.window.clone()
+    }
+
+    fn view(relm: &Relm<Self>, _model: ()) -> Self {
+        let label = Label::new(Some("Press Ctrl+N, Ctrl+R or Ctrl+Q"));
+        let window = Window::new(WindowType::Toplevel);
+        window.add(&label);
+        window.show_all();
+
+        let accelerators = Accelerators::new(&window);
+        accel!(accelerators, relm,
+            "<Ctrl>N" => Msg::New,
+            "<Ctrl>R" => Msg::Reset,
+            "<Ctrl>Q" => Msg::Quit,
+        );
+        connect!(relm, window, connect_delete_event(_, _), return (Some(Msg::Quit), Inhibit(false)));
+
+        Win {
+            _accelerators: accelerators,
+            count: 0,
+            label,
+            window,
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
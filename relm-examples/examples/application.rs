@@ -0,0 +1,84 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gio::{ApplicationFlags, File, FileExt};
+use gtk::{LabelExt, WidgetExt};
+use relm::{ApplicationWidget, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    text: String,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Activated,
+    Open(Vec<File>),
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            text: "Launch the application again to activate it.".to_string(),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Activated => self.model.text = "Activated".to_string(),
+            Open(files) => {
+                let names: Vec<_> = files.iter()
+                    .filter_map(|file| file.get_path())
+                    .map(|path| path.display().to_string())
+                    .collect();
+                self.model.text = format!("Opened {}", names.join(", "));
+            },
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Label {
+                text: &self.model.text,
+            },
+            // The application quits when its last window is closed.
+        }
+    }
+}
+
+impl ApplicationWidget for Win {
+    fn activate_msg() -> Option<Msg> {
+        Some(Activated)
+    }
+
+    fn open_msg(files: Vec<File>, _hint: &str) -> Option<Msg> {
+        Some(Open(files))
+    }
+}
+
+fn main() {
+    let status = relm::run_app::<Win>("com.github.antoyo.relm.Application", ApplicationFlags::HANDLES_OPEN, ())
+        .expect("run_app failed");
+    std::process::exit(status);
+}
@@ -0,0 +1,94 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! This example requires the org.gtk.Settings.FileChooser schema, which is installed with GTK+.
+
+use gtk::{
+    Inhibit,
+    LabelExt,
+    OrientableExt,
+    ToggleButtonExt,
+    WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::{Relm, Setting, SettingsModel, Widget};
+use relm_derive::{Msg, Settings, widget};
+
+use self::Msg::*;
+
+#[derive(Settings)]
+pub struct Model {
+    #[setting(schema = "org.gtk.Settings.FileChooser", key = "show-hidden")]
+    show_hidden: Setting<bool>,
+    text: String,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    SettingChanged(&'static str),
+    Toggle,
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        let model = Model::load_settings();
+        model.watch_settings(relm.stream(), SettingChanged);
+        model
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            // The setting was changed by another application.
+            SettingChanged(key) => {
+                self.model.show_hidden.reload();
+                self.model.text = format!("{} changed", key);
+            },
+            Toggle => {
+                let show_hidden = !*self.model.show_hidden.get();
+                self.model.show_hidden.set(show_hidden);
+            },
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                gtk::CheckButton {
+                    active: *self.model.show_hidden.get(),
+                    label: "Show hidden files in file choosers",
+                    toggled => Toggle,
+                },
+                gtk::Label {
+                    text: &self.model.text,
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
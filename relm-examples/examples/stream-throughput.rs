@@ -0,0 +1,71 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Measure the throughput of the messages going through an `EventStream`.
+//!
+//! Run in release mode to get meaningful numbers:
+//!
+//! ``` Shell
+//! cargo run --release --example stream-throughput
+//! ```
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Instant;
+
+use relm::EventStream;
+
+const MESSAGE_COUNT: usize = 1_000_000;
+
+fn run(name: &str, stream: EventStream<usize>) {
+    let received = Rc::new(Cell::new(0));
+    {
+        let received = received.clone();
+        stream.set_callback(move |_| {
+            received.set(received.get() + 1);
+        });
+    }
+
+    let context = glib::MainContext::default();
+    let start = Instant::now();
+    for i in 0..MESSAGE_COUNT {
+        stream.emit(i);
+    }
+    let emitted = start.elapsed();
+    while received.get() < MESSAGE_COUNT {
+        context.iteration(false);
+    }
+    let total = start.elapsed();
+
+    let per_second = MESSAGE_COUNT as f64 / (total.as_secs() as f64 + total.subsec_nanos() as f64 / 1e9);
+    println!("{}: emitted in {:?}, dispatched in {:?} ({:.0} messages/s)", name, emitted, total, per_second);
+}
+
+fn main() {
+    gtk::init().expect("gtk::init failed");
+
+    run("without observer", EventStream::new());
+    run("preallocated", EventStream::with_capacity(MESSAGE_COUNT));
+
+    let stream = EventStream::new();
+    stream.observe(|_: &usize| ());
+    run("with observer", stream);
+}
@@ -115,7 +115,7 @@ impl Widget for Win {
                 let widget = self.widgets.hbox.add_widget::<Counter>(());
                 self.model.counters.push(widget);
             },
-            Quit => gtk::main_quit(),
+            Quit => relm::shutdown(1000),
             Remove => {
                 if let Some(counter) = self.model.counters.pop() {
                     self.widgets.hbox.remove_widget(counter);
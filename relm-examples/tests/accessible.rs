@@ -0,0 +1,98 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    LabelExt,
+    OrientableExt,
+    WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> () {
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        #[name="window"]
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                gtk::Label {
+                    text: "Document",
+                },
+                #[name="save_button"]
+                gtk::Button {
+                    label: "Save",
+                },
+                #[name="cancel_button"]
+                gtk::Button {
+                    label: "Cancel",
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use atk::Role;
+    use gtk::{Cast, Widget};
+    use relm_test::{find_all_by_accessible_role, find_by_accessible_name, find_by_role_and_name};
+
+    use crate::Win;
+
+    #[test]
+    fn accessible_queries() {
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let window = &widgets.window;
+        let save_button: Widget = widgets.save_button.clone().upcast();
+        let cancel_button: Widget = widgets.cancel_button.clone().upcast();
+
+        assert_eq!(find_by_accessible_name(window, "Save"), Some(save_button.clone()));
+        assert_eq!(find_by_role_and_name(window, Role::PushButton, "Cancel"), Some(cancel_button.clone()));
+        assert!(find_by_role_and_name(window, Role::Label, "Document").is_some());
+        assert_eq!(find_by_role_and_name(window, Role::PushButton, "Document"), None);
+        assert_eq!(find_all_by_accessible_role(window, Role::PushButton), vec![save_button, cancel_button]);
+        assert!(find_by_accessible_name(window, "Delete").is_none());
+    }
+}
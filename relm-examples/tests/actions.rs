@@ -0,0 +1,106 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gio::SimpleActionGroup;
+use gtk::{Inhibit, LabelExt, WidgetExt};
+use relm::{Relm, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    actions: SimpleActionGroup,
+    text: String,
+}
+
+#[derive(Clone, Msg)]
+pub enum Msg {
+    #[action(name = "win.open")]
+    Open,
+    Quit,
+    #[action(name = "win.save", accel = "<Ctrl>S")]
+    Save,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        let actions = SimpleActionGroup::new();
+        relm::addactions(&actions, "win", None, relm.stream());
+        Model {
+            actions: actions,
+            text: String::new(),
+        }
+    }
+
+    fn init_view(&mut self) {
+        self.widgets.window.insert_action_group("win", Some(&self.model.actions));
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Open => self.model.text = "Open".to_string(),
+            Quit => gtk::main_quit(),
+            Save => self.model.text = "Save".to_string(),
+        }
+    }
+
+    view! {
+        #[name="window"]
+        gtk::Window {
+            gtk::Label {
+                text: &self.model.text,
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gio::{ActionGroupExt, MenuModelExt};
+    use relm::Actions;
+    use relm_test::{relm_observer_new, relm_observer_wait};
+
+    use crate::Msg::{self, Open, Quit, Save};
+    use crate::Win;
+
+    #[test]
+    fn actions() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let observer = relm_observer_new!(component, Save);
+        let group = widgets.window.get_action_group("win").expect("action group");
+        group.activate_action("save", None);
+        relm_observer_wait!(let Save = observer);
+
+        assert_eq!(Save.action_name(), Some("win.save"));
+        assert_eq!(Quit.action_name(), None);
+        let accels: Vec<_> = Msg::actions().iter().map(|action| action.accel).collect();
+        assert_eq!(accels, vec![None, Some("<Ctrl>S")]);
+
+        let menu = relm::action_menu(&[("Open", Open), ("Quit", Quit), ("Save", Save)]);
+        assert_eq!(menu.get_n_items(), 2);
+    }
+}
@@ -0,0 +1,137 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{Inhibit, LabelExt, WidgetExt};
+use relm::{Animation, Easing, Relm, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    animation: Option<Animation<f64, Msg>>,
+    frames: u32,
+    relm: Relm<Win>,
+    status: String,
+    value: f64,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Cancel,
+    Finished,
+    Frame(f64),
+    Quit,
+    Start(u32),
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        Model {
+            animation: None,
+            frames: 0,
+            relm: relm.clone(),
+            status: String::new(),
+            value: 0.0,
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Cancel => {
+                if let Some(ref animation) = self.model.animation {
+                    animation.cancel();
+                }
+            },
+            Finished =>
+                self.model.status = format!("Finished at {} after {} frames", self.model.value, self.model.frames),
+            Frame(value) => {
+                self.model.frames += 1;
+                self.model.value = value;
+            },
+            Quit => gtk::main_quit(),
+            Start(duration) => {
+                let animation = relm::animate(&self.widgets.label, self.model.relm.stream(), 0.0, 100.0, duration,
+                    Easing::EaseOut, Frame)
+                    .then(50.0, duration, Easing::Linear)
+                    .on_finished(|| Finished);
+                self.model.animation = Some(animation);
+            },
+        }
+    }
+
+    view! {
+        gtk::Window {
+            #[name="label"]
+            gtk::Label {
+                text: &self.model.status,
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use gtk::LabelExt;
+    use relm::{Easing, Interpolate};
+    use relm_test::{settle, wait_for};
+
+    use crate::Msg::{Cancel, Start};
+    use crate::Win;
+
+    #[test]
+    fn chained_animation() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        component.emit(Start(100));
+        wait_for(|| widgets.label.get_text().starts_with("Finished at 50 "), Duration::from_secs(5));
+        let frames: u32 = widgets.label.get_text().split(' ').nth(4).and_then(|frames| frames.parse().ok())
+            .expect("frames");
+        assert!(frames > 2);
+    }
+
+    #[test]
+    fn cancel() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        component.emit(Start(10_000));
+        settle();
+        component.emit(Cancel);
+        std::thread::sleep(Duration::from_millis(50));
+        settle();
+        assert_eq!(widgets.label.get_text(), "");
+    }
+
+    #[test]
+    fn easings() {
+        for &easing in &[Easing::EaseIn, Easing::EaseInOut, Easing::EaseOut, Easing::Linear] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+        }
+        assert_eq!(10_i32.interpolate(&20, 0.25), 13);
+        assert_eq!((0.0, 10.0).interpolate(&(10.0, 0.0), 0.5), (5.0, 5.0));
+    }
+}
@@ -0,0 +1,151 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    LabelExt,
+    OrientableExt,
+    WidgetExt,
+};
+use gtk::Orientation::{Horizontal, Vertical};
+use relm::{ComponentArena, ComponentKey, ContainerWidget, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+#[derive(Msg)]
+pub enum ItemMsg {
+}
+
+#[widget]
+impl Widget for Item {
+    fn model(value: usize) -> usize {
+        value
+    }
+
+    fn update(&mut self, _event: ItemMsg) {
+    }
+
+    view! {
+        gtk::Label {
+            text: &self.model.to_string(),
+        }
+    }
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    AddMany(usize),
+    Quit,
+    Remove(ComponentKey),
+}
+
+pub struct Model {
+    items: ComponentArena<Item>,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            items: ComponentArena::new(),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            AddMany(count) => {
+                self.model.items.reserve(count);
+                for value in 0..count {
+                    let _ = self.model.items.add_widget(&self.widgets.hbox, value);
+                }
+            },
+            Quit => gtk::main_quit(),
+            Remove(key) => {
+                if let Some(item) = self.model.items.remove(key) {
+                    self.widgets.hbox.remove_widget(item);
+                }
+            },
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="hbox"]
+                gtk::Box {
+                    orientation: Horizontal,
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::ContainerExt;
+    use relm::{ComponentArena, ContainerWidget};
+
+    use crate::Msg::AddMany;
+    use crate::{Item, Win};
+
+    #[test]
+    fn arena() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        component.emit(AddMany(100));
+        while widgets.hbox.get_children().len() != 100 {
+            gtk_test::run_loop();
+        }
+
+        let hbox = gtk::Box::new(gtk::Orientation::Horizontal, 0);
+        let mut arena = ComponentArena::<Item>::with_capacity(3);
+        let first = arena.add_widget(&hbox, 1);
+        let second = arena.add_widget(&hbox, 2);
+        assert_eq!(arena.len(), 2);
+
+        let removed = arena.remove(first).expect("component");
+        hbox.remove_widget(removed);
+        assert!(arena.get(first).is_none());
+        assert!(arena.get(second).is_some());
+
+        let third = arena.add_widget(&hbox, 3);
+        // The slot is reused, but the key of the removed component stays invalid.
+        assert_ne!(third, first);
+        assert!(arena.get(first).is_none());
+        assert!(arena.remove(first).is_none());
+        assert!(arena.get(third).is_some());
+        assert_eq!(arena.len(), 2);
+        assert_eq!(arena.capacity(), 3);
+        assert_eq!(hbox.get_children().len(), 2);
+
+        arena.clear();
+        let fourth = arena.add_widget(&hbox, 4);
+        assert!(arena.get(second).is_none());
+        assert!(arena.get(third).is_none());
+        assert!(arena.get(fourth).is_some());
+    }
+}
@@ -0,0 +1,133 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    ButtonExt,
+    Inhibit,
+    LabelExt,
+    OrientableExt,
+    WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+async fn fetch_data() -> u32 {
+    glib::timeout_future(10).await;
+    42
+}
+
+pub struct Model {
+    data: Option<u32>,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Loaded(u32),
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            data: None,
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Loaded(data) => self.model.data = Some(data),
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="button"]
+                gtk::Button {
+                    clicked => async {
+                        Loaded(fetch_data().await)
+                    },
+                    label: "Load",
+                },
+                #[name="label"]
+                gtk::Label {
+                    text: &self.model.data.map(|data| data.to_string()).unwrap_or_default(),
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::future;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    use gtk::{ButtonExt, LabelExt};
+    use gtk_test::assert_text;
+    use relm::EventStream;
+    use relm_test::{settle, wait_for};
+
+    use crate::Win;
+
+    struct DropGuard(Rc<Cell<bool>>);
+
+    impl Drop for DropGuard {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    #[test]
+    fn async_event() {
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        widgets.button.clicked();
+        assert_text!(widgets.label, "");
+        wait_for(|| widgets.label.get_text() == "42", Duration::from_secs(1));
+
+        // The future is cancelled when the stream is dropped.
+        let dropped = Rc::new(Cell::new(false));
+        let stream = EventStream::<u32>::new();
+        let guard = DropGuard(dropped.clone());
+        stream.spawn_local(async move {
+            let _guard = guard;
+            future::pending::<u32>().await
+        });
+        settle();
+        assert!(!dropped.get());
+        drop(stream);
+        settle();
+        assert!(dropped.get());
+    }
+}
@@ -0,0 +1,109 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    OrientableExt,
+    WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::{BoundValue, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    name: BoundValue<String>,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+    Reset,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            name: BoundValue::new("World".to_string()),
+        }
+    }
+
+    fn init_view(&mut self) {
+        self.model.name.bind_bidirectional(&self.widgets.entry, "text", |name| name.clone(),
+            |value| value.get::<String>().ok().and_then(|text| text));
+        self.model.name.bind(&self.widgets.label, "label", |name| format!("Hello, {}!", name));
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => gtk::main_quit(),
+            Reset => self.model.name.set("World".to_string()),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="entry"]
+                gtk::Entry {
+                },
+                #[name="label"]
+                gtk::Label {
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::{EntryExt, LabelExt};
+    use gtk_test::assert_text;
+
+    use crate::Msg::Reset;
+    use crate::Win;
+
+    #[test]
+    fn bound_value() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let entry = &widgets.entry;
+        let label = &widgets.label;
+        assert_text!(entry, "World");
+        assert_text!(label, "Hello, World!");
+
+        entry.set_text("relm");
+        assert_text!(label, "Hello, relm!");
+
+        component.emit(Reset);
+        while label.get_text() != "Hello, World!" {
+            gtk_test::run_loop();
+        }
+        assert_text!(entry, "World");
+    }
+}
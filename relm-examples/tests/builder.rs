@@ -0,0 +1,182 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Button,
+    ButtonExt,
+    Inhibit,
+    Label,
+    LabelExt,
+    WidgetExt,
+    Window,
+};
+use relm::{Relm, Update, Widget, WidgetTest, connect};
+use relm::builder::FromBuilder;
+use relm_derive::{FromBuilder, Msg};
+
+const UI: &str = r#"
+<interface>
+  <object class="GtkWindow" id="window">
+    <child>
+      <object class="GtkBox">
+        <property name="orientation">vertical</property>
+        <child>
+          <object class="GtkButton" id="inc_button">
+            <property name="label">+</property>
+          </object>
+        </child>
+        <child>
+          <object class="GtkLabel" id="label">
+            <property name="label">0</property>
+          </object>
+        </child>
+      </object>
+    </child>
+  </object>
+</interface>
+"#;
+
+#[derive(Msg)]
+pub enum Msg {
+    Increment,
+    Quit,
+}
+
+#[derive(Clone, FromBuilder)]
+pub struct Widgets {
+    label: Label,
+    #[builder(id = "inc_button")]
+    plus_button: Button,
+    window: Window,
+}
+
+struct Win {
+    counter: i32,
+    widgets: Widgets,
+}
+
+impl Update for Win {
+    type Model = i32;
+    type ModelParam = ();
+    type Msg = Msg;
+
+    fn model(_: &Relm<Self>, _: ()) -> i32 {
+        0
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Msg::Increment => {
+                self.counter += 1;
+                self.widgets.label.set_text(&self.counter.to_string());
+            },
+            Msg::Quit => gtk::main_quit(),
+        }
+    }
+}
+
+impl Widget for Win {
+    type Root = Window;
+
+    fn root(&self) -> Window {
+        self.widgets.window.clone()
+    }
+
+    fn view(relm: &Relm<Self>, counter: i32) -> Self {
+        let widgets = Widgets::from_string(UI).expect("invalid .ui definition");
+        connect!(relm, widgets.plus_button, connect_clicked(_), Msg::Increment);
+        connect!(relm, widgets.window, connect_delete_event(_, _), return (Some(Msg::Quit), Inhibit(false)));
+        widgets.window.show_all();
+        Win {
+            counter,
+            widgets,
+        }
+    }
+}
+
+impl WidgetTest for Win {
+    type Streams = ();
+
+    fn get_streams(&self) -> Self::Streams {
+    }
+
+    type Widgets = Widgets;
+
+    fn get_widgets(&self) -> Self::Widgets {
+        self.widgets.clone()
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::{Button, LabelExt};
+    use gtk_test::assert_text;
+    use relm::builder::{BuilderError, FromBuilder};
+    use relm_derive::FromBuilder;
+    use relm_test::click;
+
+    use crate::{UI, Win};
+
+    #[derive(Debug, FromBuilder)]
+    struct WrongType {
+        #[allow(dead_code)]
+        label: Button,
+    }
+
+    #[derive(Debug, FromBuilder)]
+    struct Missing {
+        #[allow(dead_code)]
+        entry: gtk::Entry,
+    }
+
+    #[test]
+    fn builder_objects() {
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+
+        assert_text!(widgets.label, 0);
+        click(&widgets.plus_button);
+        click(&widgets.plus_button);
+        assert_text!(widgets.label, 2);
+    }
+
+    #[test]
+    fn builder_errors() {
+        gtk::init().expect("gtk::init failed");
+
+        match WrongType::from_string(UI) {
+            Err(error @ BuilderError::WrongType { .. }) =>
+                assert_eq!(error.to_string(), "object label is a GtkLabel instead of a GtkButton"),
+            result => panic!("expected a wrong type error, got {:?}", result),
+        }
+        match Missing::from_string(UI) {
+            Err(BuilderError::MissingObject(id)) => assert_eq!(id, "entry"),
+            result => panic!("expected a missing object error, got {:?}", result),
+        }
+        match Missing::from_string("<interface>") {
+            Err(BuilderError::Builder(_)) => (),
+            result => panic!("expected a builder error, got {:?}", result),
+        }
+    }
+}
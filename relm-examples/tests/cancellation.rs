@@ -0,0 +1,127 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{EntryExt, Inhibit, LabelExt, OrientableExt, WidgetExt};
+use gtk::Orientation::Vertical;
+use relm::{CancellationToken, Relm, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+async fn search(query: String) -> Msg {
+    glib::timeout_future(50).await;
+    Results(format!("Results for {}", query))
+}
+
+pub struct Model {
+    relm: Relm<Win>,
+    request: Option<CancellationToken>,
+    responses: u32,
+    results: String,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+    Results(String),
+    Search(String),
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        Model {
+            relm: relm.clone(),
+            request: None,
+            responses: 0,
+            results: String::new(),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => gtk::main_quit(),
+            Results(results) => {
+                self.model.responses += 1;
+                self.model.results = results;
+            },
+            Search(query) => {
+                // Abort the request for the previous query.
+                if let Some(request) = self.model.request.take() {
+                    request.cancel();
+                }
+                self.model.request = Some(self.model.relm.stream().spawn_local(search(query)));
+            },
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="entry"]
+                gtk::Entry {
+                    changed(entry) => Search(entry.get_text().to_string()),
+                },
+                #[name="label"]
+                gtk::Label {
+                    text: &self.model.results,
+                },
+                #[name="responses"]
+                gtk::Label {
+                    text: &self.model.responses.to_string(),
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use gtk::{EntryExt, LabelExt};
+    use gtk_test::assert_text;
+    use relm_test::{settle, wait_for};
+
+    use crate::Win;
+
+    #[test]
+    fn previous_request_is_cancelled() {
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        widgets.entry.set_text("re");
+        settle();
+        widgets.entry.set_text("relm");
+        wait_for(|| widgets.label.get_text() == "Results for relm", Duration::from_secs(5));
+        // The response to the first query is never received.
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_millis(100) {
+            settle();
+        }
+        assert_text!(widgets.label, "Results for relm");
+        assert_text!(widgets.responses, "1");
+    }
+}
@@ -0,0 +1,114 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    LabelExt,
+    OrientableExt,
+    WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+use self::SidebarMsg::*;
+
+pub struct SidebarModel {
+    selected: String,
+}
+
+#[derive(Msg)]
+pub enum SidebarMsg {
+    Select(String),
+}
+
+#[widget]
+impl Widget for Sidebar {
+    fn model() -> SidebarModel {
+        SidebarModel {
+            selected: String::new(),
+        }
+    }
+
+    fn update(&mut self, event: SidebarMsg) {
+        match event {
+            Select(folder) => self.model.selected = folder,
+        }
+    }
+
+    view! {
+        gtk::Label {
+            text: &self.model.selected,
+        },
+    }
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+    SelectFolder(String),
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> () {
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => gtk::main_quit(),
+            SelectFolder(folder) => self.components.sidebar.emit(Select(folder)),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="sidebar"]
+                Sidebar,
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::LabelExt;
+    use gtk_test::assert_text;
+    use relm_test::send_and_settle;
+
+    use crate::Msg::SelectFolder;
+    use crate::Win;
+
+    #[test]
+    fn message_to_child_component() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        send_and_settle(&component, SelectFolder("Inbox".to_string()));
+        assert_text!(widgets.sidebar, "Inbox");
+    }
+}
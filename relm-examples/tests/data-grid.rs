@@ -0,0 +1,146 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{ContainerExt, Inhibit, LabelExt, OrientableExt, WidgetExt};
+use gtk::Orientation::Vertical;
+use relm::{Component, Relm, Widget, connect};
+use relm::data_grid::DataGrid;
+use relm::data_grid::DataGridMsg::{CellEdited, SetRows};
+use relm_derive::{GridRow, Msg, widget};
+
+use self::Msg::*;
+
+#[derive(Clone, GridRow)]
+pub struct Person {
+    #[column(title = "Name", editable)]
+    name: String,
+    #[column(title = "Age")]
+    age: u32,
+}
+
+pub struct Model {
+    grid: Component<DataGrid<Person>>,
+    people: Vec<Person>,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Edit(usize, &'static str, String),
+    Quit,
+}
+
+fn people() -> Vec<Person> {
+    vec![
+        Person {
+            name: "Alice".to_string(),
+            age: 30,
+        },
+        Person {
+            name: "Bob".to_string(),
+            age: 25,
+        },
+    ]
+}
+
+#[widget]
+impl Widget for Win {
+    fn init_view(&mut self) {
+        self.widgets.container.add(self.model.grid.widget());
+    }
+
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        let grid = relm::create_component::<DataGrid<Person>>(people());
+        connect!(grid@CellEdited(row, field, ref value), relm, Edit(row, field, value.clone()));
+        Model {
+            grid,
+            people: people(),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Edit(row, "name", value) => {
+                self.model.people[row].name = value;
+                self.model.grid.emit(SetRows(self.model.people.clone()));
+                let names: Vec<_> = self.model.people.iter().map(|person| person.name.as_str()).collect();
+                self.widgets.names.set_text(&names.join(", "));
+            },
+            Edit(_, _, _) => (),
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="container"]
+                gtk::Box {
+                },
+                #[name="names"]
+                gtk::Label {
+                    text: "Alice, Bob",
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use glib::{Cast, ObjectExt};
+    use gtk::{BinExt, CellLayoutExt, ContainerExt, LabelExt, TreeModelExt, TreeViewColumnExt, TreeViewExt};
+    use gtk_test::assert_text;
+    use relm_test::settle;
+
+    use crate::Win;
+
+    #[test]
+    fn sort_and_edit() {
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let scrolled_window: gtk::Bin = widgets.container.get_children()[0].clone().downcast().expect("scrolled window");
+        let tree_view: gtk::TreeView = scrolled_window.get_child().expect("tree view").downcast().expect("tree view");
+        let store = tree_view.get_model().expect("model");
+        let first_name = || {
+            let iter = store.get_iter_first().expect("first row");
+            store.get_value(&iter, 0).get::<String>().expect("name").expect("name")
+        };
+        assert_eq!(first_name(), "Alice");
+
+        let age_column = tree_view.get_column(1).expect("age column");
+        age_column.clicked();
+        settle();
+        assert_eq!(first_name(), "Bob");
+
+        // Editing the first row, which is the second person since the rows are sorted by age.
+        let name_column = tree_view.get_column(0).expect("name column");
+        let cell = name_column.get_cells()[0].clone();
+        cell.emit("edited", &[&"0", &"Robert"]).expect("emit edited");
+        settle();
+        assert_text!(widgets.names, "Alice, Robert");
+        assert_eq!(first_name(), "Robert");
+    }
+}
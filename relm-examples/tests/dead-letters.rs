@@ -0,0 +1,130 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+use gtk::{ButtonExt, Inhibit, WidgetExt};
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+#[derive(Msg)]
+pub enum Msg {
+    Dead(&'static str),
+    Increment,
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> () {
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Dead(_) | Increment => (),
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Button {
+                clicked => Increment,
+                label: "+",
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::type_name;
+    use std::cell::RefCell;
+    use std::panic::{self, AssertUnwindSafe};
+    use std::rc::Rc;
+
+    use relm::{DeadLetterPolicy, StreamHandle, forward_dead_letters, set_dead_letter_policy};
+
+    use crate::Msg::{self, Dead, Increment};
+    use crate::Win;
+
+    fn dropped_stream() -> StreamHandle<Msg> {
+        let (component, _, _widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let stream = component.stream();
+        drop(component);
+        assert!(stream.is_dropped());
+        stream
+    }
+
+    #[test]
+    fn forward_dead_letters_to_stream() {
+        let (receiver, _, _widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let dead_letters = Rc::new(RefCell::new(vec![]));
+        let received = dead_letters.clone();
+        receiver.stream().observe(move |msg| {
+            if let Dead(message_type) = *msg {
+                received.borrow_mut().push(message_type);
+            }
+        });
+        forward_dead_letters(&receiver.stream(), |dead_letter| Dead(dead_letter.message_type));
+
+        let stream = dropped_stream();
+        stream.emit(Increment);
+        assert_eq!(*dead_letters.borrow(), vec![type_name::<Msg>()]);
+
+        // The dead letters are logged once the receiver is dropped as well.
+        drop(receiver);
+        stream.emit(Increment);
+        assert_eq!(dead_letters.borrow().len(), 1);
+        set_dead_letter_policy(DeadLetterPolicy::Panic);
+    }
+
+    #[test]
+    fn log_dead_letters() {
+        let stream = dropped_stream();
+        set_dead_letter_policy(DeadLetterPolicy::Log);
+        stream.emit(Increment);
+        set_dead_letter_policy(DeadLetterPolicy::Panic);
+    }
+
+    #[test]
+    fn panic_on_dead_letters_in_debug() {
+        let stream = dropped_stream();
+        set_dead_letter_policy(DeadLetterPolicy::PanicInDebug);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| stream.emit(Increment)));
+        assert_eq!(result.is_err(), cfg!(debug_assertions));
+        set_dead_letter_policy(DeadLetterPolicy::Panic);
+    }
+
+    #[test]
+    #[should_panic(expected = "Trying to call emit() on a dropped EventStream")]
+    fn panic_on_dead_letters() {
+        let stream = dropped_stream();
+        set_dead_letter_policy(DeadLetterPolicy::Panic);
+        stream.emit(Increment);
+    }
+}
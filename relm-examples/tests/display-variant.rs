@@ -0,0 +1,57 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use relm_derive::Msg;
+
+#[derive(Msg)]
+pub enum Msg {
+    Open(String),
+    Quit,
+    Resize { width: i32, height: i32 },
+}
+
+fn main() {
+}
+
+#[cfg(test)]
+mod tests {
+    use relm::DisplayVariant;
+
+    use crate::Msg::{self, *};
+
+    #[test]
+    fn variant_metadata() {
+        let open = Open("file.txt".to_string());
+        let resize = Resize { width: 10, height: 20 };
+        assert_eq!(open.display_variant(), "Open");
+        assert_eq!(resize.display_variant(), "Resize");
+        assert_eq!(open.variant_index(), 0);
+        assert_eq!(Quit.variant_index(), 1);
+        assert_eq!(resize.variant_index(), 2);
+        assert_eq!(Msg::variant_names(), &["Open", "Quit", "Resize"]);
+
+        // The payloads are untouched.
+        if let (Open(name), Resize { width, height }) = (open, resize) {
+            assert_eq!(name, "file.txt");
+            assert_eq!((width, height), (10, 20));
+        }
+    }
+}
@@ -0,0 +1,143 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+use gtk::{
+    ButtonExt,
+    Inhibit,
+    OrientableExt,
+    WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::{InitError, Widget};
+use relm_derive::{Msg, widget};
+
+use self::CounterMsg::*;
+use self::Msg::*;
+
+pub struct CounterModel {
+    count: i32,
+}
+
+#[derive(Msg)]
+pub enum CounterMsg {
+    Increment,
+}
+
+#[widget]
+impl Widget for Counter {
+    fn try_model(start: i32) -> Result<CounterModel, InitError> {
+        if start < 0 {
+            return Err(format!("negative start: {}", start).into());
+        }
+        Ok(CounterModel {
+            count: start,
+        })
+    }
+
+    fn update(&mut self, event: CounterMsg) {
+        match event {
+            Increment => self.model.count += 1,
+        }
+    }
+
+    view! {
+        gtk::Button {
+            label: &self.model.count.to_string(),
+            clicked => Increment,
+        }
+    }
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> () {
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            #[name="container"]
+            gtk::Box {
+                orientation: Vertical,
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::{ButtonExt, ContainerExt};
+    use gtk_test::assert_label;
+    use relm::ContainerWidget;
+
+    use crate::{Counter, Win};
+
+    #[test]
+    fn try_init_error() {
+        gtk::init().expect("gtk::init failed");
+        let error = relm::try_init::<Counter>(-1).err().expect("try_init should fail");
+        assert_eq!(error.to_string(), "negative start: -1");
+
+        let component = relm::try_init::<Counter>(2).expect("try_init failed");
+        assert_label!(component.widget(), "2");
+    }
+
+    #[test]
+    fn try_create_component_error() {
+        gtk::init().expect("gtk::init failed");
+        let error = relm::try_create_component::<Counter>(-3).err().expect("try_create_component should fail");
+        assert_eq!(error.to_string(), "negative start: -3");
+
+        let component = relm::try_create_component::<Counter>(3).expect("try_create_component failed");
+        assert_label!(component.widget(), "3");
+    }
+
+    #[test]
+    fn try_add_widget_error() {
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let container = &widgets.container;
+
+        let error = container.try_add_widget::<Counter>(-5).err().expect("try_add_widget should fail");
+        assert_eq!(error.to_string(), "negative start: -5");
+        // Nothing was added to the container when the initialization failed.
+        assert_eq!(container.get_children().len(), 0);
+
+        let counter = container.try_add_widget::<Counter>(5).expect("try_add_widget failed");
+        assert_eq!(container.get_children().len(), 1);
+        assert_label!(counter.widget(), "5");
+    }
+}
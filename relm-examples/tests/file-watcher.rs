@@ -0,0 +1,108 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::path::PathBuf;
+
+use gtk::{Inhibit, LabelExt, WidgetExt};
+use relm::{FileChange, FileWatcher, Relm, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    dir: PathBuf,
+    status: String,
+    watcher: Option<FileWatcher>,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    FileChanged(PathBuf, FileChange),
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(dir: PathBuf) -> Model {
+        Model {
+            dir,
+            status: String::new(),
+            watcher: None,
+        }
+    }
+
+    fn subscriptions(&mut self, relm: &Relm<Self>) {
+        self.model.watcher = Some(FileWatcher::new(relm.stream(), &[&self.model.dir], FileChanged)
+            .expect("cannot watch the directory"));
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            FileChanged(path, change) => {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                self.model.status.push_str(&format!("{} {:?}\n", name, change));
+            },
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            #[name="label"]
+            gtk::Label {
+                text: &self.model.status,
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(std::env::temp_dir()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::process;
+    use std::time::Duration;
+
+    use gtk::LabelExt;
+    use relm_test::wait_for;
+
+    use crate::Win;
+
+    #[test]
+    fn file_changes() {
+        let dir = env::temp_dir().join(format!("relm-file-watcher-{}", process::id()));
+        fs::create_dir_all(&dir).expect("create dir");
+        let (_component, _, widgets) = relm::init_test::<Win>(dir.clone()).expect("init_test failed");
+
+        fs::write(dir.join("notes.txt"), "first").expect("write file");
+        wait_for(|| widgets.label.get_text().contains("notes.txt Created"), Duration::from_secs(5));
+
+        fs::remove_file(dir.join("notes.txt")).expect("remove file");
+        wait_for(|| widgets.label.get_text().contains("notes.txt Deleted"), Duration::from_secs(5));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
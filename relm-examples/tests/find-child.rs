@@ -0,0 +1,121 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    ButtonExt,
+    Inhibit,
+    LabelExt,
+    OrientableExt,
+    WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+use self::ToolbarMsg::*;
+
+#[derive(Msg)]
+pub enum ToolbarMsg {
+    Save,
+}
+
+#[widget]
+impl Widget for Toolbar {
+    fn model() -> u32 {
+        0
+    }
+
+    fn update(&mut self, event: ToolbarMsg) {
+        match event {
+            Save => {
+                self.model += 1;
+            },
+        }
+    }
+
+    view! {
+        gtk::Box {
+            gtk::Button {
+                clicked => Save,
+                label: "Save",
+                widget_name: "save",
+            },
+            gtk::Label {
+                text: &self.model.to_string(),
+                widget_name: "save_count",
+            },
+        }
+    }
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> () {
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                Toolbar,
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::LabelExt;
+    use gtk_test::assert_text;
+    use relm_test::{click, find_child, find_children};
+
+    use crate::Win;
+
+    #[test]
+    fn find_nested_child() {
+        let (component, _, _) = relm::init_test::<Win>(()).expect("init_test failed");
+        let save_button = find_child::<gtk::Button>(component.widget(), "save").expect("save button");
+        let save_count = find_child::<gtk::Label>(component.widget(), "save_count").expect("save count");
+        assert!(find_child::<gtk::Label>(component.widget(), "save").is_none());
+        assert_eq!(find_children::<gtk::Button>(component.widget()).len(), 1);
+
+        assert_text!(save_count, 0);
+        click(&save_button);
+        assert_text!(save_count, 1);
+    }
+}
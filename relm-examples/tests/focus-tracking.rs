@@ -0,0 +1,127 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    EntryExt,
+    Inhibit,
+    LabelExt,
+    OrientableExt,
+    WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::{FocusTracker, Relm, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    focused: Vec<String>,
+    relm: Relm<Win>,
+    tracker: Option<FocusTracker>,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    FocusChanged(Option<gtk::Widget>),
+    Focus(&'static str),
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn init_view(&mut self) {
+        self.model.tracker = Some(FocusTracker::new(&self.widgets.window, self.model.relm.stream(), FocusChanged));
+    }
+
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        Model {
+            focused: vec![],
+            relm: relm.clone(),
+            tracker: None,
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            FocusChanged(widget) => {
+                let name = widget.map_or("none".to_string(), |widget| widget.get_widget_name().to_string());
+                self.model.focused.push(name);
+            },
+            Focus(name) => {
+                if !self.model.relm.grab_focus(name) {
+                    self.model.focused.push(format!("{} not found", name));
+                }
+            },
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        #[name="window"]
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="focused"]
+                gtk::Label {
+                    text: &self.model.focused.join(", "),
+                },
+                #[name="name_entry"]
+                gtk::Entry {
+                    placeholder_text: Some("Name"),
+                    widget_name: "name",
+                },
+                #[name="email_entry"]
+                gtk::Entry {
+                    placeholder_text: Some("Email"),
+                    widget_name: "email",
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::LabelExt;
+    use relm_test::{assert_focused, settle};
+
+    use crate::Msg::Focus;
+    use crate::Win;
+
+    #[test]
+    fn focus_from_update() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+
+        component.emit(Focus("email"));
+        assert_focused(&widgets.email_entry);
+        component.emit(Focus("name"));
+        assert_focused(&widgets.name_entry);
+        component.emit(Focus("phone"));
+        settle();
+
+        assert!(widgets.focused.get_text().ends_with("email, name, phone not found"));
+    }
+}
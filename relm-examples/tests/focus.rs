@@ -0,0 +1,107 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    EntryExt,
+    Inhibit,
+    OrientableExt,
+    WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> () {
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        #[name="window"]
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="name_entry"]
+                gtk::Entry {
+                    placeholder_text: Some("Name"),
+                },
+                #[name="email_entry"]
+                gtk::Entry {
+                    placeholder_text: Some("Email"),
+                },
+                #[name="submit_button"]
+                gtk::Button {
+                    label: "Submit",
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::{Cast, GtkWindowExt, Widget};
+    use relm_test::{assert_focused, focus_chain, tab_backward, tab_forward};
+
+    use crate::Win;
+
+    #[test]
+    fn focus() {
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let window = &widgets.window;
+
+        let chain = focus_chain(window);
+        let expected: Vec<Widget> = vec![
+            widgets.name_entry.clone().upcast(),
+            widgets.email_entry.clone().upcast(),
+            widgets.submit_button.clone().upcast(),
+        ];
+        assert_eq!(chain, expected);
+
+        window.set_focus(Some(&widgets.submit_button));
+        tab_forward(window);
+        assert_focused(&widgets.name_entry);
+        tab_forward(window);
+        assert_focused(&widgets.email_entry);
+        tab_backward(window);
+        assert_focused(&widgets.name_entry);
+        tab_backward(window);
+        assert_focused(&widgets.submit_button);
+    }
+}
@@ -0,0 +1,142 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    ButtonExt,
+    EditableSignals,
+    EntryExt,
+    Inhibit,
+    LabelExt,
+    OrientableExt,
+    WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::Widget;
+use relm::validation::Validate;
+use relm_derive::{Msg, Validate, widget};
+
+use self::Msg::*;
+
+#[derive(Validate)]
+pub struct Model {
+    #[validate(email, message = "Invalid email address")]
+    email: String,
+    #[validate(length(min = 1, max = 20))]
+    name: String,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    EmailChanged(String),
+    NameChanged(String),
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            email: String::new(),
+            name: String::new(),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            EmailChanged(email) => self.model.email = email,
+            NameChanged(name) => self.model.name = name,
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="name_entry"]
+                gtk::Entry {
+                    changed(entry) => NameChanged(entry.get_text().to_string()),
+                },
+                #[name="name_error"]
+                gtk::Label {
+                    text: &self.model.validate().error("name"),
+                    visible: self.model.validate().has_error("name"),
+                },
+                #[name="email_entry"]
+                gtk::Entry {
+                    changed(entry) => EmailChanged(entry.get_text().to_string()),
+                },
+                #[name="email_error"]
+                gtk::Label {
+                    text: &self.model.validate().error("email"),
+                    visible: self.model.validate().has_error("email"),
+                },
+                #[name="submit"]
+                gtk::Button {
+                    label: "Submit",
+                    sensitive: self.model.validate().is_valid(),
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::{EntryExt, LabelExt, WidgetExt};
+    use gtk_test::assert_text;
+    use relm_test::settle;
+
+    use crate::Win;
+
+    #[test]
+    fn validation() {
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let email_error = &widgets.email_error;
+        let name_error = &widgets.name_error;
+        let submit = &widgets.submit;
+
+        assert_text!(name_error, "This field is required");
+        assert!(name_error.get_visible());
+        assert!(email_error.get_visible());
+        assert!(!submit.get_sensitive());
+
+        widgets.name_entry.set_text("Alice");
+        settle();
+        assert!(!name_error.get_visible());
+        assert!(!submit.get_sensitive());
+
+        widgets.email_entry.set_text("alice@example");
+        settle();
+        assert_text!(email_error, "Invalid email address");
+        assert!(email_error.get_visible());
+
+        widgets.email_entry.set_text("alice@example.com");
+        settle();
+        assert!(!email_error.get_visible());
+        assert!(submit.get_sensitive());
+    }
+}
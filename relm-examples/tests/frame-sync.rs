@@ -0,0 +1,125 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{Inhibit, LabelExt, OrientableExt, WidgetExt};
+use gtk::Orientation::Vertical;
+use relm::{Relm, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    counter: i32,
+    refreshes: i32,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Increment,
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            counter: 0,
+            refreshes: 0,
+        }
+    }
+
+    // Called once per batch of messages for a frame-synchronized widget.
+    fn after_update(&mut self) {
+        self.model.refreshes += 1;
+    }
+
+    fn frame_synchronized() -> bool {
+        true
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Increment => self.model.counter += 1,
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        #[name="window"]
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="label"]
+                gtk::Label {
+                    text: &self.model.counter.to_string(),
+                },
+                #[name="refreshes_label"]
+                gtk::Label {
+                    text: &self.model.refreshes.to_string(),
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::{LabelExt, WidgetExt};
+    use gtk_test::assert_text;
+
+    use crate::Msg::Increment;
+    use crate::Win;
+
+    #[test]
+    fn frame_synchronized_updates() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let refreshes_before = refreshes(&widgets.refreshes_label);
+        for _ in 0..100 {
+            component.emit(Increment);
+        }
+
+        let label = &widgets.label;
+        while label.get_text() != "100" {
+            gtk_test::run_loop();
+        }
+        assert_text!(label, "100");
+        // The view was refreshed once per frame, not after each of the 100 messages.
+        let refreshes = refreshes(&widgets.refreshes_label) - refreshes_before;
+        assert!(refreshes >= 1 && refreshes < 10, "{} refreshes", refreshes);
+
+        // The frame clock does not tick for a hidden window, so the messages are handled right
+        // away.
+        widgets.window.hide();
+        widgets.window.unrealize();
+        component.emit(Increment);
+        gtk_test::run_loop();
+        assert_text!(label, "101");
+    }
+
+    fn refreshes(label: &gtk::Label) -> i32 {
+        label.get_text().parse().expect("refreshes")
+    }
+}
@@ -0,0 +1,123 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{Inhibit, LabelExt, WidgetExt};
+use relm::{FrameTick, FrameTicker, Relm, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    relm: Relm<Win>,
+    ticker: Option<FrameTicker<Msg>>,
+    ticks: u32,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+    SetRunning(bool),
+    Tick(FrameTick),
+}
+
+#[widget]
+impl Widget for Win {
+    fn init_view(&mut self) {
+        self.model.ticker = Some(FrameTicker::new(&self.widgets.label, self.model.relm.stream(), Tick));
+    }
+
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        Model {
+            relm: relm.clone(),
+            ticker: None,
+            ticks: 0,
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => gtk::main_quit(),
+            SetRunning(running) => {
+                if let Some(ref ticker) = self.model.ticker {
+                    ticker.set_enabled(running);
+                }
+            },
+            Tick(_) => self.model.ticks += 1,
+        }
+    }
+
+    view! {
+        gtk::Window {
+            #[name="label"]
+            gtk::Label {
+                text: &self.model.ticks.to_string(),
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use gtk::{LabelExt, WidgetExt};
+    use relm_test::{settle, wait_for};
+
+    use crate::Msg::SetRunning;
+    use crate::Win;
+
+    fn ticks(label: &gtk::Label) -> u32 {
+        label.get_text().parse().expect("ticks")
+    }
+
+    fn assert_no_ticks(label: &gtk::Label) {
+        settle();
+        let count = ticks(label);
+        std::thread::sleep(Duration::from_millis(100));
+        settle();
+        assert_eq!(ticks(label), count);
+    }
+
+    #[test]
+    fn ticks_while_enabled_and_mapped() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        assert_no_ticks(&widgets.label);
+
+        component.emit(SetRunning(true));
+        wait_for(|| ticks(&widgets.label) > 3, Duration::from_secs(5));
+
+        // Unmapped: no ticks.
+        widgets.label.hide();
+        assert_no_ticks(&widgets.label);
+
+        widgets.label.show();
+        let count = ticks(&widgets.label);
+        wait_for(|| ticks(&widgets.label) > count, Duration::from_secs(5));
+
+        component.emit(SetRunning(false));
+        assert_no_ticks(&widgets.label);
+    }
+}
@@ -0,0 +1,145 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    LabelExt,
+    OrientableExt,
+    WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub trait RowData: Clone + 'static {
+    fn text(&self) -> String;
+}
+
+impl RowData for i32 {
+    fn text(&self) -> String {
+        format!("Number {}", self)
+    }
+}
+
+impl RowData for String {
+    fn text(&self) -> String {
+        format!("Text {}", self)
+    }
+}
+
+#[derive(Msg)]
+pub enum RowMsg {
+}
+
+#[widget]
+impl<T> Widget for Row<T>
+    where T: RowData,
+{
+    fn model(data: T) -> T {
+        data
+    }
+
+    fn update(&mut self, _event: RowMsg) {
+    }
+
+    view! {
+        gtk::Label {
+            text: &self.model.text(),
+        }
+    }
+}
+
+#[derive(Msg)]
+pub enum ListMsg {
+}
+
+#[widget]
+impl<T> Widget for ListView<T>
+    where T: RowData,
+{
+    fn model(rows: (T, T)) -> (T, T) {
+        rows
+    }
+
+    fn update(&mut self, _event: ListMsg) {
+    }
+
+    view! {
+        gtk::Box {
+            orientation: Vertical,
+            #[name="first"]
+            Row<T>(self.model.0.clone()),
+            #[name="second"]
+            Row<T>(self.model.1.clone()),
+        }
+    }
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> () {
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                ListView<i32>((1, 2)),
+                ListView<String>(("a".to_string(), "b".to_string())),
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::LabelExt;
+    use relm_test::find_children;
+
+    use crate::Win;
+
+    #[test]
+    fn generic_where_clause() {
+        let (component, _, _) = relm::init_test::<Win>(()).expect("init_test failed");
+        let texts: Vec<_> = find_children::<gtk::Label>(component.widget()).iter()
+            .map(|label| label.get_text().to_string())
+            .collect();
+        assert_eq!(texts, vec!["Number 1", "Number 2", "Text a", "Text b"]);
+    }
+}
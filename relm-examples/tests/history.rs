@@ -0,0 +1,186 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    ButtonExt,
+    Inhibit,
+    GtkWindowExt,
+    LabelExt,
+    OrientableExt,
+    WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::Widget;
+use relm::debug::TimeTravel;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    counter: i32,
+    refreshes: i32,
+}
+
+#[derive(Clone, Msg)]
+pub enum Msg {
+    Decrement,
+    Increment,
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            counter: 0,
+            refreshes: 0,
+        }
+    }
+
+    // Called once per batch of messages for a frame-synchronized widget.
+    fn after_update(&mut self) {
+        self.model.refreshes += 1;
+    }
+
+    fn frame_synchronized() -> bool {
+        true
+    }
+
+    fn set_counter(&mut self, counter: i32) {
+        self.model.counter = counter;
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Decrement => self.model.counter -= 1,
+            Increment => self.model.counter += 1,
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            title: &self.model.refreshes.to_string(),
+            gtk::Box {
+                orientation: Vertical,
+                gtk::Button {
+                    clicked => Increment,
+                    label: "+",
+                },
+                gtk::Label {
+                    text: &self.model.counter.to_string(),
+                },
+                gtk::Button {
+                    clicked => Decrement,
+                    label: "-",
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+impl TimeTravel for Win {
+    type Snapshot = i32;
+
+    fn snapshot(&self) -> i32 {
+        self.model.counter
+    }
+
+    fn restore(&mut self, counter: i32) {
+        self.set_counter(counter);
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::{GtkWindowExt, WidgetExt};
+    use gtk_test::run_loop;
+
+    use crate::Msg::{Decrement, Increment};
+    use crate::Win;
+
+    #[test]
+    fn time_travel() {
+        gtk::init().expect("gtk::init failed");
+        let (component, history) = relm::debug::init_with_history::<Win>(()).expect("init_with_history failed");
+        component.emit(Increment);
+        component.emit(Increment);
+        component.emit(Decrement);
+        while history.position() < 3 {
+            run_loop();
+        }
+        assert_eq!(history.message_names(), vec!["Increment", "Increment", "Decrement"]);
+        assert_eq!(history.snapshot(), 1);
+
+        assert!(history.step_back());
+        assert_eq!(history.snapshot(), 2);
+        history.go_to(0);
+        assert_eq!(history.snapshot(), 0);
+        assert!(!history.step_back());
+
+        history.replay();
+        assert_eq!(history.position(), 3);
+        assert_eq!(history.snapshot(), 1);
+        assert!(!history.step_forward());
+
+        history.go_to(1);
+        component.emit(Increment);
+        while history.position() < 2 {
+            run_loop();
+        }
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.snapshot(), 2);
+    }
+
+    #[test]
+    fn recorded_updates_are_batched() {
+        gtk::init().expect("gtk::init failed");
+        let (component, history) = relm::debug::init_with_history::<Win>(()).expect("init_with_history failed");
+        let window = component.widget();
+        window.show_all();
+        let refreshes_before = refreshes(&window);
+        for _ in 0..100 {
+            component.emit(Increment);
+        }
+        while history.position() < 100 {
+            run_loop();
+        }
+        assert_eq!(history.snapshot(), 100);
+        // The recorded widget is refreshed once per frame, not after each of the 100 messages.
+        let refreshes_after = refreshes(&window);
+        let batch_refreshes = refreshes_after - refreshes_before;
+        assert!(batch_refreshes >= 1 && batch_refreshes < 10, "{} refreshes", batch_refreshes);
+
+        history.go_to(0);
+        history.replay();
+        assert_eq!(history.snapshot(), 100);
+        assert_eq!(refreshes(&window), refreshes_after + 1);
+    }
+
+    fn refreshes(window: &gtk::Window) -> i32 {
+        window.get_title().expect("title").parse().expect("refreshes")
+    }
+}
@@ -0,0 +1,175 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+use gtk::{Inhibit, LabelExt, OrientableExt, WidgetExt};
+use gtk::Orientation::Vertical;
+use relm::{CancellationToken, Relm, Widget};
+use relm::http::{HttpEvent, Request};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    progress_events: u32,
+    relm: Relm<Win>,
+    request: Option<CancellationToken>,
+    text: String,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Cancel,
+    Fetch(String),
+    Fetched(HttpEvent),
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        Model {
+            progress_events: 0,
+            relm: relm.clone(),
+            request: None,
+            text: String::new(),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Cancel => {
+                if let Some(request) = self.model.request.take() {
+                    request.cancel();
+                }
+            },
+            Fetch(url) => {
+                let request = Request::get(&url).report_progress();
+                self.model.request = Some(self.model.relm.fetch(request, Fetched));
+            },
+            Fetched(HttpEvent::Completed(response)) => {
+                self.model.text = format!("{} {} {}", response.status, response.header("content-type").unwrap_or(""),
+                    response.text());
+            },
+            Fetched(HttpEvent::Failed(_)) => self.model.text = "Failed".to_string(),
+            Fetched(HttpEvent::Progress(_)) => self.model.progress_events += 1,
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="label"]
+                gtk::Label {
+                    text: &self.model.text,
+                },
+                #[name="progress_label"]
+                gtk::Label {
+                    text: &self.model.progress_events.to_string(),
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::Duration;
+
+    use gtk::LabelExt;
+    use relm_test::{settle, wait_for};
+
+    use crate::Msg::{Cancel, Fetch};
+    use crate::Win;
+
+    // Serve `responses` to the next connections and return the URL of the server.
+    fn serve(responses: Vec<(&'static str, &'static str)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+        let url = format!("http://{}/", listener.local_addr().expect("local address"));
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut connection, _) = listener.accept().expect("accept");
+                let mut request = vec![];
+                let mut buffer = [0; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let size = connection.read(&mut buffer).expect("read");
+                    if size == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buffer[..size]);
+                }
+                let response = format!("HTTP/1.1 {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\n\
+                    Connection: close\r\n\r\n{}", status, body.len(), body);
+                connection.write_all(response.as_bytes()).expect("write");
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn fetch_responses() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let url = serve(vec![("200 OK", "meow"), ("404 Not Found", "no cat")]);
+
+        component.emit(Fetch(url.clone()));
+        wait_for(|| widgets.label.get_text() == "200 text/plain meow", Duration::from_secs(5));
+        // The body fits in one chunk.
+        assert_eq!(widgets.progress_label.get_text(), "1");
+
+        // An error status is still a response.
+        component.emit(Fetch(url));
+        wait_for(|| widgets.label.get_text() == "404 text/plain no cat", Duration::from_secs(5));
+    }
+
+    #[test]
+    fn fetch_failed() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        // Nothing listens on the port of a dropped listener.
+        let url = {
+            let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+            format!("http://{}/", listener.local_addr().expect("local address"))
+        };
+        component.emit(Fetch(url));
+        wait_for(|| widgets.label.get_text() == "Failed", Duration::from_secs(5));
+    }
+
+    #[test]
+    fn cancelled_fetch() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let url = serve(vec![("200 OK", "meow")]);
+        component.emit(Fetch(url));
+        component.emit(Cancel);
+        settle();
+        thread::sleep(Duration::from_millis(100));
+        settle();
+        assert_eq!(widgets.label.get_text(), "");
+    }
+}
@@ -0,0 +1,94 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{Inhibit, LabelExt, WidgetExt};
+use relm::{Relm, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    relm: Relm<Win>,
+    text: String,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Append(&'static str),
+    Start,
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        Model {
+            relm: relm.clone(),
+            text: String::new(),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Append(text) => self.model.text.push_str(text),
+            Start => {
+                let stream = self.model.relm.stream();
+                stream.emit_idle(Append("idle"));
+                stream.emit(Append("normal "));
+            },
+            Quit => gtk::main_quit(),
+        }
+        self.widgets.label.set_text(&self.model.text);
+    }
+
+    view! {
+        gtk::Window {
+            #[name="label"]
+            gtk::Label {
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::LabelExt;
+    use gtk_test::assert_text;
+
+    use crate::Msg::Start;
+    use crate::Win;
+
+    #[test]
+    fn idle_messages_come_last() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        component.emit(Start);
+        let label = &widgets.label;
+        while label.get_text() != "normal idle" {
+            gtk_test::run_loop();
+        }
+        assert_text!(label, "normal idle");
+    }
+}
@@ -0,0 +1,148 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    LabelExt,
+    OrientableExt,
+    WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+use self::CounterMsg::*;
+
+fn is_digit(key: &gdk::EventKey) -> bool {
+    key.get_keyval().to_unicode().map_or(false, |character| character.is_ascii_digit())
+}
+
+pub struct CounterModel {
+    count: u32,
+}
+
+#[derive(Msg)]
+pub enum CounterMsg {
+    Count(bool),
+}
+
+#[widget]
+impl Widget for Counter {
+    fn model() -> CounterModel {
+        CounterModel {
+            count: 0,
+        }
+    }
+
+    fn update(&mut self, event: CounterMsg) {
+        match event {
+            Count(true) => self.model.count += 1,
+            Count(false) => (),
+        }
+    }
+
+    view! {
+        gtk::Label {
+            text: &self.model.count.to_string(),
+        },
+    }
+}
+
+pub struct Model {
+    letters: String,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Key(Option<char>),
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            letters: String::new(),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Key(Some(character)) if character.is_alphabetic() => self.model.letters.push(character),
+            Key(_) => (),
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                // The digits are not inserted in the entry, but are sent to the message.
+                #[name="entry"]
+                gtk::Entry {
+                    key_press_event(_, key) => (Key(key.get_keyval().to_unicode()), Inhibit(is_digit(key))),
+                },
+                // Same with a message sent to another component.
+                #[name="digit_entry"]
+                gtk::Entry {
+                    key_press_event(_, key) => counter@(Count(is_digit(key)), Inhibit(!is_digit(key))),
+                },
+                #[name="counter"]
+                Counter,
+                #[name="letters"]
+                gtk::Label {
+                    text: &self.model.letters,
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::{EntryExt, LabelExt};
+    use gtk_test::assert_text;
+    use relm_test::{enter_keys, settle};
+
+    use crate::Win;
+
+    #[test]
+    fn inhibit_computed_from_event() {
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+
+        enter_keys(&widgets.entry, "a1b2");
+        settle();
+        assert_text!(widgets.entry, "ab");
+        assert_text!(widgets.letters, "ab");
+
+        enter_keys(&widgets.digit_entry, "a1b2");
+        settle();
+        assert_text!(widgets.digit_entry, "12");
+        assert_text!(widgets.counter, 2);
+    }
+}
@@ -0,0 +1,89 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    LabelExt,
+    WidgetExt,
+};
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    items: Vec<String>,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    LoadData,
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn init_messages(&self) -> Vec<Msg> {
+        vec![LoadData]
+    }
+
+    fn model() -> Model {
+        Model {
+            items: vec![],
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            LoadData => self.model.items = vec!["first".to_string(), "second".to_string()],
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            #[name="label"]
+            gtk::Label {
+                text: &self.model.items.join(", "),
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::LabelExt;
+    use gtk_test::assert_text;
+
+    use crate::Win;
+
+    #[test]
+    fn init_messages() {
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        // The message was handled without running the main loop.
+        assert_text!(widgets.label, "first, second");
+    }
+}
@@ -0,0 +1,168 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+use gtk::{
+    Inhibit,
+    LabelExt,
+    OrientableExt,
+    WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::CounterMsg::*;
+use self::Msg::*;
+
+pub struct CounterModel {
+    counter: i32,
+}
+
+#[derive(Msg)]
+pub enum CounterMsg {
+    Increment,
+}
+
+#[widget]
+impl Widget for Counter {
+    fn model() -> CounterModel {
+        CounterModel {
+            counter: 0,
+        }
+    }
+
+    fn update(&mut self, event: CounterMsg) {
+        match event {
+            Increment => self.model.counter += 1,
+        }
+    }
+
+    view! {
+        gtk::Label {
+            text: &self.model.counter.to_string(),
+        }
+    }
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> () {
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="counter"]
+                Counter,
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    let _inspector = relm::debug::open_inspector();
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use glib::object::Cast;
+    use gtk::{BinExt, TreeIter, TreeModelExt, TreeViewExt};
+    use relm::debug::open_inspector;
+    use relm_test::wait_for;
+
+    use crate::CounterMsg::Increment;
+    use crate::Win;
+
+    struct Row {
+        depth: usize,
+        name: String,
+        widget_type: String,
+        last_message: String,
+    }
+
+    fn rows(model: &gtk::TreeModel) -> Vec<Row> {
+        fn add_rows(model: &gtk::TreeModel, parent: Option<&TreeIter>, depth: usize, rows: &mut Vec<Row>) {
+            if let Some(iter) = model.iter_children(parent) {
+                loop {
+                    let text = |column| model.get_value(&iter, column).get::<String>().ok().flatten()
+                        .unwrap_or_default();
+                    rows.push(Row {
+                        depth,
+                        name: text(0),
+                        widget_type: text(1),
+                        last_message: text(3),
+                    });
+                    add_rows(model, Some(&iter), depth + 1, rows);
+                    if !model.iter_next(&iter) {
+                        break;
+                    }
+                }
+            }
+        }
+
+        let mut rows = vec![];
+        add_rows(model, None, 0, &mut rows);
+        rows
+    }
+
+    #[test]
+    fn component_tree() {
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let window = open_inspector();
+        let scrolled_window = window.get_child().expect("scrolled window")
+            .downcast::<gtk::ScrolledWindow>().expect("scrolled window");
+        let tree_view = scrolled_window.get_child().expect("tree view")
+            .downcast::<gtk::TreeView>().expect("tree view");
+        let model = tree_view.get_model().expect("model");
+
+        // The counter is shown as a child of the window containing it.
+        let rows = rows(&model);
+        let win = rows.iter().position(|row| row.name.ends_with("::Win")).expect("Win row");
+        assert_eq!(rows[win].widget_type, "GtkWindow");
+        let counter = &rows[win + 1];
+        assert!(counter.name.ends_with("::Counter"), "{}", counter.name);
+        assert_eq!(counter.depth, rows[win].depth + 1);
+        assert_eq!(counter.widget_type, "GtkLabel");
+        assert_eq!(counter.last_message, "");
+
+        // The last message is tracked once the inspector is opened.
+        widgets.counter.emit(Increment);
+        wait_for(|| rows(&model).iter().any(|row| row.name.ends_with("::Counter") && row.last_message == "Increment"),
+            Duration::from_secs(5));
+    }
+}
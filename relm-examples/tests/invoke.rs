@@ -0,0 +1,91 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{Inhibit, LabelExt, WidgetExt};
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> () {
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Label {
+                text: "Invoke",
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    use relm_test::wait_for;
+
+    use crate::Win;
+
+    #[test]
+    fn invoke_on_gtk_thread() {
+        let (_component, _, _widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let gtk_thread = thread::current().id();
+
+        // Called from the GTK thread, the closure runs immediately.
+        assert_eq!(relm::invoke_and_wait(|| thread::current().id()), gtk_thread);
+
+        let (sender, receiver) = mpsc::channel();
+        let worker = thread::spawn(move || {
+            let invoked_on = relm::invoke_and_wait(|| thread::current().id());
+            sender.send(invoked_on).expect("send thread id");
+        });
+        let result = std::cell::RefCell::new(None);
+        wait_for(|| {
+            if let Ok(thread_id) = receiver.try_recv() {
+                *result.borrow_mut() = Some(thread_id);
+            }
+            result.borrow().is_some()
+        }, Duration::from_secs(5));
+        worker.join().expect("join worker");
+        assert_eq!(result.into_inner(), Some(gtk_thread));
+    }
+}
@@ -0,0 +1,210 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    LabelExt,
+    OrientableExt,
+    WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::{KeyedChildren, Widget};
+use relm_derive::{Msg, widget};
+
+use self::ItemMsg::*;
+use self::Msg::*;
+
+#[derive(Msg)]
+pub enum ItemMsg {
+    Rename(String),
+}
+
+#[widget]
+impl Widget for Item {
+    fn model(name: String) -> String {
+        name
+    }
+
+    fn update(&mut self, event: ItemMsg) {
+        match event {
+            Rename(name) => self.model = name,
+        }
+    }
+
+    view! {
+        gtk::Label {
+            text: &self.model,
+        }
+    }
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+    SetItems(Vec<(u32, String)>),
+}
+
+pub struct Model {
+    items: Option<KeyedChildren<u32, Item>>,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            items: None,
+        }
+    }
+
+    fn init_view(&mut self) {
+        self.model.items = Some(KeyedChildren::new(&self.widgets.list));
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => gtk::main_quit(),
+            SetItems(items) => {
+                if let Some(ref mut children) = self.model.items {
+                    let _ = children.update(&items, |&(id, _)| id, |(_, name)| name.clone(),
+                        |(_, name)| Some(Rename(name.clone())));
+                }
+            },
+        }
+    }
+
+    view! {
+        gtk::Window {
+            #[name="list"]
+            gtk::Box {
+                orientation: Vertical,
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::{ContainerExt, Label, LabelExt, Orientation, WidgetExt};
+    use relm::{ChildrenDiff, EventStream, KeyedChildren};
+
+    use crate::Item;
+
+    fn texts(container: &gtk::Box) -> Vec<String> {
+        gtk_test::run_loop();
+        container.get_children().into_iter()
+            .map(|child| {
+                let label: Label = relm::Cast::downcast(child).expect("label");
+                label.get_text().to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn keyed_diff() {
+        gtk::init().expect("gtk::init failed");
+        let container = gtk::Box::new(Orientation::Vertical, 0);
+        let mut children = KeyedChildren::<u32, Item>::new(&container);
+        let items = vec![(1, "a"), (2, "b"), (3, "c")];
+        let diff = children.update(&items, |&(id, _)| id, |&(_, name)| name.to_string(), |_| None);
+        assert_eq!(diff, ChildrenDiff { inserted: 3, moved: 0, removed: 0, updated: 0 });
+        assert_eq!(texts(&container), vec!["a", "b", "c"]);
+
+        let first_label = children.get(&1).expect("component").widget().clone();
+
+        let items = vec![(3, "c"), (4, "d"), (1, "a")];
+        let diff = children.update(&items, |&(id, _)| id, |&(_, name)| name.to_string(), |_| None);
+        assert_eq!(diff, ChildrenDiff { inserted: 1, moved: 1, removed: 1, updated: 0 });
+        assert_eq!(texts(&container), vec!["c", "d", "a"]);
+        // The component was kept.
+        assert_eq!(children.get(&1).expect("component").widget(), &first_label);
+    }
+
+    #[test]
+    fn moved_children() {
+        gtk::init().expect("gtk::init failed");
+        let container = gtk::Box::new(Orientation::Vertical, 0);
+        let mut children = KeyedChildren::<u32, Item>::new(&container);
+        let items = vec![(1, "a"), (2, "b"), (3, "c"), (4, "d"), (5, "e")];
+        let _ = children.update(&items, |&(id, _)| id, |&(_, name)| name.to_string(), |_| None);
+
+        // Only the first child is moved, not the ones it used to precede.
+        let items = vec![(2, "b"), (3, "c"), (4, "d"), (5, "e"), (1, "a")];
+        let diff = children.update(&items, |&(id, _)| id, |&(_, name)| name.to_string(), |_| None);
+        assert_eq!(diff, ChildrenDiff { inserted: 0, moved: 1, removed: 0, updated: 0 });
+        assert_eq!(texts(&container), vec!["b", "c", "d", "e", "a"]);
+
+        let items = vec![(5, "e"), (6, "f"), (2, "b"), (4, "d"), (3, "c")];
+        let diff = children.update(&items, |&(id, _)| id, |&(_, name)| name.to_string(), |_| None);
+        assert_eq!(diff, ChildrenDiff { inserted: 1, moved: 2, removed: 1, updated: 0 });
+        assert_eq!(texts(&container), vec!["e", "f", "b", "d", "c"]);
+    }
+
+    #[test]
+    fn reordering() {
+        gtk::init().expect("gtk::init failed");
+        let container = gtk::Box::new(Orientation::Vertical, 0);
+        let mut children = KeyedChildren::<u32, Item>::new(&container);
+        let items = vec![(1, "a"), (2, "b")];
+        let _ = children.update(&items, |&(id, _)| id, |&(_, name)| name.to_string(), |_| None);
+
+        let stream = EventStream::<(usize, usize)>::new();
+        children.enable_reordering(&stream.stream(), |from, to| (from, to));
+        assert!(container.drag_dest_get_target_list().is_some());
+
+        // The children added later can be dragged too.
+        let items = vec![(1, "a"), (2, "b"), (3, "c")];
+        let _ = children.update(&items, |&(id, _)| id, |&(_, name)| name.to_string(), |_| None);
+        for child in container.get_children() {
+            assert!(child.drag_source_get_target_list().is_some());
+        }
+    }
+
+    #[test]
+    fn drop_moves_dropped_child() {
+        gtk::init().expect("gtk::init failed");
+        let container = gtk::Box::new(Orientation::Vertical, 0);
+        let mut children = KeyedChildren::<u32, Item>::new(&container);
+        let mut items = vec![(1, "a"), (2, "b"), (3, "c"), (4, "d"), (5, "e")];
+        let _ = children.update(&items, |&(id, _)| id, |&(_, name)| name.to_string(), |_| None);
+
+        // Handle the message sent when the first child is dropped after the fourth one, as shown
+        // in the documentation of enable_reordering().
+        let (from, to) = (0, 3);
+        let item = items.remove(from);
+        items.insert(to, item);
+        let diff = children.update(&items, |&(id, _)| id, |&(_, name)| name.to_string(), |_| None);
+        assert_eq!(diff.moved, 1);
+        assert_eq!(texts(&container), vec!["b", "c", "d", "a", "e"]);
+
+        // Same when dragging up.
+        let (from, to) = (4, 0);
+        let item = items.remove(from);
+        items.insert(to, item);
+        let diff = children.update(&items, |&(id, _)| id, |&(_, name)| name.to_string(), |_| None);
+        assert_eq!(diff.moved, 1);
+        assert_eq!(texts(&container), vec!["e", "b", "c", "d", "a"]);
+    }
+}
@@ -0,0 +1,190 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    LabelExt,
+    StackExt,
+    StackSwitcherExt,
+    WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::{ContainerWidget, LazyComponent, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+use self::PageMsg::*;
+
+#[derive(Msg)]
+pub enum PageMsg {
+    SetText(String),
+}
+
+#[widget]
+impl Widget for Page {
+    fn model() -> String {
+        String::new()
+    }
+
+    fn update(&mut self, event: PageMsg) {
+        match event {
+            SetText(text) => self.model = text,
+        }
+    }
+
+    view! {
+        gtk::Label {
+            widget_name: "page_label",
+            text: &self.model,
+        }
+    }
+}
+
+#[derive(Msg)]
+pub enum PackedPageMsg {
+}
+
+#[widget]
+impl Widget for PackedPage {
+    fn model() -> () {
+    }
+
+    fn update(&mut self, _event: PackedPageMsg) {
+    }
+
+    view! {
+        gtk::Label {
+            child: {
+                padding: 7,
+            },
+            text: "Packed page",
+        }
+    }
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+}
+
+pub struct Model {
+    page: Option<LazyComponent<Page>>,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            page: None,
+        }
+    }
+
+    fn init_view(&mut self) {
+        // The view of this page is only created when it is first shown.
+        let page = self.widgets.stack.add_widget_lazy::<Page>(());
+        self.widgets.stack.set_child_title(page.placeholder(), Some("Heavy page"));
+        page.emit(SetText("Created lazily".to_string()));
+        self.model.page = Some(page);
+        self.widgets.switcher.set_stack(Some(&self.widgets.stack));
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            #[name="vbox"]
+            gtk::Box {
+                orientation: Vertical,
+                #[name="packed_page"]
+                #[lazy]
+                PackedPage {
+                },
+                #[name="switcher"]
+                gtk::StackSwitcher {
+                },
+                #[name="stack"]
+                gtk::Stack {
+                    gtk::Label {
+                        text: "First page",
+                    },
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::{BoxExt, ContainerExt, Label, LabelExt, StackExt, WidgetExt, Window, WindowType};
+    use gtk_test::{assert_text, find_child_by_name};
+    use relm::ContainerWidget;
+
+    use crate::{Page, Win};
+    use crate::PageMsg::SetText;
+
+    #[test]
+    fn lazy_page() {
+        gtk::init().expect("gtk::init failed");
+        let window = Window::new(WindowType::Toplevel);
+        let stack = gtk::Stack::new();
+        window.add(&stack);
+        let first_page = Label::new(Some("First page"));
+        stack.add(&first_page);
+        let page = stack.add_widget_lazy::<Page>(());
+        window.show_all();
+        gtk_test::run_loop();
+
+        // The messages are buffered until the view is created.
+        page.emit(SetText("Created lazily".to_string()));
+        gtk_test::run_loop();
+        assert!(!page.is_created());
+
+        stack.set_visible_child(page.placeholder());
+        while !page.is_created() {
+            gtk_test::run_loop();
+        }
+        let label: Label = find_child_by_name(page.placeholder(), "page_label").expect("page label");
+        while label.get_text() != "Created lazily" {
+            gtk_test::run_loop();
+        }
+        assert_text!(label, "Created lazily");
+    }
+
+    #[test]
+    fn lazy_attribute() {
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let placeholder = &widgets.packed_page;
+        while placeholder.get_children().is_empty() {
+            gtk_test::run_loop();
+        }
+        // The child properties of the page are set on the placeholder, in the real parent.
+        assert_eq!(widgets.vbox.get_child_padding(placeholder), 7);
+    }
+}
@@ -0,0 +1,180 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    LabelExt,
+    OrientableExt,
+    WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::{KeyedChildren, ListNavigation, Relm, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+#[derive(Msg)]
+pub enum ItemMsg {
+}
+
+#[widget]
+impl Widget for Item {
+    fn model(name: String) -> String {
+        name
+    }
+
+    fn update(&mut self, _event: ItemMsg) {
+    }
+
+    view! {
+        gtk::Label {
+            text: &self.model,
+        }
+    }
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Navigate(ListNavigation),
+    Quit,
+}
+
+pub struct Model {
+    events: Vec<String>,
+    items: Option<KeyedChildren<u32, Item>>,
+    relm: Relm<Win>,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        Model {
+            events: vec![],
+            items: None,
+            relm: relm.clone(),
+        }
+    }
+
+    fn init_view(&mut self) {
+        let mut items = KeyedChildren::new(&self.widgets.list);
+        let _ = items.update(&[1, 2, 3], |&id| id, |id| format!("Item {}", id), |_| None);
+        items.enable_keyboard_navigation(self.model.relm.stream(), Navigate);
+        self.model.items = Some(items);
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Navigate(ListNavigation::Activated(index)) => self.model.events.push(format!("activated {}", index)),
+            Navigate(ListNavigation::SelectionMoved(index)) => self.model.events.push(format!("moved {}", index)),
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="events"]
+                gtk::Label {
+                    text: &self.model.events.join(", "),
+                },
+                gtk::ScrolledWindow {
+                    #[name="list"]
+                    gtk::Box {
+                        orientation: Vertical,
+                    },
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gdk::keys::constants as key;
+    use gtk::{ContainerExt, LabelExt, StateFlags, WidgetExt};
+    use relm::KeyedChildren;
+    use relm_test::enter_key;
+
+    use crate::{Item, Win};
+
+    #[test]
+    fn keyboard_navigation() {
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let list = &widgets.list;
+
+        enter_key(list, key::downarrow);
+        enter_key(list, key::downarrow);
+        enter_key(list, key::End);
+        enter_key(list, key::Return);
+        enter_key(list, key::Home);
+        assert_eq!(widgets.events.get_text(), "moved 0, moved 1, moved 2, activated 2, moved 0");
+
+        let selected: Vec<bool> = list.get_children().iter()
+            .map(|child| child.get_state_flags().contains(StateFlags::SELECTED))
+            .collect();
+        assert_eq!(selected, vec![true, false, false]);
+    }
+
+    #[test]
+    fn hidden_children() {
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let list = &widgets.list;
+        list.get_children()[1].hide();
+
+        enter_key(list, key::downarrow);
+        enter_key(list, key::downarrow);
+        enter_key(list, key::Return);
+        enter_key(list, key::uparrow);
+        assert_eq!(widgets.events.get_text(), "moved 0, moved 2, activated 2, moved 0");
+    }
+
+    #[test]
+    fn selection_follows_item() {
+        gtk::init().expect("gtk::init failed");
+        let container = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        let mut items = KeyedChildren::<u32, Item>::new(&container);
+        let _ = items.update(&[1, 2, 3], |&id| id, |id| format!("Item {}", id), |_| None);
+        items.select(Some(1));
+
+        // Inserting an item before the selected one does not move the selection to another item.
+        let _ = items.update(&[0, 1, 2, 3], |&id| id, |id| format!("Item {}", id), |_| None);
+        assert_eq!(items.selected(), Some(2));
+        let selected: Vec<bool> = container.get_children().iter()
+            .map(|child| child.get_state_flags().contains(StateFlags::SELECTED))
+            .collect();
+        assert_eq!(selected, vec![false, false, true, false]);
+
+        let _ = items.update(&[3, 2, 0], |&id| id, |id| format!("Item {}", id), |_| None);
+        assert_eq!(items.selected(), Some(1));
+
+        // The selection is removed with its item.
+        let _ = items.update(&[3, 0], |&id| id, |id| format!("Item {}", id), |_| None);
+        assert_eq!(items.selected(), None);
+        assert!(container.get_children().iter()
+            .all(|child| !child.get_state_flags().contains(StateFlags::SELECTED)));
+    }
+}
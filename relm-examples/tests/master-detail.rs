@@ -0,0 +1,173 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::fmt::{self, Display, Formatter};
+
+use gtk::{ContainerExt, Inhibit, LabelExt, OrientableExt, WidgetExt};
+use gtk::Orientation::Vertical;
+use relm::{Component, Relm, Widget, connect};
+use relm::master_detail::{Detail, MasterDetail};
+use relm::master_detail::MasterDetailMsg::{SelectionChanged, SetItems};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+#[derive(Clone)]
+pub struct Contact {
+    name: String,
+}
+
+impl Display for Contact {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "{}", self.name)
+    }
+}
+
+#[derive(Msg)]
+pub enum ContactMsg {
+    Set(Contact),
+}
+
+#[widget]
+impl Widget for ContactView {
+    fn model(contact: Contact) -> Contact {
+        contact
+    }
+
+    fn update(&mut self, event: ContactMsg) {
+        match event {
+            ContactMsg::Set(contact) => self.model.name = contact.name,
+        }
+    }
+
+    view! {
+        gtk::Label {
+            text: &self.model.name,
+        }
+    }
+}
+
+impl Detail<Contact> for ContactView {
+    fn model_param(contact: &Contact) -> Contact {
+        contact.clone()
+    }
+
+    fn refresh(contact: &Contact) -> Option<ContactMsg> {
+        Some(ContactMsg::Set(contact.clone()))
+    }
+}
+
+fn contacts(names: &[&str]) -> Vec<Contact> {
+    names.iter()
+        .map(|name| Contact {
+            name: name.to_string(),
+        })
+        .collect()
+}
+
+pub struct Model {
+    contacts: Component<MasterDetail<Contact, ContactView>>,
+    selected: String,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+    Rename,
+    Selected(Option<usize>),
+}
+
+#[widget]
+impl Widget for Win {
+    fn init_view(&mut self) {
+        self.widgets.container.add(self.model.contacts.widget());
+    }
+
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        let contacts = relm::create_component::<MasterDetail<Contact, ContactView>>(contacts(&["Alice", "Bob"]));
+        connect!(contacts@SelectionChanged(index), relm, Selected(index));
+        Model {
+            contacts,
+            selected: "None".to_string(),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => gtk::main_quit(),
+            Rename => self.model.contacts.emit(SetItems(contacts(&["Alice", "Robert"]))),
+            Selected(index) => self.model.selected = format!("{:?}", index),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="container"]
+                gtk::Box {
+                },
+                #[name="selected"]
+                gtk::Label {
+                    text: &self.model.selected,
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use glib::Cast;
+    use gtk::{BinExt, ContainerExt, LabelExt, ListBoxExt, PanedExt};
+    use gtk_test::assert_text;
+    use relm_test::{send_and_settle, settle};
+
+    use crate::Msg::Rename;
+    use crate::Win;
+
+    #[test]
+    fn master_detail() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let paned: gtk::Paned = widgets.container.get_children()[0].clone().downcast().expect("paned");
+        let scrolled_window: gtk::Bin = paned.get_child1().expect("sidebar").downcast().expect("scrolled window");
+        let viewport: gtk::Bin = scrolled_window.get_child().expect("viewport").downcast().expect("viewport");
+        let list: gtk::ListBox = viewport.get_child().expect("list").downcast().expect("list");
+        let detail_box: gtk::Container = paned.get_child2().expect("detail").downcast().expect("detail box");
+        assert_text!(widgets.selected, "None");
+        assert!(detail_box.get_children().is_empty());
+
+        list.select_row(list.get_row_at_index(1).as_ref());
+        settle();
+        assert_text!(widgets.selected, "Some(1)");
+        let detail: gtk::Label = detail_box.get_children()[0].clone().downcast().expect("detail label");
+        assert_text!(detail, "Bob");
+
+        send_and_settle(&component, Rename);
+        assert_text!(detail, "Robert");
+        assert_text!(widgets.selected, "Some(1)");
+    }
+}
@@ -0,0 +1,137 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    LabelExt,
+    NotebookExt,
+    OrientableExt,
+    WidgetExt,
+};
+use gtk::Orientation::Horizontal;
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    page: u32,
+    unread: u32,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    PageSwitched(u32),
+    Quit,
+    ShowPage(u32),
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            page: 0,
+            unread: 3,
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            PageSwitched(page) => self.model.page = page,
+            Quit => gtk::main_quit(),
+            ShowPage(page) => self.model.page = page,
+        }
+    }
+
+    view! {
+        gtk::Window {
+            #[name="notebook"]
+            gtk::Notebook {
+                current_page: Some(self.model.page),
+                switch_page(_, _, page) => PageSwitched(page),
+                #[name="settings"]
+                gtk::Label {
+                    tab: {
+                        label_text: "Settings",
+                    },
+                    text: "Settings page",
+                },
+                #[name="inbox"]
+                gtk::Label {
+                    tab: {
+                        label: view! {
+                            gtk::Box {
+                                orientation: Horizontal,
+                                gtk::Label {
+                                    text: "Inbox",
+                                },
+                                #[name="unread_label"]
+                                gtk::Label {
+                                    text: &self.model.unread.to_string(),
+                                },
+                            }
+                        },
+                        reorderable: true,
+                    },
+                    text: "Inbox page",
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::{LabelExt, NotebookExt, WidgetExt};
+    use gtk_test::assert_text;
+    use relm_test::{send_and_settle, settle};
+
+    use crate::Msg::ShowPage;
+    use crate::Win;
+
+    #[test]
+    fn notebook_pages() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let notebook = &widgets.notebook;
+        let settings = &widgets.settings;
+        let inbox = &widgets.inbox;
+
+        assert_eq!(notebook.get_n_pages(), 2);
+        assert_eq!(notebook.get_tab_label_text(settings).as_deref(), Some("Settings"));
+        assert!(widgets.unread_label.is_ancestor(&notebook.get_tab_label(inbox).expect("tab label")));
+        assert_text!(widgets.unread_label, 3);
+        assert!(notebook.get_tab_reorderable(inbox));
+
+        assert_eq!(notebook.get_current_page(), Some(0));
+        send_and_settle(&component, ShowPage(1));
+        assert_eq!(notebook.get_current_page(), Some(1));
+
+        // Switching the page from the UI updates the model, so the binding doesn't switch it back.
+        notebook.set_current_page(Some(0));
+        settle();
+        assert_eq!(notebook.get_current_page(), Some(0));
+    }
+}
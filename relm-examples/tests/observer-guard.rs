@@ -0,0 +1,90 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{ButtonExt, Inhibit, WidgetExt};
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+#[derive(Msg)]
+pub enum Msg {
+    Increment,
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> () {
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Increment => (),
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Button {
+                clicked => Increment,
+                label: "+",
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use crate::Msg::Increment;
+    use crate::Win;
+
+    #[test]
+    fn observer_removed_on_drop() {
+        let (component, _, _widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let stream = component.stream();
+
+        let count = Rc::new(Cell::new(0));
+        let counter = count.clone();
+        let guard = stream.observe_scoped(move |_| counter.set(counter.get() + 1));
+        stream.emit(Increment);
+        assert_eq!(count.get(), 1);
+
+        drop(guard);
+        stream.emit(Increment);
+        assert_eq!(count.get(), 1);
+
+        // A forgotten guard keeps its observer.
+        let counter = count.clone();
+        stream.observe_scoped(move |_| counter.set(counter.get() + 1)).forget();
+        stream.emit(Increment);
+        assert_eq!(count.get(), 2);
+    }
+}
@@ -0,0 +1,92 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::fs::File;
+
+use gtk::{Inhibit, LabelExt, WidgetExt};
+use relm::{Relm, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    relm: Relm<Win>,
+    text: String,
+}
+
+// File cannot be cloned, so this message type cannot be observed with a predicate.
+#[derive(Msg)]
+pub enum Msg {
+    Open(String),
+    Opened(File, String),
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        Model {
+            relm: relm.clone(),
+            text: String::new(),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Open(path) => {
+                if let Ok(file) = File::open(&path) {
+                    self.model.relm.stream().emit(Opened(file, path));
+                }
+            },
+            Opened(_, path) => self.model.text = path,
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Label {
+                text: &self.model.text,
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use relm_test::relm_observer_new;
+
+    use crate::Msg::{Open, Opened};
+    use crate::Win;
+
+    #[test]
+    fn observe_non_clone_message() {
+        let (component, _, _) = relm::init_test::<Win>(()).expect("init_test failed");
+        let observer = relm_observer_new!(component, Opened(_, path) => path.clone());
+        component.emit(Open("Cargo.toml".to_string()));
+        assert_eq!(observer.wait(), "Cargo.toml");
+    }
+}
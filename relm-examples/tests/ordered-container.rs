@@ -0,0 +1,131 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    LabelExt,
+    OrientableExt,
+    WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::{Component, OrderedContainerWidget, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+#[derive(Msg)]
+pub enum ItemMsg {
+}
+
+#[widget]
+impl Widget for Item {
+    fn model(text: String) -> String {
+        text
+    }
+
+    fn update(&mut self, _event: ItemMsg) {
+    }
+
+    view! {
+        gtk::Label {
+            text: &self.model,
+        },
+    }
+}
+
+pub struct Model {
+    items: Vec<Component<Item>>,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Insert(usize, String),
+    MoveUp(usize),
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            items: vec![],
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Insert(index, text) => {
+                let item = self.widgets.list.insert_widget_at::<Item>(index, text);
+                self.model.items.insert(index, item);
+            },
+            MoveUp(index) => {
+                if index > 0 {
+                    self.widgets.list.reorder_widget(&self.model.items[index], index - 1);
+                    self.model.items.swap(index, index - 1);
+                }
+            },
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            #[name="list"]
+            gtk::Box {
+                orientation: Vertical,
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::{Cast, ContainerExt, Label, LabelExt};
+    use relm_test::send_and_settle;
+
+    use crate::Msg::{Insert, MoveUp};
+    use crate::Win;
+
+    fn texts(list: &gtk::Box) -> Vec<String> {
+        list.get_children().into_iter()
+            .map(|child| child.downcast::<Label>().expect("label").get_text().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn insert_and_reorder() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let list = &widgets.list;
+
+        send_and_settle(&component, Insert(0, "first".to_string()));
+        send_and_settle(&component, Insert(1, "third".to_string()));
+        send_and_settle(&component, Insert(1, "second".to_string()));
+        assert_eq!(texts(list), ["first", "second", "third"]);
+
+        send_and_settle(&component, MoveUp(2));
+        assert_eq!(texts(list), ["first", "third", "second"]);
+    }
+}
@@ -0,0 +1,143 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Align,
+    ButtonExt,
+    Inhibit,
+    LabelExt,
+    OverlayExt,
+    WidgetExt,
+};
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+#[derive(Msg)]
+pub enum BadgeMsg {
+}
+
+#[widget]
+impl Widget for Badge {
+    fn model() -> () {
+    }
+
+    fn update(&mut self, _msg: BadgeMsg) {
+    }
+
+    view! {
+        gtk::Label {
+            text: "new",
+        },
+    }
+}
+
+pub struct Model {
+    counter: i32,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Increment,
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            counter: 0,
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Increment => self.model.counter += 1,
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            #[name="overlay"]
+            gtk::Overlay {
+                #[name="label"]
+                gtk::Label {
+                    text: &self.model.counter.to_string(),
+                },
+                #[name="fab"]
+                #[overlay]
+                gtk::Button {
+                    clicked => Increment,
+                    halign: Align::End,
+                    valign: Align::End,
+                    label: "+",
+                },
+                #[name="badge"]
+                #[overlay]
+                Badge {
+                    overlay: {
+                        pass_through: true,
+                    },
+                    halign: Align::Start,
+                    valign: Align::Start,
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::{Align, BinExt, Cast, LabelExt, OverlayExt, Widget, WidgetExt};
+    use gtk_test::{assert_text, click};
+
+    use crate::Win;
+
+    #[test]
+    fn overlay_children() {
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let overlay = &widgets.overlay;
+        let label = &widgets.label;
+        let fab = &widgets.fab;
+        let badge = &widgets.badge;
+
+        // Only the main child is added with ContainerExt::add().
+        assert_eq!(overlay.get_child().as_ref(), Some(label.upcast_ref::<Widget>()));
+        assert_eq!(fab.get_parent().as_ref(), Some(overlay.upcast_ref::<Widget>()));
+        assert_eq!(badge.get_parent().as_ref(), Some(overlay.upcast_ref::<Widget>()));
+
+        assert_eq!(fab.get_halign(), Align::End);
+        assert_eq!(fab.get_valign(), Align::End);
+        assert!(!overlay.get_overlay_pass_through(fab));
+        assert!(overlay.get_overlay_pass_through(badge));
+
+        assert_text!(label, 0);
+        click(fab);
+        assert_text!(label, 1);
+    }
+}
@@ -0,0 +1,96 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{Inhibit, LabelExt, WidgetExt};
+use relm::{ComponentPanicked, Relm, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    text: String,
+}
+
+#[derive(Clone, Msg)]
+pub enum Msg {
+    Crash,
+    Panicked(ComponentPanicked),
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        relm::isolate_panics(relm.stream(), Panicked);
+        Model {
+            text: String::new(),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Crash => panic!("crash requested"),
+            Panicked(info) => self.model.text = info.panic_message,
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            #[name="label"]
+            gtk::Label {
+                text: &self.model.text,
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::LabelExt;
+    use gtk_test::assert_text;
+    use relm_test::{relm_observer_new, relm_observer_wait};
+
+    use crate::Msg::{Crash, Panicked};
+    use crate::Win;
+
+    #[test]
+    fn panic_isolation() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let observer = relm_observer_new!(component, Panicked(_));
+        component.emit(Crash);
+        relm_observer_wait!(let Panicked(info) = observer);
+        assert_eq!(info.message, "Crash");
+        assert_eq!(info.panic_message, "crash requested");
+        relm::stop_isolating_panics();
+
+        let label = &widgets.label;
+        while label.get_text() != "crash requested" {
+            gtk_test::run_loop();
+        }
+        assert_text!(label, "crash requested");
+    }
+}
@@ -0,0 +1,145 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+use gtk::{ButtonExt, Inhibit, WidgetExt};
+use relm::Widget;
+use relm_derive::{Msg, PersistentModel, widget};
+use serde::{Deserialize, Serialize};
+
+use self::Msg::*;
+
+#[derive(Debug, Default, Deserialize, PartialEq, PersistentModel, Serialize)]
+#[persist(file = "counter.json")]
+pub struct Model {
+    counter: i32,
+    // Added after the first version of the model: the files saved before have no zoom.
+    #[serde(default)]
+    zoom: u32,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Increment,
+    Quit,
+    Save,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        relm::persist::load().unwrap_or_default()
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Increment => self.model.counter += 1,
+            Quit => {
+                self.update(Save);
+                gtk::main_quit();
+            },
+            Save => {
+                if let Err(error) = relm::persist::save(&self.model) {
+                    eprintln!("Cannot save the state: {}", error);
+                }
+            },
+        }
+    }
+
+    view! {
+        gtk::Window {
+            #[name="button"]
+            gtk::Button {
+                clicked => Increment,
+                label: &self.model.counter.to_string(),
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::io::ErrorKind;
+    use std::path::PathBuf;
+    use std::process;
+
+    use gtk::ButtonExt;
+    use gtk_test::assert_label;
+    use relm::persist::{self, load, save_to, state_path};
+    use relm_test::settle;
+    use serde::Serialize;
+
+    use crate::Msg::{Increment, Save};
+    use crate::{Model, Win};
+
+    // The first version of the model, before the zoom was added.
+    #[derive(Serialize)]
+    struct ModelV1 {
+        counter: i32,
+    }
+
+    fn state_dir(name: &str) -> PathBuf {
+        let state_dir = env::temp_dir().join(format!("relm-persist-{}-{}", name, process::id()));
+        persist::set_state_dir(&state_dir);
+        state_dir
+    }
+
+    #[test]
+    fn save_and_restore() {
+        let state_dir = state_dir("round-trip");
+        assert_eq!(state_path::<Model>(), state_dir.join("counter.json"));
+        let error = load::<Model>().expect_err("the model should not be saved yet");
+        assert_eq!(error.kind(), ErrorKind::NotFound);
+
+        let (component, _, _widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        component.emit(Increment);
+        component.emit(Increment);
+        component.emit(Save);
+        settle();
+        drop(component);
+        assert_eq!(load::<Model>().expect("load failed"), Model { counter: 2, zoom: 0 });
+
+        // The saved model is restored at startup.
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        assert_label!(widgets.button, "2");
+        let _ = fs::remove_dir_all(&state_dir);
+    }
+
+    #[test]
+    fn load_older_model() {
+        let state_dir = state_dir("older");
+        save_to(state_path::<Model>(), &ModelV1 { counter: 5 }).expect("save_to failed");
+        assert_eq!(load::<Model>().expect("load failed"), Model { counter: 5, zoom: 0 });
+
+        // A file which is not a model is reported as invalid data.
+        fs::write(state_path::<Model>(), "[1, 2]").expect("write failed");
+        let error = load::<Model>().expect_err("the model should be invalid");
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        let _ = fs::remove_dir_all(&state_dir);
+    }
+}
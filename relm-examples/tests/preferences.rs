@@ -0,0 +1,139 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{ButtonExt, GtkWindowExt, Inhibit, LabelExt, OrientableExt, WidgetExt};
+use gtk::Orientation::Vertical;
+use relm::{Component, Relm, Widget, connect};
+use relm::preferences::PreferencesDialog;
+use relm::preferences::PreferencesMsg::Changed;
+use relm_derive::{Msg, PreferenceChoice, Preferences, widget};
+
+use self::Msg::*;
+
+#[derive(Clone, PreferenceChoice)]
+pub enum Layout {
+    Compact,
+    #[preference(label = "Wide layout")]
+    Wide,
+}
+
+#[derive(Clone, Preferences)]
+pub struct Prefs {
+    #[preference(label = "Dark theme")]
+    dark_theme: bool,
+    #[preference(label = "Font size", range(min = 6, max = 72))]
+    font_size: u32,
+    #[preference(label = "Layout")]
+    layout: Layout,
+    #[preference(label = "User name")]
+    user_name: String,
+}
+
+pub struct Model {
+    dialog: Component<PreferencesDialog<Prefs>>,
+    prefs: Prefs,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    PrefsChanged(Prefs),
+    Quit,
+    ShowPreferences,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        let prefs = Prefs {
+            dark_theme: false,
+            font_size: 12,
+            layout: Layout::Compact,
+            user_name: String::new(),
+        };
+        let dialog = relm::create_component::<PreferencesDialog<Prefs>>(prefs.clone());
+        connect!(dialog@Changed(_, ref prefs), relm, PrefsChanged(prefs.clone()));
+        Model {
+            dialog,
+            prefs,
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            PrefsChanged(prefs) => self.model.prefs = prefs,
+            Quit => gtk::main_quit(),
+            ShowPreferences => self.model.dialog.widget().present(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                gtk::Button {
+                    clicked => ShowPreferences,
+                    label: "Preferences",
+                },
+                #[name="font_size"]
+                gtk::Label {
+                    text: &self.model.prefs.font_size.to_string(),
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use glib::Cast;
+    use gtk::{BinExt, GridExt, GtkWindowExt, LabelExt, SpinButtonExt};
+    use gtk_test::assert_text;
+    use relm_test::wait_for;
+
+    use crate::Win;
+
+    #[test]
+    fn preferences_dialog() {
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let font_size = &widgets.font_size;
+        assert_text!(font_size, "12");
+
+        let dialog = gtk::Window::list_toplevels().into_iter()
+            .filter_map(|window| window.downcast::<gtk::Window>().ok())
+            .find(|window| window.get_title().map(|title| title == "Preferences").unwrap_or(false))
+            .expect("preferences window");
+        let grid: gtk::Grid = dialog.get_child().expect("grid").downcast().expect("grid");
+        let label: gtk::Label = grid.get_child_at(0, 1).expect("label").downcast().expect("label");
+        assert_text!(label, "Font size");
+
+        let spin: gtk::SpinButton = grid.get_child_at(1, 1).expect("spin button").downcast().expect("spin button");
+        assert_eq!(spin.get_value(), 12.0);
+        spin.set_value(20.0);
+        wait_for(|| font_size.get_text() == "20", Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,122 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use glib::Priority;
+use gtk::{Inhibit, WidgetExt};
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+type Log = Rc<RefCell<Vec<&'static str>>>;
+
+pub struct Model {
+    log: Log,
+    name: &'static str,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+    Record,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model((log, name): (Log, &'static str)) -> Model {
+        Model {
+            log,
+            name,
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => gtk::main_quit(),
+            Record => self.model.log.borrow_mut().push(self.model.name),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+pub struct LogViewerModel {
+    log: Log,
+}
+
+#[widget]
+impl Widget for LogViewer {
+    fn model(log: Log) -> LogViewerModel {
+        LogViewerModel {
+            log,
+        }
+    }
+
+    fn priority() -> Priority {
+        glib::PRIORITY_LOW
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => (),
+            Record => self.model.log.borrow_mut().push("log viewer"),
+        }
+    }
+
+    view! {
+        gtk::Label {
+        }
+    }
+}
+
+fn main() {
+    Win::run((Rc::new(RefCell::new(vec![])), "window")).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use relm_test::settle;
+
+    use crate::{LogViewer, Win};
+    use crate::Msg::Record;
+
+    #[test]
+    fn low_priority_messages_are_handled_last() {
+        let log = Rc::new(RefCell::new(vec![]));
+        let (component, _, _) = relm::init_test::<Win>((log.clone(), "window")).expect("init_test failed");
+        let log_viewer = relm::create_component::<LogViewer>(log.clone());
+
+        log_viewer.emit(Record);
+        component.emit(Record);
+        settle();
+        assert_eq!(*log.borrow(), vec!["window", "log viewer"]);
+    }
+}
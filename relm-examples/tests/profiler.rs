@@ -0,0 +1,109 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+use std::time::Duration;
+
+use gtk::{Inhibit, WidgetExt};
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+#[derive(Msg)]
+pub enum Msg {
+    Fast,
+    Quit,
+    Slow,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> () {
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Fast => (),
+            Quit => gtk::main_quit(),
+            Slow => std::thread::sleep(Duration::from_millis(50)),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    relm::debug::start_profiling();
+    Win::run(()).expect("Win::run failed");
+    relm::debug::log_profile_report(10);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use relm::debug::{profile_report, reset_profile, set_frame_budget, start_profiling, stop_profiling};
+    use relm_test::settle;
+
+    use crate::Msg::{Fast, Slow};
+    use crate::Win;
+
+    #[test]
+    fn slowest_handlers_first() {
+        let (component, _, _widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        settle();
+        reset_profile();
+        set_frame_budget(Duration::from_millis(20));
+        start_profiling();
+        for _ in 0..3 {
+            component.emit(Fast);
+        }
+        component.emit(Slow);
+        settle();
+        stop_profiling();
+
+        let report = profile_report();
+        assert_eq!(report.len(), 2);
+        let slow = &report[0];
+        assert!(slow.component.ends_with("Win"), "{}", slow.component);
+        assert_eq!(slow.message, "Slow");
+        assert_eq!(slow.count, 1);
+        assert_eq!(slow.over_budget, 1);
+        assert!(slow.max >= Duration::from_millis(50));
+        let fast = &report[1];
+        assert_eq!(fast.message, "Fast");
+        assert_eq!(fast.count, 3);
+        assert_eq!(fast.over_budget, 0);
+        assert!(fast.average() <= fast.max);
+
+        // The messages are not measured anymore once the profiling is stopped.
+        component.emit(Slow);
+        settle();
+        assert_eq!(profile_report()[0].count, 1);
+        reset_profile();
+        assert!(profile_report().is_empty());
+    }
+}
@@ -0,0 +1,110 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+use gtk::{
+    ButtonExt,
+    Inhibit,
+    LabelExt,
+    OrientableExt,
+    ProgressBarExt,
+    WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    step: u32,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Next,
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            step: 0,
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Next => self.model.step += 1,
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                // The text of a ProgressBar is an Option.
+                #[name="progress_bar"]
+                gtk::ProgressBar {
+                    show_text: true,
+                    text: Some(&format!("Step {}", self.model.step)),
+                },
+                #[name="label"]
+                gtk::Label {
+                    text: &self.model.step.to_string(),
+                },
+                #[name="next_button"]
+                gtk::Button {
+                    clicked => Next,
+                    label: "Next",
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::{LabelExt, ProgressBarExt};
+    use gtk_test::assert_text;
+    use relm_test::click;
+
+    use crate::Win;
+
+    #[test]
+    fn progress_bar_text_binding() {
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        assert_eq!(widgets.progress_bar.get_text().map(|text| text.to_string()), Some("Step 0".to_string()));
+        assert_text!(widgets.label, "0");
+
+        click(&widgets.next_button);
+        click(&widgets.next_button);
+        assert_eq!(widgets.progress_bar.get_text().map(|text| text.to_string()), Some("Step 2".to_string()));
+        assert_text!(widgets.label, "2");
+    }
+}
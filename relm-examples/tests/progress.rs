@@ -0,0 +1,144 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::thread;
+use std::time::Duration;
+
+use gtk::{ButtonExt, Inhibit, OrientableExt, WidgetExt};
+use gtk::Orientation::Vertical;
+use relm::{Channel, Progress, ProgressBarBinding, Relm, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    binding: Option<ProgressBarBinding<Msg>>,
+    cancelled: bool,
+    channel: Option<Channel<Progress>>,
+    relm: Relm<Win>,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Cancel,
+    JobProgress(Progress),
+    Quit,
+    Start,
+}
+
+#[widget]
+impl Widget for Win {
+    fn init_view(&mut self) {
+        let binding = ProgressBarBinding::new(self.model.relm.stream(), &self.widgets.progress_bar, |msg|
+            match *msg {
+                JobProgress(ref progress) => Some(progress),
+                _ => None,
+            })
+            .with_cancel_button(&self.widgets.cancel_button, || Cancel);
+        self.model.binding = Some(binding);
+    }
+
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        Model {
+            binding: None,
+            cancelled: false,
+            channel: None,
+            relm: relm.clone(),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Cancel => self.model.cancelled = true,
+            // Handled by the binding.
+            JobProgress(_) => (),
+            Quit => gtk::main_quit(),
+            Start => {
+                let stream = self.model.relm.stream().clone();
+                let (channel, sender) = Channel::new(move |progress| stream.emit(JobProgress(progress)));
+                thread::spawn(move || {
+                    let _ = sender.send(Progress::indeterminate("Counting files"));
+                    thread::sleep(Duration::from_millis(200));
+                    for done in 0..=4 {
+                        let _ = sender.send(Progress::new(done, 4, "Copying files"));
+                        thread::sleep(Duration::from_millis(10));
+                    }
+                });
+                self.model.channel = Some(channel);
+            },
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="progress_bar"]
+                gtk::ProgressBar {
+                },
+                #[name="start_button"]
+                gtk::Button {
+                    clicked => Start,
+                    label: "Start",
+                },
+                #[name="cancel_button"]
+                gtk::Button {
+                    label: "Cancel",
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use gtk::{ProgressBarExt, WidgetExt};
+    use relm::Progress;
+    use relm_test::{click, wait_for};
+
+    use crate::Msg::JobProgress;
+    use crate::Win;
+
+    #[test]
+    fn progress_bar_follows_the_job() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        click(&widgets.start_button);
+        wait_for(|| widgets.progress_bar.get_text().map(|text| text.to_string()) ==
+            Some("Counting files".to_string()), Duration::from_secs(5));
+        assert!(widgets.cancel_button.is_sensitive());
+
+        wait_for(|| widgets.progress_bar.get_fraction() == 1.0, Duration::from_secs(5));
+        assert_eq!(widgets.progress_bar.get_text().map(|text| text.to_string()),
+            Some("Copying files (4/4)".to_string()));
+        assert!(!widgets.cancel_button.is_sensitive());
+
+        component.emit(JobProgress(Progress::new(1, 4, "Copying files")));
+        assert_eq!(widgets.progress_bar.get_fraction(), 0.25);
+        assert!(widgets.cancel_button.is_sensitive());
+    }
+}
@@ -0,0 +1,183 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+use gtk::{
+    ButtonExt,
+    Inhibit,
+    LabelExt,
+    OrientableExt,
+    WidgetExt,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use gtk::Orientation::Vertical;
+use relm::{Relm, Widget};
+use relm::debug::Value;
+use relm_derive::{Msg, widget};
+use serde::{Deserialize, Serialize};
+
+use self::Msg::*;
+
+pub struct Model {
+    count: i32,
+    relm: Relm<Win>,
+}
+
+static MIGRATIONS: AtomicUsize = AtomicUsize::new(0);
+
+// The version 1 of the messages, before Increment was renamed to Increase.
+#[derive(Msg, Serialize)]
+#[msg(version = 1)]
+pub enum OldMsg {
+    Add(i32),
+    Increment,
+}
+
+#[derive(Deserialize, Msg, Serialize)]
+#[msg(version = 2, migrate = "migrate_msg")]
+pub enum Msg {
+    Add(i32),
+    AddTwice(i32),
+    Increase,
+    Quit,
+}
+
+fn migrate_msg(version: u32, value: Value) -> Result<Value, String> {
+    MIGRATIONS.fetch_add(1, Ordering::SeqCst);
+    match version {
+        1 if value == Value::String("Increment".to_string()) => Ok(Value::String("Increase".to_string())),
+        1 => Ok(value),
+        _ => Err(format!("unknown version {}", version)),
+    }
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        Model {
+            count: 0,
+            relm: relm.clone(),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Add(value) => self.model.count += value,
+            // These messages are emitted again when AddTwice is replayed, so they must not be
+            // recorded.
+            AddTwice(value) => {
+                self.model.relm.stream().emit(Add(value));
+                self.model.relm.stream().emit(Add(value));
+            },
+            Increase => self.model.count += 1,
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="label"]
+                gtk::Label {
+                    text: &self.model.count.to_string(),
+                },
+                #[name="add_button"]
+                gtk::Button {
+                    clicked => Add(1),
+                    label: "+",
+                },
+                gtk::Button {
+                    clicked => Increase,
+                    label: "Increase",
+                },
+                #[name="add_twice_button"]
+                gtk::Button {
+                    clicked => AddTwice(10),
+                    label: "+ 2 × 10",
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use gtk::LabelExt;
+    use gtk_test::assert_text;
+    use relm::EventStream;
+    use relm::debug::{record, record_versioned, replay, replay_versioned};
+    use relm_test::{click, settle};
+
+    use crate::{MIGRATIONS, OldMsg, Win};
+
+    #[test]
+    fn record_and_replay() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let recorder = record(&component.stream());
+        click(&widgets.add_button);
+        click(&widgets.add_twice_button);
+        click(&widgets.add_button);
+        settle();
+        assert_text!(widgets.label, "22");
+
+        let session = recorder.session();
+        // Only the clicks are recorded, not the messages emitted by update().
+        assert_eq!(session.len(), 3);
+
+        // Messages emitted after the recorder is dropped are not recorded.
+        drop(recorder);
+        click(&widgets.add_button);
+        settle();
+        assert_text!(widgets.label, "23");
+
+        let (replayed_component, _, replayed_widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        replay(&replayed_component.stream(), &session).expect("replay failed");
+        settle();
+        assert_text!(replayed_widgets.label, "22");
+    }
+
+    #[test]
+    fn replay_older_version() {
+        // Record a session with the version 1 of the messages.
+        let stream = EventStream::new();
+        let recorder = record_versioned(&stream.stream());
+        stream.emit(OldMsg::Increment);
+        stream.emit(OldMsg::Add(5));
+        stream.emit(OldMsg::Increment);
+        let session = recorder.session();
+        assert_eq!(session.version(), 1);
+
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        replay_versioned(&component.stream(), &session).expect("replay_versioned failed");
+        settle();
+        assert_eq!(MIGRATIONS.load(Ordering::SeqCst), 3);
+        assert_text!(widgets.label, "7");
+    }
+}
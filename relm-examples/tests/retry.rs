@@ -0,0 +1,118 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use gtk::{Inhibit, LabelExt, WidgetExt};
+use relm::{Backoff, Relm, RetryEvent, Widget};
+use relm::RetryEvent::{GaveUp, Retrying, Succeeded};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    status: String,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Fetch(RetryEvent<u32, String>),
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    // The request fails `failures` times before succeeding.
+    fn model(relm: &Relm<Self>, (failures, max_attempts): (u32, u32)) -> Model {
+        let attempts = Rc::new(Cell::new(0));
+        let _ = relm::retry(relm.stream(), Backoff::new(10).max_attempts(max_attempts), move || {
+            attempts.set(attempts.get() + 1);
+            let attempt = attempts.get();
+            async move {
+                glib::timeout_future(1).await;
+                if attempt > failures {
+                    Ok(42)
+                }
+                else {
+                    Err(format!("error {}", attempt))
+                }
+            }
+        }, Fetch);
+        Model {
+            status: String::new(),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Fetch(GaveUp(error)) => self.model.status = format!("Gave up: {}", error),
+            Fetch(Retrying(attempt)) => self.model.status = format!("Retrying {}", attempt),
+            Fetch(Succeeded(value)) => self.model.status = format!("Succeeded: {}", value),
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            #[name="label"]
+            gtk::Label {
+                text: &self.model.status,
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run((2, 5)).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use gtk::LabelExt;
+    use relm::Backoff;
+    use relm_test::wait_for;
+
+    use crate::Win;
+
+    #[test]
+    fn retry_until_success() {
+        let (_component, _, widgets) = relm::init_test::<Win>((2, 5)).expect("init_test failed");
+        wait_for(|| widgets.label.get_text() == "Retrying 2", Duration::from_secs(5));
+        wait_for(|| widgets.label.get_text() == "Succeeded: 42", Duration::from_secs(5));
+    }
+
+    #[test]
+    fn give_up() {
+        let (_component, _, widgets) = relm::init_test::<Win>((5, 3)).expect("init_test failed");
+        wait_for(|| widgets.label.get_text() == "Gave up: error 3", Duration::from_secs(5));
+    }
+
+    #[test]
+    fn backoff_delays() {
+        let backoff = Backoff::new(100).max_delay(500);
+        let delays: Vec<_> = (1..=5).map(|attempt| backoff.delay(attempt)).collect();
+        assert_eq!(delays, vec![100, 200, 400, 500, 500]);
+    }
+}
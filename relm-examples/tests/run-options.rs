@@ -0,0 +1,83 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+use gtk::{Inhibit, WidgetExt};
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> () {
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use relm::{RunOptions, Widget};
+
+    use crate::Msg::Quit;
+    use crate::Win;
+
+    #[test]
+    fn args_are_parsed_by_gtk() {
+        let startup_args = Rc::new(RefCell::new(vec![]));
+        let received_args = startup_args.clone();
+        let args = vec!["relm-app".to_string(), "--name=run-options".to_string(), "notes.txt".to_string()];
+        let options = RunOptions::new()
+            .args(args)
+            .startup_msgs(move |args| {
+                *received_args.borrow_mut() = args.to_vec();
+                vec![Quit]
+            });
+        Win::run_with(options, ()).expect("run_with failed");
+
+        // GTK+ handled the --name option and the other arguments were converted to messages.
+        assert_eq!(glib::get_prgname().as_ref().map(|name| name.as_str()), Some("run-options"));
+        assert_eq!(*startup_args.borrow(), vec!["notes.txt".to_string()]);
+    }
+}
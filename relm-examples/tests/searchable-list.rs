@@ -0,0 +1,118 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{ContainerExt, Inhibit, LabelExt, OrientableExt, WidgetExt};
+use gtk::Orientation::Vertical;
+use relm::{Component, Relm, Widget, connect};
+use relm::searchable_list::SearchableList;
+use relm::searchable_list::SearchableListMsg::Activated;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+const FRUITS: &[&str] = &["Apple", "Banana", "Blueberry", "Cherry"];
+
+pub struct Model {
+    chosen: String,
+    fruits: Component<SearchableList<&'static str>>,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Chosen(usize),
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn init_view(&mut self) {
+        self.widgets.container.add(self.model.fruits.widget());
+    }
+
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        let fruits = relm::create_component::<SearchableList<&'static str>>(FRUITS.to_vec());
+        connect!(fruits@Activated(index), relm, Chosen(index));
+        Model {
+            chosen: String::new(),
+            fruits,
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Chosen(index) => self.model.chosen = FRUITS[index].to_string(),
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="container"]
+                gtk::Box {
+                },
+                #[name="chosen"]
+                gtk::Label {
+                    text: &self.model.chosen,
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use glib::Cast;
+    use gtk::{BinExt, ContainerExt, EntryExt, LabelExt, WidgetExt};
+    use gtk_test::assert_text;
+    use relm_test::{settle, wait_for};
+
+    use crate::Win;
+
+    #[test]
+    fn filter_and_activate() {
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let root: gtk::Container = widgets.container.get_children()[0].clone().downcast().expect("root");
+        let search_entry: gtk::SearchEntry = root.get_children()[0].clone().downcast().expect("search entry");
+        let scrolled_window: gtk::Bin = root.get_children()[1].clone().downcast().expect("scrolled window");
+        let viewport: gtk::Bin = scrolled_window.get_child().expect("viewport").downcast().expect("viewport");
+        let list: gtk::Container = viewport.get_child().expect("list").downcast().expect("list");
+        let visible_rows = || list.get_children().iter().filter(|row| row.get_child_visible()).count();
+        assert_eq!(visible_rows(), 4);
+
+        search_entry.set_text("b");
+        wait_for(|| visible_rows() == 2, Duration::from_secs(5));
+
+        search_entry.set_text("BLUE");
+        wait_for(|| visible_rows() == 1, Duration::from_secs(5));
+        search_entry.activate();
+        settle();
+        assert_text!(widgets.chosen, "Blueberry");
+    }
+}
@@ -0,0 +1,113 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use gtk::{Inhibit, LabelExt, WidgetExt};
+use relm::{Channel, Relm, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    _channel: Channel<i32>,
+    value: i32,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+    Value(i32),
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(relm: &Relm<Self>, stopped: Arc<AtomicBool>) -> Model {
+        let stream = relm.stream().clone();
+        let (channel, sender) = Channel::new(move |num| stream.emit(Value(num)));
+        thread::spawn(move || {
+            let mut num = 0;
+            // The producer stops by itself once the component is destroyed.
+            while sender.send(num).is_ok() {
+                num += 1;
+                thread::sleep(Duration::from_millis(10));
+            }
+            stopped.store(true, Ordering::SeqCst);
+        });
+        Model {
+            _channel: channel,
+            value: 0,
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => gtk::main_quit(),
+            Value(num) => self.model.value = num,
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Label {
+                text: &self.model.value.to_string(),
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(Arc::new(AtomicBool::new(false))).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+
+    use relm::Sender;
+    use relm_test::{relm_observer_new, relm_observer_wait, wait_for};
+
+    use crate::Msg::Value;
+    use crate::Win;
+
+    fn assert_send_sync<T: Send + Sync>() {
+    }
+
+    #[test]
+    fn sender_error_after_destroy() {
+        assert_send_sync::<Sender<i32>>();
+
+        let stopped = Arc::new(AtomicBool::new(false));
+        let (component, _, _widgets) = relm::init_test::<Win>(stopped.clone()).expect("init_test failed");
+        let observer = relm_observer_new!(component, Value(_));
+        relm_observer_wait!(let Value(_value) = observer);
+        assert!(!stopped.load(Ordering::SeqCst));
+
+        drop(component);
+        wait_for(|| stopped.load(Ordering::SeqCst), Duration::from_secs(5));
+    }
+}
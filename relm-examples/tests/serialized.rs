@@ -0,0 +1,113 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    Inhibit,
+    LabelExt,
+    WidgetExt,
+};
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+#[derive(Msg)]
+pub enum Msg {
+    Increment,
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> i32 {
+        0
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Increment => self.model += 1,
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            #[name="label"]
+            gtk::Label {
+                text: &self.model.to_string(),
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::LabelExt;
+    use gtk_test::assert_text;
+    use relm_test::{relm_test, run_serialized, send_and_settle};
+
+    use crate::Msg::Increment;
+    use crate::Win;
+
+    // These tests run on different test threads but the attribute runs them on the same GTK
+    // thread, one after the other.
+    #[relm_test]
+    fn first_window() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        send_and_settle(&component, Increment);
+        assert_text!(widgets.label, 1);
+    }
+
+    #[relm_test]
+    fn second_window() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        send_and_settle(&component, Increment);
+        send_and_settle(&component, Increment);
+        assert_text!(widgets.label, 2);
+    }
+
+    // The returned value is propagated, so that an Err fails the test.
+    #[relm_test]
+    fn result_is_returned() -> Result<(), String> {
+        let (component, _, widgets) = relm::init_test::<Win>(()).map_err(|()| "init_test failed".to_string())?;
+        send_and_settle(&component, Increment);
+        let count: i32 = widgets.label.get_text().parse().map_err(|error| format!("{}", error))?;
+        assert_eq!(count, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn err_is_returned() {
+        let result: Result<(), String> = run_serialized(|| Err("failure in the GTK thread".to_string()));
+        assert_eq!(result, Err("failure in the GTK thread".to_string()));
+    }
+
+    #[relm_test]
+    #[should_panic(expected = "failure in the GTK thread")]
+    fn panic_is_propagated() {
+        panic!("failure in the GTK thread");
+    }
+}
@@ -0,0 +1,102 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use gtk::{Inhibit, WidgetExt};
+use relm::{Relm, ShutdownDelay, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    delay: Option<ShutdownDelay>,
+    relm: Relm<Win>,
+    saved: Rc<Cell<bool>>,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+    Save,
+    Saved,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(relm: &Relm<Self>, saved: Rc<Cell<bool>>) -> Model {
+        Model {
+            delay: None,
+            relm: relm.clone(),
+            saved,
+        }
+    }
+
+    fn shutdown_msg() -> Option<Msg> {
+        Some(Save)
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => relm::shutdown(5000),
+            Save => {
+                // Simulate saving the state asynchronously.
+                self.model.delay = Some(relm::delay_shutdown());
+                relm::timeout(self.model.relm.stream(), 50, || Saved);
+            },
+            Saved => {
+                self.model.saved.set(true);
+                self.model.delay = None;
+            },
+        }
+    }
+
+    view! {
+        gtk::Window {
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(Rc::new(Cell::new(false))).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use crate::Msg::Quit;
+    use crate::Win;
+
+    #[test]
+    fn shutdown_waits_for_the_components() {
+        let saved = Rc::new(Cell::new(false));
+        let (component, _, _) = relm::init_test::<Win>(saved.clone()).expect("init_test failed");
+        component.emit(Quit);
+        // Returns once the shutdown stops the main loop.
+        gtk::main();
+        assert!(saved.get());
+        assert!(!relm::is_shutting_down());
+    }
+}
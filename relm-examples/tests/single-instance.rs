@@ -0,0 +1,151 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+use std::cell::RefCell;
+use std::ffi::OsString;
+use std::rc::Rc;
+
+use gio::{ApplicationExt, File, FileExt};
+use gtk::WidgetExt;
+use relm::{ApplicationWidget, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    events: Rc<RefCell<Vec<String>>>,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    CommandLine(Vec<String>),
+    Open(Vec<String>),
+    Quit,
+    Startup(Vec<String>),
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(events: Rc<RefCell<Vec<String>>>) -> Model {
+        Model {
+            events,
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        let event =
+            match event {
+                CommandLine(arguments) => format!("command line {}", arguments.join(" ")),
+                Open(names) => format!("open {}", names.join(" ")),
+                Quit => {
+                    if let Some(application) = gio::Application::get_default() {
+                        application.quit();
+                    }
+                    return;
+                },
+                Startup(arguments) => format!("startup {}", arguments.join(" ")),
+            };
+        self.model.events.borrow_mut().push(event);
+    }
+
+    view! {
+        gtk::Window {
+        }
+    }
+}
+
+impl ApplicationWidget for Win {
+    fn open_msg(files: Vec<File>, _hint: &str) -> Option<Msg> {
+        let names = files.iter()
+            .filter_map(|file| file.get_basename())
+            .map(|name| name.display().to_string())
+            .collect();
+        Some(Open(names))
+    }
+
+    fn command_line_msg(arguments: Vec<OsString>) -> Option<Msg> {
+        Some(CommandLine(arguments.iter().map(|argument| argument.to_string_lossy().into_owned()).collect()))
+    }
+}
+
+fn main() {
+    let status = relm::run_single_instance::<Win>("com.github.antoyo.relm.SingleInstance", relm::RunOptions::new(),
+        Rc::new(RefCell::new(vec![])))
+        .expect("run_single_instance failed");
+    std::process::exit(status);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use glib::Continue;
+    use relm::{RunOptions, run_single_instance};
+
+    use crate::Msg::{Quit, Startup};
+    use crate::Win;
+
+    fn args() -> Vec<String> {
+        vec!["relm-app".to_string(), "--verbose".to_string(), "a.txt".to_string(), "b.txt".to_string()]
+    }
+
+    // Quit once the messages sent at startup are handled.
+    fn quit_when_idle(options: RunOptions<Win>) -> RunOptions<Win> {
+        options.before_show(|component| {
+            let stream = component.stream();
+            glib::idle_add_local(move || {
+                stream.emit(Quit);
+                Continue(false)
+            });
+        })
+    }
+
+    #[test]
+    fn open_files_of_first_instance() {
+        let events = Rc::new(RefCell::new(vec![]));
+        let options = quit_when_idle(RunOptions::new().args(args()));
+        let status = run_single_instance::<Win>("com.github.antoyo.relm.SingleInstanceOpen", options, events.clone())
+            .expect("run_single_instance failed");
+        assert_eq!(status, 0);
+        // The options are not considered as files.
+        assert_eq!(*events.borrow(), vec![
+            "command line relm-app --verbose a.txt b.txt".to_string(),
+            "open a.txt b.txt".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn startup_msgs_of_first_instance() {
+        let events = Rc::new(RefCell::new(vec![]));
+        let options = RunOptions::new()
+            .args(args())
+            .startup_msgs(|arguments| vec![Startup(arguments.to_vec())]);
+        let options = quit_when_idle(options);
+        let status = run_single_instance::<Win>("com.github.antoyo.relm.SingleInstanceStartup", options,
+            events.clone())
+            .expect("run_single_instance failed");
+        assert_eq!(status, 0);
+        // The files are only opened through the startup messages.
+        assert_eq!(*events.borrow(), vec!["startup --verbose a.txt b.txt".to_string()]);
+    }
+}
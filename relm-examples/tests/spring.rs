@@ -0,0 +1,121 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{Inhibit, LabelExt, WidgetExt};
+use relm::{Relm, Spring, SpringAnimation, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    animation: Option<SpringAnimation<Msg>>,
+    events: Vec<String>,
+    max_position: f64,
+    relm: Relm<Win>,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Interrupted,
+    Move(f64),
+    Position(f64),
+    Quit,
+    Settled,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        Model {
+            animation: None,
+            events: vec![],
+            max_position: 0.0,
+            relm: relm.clone(),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Interrupted => self.model.events.push("interrupted".to_string()),
+            Move(target) => {
+                match self.model.animation {
+                    Some(ref animation) => animation.set_target(target),
+                    None => {
+                        // A bouncy spring, to check that it overshoots.
+                        let animation = relm::animate_spring(&self.widgets.label, self.model.relm.stream(), 0.0,
+                            target, Spring::new(300.0, 10.0), Position)
+                            .on_interrupted(|| Interrupted)
+                            .on_settled(|| Settled);
+                        self.model.animation = Some(animation);
+                    },
+                }
+            },
+            Position(position) => self.model.max_position = self.model.max_position.max(position),
+            Quit => gtk::main_quit(),
+            Settled => {
+                let position = self.model.animation.as_ref().map_or(0.0, SpringAnimation::position);
+                let overshot = self.model.max_position > position;
+                self.model.events.push(format!("settled at {} (overshot: {})", position, overshot));
+            },
+        }
+    }
+
+    view! {
+        gtk::Window {
+            #[name="label"]
+            gtk::Label {
+                text: &self.model.events.join(", "),
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use gtk::LabelExt;
+    use relm_test::{settle, wait_for};
+
+    use crate::Msg::Move;
+    use crate::Win;
+
+    #[test]
+    fn settles_on_target() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        component.emit(Move(100.0));
+        wait_for(|| widgets.label.get_text() == "settled at 100 (overshot: true)", Duration::from_secs(10));
+    }
+
+    #[test]
+    fn retarget_interrupts() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        component.emit(Move(100.0));
+        settle();
+        component.emit(Move(-50.0));
+        wait_for(|| widgets.label.get_text() == "interrupted, settled at -50 (overshot: true)", Duration::from_secs(10));
+    }
+}
@@ -0,0 +1,98 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk::{Inhibit, WidgetExt};
+use relm::{RunOptions, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    opened: Rc<RefCell<Vec<String>>>,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Open(String),
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(opened: Rc<RefCell<Vec<String>>>) -> Model {
+        Model {
+            opened,
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Open(path) => self.model.opened.borrow_mut().push(path),
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+// Convert each argument to a message opening it, and quit once they are handled.
+fn open_args(args: &[String]) -> Vec<Msg> {
+    args.iter()
+        .cloned()
+        .map(Open)
+        .chain(Some(Quit))
+        .collect()
+}
+
+fn main() {
+    let options = RunOptions::new().startup_msgs(|args| args.iter().cloned().map(Open).collect());
+    Win::run_with(options, Rc::new(RefCell::new(vec![]))).expect("Win::run_with failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use relm::{RunOptions, Widget};
+
+    use crate::{Win, open_args};
+
+    #[test]
+    fn args_converted_to_messages() {
+        let opened = Rc::new(RefCell::new(vec![]));
+        let args = vec!["notes".to_string(), "todo.txt".to_string(), "done.txt".to_string()];
+        let options = RunOptions::new()
+            .args(args)
+            .startup_msgs(open_args);
+        Win::run_with(options, opened.clone()).expect("run_with failed");
+        // The program name is not converted and the messages are sent in the order of the arguments.
+        assert_eq!(*opened.borrow(), vec!["todo.txt".to_string(), "done.txt".to_string()]);
+    }
+}
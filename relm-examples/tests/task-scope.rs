@@ -0,0 +1,121 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use gtk::{Inhibit, LabelExt, WidgetExt};
+use relm::{Relm, TaskScope, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    _tasks: TaskScope,
+    ticks: u32,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+    Tick,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(relm: &Relm<Self>, (timeouts, stopped): (Rc<Cell<u32>>, Arc<AtomicBool>)) -> Model {
+        let tasks = TaskScope::new();
+        tasks.interval(relm.stream(), 10, || Tick);
+        tasks.timeout(relm.stream(), 200, move || {
+            timeouts.set(timeouts.get() + 1);
+            Tick
+        });
+        tasks.spawn_worker(move |stop| {
+            while !stop.load(Ordering::SeqCst) {
+                thread::sleep(Duration::from_millis(10));
+            }
+            stopped.store(true, Ordering::SeqCst);
+        });
+        Model {
+            _tasks: tasks,
+            ticks: 0,
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => gtk::main_quit(),
+            Tick => self.model.ticks += 1,
+        }
+    }
+
+    view! {
+        gtk::Window {
+            #[name="label"]
+            gtk::Label {
+                text: &self.model.ticks.to_string(),
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run((Rc::new(Cell::new(0)), Arc::new(AtomicBool::new(false)))).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::{Duration, Instant};
+
+    use gtk::LabelExt;
+    use relm_test::{settle, wait_for};
+
+    use crate::Win;
+
+    #[test]
+    fn tasks_are_cancelled_with_the_component() {
+        let timeouts = Rc::new(Cell::new(0));
+        let stopped = Arc::new(AtomicBool::new(false));
+        let (component, _, widgets) = relm::init_test::<Win>((timeouts.clone(), stopped.clone()))
+            .expect("init_test failed");
+        wait_for(|| widgets.label.get_text().as_str() != "0", Duration::from_secs(5));
+        assert!(!stopped.load(Ordering::SeqCst));
+
+        drop(component);
+        wait_for(|| stopped.load(Ordering::SeqCst), Duration::from_secs(5));
+
+        // The timeout was removed before firing.
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_millis(300) {
+            settle();
+        }
+        assert_eq!(timeouts.get(), 0);
+    }
+}
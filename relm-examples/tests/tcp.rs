@@ -0,0 +1,103 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{Inhibit, LabelExt, WidgetExt};
+use relm::{Relm, Widget};
+use relm::net::{ServerEvent, TcpClient, TcpEvent, TcpServer};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    client: TcpClient,
+    received: String,
+    server: TcpServer,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Client(TcpEvent),
+    Quit,
+    Send(String),
+    Server(ServerEvent),
+}
+
+#[widget]
+impl Widget for Win {
+    // An echo server replying in uppercase, and a client connected to it.
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        let server = TcpServer::listen(0, relm.stream(), Server).expect("listen");
+        let client = TcpClient::connect("localhost", server.port(), relm.stream(), Client);
+        Model {
+            client,
+            received: String::new(),
+            server,
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Client(TcpEvent::Received(data)) => self.model.received.push_str(&String::from_utf8_lossy(&data)),
+            Client(_) => (),
+            Quit => gtk::main_quit(),
+            Send(text) => self.model.client.send(text.as_bytes()),
+            Server(ServerEvent::Received(id, data)) => {
+                let reply = String::from_utf8_lossy(&data).to_uppercase();
+                self.model.server.send(id, reply.as_bytes());
+            },
+            Server(_) => (),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            #[name="label"]
+            gtk::Label {
+                text: &self.model.received,
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use gtk::LabelExt;
+    use relm_test::wait_for;
+
+    use crate::Msg::Send;
+    use crate::Win;
+
+    #[test]
+    fn echo() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        // Sent before the connection is established: it is queued.
+        component.emit(Send("hello ".to_string()));
+        component.emit(Send("world".to_string()));
+        wait_for(|| widgets.label.get_text() == "HELLO WORLD", Duration::from_secs(5));
+    }
+}
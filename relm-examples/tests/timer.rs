@@ -0,0 +1,124 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::time::Duration;
+
+use gtk::{Inhibit, LabelExt, WidgetExt};
+use relm::{EventStream, Relm, Widget, connect_stream, execute};
+use relm::timer::{Timer, TimerMsg};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    status: String,
+    timer: EventStream<TimerMsg>,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Command(TimerMsg),
+    Finished,
+    Quit,
+    Tick(Duration),
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(relm: &Relm<Self>, duration: Option<Duration>) -> Model {
+        let timer = execute::<Timer>((10, duration));
+        connect_stream!(timer@TimerMsg::Tick(elapsed), relm.stream(), Tick(elapsed));
+        connect_stream!(timer@TimerMsg::Finished, relm.stream(), Finished);
+        Model {
+            status: String::new(),
+            timer,
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Command(command) => self.model.timer.emit(command),
+            Finished => self.model.status = "Finished".to_string(),
+            Quit => gtk::main_quit(),
+            Tick(elapsed) => self.model.status = elapsed.as_millis().to_string(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            #[name="label"]
+            gtk::Label {
+                text: &self.model.status,
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(Some(Duration::from_secs(5))).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use gtk::LabelExt;
+    use relm::timer::TimerMsg::{Pause, Reset, Resume, Start};
+    use relm_test::{settle, wait_for};
+
+    use crate::Msg::Command;
+    use crate::Win;
+
+    fn elapsed(label: &gtk::Label) -> u64 {
+        label.get_text().parse().expect("elapsed")
+    }
+
+    #[test]
+    fn countdown_finishes() {
+        let (component, _, widgets) = relm::init_test::<Win>(Some(Duration::from_millis(100)))
+            .expect("init_test failed");
+        component.emit(Command(Start));
+        wait_for(|| widgets.label.get_text() == "Finished", Duration::from_secs(5));
+    }
+
+    #[test]
+    fn pause_resume_reset() {
+        let (component, _, widgets) = relm::init_test::<Win>(None).expect("init_test failed");
+        component.emit(Command(Start));
+        wait_for(|| widgets.label.get_text().parse::<u64>().map_or(false, |elapsed| elapsed >= 50),
+            Duration::from_secs(5));
+
+        component.emit(Command(Pause));
+        settle();
+        let paused = elapsed(&widgets.label);
+        std::thread::sleep(Duration::from_millis(50));
+        settle();
+        assert_eq!(elapsed(&widgets.label), paused);
+
+        component.emit(Command(Resume));
+        wait_for(|| elapsed(&widgets.label) > paused, Duration::from_secs(5));
+
+        component.emit(Command(Reset));
+        settle();
+        assert_eq!(elapsed(&widgets.label), 0);
+    }
+}
@@ -0,0 +1,121 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{ButtonExt, ContainerExt, Inhibit, OrientableExt, WidgetExt};
+use gtk::Orientation::Vertical;
+use relm::{Component, Relm, Widget};
+use relm::toast::{ToastHandle, ToastLevel, Toaster};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    toaster: Component<Toaster>,
+    toasts: ToastHandle,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+    Save,
+    SaveFailed,
+}
+
+#[widget]
+impl Widget for Win {
+    fn init_view(&mut self) {
+        // Wrap the content of the window in the toaster.
+        self.widgets.window.remove(&self.widgets.content);
+        self.model.toaster.widget().add(&self.widgets.content);
+        self.widgets.window.add(self.model.toaster.widget());
+    }
+
+    fn model(_: &Relm<Self>, _: ()) -> Model {
+        let toaster = relm::create_component::<Toaster>(());
+        let toasts = toaster.handle();
+        Model {
+            toaster,
+            toasts,
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => gtk::main_quit(),
+            Save => self.model.toasts.show("Saved", ToastLevel::Success, 100),
+            SaveFailed => self.model.toasts.show("Could not save", ToastLevel::Error, 0),
+        }
+    }
+
+    view! {
+        #[name="window"]
+        gtk::Window {
+            #[name="content"]
+            gtk::Box {
+                orientation: Vertical,
+                #[name="save_button"]
+                gtk::Button {
+                    clicked => Save,
+                    label: "Save",
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use glib::Cast;
+    use gtk::{ContainerExt, StyleContextExt, WidgetExt};
+    use relm_test::{click, settle, wait_for};
+
+    use crate::Win;
+
+    #[test]
+    fn toasts_are_stacked_and_dismissed() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let overlay: gtk::Container = widgets.window.get_children()[0].clone().downcast().expect("overlay");
+        let toast_box: gtk::Container = overlay.get_children().last().cloned().expect("toast box")
+            .downcast().expect("toast box");
+        assert!(toast_box.get_children().is_empty());
+
+        component.emit(crate::Msg::SaveFailed);
+        click(&widgets.save_button);
+        settle();
+        assert_eq!(toast_box.get_children().len(), 2);
+        let error_toast: gtk::Container = toast_box.get_children()[0].clone().downcast().expect("toast");
+        assert!(error_toast.get_style_context().has_class("error"));
+
+        // The success toast times out while the error toast stays until it is closed.
+        wait_for(|| toast_box.get_children().len() == 1, Duration::from_secs(5));
+        let close_button: gtk::Button = error_toast.get_children()[1].clone().downcast().expect("close button");
+        click(&close_button);
+        settle();
+        assert!(toast_box.get_children().is_empty());
+    }
+}
@@ -0,0 +1,155 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    ContainerExt,
+    Inhibit,
+    LabelExt,
+    OrientableExt,
+    RevealerExt,
+    RevealerTransitionType,
+    StackExt,
+    StackTransitionType,
+    WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::{TransitionWidget, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+#[derive(Clone, Copy)]
+pub enum Page {
+    Details,
+    Summary,
+}
+
+impl Page {
+    fn name(self) -> &'static str {
+        match self {
+            Page::Details => "details",
+            Page::Summary => "summary",
+        }
+    }
+}
+
+pub struct Model {
+    events: Vec<String>,
+    revealed: bool,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    PageShown(String),
+    Quit,
+    Reveal(bool),
+    Revealed(bool),
+    ShowPage(Page),
+}
+
+#[widget]
+impl Widget for Win {
+    fn init_view(&mut self) {
+        for page in &[Page::Summary, Page::Details] {
+            let label = gtk::Label::new(Some(page.name()));
+            self.widgets.stack.add_named(&label, page.name());
+            label.show();
+        }
+    }
+
+    fn model() -> Model {
+        Model {
+            events: vec![],
+            revealed: false,
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            PageShown(name) => self.model.events.push(format!("page {}", name)),
+            Quit => gtk::main_quit(),
+            Reveal(revealed) => self.model.revealed = revealed,
+            Revealed(revealed) => self.model.events.push(format!("revealed {}", revealed)),
+            // The pages are added in init_view(), so the visible child cannot be bound in the view.
+            ShowPage(page) => self.widgets.stack.set_visible_child_name(page.name()),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="label"]
+                gtk::Label {
+                    text: &self.model.events.join(", "),
+                },
+                #[name="revealer"]
+                gtk::Revealer {
+                    reveal_child: self.model.revealed,
+                    transition_duration: 50,
+                    transition_type: RevealerTransitionType::SlideDown,
+                    transition_finished(revealer) => Revealed(revealer.get_reveal_child()),
+                    gtk::Label {
+                        text: "Details",
+                    },
+                },
+                #[name="stack"]
+                gtk::Stack {
+                    transition_duration: 50,
+                    transition_type: StackTransitionType::Crossfade,
+                    transition_finished(stack) =>
+                        PageShown(stack.get_visible_child_name().map(|name| name.to_string()).unwrap_or_default()),
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use gtk::LabelExt;
+    use relm_test::wait_for;
+
+    use crate::Msg::{Reveal, ShowPage};
+    use crate::Page;
+    use crate::Win;
+
+    #[test]
+    fn transitions_finished() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let events = || widgets.label.get_text().to_string();
+
+        component.emit(Reveal(true));
+        wait_for(|| events().ends_with("revealed true"), Duration::from_secs(5));
+        component.emit(Reveal(false));
+        wait_for(|| events().ends_with("revealed false"), Duration::from_secs(5));
+
+        component.emit(ShowPage(Page::Details));
+        wait_for(|| events().ends_with("page details"), Duration::from_secs(5));
+    }
+}
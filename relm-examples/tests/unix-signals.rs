@@ -0,0 +1,92 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+use gtk::{Inhibit, LabelExt, WidgetExt};
+use relm::{Relm, UnixSignal, UnixSignals, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    received: String,
+    _signals: UnixSignals,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+    Received(UnixSignal),
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        Model {
+            received: String::new(),
+            _signals: UnixSignals::new(relm.stream(), &[UnixSignal::User1, UnixSignal::User2], Received),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => gtk::main_quit(),
+            Received(signal) => self.model.received = format!("{:?}", signal),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            #[name="label"]
+            gtk::Label {
+                text: &self.model.received,
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use gtk::LabelExt;
+    use relm_test::wait_for;
+
+    use crate::Win;
+
+    #[test]
+    fn signals_converted_to_messages() {
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        unsafe {
+            assert_eq!(libc::raise(libc::SIGUSR1), 0);
+        }
+        wait_for(|| widgets.label.get_text() == "User1", Duration::from_secs(5));
+        unsafe {
+            assert_eq!(libc::raise(libc::SIGUSR2), 0);
+        }
+        wait_for(|| widgets.label.get_text() == "User2", Duration::from_secs(5));
+    }
+}
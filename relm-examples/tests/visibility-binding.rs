@@ -0,0 +1,127 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    ButtonExt,
+    Inhibit,
+    LabelExt,
+    OrientableExt,
+    WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    advanced_mode: bool,
+    items: Vec<String>,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Add,
+    Clear,
+    Quit,
+    ToggleAdvanced,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            advanced_mode: false,
+            items: vec![],
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            // The model fields are mutated without being assigned, so only the re-evaluation after the update
+            // refreshes the bound properties.
+            Add => self.model.items.push("item".to_string()),
+            Clear => self.model.items.clear(),
+            Quit => gtk::main_quit(),
+            ToggleAdvanced => self.toggle_advanced(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="clear_button"]
+                gtk::Button {
+                    clicked => Clear,
+                    label: "Clear",
+                    sensitive: !self.model.items.is_empty(),
+                },
+                #[name="advanced_label"]
+                gtk::Label {
+                    text: "Advanced settings",
+                    visible: self.model.advanced_mode,
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+impl Win {
+    fn toggle_advanced(&mut self) {
+        let model = &mut self.model;
+        model.advanced_mode = !model.advanced_mode;
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk::WidgetExt;
+    use relm_test::send_and_settle;
+
+    use crate::Msg::{Add, Clear, ToggleAdvanced};
+    use crate::Win;
+
+    #[test]
+    fn bindings_reevaluated_after_update() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let clear_button = &widgets.clear_button;
+        let advanced_label = &widgets.advanced_label;
+
+        assert!(!clear_button.get_sensitive());
+        assert!(!advanced_label.get_visible());
+
+        send_and_settle(&component, Add);
+        assert!(clear_button.get_sensitive());
+        send_and_settle(&component, Clear);
+        assert!(!clear_button.get_sensitive());
+
+        send_and_settle(&component, ToggleAdvanced);
+        assert!(advanced_label.get_visible());
+        send_and_settle(&component, ToggleAdvanced);
+        assert!(!advanced_label.get_visible());
+    }
+}
@@ -0,0 +1,104 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+use gtk::{Inhibit, LabelExt, WidgetExt};
+use relm::{Relm, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    relm: Relm<Win>,
+    status: &'static str,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Done,
+    Quit,
+    Start,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        Model {
+            relm: relm.clone(),
+            status: "",
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Done => self.model.status = "Done",
+            Quit => gtk::main_quit(),
+            Start => relm::timeout(self.model.relm.stream(), 100, || Done),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            #[name="label"]
+            gtk::Label {
+                text: self.model.status,
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::{self, AssertUnwindSafe};
+    use std::time::{Duration, Instant};
+
+    use gtk::LabelExt;
+    use relm_test::{try_wait_for, wait_for};
+
+    use crate::Msg::Start;
+    use crate::Win;
+
+    #[test]
+    fn wait_for_delayed_message() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        component.emit(Start);
+        assert_ne!(widgets.label.get_text(), "Done");
+        wait_for(|| widgets.label.get_text() == "Done", Duration::from_secs(5));
+    }
+
+    #[test]
+    fn try_wait_for_timeout() {
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let start = Instant::now();
+        assert!(!try_wait_for(|| widgets.label.get_text() == "Done", Duration::from_millis(100)));
+        assert!(start.elapsed() >= Duration::from_millis(100));
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            wait_for(|| widgets.label.get_text() == "Done", Duration::from_millis(100))
+        }));
+        assert!(result.is_err());
+    }
+}
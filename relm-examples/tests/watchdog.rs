@@ -0,0 +1,135 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+use std::time::Duration;
+
+use gtk::{Inhibit, WidgetExt};
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+#[derive(Msg)]
+pub enum Msg {
+    Fast,
+    Quit,
+    Slow,
+    // The byte at index 100 of this name is in the middle of a character.
+    Aéééééééééééééééééééééééééééééééééééééééééééééééééééééééééééé,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> () {
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Fast => (),
+            Quit => gtk::main_quit(),
+            Slow | Aéééééééééééééééééééééééééééééééééééééééééééééééééééééééééééé => std::thread::sleep(Duration::from_millis(50)),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::time::Duration;
+
+    use log::{Level, LevelFilter, Log, Metadata, Record};
+    use relm::debug::set_starvation_threshold;
+    use relm_test::settle;
+
+    use crate::Msg::{Fast, Slow, Aéééééééééééééééééééééééééééééééééééééééééééééééééééééééééééé};
+    use crate::Win;
+
+    thread_local! {
+        static WARNINGS: RefCell<Vec<String>> = RefCell::new(vec![]);
+    }
+
+    struct Logger;
+
+    impl Log for Logger {
+        fn enabled(&self, metadata: &Metadata) -> bool {
+            metadata.level() <= Level::Warn
+        }
+
+        fn log(&self, record: &Record) {
+            if self.enabled(record.metadata()) {
+                WARNINGS.with(|warnings| warnings.borrow_mut().push(record.args().to_string()));
+            }
+        }
+
+        fn flush(&self) {
+        }
+    }
+
+    static LOGGER: Logger = Logger;
+
+    fn take_warnings() -> Vec<String> {
+        WARNINGS.with(|warnings| warnings.replace(vec![]))
+    }
+
+    #[test]
+    fn starvation_threshold() {
+        // Another test of this file might have installed the logger already.
+        let _ = log::set_logger(&LOGGER);
+        log::set_max_level(LevelFilter::Warn);
+        let (component, _, _widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        set_starvation_threshold(Some(Duration::from_millis(20)));
+        settle();
+        let _ = take_warnings();
+
+        component.emit(Fast);
+        settle();
+        assert!(take_warnings().is_empty());
+
+        component.emit(Slow);
+        settle();
+        let warnings = take_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("for message Slow:"), "{}", warnings[0]);
+
+        component.emit(Aéééééééééééééééééééééééééééééééééééééééééééééééééééééééééééé);
+        settle();
+        let warnings = take_warnings();
+        assert_eq!(warnings.len(), 1);
+        let truncated = format!("for message A{}…:", "é".repeat(99));
+        assert!(warnings[0].contains(&truncated), "{}", warnings[0]);
+
+        set_starvation_threshold(None);
+        component.emit(Slow);
+        settle();
+        assert!(take_warnings().is_empty());
+    }
+}
@@ -0,0 +1,88 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{Inhibit, LabelExt, WidgetExt};
+use relm::{Relm, Widget};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    ticks: u32,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+    Tick,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+            ticks: 0,
+        }
+    }
+
+    fn subscriptions(&mut self, relm: &Relm<Self>) {
+        relm::interval(relm.stream(), 10, || Tick);
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => gtk::main_quit(),
+            Tick => self.model.ticks += 1,
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Label {
+                text: &self.model.ticks.to_string(),
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Msg::Tick;
+    use crate::Win;
+
+    #[test]
+    fn weak_stream_handle() {
+        let (component, _, _widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        let stream = component.stream();
+        assert!(!stream.is_dropped());
+        stream.emit(Tick);
+
+        // The interval and this handle are weak, so they do not keep the component alive.
+        drop(component);
+        assert!(stream.is_dropped());
+        relm::debug::assert_no_leaks();
+    }
+}
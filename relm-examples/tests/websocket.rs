@@ -0,0 +1,146 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+
+use gtk::{Inhibit, LabelExt, WidgetExt};
+use relm::{EventStream, Relm, Widget, connect_stream, execute};
+use relm::websocket::{Frame, WebSocket, WebSocketMsg};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+    _socket: EventStream<WebSocketMsg>,
+    text: String,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+    Received(Frame),
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(relm: &Relm<Self>, url: String) -> Model {
+        let socket = execute::<WebSocket>((url, relm::Backoff::new(500)));
+        connect_stream!(socket@WebSocketMsg::Message(ref frame), relm.stream(), Received(frame.clone()));
+        Model {
+            _socket: socket,
+            text: String::new(),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => gtk::main_quit(),
+            Received(Frame::Binary(data)) => self.model.text = format!("{} bytes", data.len()),
+            Received(Frame::Text(text)) => self.model.text = text,
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Label {
+                text: &self.model.text,
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run("ws://localhost:8080".to_string()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::net::TcpListener;
+    use std::rc::Rc;
+    use std::thread;
+    use std::time::Duration;
+
+    use relm::Backoff;
+    use relm::websocket::{Frame, WebSocket, WebSocketMsg};
+    use relm_test::wait_for;
+    use tungstenite::Message;
+    use tungstenite::protocol::CloseFrame;
+    use tungstenite::protocol::frame::coding::CloseCode;
+
+    // Accept one connection, answer its first message and close it. The following connections
+    // are refused.
+    fn serve() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+        let url = format!("ws://{}", listener.local_addr().expect("local address"));
+        thread::spawn(move || {
+            let (connection, _) = listener.accept().expect("accept");
+            drop(listener);
+            let mut socket = tungstenite::accept(connection).expect("handshake");
+            let message = socket.read_message().expect("read");
+            let text = message.into_text().expect("text message");
+            socket.write_message(Message::Text(format!("echo {}", text))).expect("write");
+            socket.close(Some(CloseFrame {
+                code: CloseCode::Normal,
+                reason: "bye".into(),
+            })).expect("close");
+            // Wait until the client acknowledges the close.
+            while socket.read_message().is_ok() {
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn reconnect_until_giving_up() {
+        gtk::init().expect("gtk::init failed");
+        let url = serve();
+        let socket = relm::execute::<WebSocket>((url, Backoff::new(10).max_attempts(2)));
+        let events = Rc::new(RefCell::new(vec![]));
+        let received_events = events.clone();
+        socket.observe(move |msg| {
+            let event =
+                match *msg {
+                    WebSocketMsg::Close | WebSocketMsg::Send(_) => return,
+                    WebSocketMsg::Closed(ref reason) if reason == "bye" => "Closed bye".to_string(),
+                    WebSocketMsg::Closed(_) => "Closed".to_string(),
+                    WebSocketMsg::Connected => "Connected".to_string(),
+                    WebSocketMsg::GaveUp => "GaveUp".to_string(),
+                    WebSocketMsg::Message(ref frame) => format!("Message {:?}", frame),
+                    WebSocketMsg::Reconnecting(attempt) => format!("Reconnecting {}", attempt),
+                };
+            received_events.borrow_mut().push(event);
+        });
+        // The frames sent before the connection is established are sent once it is.
+        socket.emit(WebSocketMsg::Send(Frame::Text("hello".to_string())));
+
+        wait_for(|| events.borrow().last().map(String::as_str) == Some("GaveUp"), Duration::from_secs(5));
+        assert_eq!(*events.borrow(), vec![
+            "Connected".to_string(),
+            format!("Message {:?}", Frame::Text("echo hello".to_string())),
+            "Closed bye".to_string(),
+            "Reconnecting 1".to_string(),
+            // The listener is closed, so the reconnection fails.
+            "Closed".to_string(),
+            "GaveUp".to_string(),
+        ]);
+    }
+}
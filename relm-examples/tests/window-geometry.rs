@@ -0,0 +1,99 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{Inhibit, WidgetExt};
+use relm::Widget;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+pub struct Model {
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Quit,
+}
+
+#[widget(persist_geometry = "main-window")]
+impl Widget for Win {
+    fn model() -> Model {
+        Model {
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Quit => {
+                // The test closes the window without running the main loop.
+                if gtk::main_level() > 0 {
+                    gtk::main_quit();
+                }
+            },
+        }
+    }
+
+    view! {
+        #[name="window"]
+        gtk::Window {
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::process;
+    use std::time::Duration;
+
+    use gtk::{GtkWindowExt, WidgetExt};
+    use relm_test::wait_for;
+
+    use crate::Win;
+
+    #[test]
+    fn geometry_is_restored_and_saved() {
+        let state_dir = env::temp_dir().join(format!("relm-window-geometry-{}", process::id()));
+        fs::create_dir_all(&state_dir).expect("create state dir");
+        let path = state_dir.join("window-geometry.ini");
+        fs::write(&path, "[main-window]\nheight=300\nmaximized=false\nwidth=400\nx=10\ny=20\n")
+            .expect("write geometry");
+        relm::set_state_dir(&state_dir);
+
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        wait_for(|| widgets.window.get_size() == (400, 300), Duration::from_secs(5));
+
+        widgets.window.resize(500, 350);
+        wait_for(|| widgets.window.get_size() == (500, 350), Duration::from_secs(5));
+        widgets.window.close();
+        wait_for(|| fs::read_to_string(&path).map(|content| content.contains("width=500")).unwrap_or(false),
+            Duration::from_secs(5));
+        let content = fs::read_to_string(&path).expect("read geometry");
+        assert!(content.contains("height=350"));
+        let _ = fs::remove_dir_all(&state_dir);
+    }
+}
@@ -0,0 +1,138 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    ButtonExt,
+    Inhibit,
+    LabelExt,
+    OrientableExt,
+    WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::{Relm, Widget, WindowManager};
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+#[derive(Msg)]
+pub enum SettingsMsg {
+}
+
+#[widget]
+impl Widget for SettingsWin {
+    fn model(title: String) -> String {
+        title
+    }
+
+    fn update(&mut self, _event: SettingsMsg) {
+    }
+
+    view! {
+        gtk::Window {
+            title: &self.model,
+            gtk::Label {
+                text: "Settings",
+            },
+        }
+    }
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Closed(u32),
+    OpenSettings,
+    Quit,
+}
+
+pub struct Model {
+    next_key: u32,
+    windows: WindowManager<u32, Msg>,
+}
+
+#[widget]
+impl Widget for Win {
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        Model {
+            next_key: 0,
+            windows: WindowManager::new(relm.stream(), Closed),
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Closed(key) => println!("Settings window {} closed", key),
+            OpenSettings => {
+                let key = self.model.next_key;
+                self.model.next_key += 1;
+                if let Err(error) = self.model.windows.open::<SettingsWin>(key, format!("Settings {}", key)) {
+                    eprintln!("Cannot open window: {}", error);
+                }
+            },
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="open_button"]
+                gtk::Button {
+                    clicked => OpenSettings,
+                    label: "Open settings",
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk_test::click;
+    use relm::WindowManager;
+
+    use crate::Msg::Closed;
+    use crate::{SettingsWin, Win};
+
+    #[test]
+    fn window_manager() {
+        let (component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        click(&widgets.open_button);
+
+        let mut windows = WindowManager::new(&component.stream(), Closed);
+        windows.open::<SettingsWin>(1, "First".to_string()).expect("open");
+        windows.open::<SettingsWin>(2, "Second".to_string()).expect("open");
+        windows.open::<SettingsWin>(1, "Again".to_string()).expect("open");
+        assert_eq!(windows.len(), 2);
+        assert!(windows.present(&2));
+
+        assert!(windows.close(&1));
+        assert!(!windows.contains(&1));
+        assert!(!windows.close(&1));
+        assert!(!windows.present(&1));
+        assert_eq!(windows.keys(), vec![2]);
+    }
+}
@@ -0,0 +1,221 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use gtk::{
+    ContainerExt,
+    EditableSignals,
+    EntryExt,
+    Inhibit,
+    LabelExt,
+    OrientableExt,
+    ToggleButtonExt,
+    WidgetExt,
+};
+use gtk::Orientation::Vertical;
+use relm::{Component, Relm, Widget, connect};
+use relm::wizard::{Wizard, WizardStep};
+use relm::wizard::WizardMsg::Finished;
+use relm_derive::{Msg, widget};
+
+use self::Msg::*;
+
+#[derive(Clone, Default)]
+pub struct Signup {
+    accepted: bool,
+    name: String,
+}
+
+#[derive(Msg)]
+pub enum NameMsg {
+    NameChanged(String),
+}
+
+#[widget]
+impl Widget for NameStep {
+    fn model() -> () {
+    }
+
+    fn update(&mut self, _event: NameMsg) {
+    }
+
+    view! {
+        gtk::Entry {
+            changed(entry) => NameMsg::NameChanged(entry.get_text().to_string()),
+        }
+    }
+}
+
+impl WizardStep<Signup> for NameStep {
+    fn initially_complete() -> bool {
+        false
+    }
+
+    fn step_update(msg: &NameMsg, signup: &mut Signup) -> Option<bool> {
+        match *msg {
+            NameMsg::NameChanged(ref name) => {
+                signup.name = name.clone();
+                Some(!name.is_empty())
+            },
+        }
+    }
+}
+
+#[derive(Msg)]
+pub enum TermsMsg {
+    Toggled(bool),
+}
+
+#[widget]
+impl Widget for TermsStep {
+    fn model() -> () {
+    }
+
+    fn update(&mut self, _event: TermsMsg) {
+    }
+
+    view! {
+        gtk::CheckButton {
+            label: "I accept the terms",
+            toggled(button) => TermsMsg::Toggled(button.get_active()),
+        }
+    }
+}
+
+impl WizardStep<Signup> for TermsStep {
+    fn initially_complete() -> bool {
+        false
+    }
+
+    fn step_update(msg: &TermsMsg, signup: &mut Signup) -> Option<bool> {
+        match *msg {
+            TermsMsg::Toggled(accepted) => {
+                signup.accepted = accepted;
+                Some(accepted)
+            },
+        }
+    }
+}
+
+pub struct Model {
+    text: String,
+    wizard: Component<Wizard<Signup>>,
+}
+
+#[derive(Msg)]
+pub enum Msg {
+    Done(Signup),
+    Quit,
+}
+
+#[widget]
+impl Widget for Win {
+    fn init_view(&mut self) {
+        self.widgets.container.add(self.model.wizard.widget());
+    }
+
+    fn model(relm: &Relm<Self>, _: ()) -> Model {
+        let wizard = relm::create_component::<Wizard<Signup>>(Signup::default());
+        wizard.add_step::<NameStep>("Name", ());
+        wizard.add_step::<TermsStep>("Terms", ());
+        connect!(wizard@Finished(ref signup), relm, Done(signup.clone()));
+        Model {
+            text: String::new(),
+            wizard,
+        }
+    }
+
+    fn update(&mut self, event: Msg) {
+        match event {
+            Done(signup) => self.model.text = format!("{} signed up", signup.name),
+            Quit => gtk::main_quit(),
+        }
+    }
+
+    view! {
+        gtk::Window {
+            gtk::Box {
+                orientation: Vertical,
+                #[name="container"]
+                gtk::Box {
+                },
+                #[name="result"]
+                gtk::Label {
+                    text: &self.model.text,
+                },
+            },
+            delete_event(_, _) => (Quit, Inhibit(false)),
+        }
+    }
+}
+
+fn main() {
+    Win::run(()).expect("Win::run failed");
+}
+
+#[cfg(test)]
+mod tests {
+    use glib::Cast;
+    use gtk::{ButtonExt, ContainerExt, EntryExt, LabelExt, ToggleButtonExt, WidgetExt};
+    use gtk_test::assert_text;
+    use relm_test::settle;
+
+    use crate::Win;
+
+    fn child<W: glib::IsA<gtk::Widget>>(container: &gtk::Container, index: usize) -> W {
+        container.get_children()[index].clone().downcast().expect("child")
+    }
+
+    #[test]
+    fn wizard_steps() {
+        let (_component, _, widgets) = relm::init_test::<Win>(()).expect("init_test failed");
+        settle();
+        let wizard: gtk::Container = child(widgets.container.upcast_ref(), 0);
+        let title: gtk::Label = child(&wizard, 0);
+        let stack: gtk::Stack = child(&wizard, 1);
+        let buttons: gtk::Container = child(&wizard, 2);
+        let back: gtk::Button = child(&buttons, 1);
+        let next: gtk::Button = child(&buttons, 2);
+        let finish: gtk::Button = child(&buttons, 3);
+        let entry: gtk::Entry = child(stack.upcast_ref(), 0);
+        let terms: gtk::CheckButton = child(stack.upcast_ref(), 1);
+
+        assert_text!(title, "Step 1 of 2: Name");
+        assert!(!back.get_sensitive());
+        assert!(!next.get_sensitive());
+        assert!(!finish.get_visible());
+
+        entry.set_text("Alice");
+        settle();
+        assert!(next.get_sensitive());
+        next.clicked();
+        settle();
+        assert_text!(title, "Step 2 of 2: Terms");
+        assert!(back.get_sensitive());
+        assert!(!next.get_visible());
+        assert!(!finish.get_sensitive());
+
+        terms.set_active(true);
+        settle();
+        finish.clicked();
+        settle();
+        assert_text!(widgets.result, "Alice signed up");
+    }
+}
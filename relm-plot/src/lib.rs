@@ -0,0 +1,445 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Line, bar and scatter plot components for relm, drawn with cairo.
+//!
+//! The points are sent to the component by its parent, from its model:
+//!
+//! ```ignore
+//! let plot = relm::create_component::<Plot>(PlotKind::Line);
+//! plot.emit(SetPoints(self.model.samples.clone()));
+//! // Later, when new samples arrive:
+//! plot.emit(AppendPoints(new_samples));
+//! ```
+//!
+//! Appending points to a line or scatter plot whose view does not change only draws the new
+//! points. Scrolling zooms around the pointer and dragging pans the view, which can also be done
+//! with the `Zoom` and `Pan` messages.
+
+#![warn(
+    missing_docs,
+    trivial_casts,
+    trivial_numeric_casts,
+    unused_extern_crates,
+    unused_import_braces,
+    unused_qualifications,
+)]
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use gdk::EventMask;
+use gtk::{Inhibit, WidgetExt};
+use relm::{DisplayVariant, DrawContext, DrawHandler, Relm, StreamHandle, Update, Widget};
+
+use self::PlotMsg::*;
+
+const MARGIN: f64 = 30.0;
+
+/// The kind of plot.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlotKind {
+    /// Vertical bars from the x axis to each point.
+    Bar,
+    /// Points joined by lines.
+    Line,
+    /// Unjoined points.
+    Scatter,
+}
+
+/// The range of the data shown by the plot.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bounds {
+    /// The minimum x value.
+    pub x_min: f64,
+    /// The maximum x value.
+    pub x_max: f64,
+    /// The minimum y value.
+    pub y_min: f64,
+    /// The maximum y value.
+    pub y_max: f64,
+}
+
+impl Bounds {
+    fn of_points(points: &[(f64, f64)]) -> Self {
+        let mut bounds = Bounds {
+            x_min: 0.0,
+            x_max: 1.0,
+            y_min: 0.0,
+            y_max: 1.0,
+        };
+        if let Some(&(x, y)) = points.first() {
+            bounds = Bounds {
+                x_min: x,
+                x_max: x,
+                y_min: y.min(0.0),
+                y_max: y.max(0.0),
+            };
+            for &(x, y) in points {
+                bounds.x_min = bounds.x_min.min(x);
+                bounds.x_max = bounds.x_max.max(x);
+                bounds.y_min = bounds.y_min.min(y);
+                bounds.y_max = bounds.y_max.max(y);
+            }
+        }
+        if bounds.x_max <= bounds.x_min {
+            bounds.x_max = bounds.x_min + 1.0;
+        }
+        if bounds.y_max <= bounds.y_min {
+            bounds.y_max = bounds.y_min + 1.0;
+        }
+        bounds
+    }
+
+    fn contains(&self, (x, y): (f64, f64)) -> bool {
+        x >= self.x_min && x <= self.x_max && y >= self.y_min && y <= self.y_max
+    }
+
+    fn zoom(&self, factor: f64, (center_x, center_y): (f64, f64)) -> Self {
+        Bounds {
+            x_min: center_x - (center_x - self.x_min) / factor,
+            x_max: center_x + (self.x_max - center_x) / factor,
+            y_min: center_y - (center_y - self.y_min) / factor,
+            y_max: center_y + (self.y_max - center_y) / factor,
+        }
+    }
+}
+
+/// Messages of the [`Plot`](struct.Plot.html) component.
+pub enum PlotMsg {
+    /// Add points after the existing ones.
+    AppendPoints(Vec<(f64, f64)>),
+    /// Move the view by this fraction of its width and height.
+    Pan(f64, f64),
+    #[doc(hidden)]
+    Redraw,
+    /// Go back to the view fitting all the points.
+    ResetView,
+    /// Replace all the points.
+    SetPoints(Vec<(f64, f64)>),
+    /// Zoom by this factor, greater than 1 to zoom in, around the center of the view.
+    Zoom(f64),
+    #[doc(hidden)]
+    ZoomAt(f64, f64, f64),
+}
+
+impl DisplayVariant for PlotMsg {
+    fn display_variant(&self) -> &'static str {
+        match *self {
+            AppendPoints(_) => "AppendPoints",
+            Pan(_, _) => "Pan",
+            Redraw => "Redraw",
+            ResetView => "ResetView",
+            SetPoints(_) => "SetPoints",
+            Zoom(_) => "Zoom",
+            ZoomAt(_, _, _) => "ZoomAt",
+        }
+    }
+}
+
+#[doc(hidden)]
+pub struct PlotModel {
+    // The number of points already drawn, to only draw the new ones when possible.
+    drawn_points: usize,
+    draw_handler: DrawHandler<gtk::DrawingArea>,
+    // Whether the whole plot must be drawn again at the next redraw.
+    full_redraw: bool,
+    kind: PlotKind,
+    points: Vec<(f64, f64)>,
+    size: (i32, i32),
+    // The bounds chosen by zooming or panning, instead of fitting the points.
+    view: Option<Bounds>,
+}
+
+/// Plot component, whose model parameter is the kind of plot.
+pub struct Plot {
+    drawing_area: gtk::DrawingArea,
+    model: PlotModel,
+}
+
+impl Plot {
+    fn bounds(&self) -> Bounds {
+        self.model.view.unwrap_or_else(|| Bounds::of_points(&self.model.points))
+    }
+
+    fn invalidate(&mut self) {
+        self.model.full_redraw = true;
+        self.model.draw_handler.queue_redraw();
+    }
+
+    fn draw(&mut self) {
+        let allocation = self.drawing_area.get_allocation();
+        let size = (allocation.width, allocation.height);
+        if size != self.model.size {
+            self.model.size = size;
+            self.model.full_redraw = true;
+        }
+        let bounds = self.bounds();
+        let transform = Transform::new(bounds, size);
+        let kind = self.model.kind;
+        let full_redraw = self.model.full_redraw || kind == PlotKind::Bar;
+        let first_point =
+            if full_redraw {
+                0
+            }
+            else {
+                // Redraw the last drawn point to join it to the new ones.
+                self.model.drawn_points.saturating_sub(1)
+            };
+        let mut context = self.model.draw_handler.get_context();
+        if full_redraw {
+            context.set_source_rgb(1.0, 1.0, 1.0);
+            context.paint();
+            draw_axes(&context, &bounds, &transform);
+        }
+        let new_points = &self.model.points[first_point.min(self.model.points.len())..];
+        draw_points(&mut context, kind, new_points, &transform, !full_redraw);
+        self.model.drawn_points = self.model.points.len();
+        self.model.full_redraw = false;
+    }
+}
+
+struct Transform {
+    bounds: Bounds,
+    height: f64,
+    width: f64,
+}
+
+impl Transform {
+    fn new(bounds: Bounds, (width, height): (i32, i32)) -> Self {
+        Transform {
+            bounds,
+            height: (height as f64 - 2.0 * MARGIN).max(1.0),
+            width: (width as f64 - 2.0 * MARGIN).max(1.0),
+        }
+    }
+
+    fn to_screen(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        let bounds = &self.bounds;
+        (MARGIN + (x - bounds.x_min) / (bounds.x_max - bounds.x_min) * self.width,
+            MARGIN + (bounds.y_max - y) / (bounds.y_max - bounds.y_min) * self.height)
+    }
+
+    fn to_data(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        let bounds = &self.bounds;
+        (bounds.x_min + (x - MARGIN) / self.width * (bounds.x_max - bounds.x_min),
+            bounds.y_max - (y - MARGIN) / self.height * (bounds.y_max - bounds.y_min))
+    }
+}
+
+fn draw_axes(context: &cairo::Context, bounds: &Bounds, transform: &Transform) {
+    context.set_source_rgb(0.3, 0.3, 0.3);
+    context.set_line_width(1.0);
+    let (left, bottom) = transform.to_screen((bounds.x_min, bounds.y_min));
+    let (right, top) = transform.to_screen((bounds.x_max, bounds.y_max));
+    context.move_to(left, top);
+    context.line_to(left, bottom);
+    context.line_to(right, bottom);
+    context.stroke();
+
+    context.set_font_size(10.0);
+    context.move_to(2.0, top + 10.0);
+    context.show_text(&format!("{:.1}", bounds.y_max));
+    context.move_to(2.0, bottom);
+    context.show_text(&format!("{:.1}", bounds.y_min));
+    context.move_to(left, bottom + 14.0);
+    context.show_text(&format!("{:.1}", bounds.x_min));
+    context.move_to(right - 20.0, bottom + 14.0);
+    context.show_text(&format!("{:.1}", bounds.x_max));
+}
+
+fn draw_points(context: &mut DrawContext<gtk::DrawingArea>, kind: PlotKind, points: &[(f64, f64)],
+    transform: &Transform, partial: bool)
+{
+    context.save();
+    context.rectangle(MARGIN, MARGIN, transform.width, transform.height);
+    context.clip();
+    context.set_source_rgb(0.2, 0.4, 0.8);
+    context.set_line_width(2.0);
+    let screen_points: Vec<_> = points.iter().map(|&point| transform.to_screen(point)).collect();
+    match kind {
+        PlotKind::Bar => {
+            let bar_width = (transform.width / points.len().max(1) as f64 * 0.8).max(1.0);
+            let (_, zero) = transform.to_screen((0.0, 0.0_f64.max(transform.bounds.y_min)));
+            for &(x, y) in &screen_points {
+                context.rectangle(x - bar_width / 2.0, y.min(zero), bar_width, (zero - y).abs());
+            }
+            context.fill();
+        },
+        PlotKind::Line => {
+            if let Some(&(x, y)) = screen_points.first() {
+                context.move_to(x, y);
+                for &(x, y) in &screen_points[1..] {
+                    context.line_to(x, y);
+                }
+                context.stroke();
+            }
+        },
+        PlotKind::Scatter => {
+            for &(x, y) in &screen_points {
+                context.arc(x, y, 3.0, 0.0, 2.0 * std::f64::consts::PI);
+                context.fill();
+            }
+        },
+    }
+    context.restore();
+
+    if partial {
+        // Only show the area of the new points.
+        let (mut left, mut top) = (f64::INFINITY, f64::INFINITY);
+        let (mut right, mut bottom) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for &(x, y) in &screen_points {
+            left = left.min(x);
+            right = right.max(x);
+            top = top.min(y);
+            bottom = bottom.max(y);
+        }
+        if left <= right {
+            context.invalidate_area(left as i32 - 4, top as i32 - 4, (right - left) as i32 + 8,
+                (bottom - top) as i32 + 8);
+        }
+    }
+}
+
+impl Update for Plot {
+    type Model = PlotModel;
+    type ModelParam = PlotKind;
+    type Msg = PlotMsg;
+
+    fn model(_: &Relm<Self>, kind: PlotKind) -> PlotModel {
+        PlotModel {
+            drawn_points: 0,
+            draw_handler: DrawHandler::new().expect("draw handler"),
+            full_redraw: true,
+            kind,
+            points: vec![],
+            size: (0, 0),
+            view: None,
+        }
+    }
+
+    fn update(&mut self, event: PlotMsg) {
+        match event {
+            AppendPoints(points) => {
+                let bounds = self.bounds();
+                // Points outside of the view change the bounds fitting the points.
+                if self.model.view.is_none() && !points.iter().all(|&point| bounds.contains(point)) {
+                    self.model.full_redraw = true;
+                }
+                self.model.points.extend(points);
+                self.model.draw_handler.queue_redraw();
+            },
+            Pan(dx, dy) => {
+                let bounds = self.bounds();
+                let (width, height) = (bounds.x_max - bounds.x_min, bounds.y_max - bounds.y_min);
+                self.model.view = Some(Bounds {
+                    x_min: bounds.x_min + dx * width,
+                    x_max: bounds.x_max + dx * width,
+                    y_min: bounds.y_min + dy * height,
+                    y_max: bounds.y_max + dy * height,
+                });
+                self.invalidate();
+            },
+            Redraw => self.draw(),
+            ResetView => {
+                self.model.view = None;
+                self.invalidate();
+            },
+            SetPoints(points) => {
+                self.model.points = points;
+                self.invalidate();
+            },
+            Zoom(factor) => {
+                let bounds = self.bounds();
+                let center = ((bounds.x_min + bounds.x_max) / 2.0, (bounds.y_min + bounds.y_max) / 2.0);
+                self.model.view = Some(bounds.zoom(factor, center));
+                self.invalidate();
+            },
+            ZoomAt(factor, x, y) => {
+                let bounds = self.bounds();
+                let transform = Transform::new(bounds, self.model.size);
+                self.model.view = Some(bounds.zoom(factor, transform.to_data((x, y))));
+                self.invalidate();
+            },
+        }
+    }
+}
+
+impl Widget for Plot {
+    type Root = gtk::DrawingArea;
+
+    fn root(&self) -> Self::Root {
+        self.drawing_area.clone()
+    }
+
+    fn view(relm: &Relm<Self>, mut model: PlotModel) -> Self {
+        let drawing_area = gtk::DrawingArea::new();
+        drawing_area.set_size_request(200, 150);
+        drawing_area.add_events(EventMask::SCROLL_MASK | EventMask::BUTTON_PRESS_MASK |
+            EventMask::BUTTON_MOTION_MASK);
+        model.draw_handler.init(&drawing_area);
+        model.draw_handler.connect_redraw(relm.stream(), || Redraw);
+
+        let stream = relm.stream().clone();
+        drawing_area.connect_scroll_event(move |_, event| {
+            let (x, y) = event.get_position();
+            let factor =
+                match event.get_direction() {
+                    gdk::ScrollDirection::Up => 1.25,
+                    gdk::ScrollDirection::Down => 0.8,
+                    _ => return Inhibit(false),
+                };
+            stream.emit(ZoomAt(factor, x, y));
+            Inhibit(true)
+        });
+
+        let drag_position = Rc::new(Cell::new(None));
+        let press_position = drag_position.clone();
+        drawing_area.connect_button_press_event(move |_, event| {
+            press_position.set(Some(event.get_position()));
+            Inhibit(false)
+        });
+        connect_drag(&drawing_area, relm.stream(), drag_position);
+        drawing_area.show();
+
+        Plot {
+            drawing_area,
+            model,
+        }
+    }
+}
+
+fn connect_drag(drawing_area: &gtk::DrawingArea, stream: &StreamHandle<PlotMsg>,
+    drag_position: Rc<Cell<Option<(f64, f64)>>>)
+{
+    let stream = stream.clone();
+    drawing_area.connect_motion_notify_event(move |widget, event| {
+        let (x, y) = event.get_position();
+        if let Some((previous_x, previous_y)) = drag_position.replace(Some((x, y))) {
+            let allocation = widget.get_allocation();
+            let width = (allocation.width as f64 - 2.0 * MARGIN).max(1.0);
+            let height = (allocation.height as f64 - 2.0 * MARGIN).max(1.0);
+            // Dragging to the right shows what is on the left.
+            stream.emit(Pan((previous_x - x) / width, (y - previous_y) / height));
+        }
+        Inhibit(false)
+    });
+}
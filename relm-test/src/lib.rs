@@ -19,59 +19,113 @@
  * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
  */
 
+use std::any::Any;
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
+use atk::{AtkObjectExt, Role};
 use enigo::{Enigo, KeyboardControllable, MouseButton, MouseControllable};
 use gdk::keyval_to_unicode;
 use gdk::keys::Key;
 use gdk::keys::constants as key;
-use glib::{IsA, Object, object::Cast};
-use gtk::{Inhibit, ToolButton, ToolButtonExt, Widget, WidgetExt};
+use glib::{IsA, Object, ObjectExt, SignalHandlerId, object::Cast};
+use gtk::{BinExt, ContainerExt, DirectionType, GtkWindowExt, Inhibit, ToolButton, ToolButtonExt, Widget, WidgetExt};
 use gtk_test::{focus, mouse_move, run_loop, wait_for_draw};
-use relm::StreamHandle;
+use relm::{Component, DisplayVariant, StreamHandle, WidgetTest};
+
+mod recorder;
+mod serial;
+
+pub use recorder::Recorder;
+pub use relm_derive::relm_test;
+pub use serial::run_serialized;
 
-// TODO: should remove the signal after wait()?
 // FIXME: remove when it's in gtk-test.
 macro_rules! gtk_observer_new {
     ($widget:expr, $signal_name:ident, |$e1:pat $(,$e:pat)*|) => {{
         let observer = gtk_test::Observer::new();
         let res = (*observer.get_inner()).clone();
-        $widget.$signal_name(move |$e1 $(,$e:expr)*| {
+        let handler_id = $widget.$signal_name(move |$e1 $(,$e:expr)*| {
             *res.borrow_mut() = true;
         });
-        observer
+        SignalObserver::new($widget.clone().upcast::<Object>(), handler_id, observer)
     }};
     ($widget:expr, $signal_name:ident, |$e1:pat $(,$e:pat)*| $block:block) => {{
         let observer = gtk_test::Observer::new();
         let res = (*observer.get_inner()).clone();
-        $widget.$signal_name(move |$e1 $(,$e)*| {
+        let handler_id = $widget.$signal_name(move |$e1 $(,$e)*| {
             *res.borrow_mut() = true;
             $block
         });
-        observer
+        SignalObserver::new($widget.clone().upcast::<Object>(), handler_id, observer)
     }}
 }
 
-pub struct Observer<MSG> {
-    result: Rc<RefCell<Option<MSG>>>,
+/// A gtk signal observer which disconnects its handler once the signal was received.
+struct SignalObserver {
+    handler_id: SignalHandlerId,
+    object: Object,
+    observer: gtk_test::Observer,
+}
+
+impl SignalObserver {
+    fn new(object: Object, handler_id: SignalHandlerId, observer: gtk_test::Observer) -> Self {
+        Self {
+            handler_id,
+            object,
+            observer,
+        }
+    }
+
+    fn wait(self) {
+        self.observer.wait();
+        self.object.disconnect(self.handler_id);
+    }
+}
+
+/// An observer of the messages of a relm stream.
+/// The observer is removed from the stream when this value is dropped.
+pub struct Observer<T> {
+    result: Rc<RefCell<Option<T>>>,
+    _guard: Box<dyn Any>,
 }
 
 impl<MSG: Clone + 'static> Observer<MSG> {
     pub fn new<F: Fn(&MSG) -> bool + 'static>(stream: StreamHandle<MSG>, predicate: F) -> Self {
+        Self::with_projection(stream, move |msg| {
+            if predicate(msg) {
+                Some(msg.clone())
+            }
+            else {
+                None
+            }
+        })
+    }
+}
+
+impl<T: 'static> Observer<T> {
+    /// Observe the messages for which `projection` returns a value.
+    /// This is useful for messages that cannot be cloned: the projection can extract the part of
+    /// the message to check in the test.
+    pub fn with_projection<MSG, F>(stream: StreamHandle<MSG>, projection: F) -> Self
+        where F: Fn(&MSG) -> Option<T> + 'static,
+              MSG: 'static,
+    {
         let result = Rc::new(RefCell::new(None));
         let res = result.clone();
-        stream.observe(move |msg| {
-            if predicate(msg) {
-                *res.borrow_mut() = Some(msg.clone());
+        let guard = stream.observe_scoped(move |msg| {
+            if let Some(value) = projection(msg) {
+                *res.borrow_mut() = Some(value);
             }
         });
         Self {
             result,
+            _guard: Box::new(guard),
         }
     }
 
-    pub fn wait(&self) -> MSG {
+    pub fn wait(&self) -> T {
         loop {
             if let Ok(ref result) = self.result.try_borrow() {
                 if result.is_some() {
@@ -97,6 +151,16 @@ macro_rules! relm_observer_new {
             }
         );
     };
+    ($component:expr, $pat:pat => $projection:expr) => {
+        $crate::Observer::with_projection($component.stream(), |msg|
+            if let $pat = msg {
+                Some($projection)
+            }
+            else {
+                None
+            }
+        )
+    };
 }
 
 #[macro_export]
@@ -229,6 +293,273 @@ pub fn enter_keys<W: Clone + IsA<Object> + IsA<Widget> + WidgetExt>(widget: &W,
     });
 }
 
+/// Panic if `widget` does not have the keyboard focus in its window.
+pub fn assert_focused<W: IsA<Widget>>(widget: &W) {
+    run_loop();
+    if !widget.has_focus() {
+        let window = widget.get_toplevel()
+            .and_then(|toplevel| toplevel.dynamic_cast::<gtk::Window>().ok());
+        let focused = window.and_then(|window| window.get_focus())
+            .map(|focused| focused.get_type().name())
+            .unwrap_or_else(|| "no widget".to_string());
+        panic!("Expected {} to be focused, but {} is focused", widget.get_type().name(), focused);
+    }
+}
+
+/// Move the focus of `window` to the next widget, like when pressing Tab.
+pub fn tab_forward<W: IsA<gtk::Window>>(window: &W) {
+    move_focus(window, DirectionType::TabForward);
+}
+
+/// Move the focus of `window` to the previous widget, like when pressing Shift+Tab.
+pub fn tab_backward<W: IsA<gtk::Window>>(window: &W) {
+    move_focus(window, DirectionType::TabBackward);
+}
+
+/// Get the widgets of `window` in the order they get the focus when pressing Tab, starting from
+/// the first one.
+/// The focus is restored afterwards.
+pub fn focus_chain<W: IsA<gtk::Window>>(window: &W) -> Vec<Widget> {
+    let initial_focus = window.get_focus();
+    window.set_focus(None::<&Widget>);
+    let mut chain: Vec<Widget> = vec![];
+    loop {
+        move_focus(window, DirectionType::TabForward);
+        match window.get_focus() {
+            Some(widget) if !chain.contains(&widget) => chain.push(widget),
+            _ => break,
+        }
+    }
+    window.set_focus(initial_focus.as_ref());
+    run_loop();
+    chain
+}
+
+/// Find the first widget of the tree of `root` whose accessible name is `name`, like the label of a
+/// button.
+pub fn find_by_accessible_name<W: IsA<Widget>>(root: &W, name: &str) -> Option<Widget> {
+    descendants(root).into_iter()
+        .find(|widget| accessible_name(widget).as_deref() == Some(name))
+}
+
+/// Find the widgets of the tree of `root` whose accessible role is `role`.
+pub fn find_all_by_accessible_role<W: IsA<Widget>>(root: &W, role: Role) -> Vec<Widget> {
+    descendants(root).into_iter()
+        .filter(|widget| accessible_role(widget) == Some(role))
+        .collect()
+}
+
+/// Find the first widget of the tree of `root` whose accessible role is `role` and whose
+/// accessible name is `name`, like the push button labeled "Save".
+pub fn find_by_role_and_name<W: IsA<Widget>>(root: &W, role: Role, name: &str) -> Option<Widget> {
+    descendants(root).into_iter()
+        .find(|widget| accessible_role(widget) == Some(role) && accessible_name(widget).as_deref() == Some(name))
+}
+
+/// Find the widget of type `W` named `name` (with the `widget_name` property) in the tree of `root`,
+/// including the widgets of the nested relm components.
+///
+/// ```ignore
+/// let button = find_child::<gtk::Button>(component.widget(), "save").expect("save button");
+/// ```
+pub fn find_child<W: IsA<Widget>>(root: &dyn AsRef<Widget>, name: &str) -> Option<W> {
+    descendants(root.as_ref()).into_iter()
+        .filter(|widget| widget.get_widget_name().as_str() == name)
+        .find_map(|widget| widget.downcast::<W>().ok())
+}
+
+/// Find the widgets of type `W` in the tree of `root`, including the widgets of the nested relm
+/// components.
+pub fn find_children<W: IsA<Widget>>(root: &dyn AsRef<Widget>) -> Vec<W> {
+    descendants(root.as_ref()).into_iter()
+        .filter_map(|widget| widget.downcast::<W>().ok())
+        .collect()
+}
+
+/// Resize `window` to `width`x`height` and wait until the window manager resized it.
+pub fn resize_window<W: IsA<gtk::Window>>(window: &W, width: i32, height: i32) {
+    window.resize(width, height);
+    pump_until(|| window.get_size() == (width, height),
+        &format!("Window was not resized to {}x{}", width, height));
+}
+
+/// Move `window` to (`x`, `y`) and wait until the window manager moved it.
+pub fn move_window<W: IsA<gtk::Window>>(window: &W, x: i32, y: i32) {
+    window.move_(x, y);
+    pump_until(|| window.get_position() == (x, y), &format!("Window was not moved to ({}, {})", x, y));
+}
+
+/// Maximize `window` and wait until the window manager maximized it.
+pub fn maximize<W: IsA<gtk::Window>>(window: &W) {
+    window.maximize();
+    pump_until(|| window.is_maximized(), "Window was not maximized");
+}
+
+/// Unmaximize `window` and wait until the window manager unmaximized it.
+pub fn unmaximize<W: IsA<gtk::Window>>(window: &W) {
+    window.unmaximize();
+    pump_until(|| !window.is_maximized(), "Window was not unmaximized");
+}
+
+/// Run `body` with the scale factor of the display simulated to `scale`, like on a HiDPI screen.
+///
+/// The scale factor is read by GDK when GTK is initialized, so this function must be called
+/// before `gtk::init()` (e.g. before `relm::init_test()`), which is easier by having the test in
+/// its own test file. It panics if GTK was already initialized with another scale factor.
+pub fn with_scale_factor<F: FnOnce()>(scale: i32, body: F) {
+    if !gtk::is_initialized() {
+        std::env::set_var("GDK_SCALE", scale.to_string());
+        gtk::init().expect("gtk::init failed");
+    }
+    let current_scale = gdk::Display::get_default()
+        .and_then(|display| display.get_monitor(0))
+        .map(|monitor| monitor.get_scale_factor())
+        .unwrap_or(1);
+    if current_scale != scale {
+        panic!("GTK was initialized with the scale factor {} instead of {}", current_scale, scale);
+    }
+    body();
+}
+
+/// Panic if the allocation of `widget` is not `width`x`height` logical pixels.
+pub fn assert_logical_size<W: IsA<Widget>>(widget: &W, width: i32, height: i32) {
+    run_loop();
+    let allocation = widget.get_allocation();
+    assert_eq!((allocation.width, allocation.height), (width, height),
+        "Unexpected logical size of {}", widget.get_type().name());
+}
+
+/// Panic if the allocation of `widget` is not `width`x`height` device pixels, i.e. the logical
+/// size multiplied by the scale factor.
+pub fn assert_device_size<W: IsA<Widget>>(widget: &W, width: i32, height: i32) {
+    run_loop();
+    let allocation = widget.get_allocation();
+    let scale = widget.get_scale_factor();
+    assert_eq!((allocation.width * scale, allocation.height * scale), (width, height),
+        "Unexpected device size of {} at scale factor {}", widget.get_type().name(), scale);
+}
+
+/// Initialize a widget like `relm::init_test()`, but inside a `gtk::OffscreenWindow`, so that the
+/// tests can read the state of its widgets without any window appearing on the screen.
+///
+/// When the root of the widget is a window, its content is moved to the offscreen window and the
+/// window is hidden.
+pub fn init_offscreen<WIDGET>(model_param: WIDGET::ModelParam) ->
+    Result<(Component<WIDGET>, WIDGET::Streams, WIDGET::Widgets), ()>
+    where WIDGET: relm::Widget + WidgetTest + 'static,
+          WIDGET::Msg: DisplayVariant + 'static,
+{
+    let (component, streams, widgets) = relm::init_test::<WIDGET>(model_param)?;
+    let offscreen = gtk::OffscreenWindow::new();
+    let root: Widget = component.widget().clone().upcast();
+    match root.clone().downcast::<gtk::Window>() {
+        Ok(window) => {
+            window.hide();
+            if let Some(child) = window.get_child() {
+                window.remove(&child);
+                offscreen.add(&child);
+            }
+        },
+        Err(_) => offscreen.add(&root),
+    }
+    offscreen.show_all();
+    run_loop();
+    Ok((component, streams, widgets))
+}
+
+/// Send `msg` to `component` and run the GTK and relm event loops until they have nothing left to
+/// process, including the messages sent while handling `msg`, so that the effects of `msg` can be
+/// asserted right after.
+pub fn send_and_settle<WIDGET>(component: &Component<WIDGET>, msg: WIDGET::Msg)
+    where WIDGET: relm::Widget,
+{
+    component.emit(msg);
+    settle();
+}
+
+/// Run the GTK and relm event loops until they have nothing left to process.
+pub fn settle() {
+    let context = glib::MainContext::default();
+    while context.iteration(false) {
+    }
+}
+
+const WINDOW_MANAGER_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn pump_until<F: Fn() -> bool>(condition: F, error: &str) {
+    if !try_wait_for(condition, WINDOW_MANAGER_TIMEOUT) {
+        panic!("{} after {:?}", error, WINDOW_MANAGER_TIMEOUT);
+    }
+    // Process the relayout following the configure event.
+    run_loop();
+}
+
+/// Run the GTK and relm event loops until `predicate` returns true, like when waiting for a
+/// label to show the result of a message:
+///
+/// ```ignore
+/// click(&widgets.plus_button);
+/// wait_for(|| widgets.label.get_text() == "3", Duration::from_secs(1));
+/// ```
+///
+/// Panic if `predicate` is still false after `timeout`.
+pub fn wait_for<F: Fn() -> bool>(predicate: F, timeout: Duration) {
+    if !try_wait_for(predicate, timeout) {
+        panic!("The condition was not met after {:?}", timeout);
+    }
+}
+
+/// Run the GTK and relm event loops until `predicate` returns true.
+/// Return false if `predicate` is still false after `timeout`.
+pub fn try_wait_for<F: Fn() -> bool>(predicate: F, timeout: Duration) -> bool {
+    let start = Instant::now();
+    let context = glib::MainContext::default();
+    while !predicate() {
+        if start.elapsed() > timeout {
+            return false;
+        }
+        // The relm messages are dispatched by a source of the default main context.
+        if !context.iteration(false) {
+            // Avoid spinning while waiting for a timeout or an event.
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+    true
+}
+
+fn accessible_name(widget: &Widget) -> Option<String> {
+    widget.get_accessible()
+        .and_then(|accessible| accessible.get_name())
+        .map(|name| name.to_string())
+}
+
+fn accessible_role(widget: &Widget) -> Option<Role> {
+    widget.get_accessible()
+        .map(|accessible| accessible.get_role())
+}
+
+/// Get `root` and its descendants, parents first.
+pub(crate) fn descendants<W: IsA<Widget>>(root: &W) -> Vec<Widget> {
+    let mut widgets = vec![];
+    let mut stack = vec![root.clone().upcast::<Widget>()];
+    while let Some(widget) = stack.pop() {
+        if let Some(container) = widget.dynamic_cast_ref::<gtk::Container>() {
+            stack.extend(container.get_children().into_iter().rev());
+        }
+        widgets.push(widget);
+    }
+    widgets
+}
+
+fn move_focus<W: IsA<gtk::Window>>(window: &W, direction: DirectionType) {
+    // Wrap around like GTK does when the focus leaves the last widget.
+    if !window.child_focus(direction) {
+        window.set_focus(None::<&Widget>);
+        window.child_focus(direction);
+    }
+    run_loop();
+}
+
 fn gdk_key_to_enigo_key(key: Key) -> enigo::Key {
     use enigo::Key::*;
     match key {
@@ -0,0 +1,159 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::cell::RefCell;
+use std::fmt::Write;
+use std::rc::Rc;
+
+use atk::AtkObjectExt;
+use glib::ObjectExt;
+use gtk::{Inhibit, Widget, WidgetExt};
+use relm::{Component, DisplayVariant};
+
+use crate::descendants;
+
+enum Action {
+    Click(String),
+    Key(String, u32),
+    Message(&'static str),
+}
+
+/// Recorder of the interactions of a user with a running component, to bootstrap a relm-test
+/// test reproducing a session, like the steps to reproduce a bug.
+///
+/// ```ignore
+/// let component = relm::init::<Win>(()).expect("init");
+/// let recorder = Recorder::new(&component);
+/// gtk::main();
+/// println!("{}", recorder.test_code("reproduce_bug", "Win"));
+/// ```
+///
+/// The clicks and the key presses are recorded on the widgets existing when the recorder is
+/// created, and the messages received by the component are recorded as comments.
+pub struct Recorder {
+    actions: Rc<RefCell<Vec<Action>>>,
+}
+
+impl Recorder {
+    /// Start recording the interactions with `component`.
+    pub fn new<WIDGET>(component: &Component<WIDGET>) -> Self
+        where WIDGET: relm::Widget,
+              WIDGET::Msg: DisplayVariant + 'static,
+    {
+        let actions = Rc::new(RefCell::new(vec![]));
+        for widget in descendants(component.widget()) {
+            let locator =
+                match widget_locator(&widget) {
+                    Some(locator) => locator,
+                    None => continue,
+                };
+            {
+                let actions = actions.clone();
+                let locator = locator.clone();
+                widget.connect_button_release_event(move |_, _| {
+                    actions.borrow_mut().push(Action::Click(locator.clone()));
+                    Inhibit(false)
+                });
+            }
+            let actions = actions.clone();
+            widget.connect_key_press_event(move |_, event| {
+                actions.borrow_mut().push(Action::Key(locator.clone(), *event.get_keyval()));
+                Inhibit(false)
+            });
+        }
+        {
+            let actions = actions.clone();
+            component.stream().observe(move |msg: &WIDGET::Msg| {
+                actions.borrow_mut().push(Action::Message(msg.display_variant()));
+            });
+        }
+        Recorder {
+            actions,
+        }
+    }
+
+    /// Generate the code of a test named `test_name` reproducing the recorded interactions with the
+    /// widget of type `widget_type`.
+    /// The code is a skeleton: the model parameter and the assertions must be added.
+    pub fn test_code(&self, test_name: &str, widget_type: &str) -> String {
+        let mut code = String::new();
+        let _ = writeln!(code, "#[test]");
+        let _ = writeln!(code, "fn {}() {{", test_name);
+        let _ = writeln!(code, "    let (component, _, _) = relm::init_test::<{}>(()).expect(\"init_test failed\");",
+            widget_type);
+        let _ = writeln!(code, "    let root = component.widget();");
+        let actions = self.actions.borrow();
+        let mut index = 0;
+        while index < actions.len() {
+            match actions[index] {
+                Action::Click(ref locator) => {
+                    let _ = writeln!(code, "    click(&{});", locator);
+                },
+                Action::Key(ref locator, keyval) => {
+                    // Merge the consecutive characters typed in the same widget.
+                    let mut text = String::new();
+                    while let Some(&Action::Key(ref next_locator, keyval)) = actions.get(index) {
+                        match gdk::keyval_to_unicode(keyval) {
+                            Some(character) if next_locator == locator && !character.is_control() => {
+                                text.push(character);
+                                index += 1;
+                            },
+                            _ => break,
+                        }
+                    }
+                    if text.is_empty() {
+                        let name = gdk::keyval_name(keyval)
+                            .map(|name| name.to_string())
+                            .unwrap_or_else(|| keyval.to_string());
+                        let _ = writeln!(code, "    enter_key(&{}, key::{});", locator, name);
+                    }
+                    else {
+                        let _ = writeln!(code, "    enter_keys(&{}, {:?});", locator, text);
+                        continue;
+                    }
+                },
+                Action::Message(name) => {
+                    let _ = writeln!(code, "    // Message {} received.", name);
+                },
+            }
+            index += 1;
+        }
+        let _ = writeln!(code, "    // TODO: add the assertions.");
+        let _ = writeln!(code, "}}");
+        code
+    }
+}
+
+/// Get the code to find `widget` in a test, from its name or its accessible role and name.
+fn widget_locator(widget: &Widget) -> Option<String> {
+    let type_name = widget.get_type().name();
+    let name = widget.get_widget_name();
+    if name.as_str() != type_name {
+        let rust_type = type_name.trim_start_matches("Gtk");
+        return Some(format!("find_child::<gtk::{}>(root, {:?}).expect(\"{}\")", rust_type, name.as_str(),
+            name.as_str()));
+    }
+    let accessible = widget.get_accessible()?;
+    let accessible_name = accessible.get_name()?;
+    let role = accessible.get_role();
+    Some(format!("find_by_role_and_name(root, Role::{:?}, {:?}).expect(\"{}\")", role, accessible_name.as_str(),
+        accessible_name.as_str()))
+}
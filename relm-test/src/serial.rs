@@ -0,0 +1,76 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Serialization of the GUI tests on a single GTK thread.
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Mutex, PoisonError};
+use std::sync::mpsc::{Sender, channel};
+use std::thread;
+
+use lazy_static::lazy_static;
+
+type Test = Box<dyn FnOnce() + Send>;
+
+lazy_static! {
+    static ref GTK_THREAD: Mutex<Sender<Test>> = Mutex::new(spawn_gtk_thread());
+}
+
+/// Run `test` on the GTK thread shared by all the tests of the process, after the tests sent
+/// before it finished.
+///
+/// Since `cargo test` runs the tests of a file on many threads but GTK can only be used by the
+/// thread that initialized it, the GTK tests intermittently fail unless they all run on the same
+/// thread. A panic in `test` is propagated to the caller, so that the test fails as usual, and
+/// the value returned by `test`, like a `Result`, is returned to the caller.
+/// The `#[relm_test]` attribute wraps a test function with this function.
+pub fn run_serialized<T, F>(test: F) -> T
+    where F: FnOnce() -> T + Send + 'static,
+          T: Send + 'static,
+{
+    let (sender, receiver) = channel();
+    let job: Test = Box::new(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(test));
+        let _ = sender.send(result);
+    });
+    GTK_THREAD.lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .send(job)
+        .expect("GTK test thread stopped");
+    match receiver.recv().expect("GTK test thread stopped") {
+        Ok(value) => value,
+        Err(payload) => panic::resume_unwind(payload),
+    }
+}
+
+fn spawn_gtk_thread() -> Sender<Test> {
+    let (sender, receiver) = channel::<Test>();
+    thread::Builder::new()
+        .name("relm-test-gtk".to_string())
+        .spawn(move || {
+            gtk::init().expect("gtk::init failed");
+            for test in receiver {
+                test();
+            }
+        })
+        .expect("Cannot spawn the GTK test thread");
+    sender
+}
@@ -0,0 +1,83 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use glib::{Cast, Closure, IsA, ToValue};
+use gtk::{AccelFlags, AccelGroup, AccelGroupExt, GtkWindowExt};
+use log::error;
+
+use crate::core::StreamHandle;
+use crate::vendor::fragile::Fragile;
+
+/// Keyboard accelerators of a window sending messages.
+///
+/// The accelerators are registered on their own `gtk::AccelGroup`, which is removed from the
+/// window when the `Accelerators` is dropped: keep it in the model of the component declaring the
+/// accelerators so that they are cleaned up with the component.
+/// See the [`accel!`](macro.accel.html) macro to declare many accelerators at once.
+pub struct Accelerators {
+    group: AccelGroup,
+    window: gtk::Window,
+}
+
+impl Accelerators {
+    /// Create an empty set of accelerators for `window`.
+    /// For a component that is not a window, use its toplevel window.
+    pub fn new<WINDOW: IsA<gtk::Window>>(window: &WINDOW) -> Self {
+        let group = AccelGroup::new();
+        window.add_accel_group(&group);
+        Accelerators {
+            group,
+            window: window.clone().upcast(),
+        }
+    }
+
+    /// Send `msg` to `stream` when the accelerator `accel` (e.g. `<Ctrl>N`) is pressed.
+    /// Returns `false` if `accel` could not be parsed.
+    pub fn add<MSG>(&self, accel: &str, stream: &StreamHandle<MSG>, msg: MSG) -> bool
+        where MSG: Clone + 'static,
+    {
+        let (key, modifiers) = gtk::accelerator_parse(accel);
+        if key == 0 {
+            error!("Invalid accelerator {}", accel);
+            return false;
+        }
+        // TODO: remove any use of Fragile when glib closures stop requiring Send.
+        let handler = Fragile::new((stream.clone(), msg));
+        let closure = Closure::new(move |_| {
+            let (ref stream, ref msg) = *handler.get();
+            stream.emit(msg.clone());
+            Some(true.to_value())
+        });
+        self.group.connect(key, modifiers, AccelFlags::VISIBLE, &closure);
+        true
+    }
+
+    /// Get the underlying `gtk::AccelGroup`, for instance to show the accelerators in menus.
+    pub fn group(&self) -> &AccelGroup {
+        &self.group
+    }
+}
+
+impl Drop for Accelerators {
+    fn drop(&mut self) {
+        self.window.remove_accel_group(&self.group);
+    }
+}
@@ -0,0 +1,95 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Integration of the messages with `gio::Action` and `gio::Menu`.
+
+use gio::{ActionMapExt, Menu, MenuExt, SimpleAction, SimpleActionExt};
+use glib::IsA;
+use gtk::GtkApplicationExt;
+
+use crate::core::StreamHandle;
+
+/// Action that emits a message when activated.
+pub struct Action<MSG> {
+    /// The accelerator of the action, like `<Ctrl>S`.
+    pub accel: Option<&'static str>,
+    /// Function creating the message to emit when the action is activated.
+    pub constructor: fn() -> MSG,
+    /// The detailed name of the action, like `win.save`.
+    pub name: &'static str,
+}
+
+/// Trait implemented by `#[derive(Msg)]` for the message enums having variants with the
+/// `#[action(name = "win.save", accel = "<Ctrl>S")]` attribute.
+pub trait Actions: Sized {
+    /// Get the actions of the message enum.
+    fn actions() -> Vec<Action<Self>>;
+
+    /// Get the detailed name of the action emitting this message, if any.
+    fn action_name(&self) -> Option<&'static str>;
+}
+
+/// Add the actions of the `MSG` enum to `action_map`, so that activating them emits their message
+/// to `stream`.
+///
+/// Only the actions whose scope (the part before the dot, e.g. `win` or `app`) is `scope` are
+/// added, since each scope is usually a different action map.
+/// When `application` is provided, the accelerators of the actions are set on it.
+pub fn add_actions<MSG, MAP>(action_map: &MAP, scope: &str, application: Option<&gtk::Application>,
+    stream: &StreamHandle<MSG>)
+    where MAP: IsA<gio::ActionMap>,
+          MSG: Actions + 'static,
+{
+    for action in MSG::actions() {
+        let name =
+            match split_action_name(action.name) {
+                Some((action_scope, name)) if action_scope == scope => name,
+                _ => continue,
+            };
+        let simple_action = SimpleAction::new(name, None);
+        let stream = stream.clone();
+        let constructor = action.constructor;
+        let _ = simple_action.connect_activate(move |_, _| {
+            stream.emit(constructor());
+        });
+        action_map.add_action(&simple_action);
+        if let (Some(application), Some(accel)) = (application, action.accel) {
+            application.set_accels_for_action(action.name, &[accel]);
+        }
+    }
+}
+
+/// Create a menu whose items activate the action of the messages.
+/// The messages without an action are ignored.
+pub fn action_menu<MSG: Actions>(items: &[(&str, MSG)]) -> Menu {
+    let menu = Menu::new();
+    for &(label, ref msg) in items {
+        if let Some(action_name) = msg.action_name() {
+            menu.append(Some(label), Some(action_name));
+        }
+    }
+    menu
+}
+
+fn split_action_name(name: &str) -> Option<(&str, &str)> {
+    let index = name.find('.')?;
+    Some((&name[..index], &name[index + 1..]))
+}
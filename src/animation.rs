@@ -0,0 +1,568 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Tweens sending the interpolated values at each frame of a widget.
+
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use glib::{Cast, Continue, IsA};
+use gtk::{TickCallbackId, WidgetExt};
+
+use crate::core::StreamHandle;
+
+/// Value which can be animated by [`animate()`](fn.animate.html).
+pub trait Interpolate: Clone {
+    /// Get the value at `progress` between `self` (0) and `to` (1).
+    /// The progress can be outside this range with an easing overshooting the target.
+    fn interpolate(&self, to: &Self, progress: f64) -> Self;
+}
+
+impl Interpolate for f64 {
+    fn interpolate(&self, to: &Self, progress: f64) -> Self {
+        self + (to - self) * progress
+    }
+}
+
+impl Interpolate for f32 {
+    fn interpolate(&self, to: &Self, progress: f64) -> Self {
+        self + (to - self) * progress as f32
+    }
+}
+
+impl Interpolate for i32 {
+    fn interpolate(&self, to: &Self, progress: f64) -> Self {
+        (*self as f64).interpolate(&(*to as f64), progress).round() as i32
+    }
+}
+
+impl<A: Interpolate, B: Interpolate> Interpolate for (A, B) {
+    fn interpolate(&self, to: &Self, progress: f64) -> Self {
+        (self.0.interpolate(&to.0, progress), self.1.interpolate(&to.1, progress))
+    }
+}
+
+impl Interpolate for gdk::RGBA {
+    fn interpolate(&self, to: &Self, progress: f64) -> Self {
+        gdk::RGBA {
+            red: self.red.interpolate(&to.red, progress),
+            green: self.green.interpolate(&to.green, progress),
+            blue: self.blue.interpolate(&to.blue, progress),
+            alpha: self.alpha.interpolate(&to.alpha, progress),
+        }
+    }
+}
+
+/// Curve mapping the elapsed fraction of the duration of an animation to its progress.
+#[derive(Clone, Copy, Debug)]
+pub enum Easing {
+    /// Accelerate from zero velocity (cubic).
+    EaseIn,
+    /// Accelerate, then decelerate (cubic).
+    EaseInOut,
+    /// Decelerate to zero velocity (cubic). This is the most natural easing for the user
+    /// interface.
+    EaseOut,
+    /// Constant velocity.
+    Linear,
+    /// Custom curve, which should map 0 to 0 and 1 to 1.
+    Custom(fn(f64) -> f64),
+}
+
+impl Easing {
+    /// Get the progress when the fraction `time`, between 0 and 1, of the duration elapsed.
+    pub fn apply(self, time: f64) -> f64 {
+        match self {
+            Easing::EaseIn => time * time * time,
+            Easing::EaseInOut =>
+                if time < 0.5 {
+                    4.0 * time * time * time
+                }
+                else {
+                    1.0 - (-2.0 * time + 2.0).powi(3) / 2.0
+                },
+            Easing::EaseOut => 1.0 - (1.0 - time).powi(3),
+            Easing::Linear => time,
+            Easing::Custom(function) => function(time),
+        }
+    }
+}
+
+struct Segment<T> {
+    duration: i64,
+    easing: Easing,
+    from: T,
+    to: T,
+}
+
+struct State<T, MSG> {
+    constructor: Box<dyn Fn(T) -> MSG>,
+    finished_msg: RefCell<Option<Box<dyn Fn() -> MSG>>>,
+    // Value at the end of the last segment, from which the next chained segment starts.
+    last_value: RefCell<T>,
+    segments: RefCell<VecDeque<Segment<T>>>,
+    // Frame time, in µs, at which the current segment started.
+    segment_start: Cell<Option<i64>>,
+    stream: StreamHandle<MSG>,
+    tick_callback: RefCell<Option<TickCallbackId>>,
+    widget: gtk::Widget,
+}
+
+impl<T: Interpolate + 'static, MSG: 'static> State<T, MSG> {
+    fn emit(&self, value: T) {
+        if !self.stream.is_dropped() {
+            self.stream.emit((self.constructor)(value));
+        }
+    }
+
+    fn finish(&self) {
+        if self.stream.is_dropped() {
+            return;
+        }
+        if let Some(ref finished_msg) = *self.finished_msg.borrow() {
+            self.stream.emit(finished_msg());
+        }
+    }
+
+    // Emit the value for the frame at `time` and return false when all the segments are finished.
+    fn tick(&self, time: i64) -> bool {
+        loop {
+            let start = self.segment_start.get().unwrap_or(time);
+            self.segment_start.set(Some(start));
+            let segment = self.segments.borrow_mut().pop_front();
+            let segment =
+                match segment {
+                    Some(segment) => segment,
+                    None => {
+                        self.segment_start.set(None);
+                        self.finish();
+                        return false;
+                    },
+                };
+            let elapsed = time - start;
+            if elapsed < segment.duration {
+                let fraction = elapsed as f64 / segment.duration as f64;
+                let value = segment.from.interpolate(&segment.to, segment.easing.apply(fraction));
+                // Put the segment back before emitting, since update() can cancel the animation.
+                self.segments.borrow_mut().push_front(segment);
+                self.emit(value);
+                return true;
+            }
+            // Start the next segment when this one should have ended, to keep the timing of the
+            // chain when a frame is late.
+            self.segment_start.set(Some(start + segment.duration));
+            if self.segments.borrow().is_empty() {
+                self.emit(segment.to);
+            }
+        }
+    }
+
+    fn jump_to_end(&self) {
+        let last = self.segments.borrow_mut().drain(..).last();
+        if let Some(segment) = last {
+            self.emit(segment.to);
+            self.finish();
+        }
+        self.segment_start.set(None);
+    }
+}
+
+/// Handle to an animation started with [`animate()`](fn.animate.html), to chain more segments
+/// or cancel it.
+///
+/// The animation keeps running when this handle is dropped.
+pub struct Animation<T, MSG> {
+    state: Rc<State<T, MSG>>,
+}
+
+impl<T, MSG> Clone for Animation<T, MSG> {
+    fn clone(&self) -> Self {
+        Animation {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T: Interpolate + 'static, MSG: 'static> Animation<T, MSG> {
+    /// Stop the animation where it is. The finished message is not sent.
+    pub fn cancel(&self) {
+        self.state.segments.borrow_mut().clear();
+        self.state.segment_start.set(None);
+        if let Some(tick_callback) = self.state.tick_callback.borrow_mut().take() {
+            tick_callback.remove();
+        }
+    }
+
+    /// Check if the animation is still running.
+    pub fn is_running(&self) -> bool {
+        !self.state.segments.borrow().is_empty()
+    }
+
+    /// Send the message created by `constructor` when the animation finishes, but not when it is
+    /// cancelled.
+    pub fn on_finished<F>(self, constructor: F) -> Self
+        where F: Fn() -> MSG + 'static,
+    {
+        *self.state.finished_msg.borrow_mut() = Some(Box::new(constructor));
+        self
+    }
+
+    /// Chain another animation from the end value of the previous one to `to`.
+    pub fn then(self, to: T, duration: u32, easing: Easing) -> Self {
+        let from = self.state.last_value.replace(to.clone());
+        self.state.segments.borrow_mut().push_back(Segment {
+            duration: duration as i64 * 1000,
+            easing,
+            from,
+            to,
+        });
+        self.start();
+        self
+    }
+
+    fn start(&self) {
+        if self.state.tick_callback.borrow().is_some() {
+            return;
+        }
+        if !self.state.widget.get_realized() {
+            // The frame clock of a widget which is not realized does not tick: jump to the end,
+            // after the chain is built.
+            let state = self.state.clone();
+            glib::idle_add_local(move || {
+                state.jump_to_end();
+                Continue(false)
+            });
+            return;
+        }
+        let state = self.state.clone();
+        let tick_callback = self.state.widget.add_tick_callback(move |_, frame_clock| {
+            if state.tick(frame_clock.get_frame_time()) {
+                Continue(true)
+            }
+            else {
+                state.tick_callback.borrow_mut().take();
+                Continue(false)
+            }
+        });
+        *self.state.tick_callback.borrow_mut() = Some(tick_callback);
+    }
+}
+
+/// Animate a value from `from` to `to` in `duration` ms, with `easing`, and send the message
+/// created by `constructor` with the interpolated value to `stream` at each frame of `widget`.
+/// The last message always contains `to`.
+///
+/// The animation is driven by the frame clock of `widget`, usually the root of the component, so
+/// the values are sent at the pace of the display.
+/// If the widget is not realized, only the end value is sent.
+///
+/// ```ignore
+/// relm::animate(&self.widgets.panel, self.model.relm.stream(), 0.0, 1.0, 250, Easing::EaseOut, Opacity)
+///     .then(0.5, 250, Easing::EaseInOut)
+///     .on_finished(|| FadedIn);
+/// ```
+pub fn animate<W, T, F, MSG>(widget: &W, stream: &StreamHandle<MSG>, from: T, to: T, duration: u32, easing: Easing,
+    constructor: F) -> Animation<T, MSG>
+    where W: IsA<gtk::Widget>,
+          T: Interpolate + 'static,
+          F: Fn(T) -> MSG + 'static,
+          MSG: 'static,
+{
+    let animation = Animation {
+        state: Rc::new(State {
+            constructor: Box::new(constructor),
+            finished_msg: RefCell::new(None),
+            last_value: RefCell::new(from),
+            segments: RefCell::new(VecDeque::new()),
+            segment_start: Cell::new(None),
+            stream: stream.clone(),
+            tick_callback: RefCell::new(None),
+            widget: widget.clone().upcast(),
+        }),
+    };
+    animation.then(to, duration, easing)
+}
+
+/// Parameters of the damped spring driving an [`animate_spring()`](fn.animate_spring.html)
+/// animation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spring {
+    damping: f64,
+    mass: f64,
+    precision: f64,
+    stiffness: f64,
+}
+
+impl Spring {
+    /// Create a spring with a mass of 1. A higher `stiffness` moves faster and a lower `damping`
+    /// oscillates more around the target.
+    pub fn new(stiffness: f64, damping: f64) -> Self {
+        Spring {
+            damping,
+            mass: 1.0,
+            precision: 0.01,
+            stiffness,
+        }
+    }
+
+    /// Set the mass: a heavier spring is slower to start and to stop.
+    pub fn mass(mut self, mass: f64) -> Self {
+        self.mass = mass;
+        self
+    }
+
+    /// The spring is settled when its distance to the target and its velocity are below
+    /// `precision`. The default is 0.01, which is fine for pixels.
+    pub fn precision(mut self, precision: f64) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    // Advance the simulation of `duration` seconds with small fixed steps, to stay stable with
+    // long frames.
+    fn step(&self, position: &mut f64, velocity: &mut f64, target: f64, duration: f64) {
+        const STEP: f64 = 0.001;
+        let mut remaining = duration;
+        while remaining > 0.0 {
+            let step = remaining.min(STEP);
+            let force = -self.stiffness * (*position - target) - self.damping * *velocity;
+            *velocity += force / self.mass * step;
+            *position += *velocity * step;
+            remaining -= step;
+        }
+    }
+
+    fn is_settled(&self, position: f64, velocity: f64, target: f64) -> bool {
+        (position - target).abs() < self.precision && velocity.abs() < self.precision
+    }
+}
+
+impl Default for Spring {
+    /// A spring slightly oscillating before settling in about half a second.
+    fn default() -> Self {
+        Spring::new(170.0, 26.0)
+    }
+}
+
+struct SpringState<MSG> {
+    constructor: Box<dyn Fn(f64) -> MSG>,
+    interrupted_msg: RefCell<Option<Box<dyn Fn() -> MSG>>>,
+    last_time: Cell<Option<i64>>,
+    position: Cell<f64>,
+    running: Cell<bool>,
+    settled_msg: RefCell<Option<Box<dyn Fn() -> MSG>>>,
+    spring: Spring,
+    stream: StreamHandle<MSG>,
+    target: Cell<f64>,
+    tick_callback: RefCell<Option<TickCallbackId>>,
+    velocity: Cell<f64>,
+    widget: gtk::Widget,
+}
+
+impl<MSG: 'static> SpringState<MSG> {
+    fn emit_msg(&self, msg: &RefCell<Option<Box<dyn Fn() -> MSG>>>) {
+        if self.stream.is_dropped() {
+            return;
+        }
+        if let Some(ref constructor) = *msg.borrow() {
+            self.stream.emit(constructor());
+        }
+    }
+
+    fn emit_position(&self) {
+        if !self.stream.is_dropped() {
+            self.stream.emit((self.constructor)(self.position.get()));
+        }
+    }
+
+    fn settle(&self) {
+        self.position.set(self.target.get());
+        self.velocity.set(0.0);
+        self.running.set(false);
+        self.emit_position();
+        self.emit_msg(&self.settled_msg);
+    }
+
+    // Emit the position for the frame at `time` and return false when the spring is settled.
+    fn tick(&self, time: i64) -> bool {
+        if !self.running.get() {
+            return false;
+        }
+        let elapsed = self.last_time.replace(Some(time)).map_or(0, |last_time| time - last_time);
+        let mut position = self.position.get();
+        let mut velocity = self.velocity.get();
+        let target = self.target.get();
+        self.spring.step(&mut position, &mut velocity, target, elapsed as f64 / 1_000_000.0);
+        self.position.set(position);
+        self.velocity.set(velocity);
+        if self.spring.is_settled(position, velocity, target) {
+            self.settle();
+            false
+        }
+        else {
+            self.emit_position();
+            true
+        }
+    }
+}
+
+/// Handle to a spring animation started with [`animate_spring()`](fn.animate_spring.html), to
+/// move its target or cancel it.
+///
+/// The animation keeps running when this handle is dropped.
+pub struct SpringAnimation<MSG> {
+    state: Rc<SpringState<MSG>>,
+}
+
+impl<MSG> Clone for SpringAnimation<MSG> {
+    fn clone(&self) -> Self {
+        SpringAnimation {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<MSG: 'static> SpringAnimation<MSG> {
+    /// Stop the spring where it is and send the interrupted message, if it was still moving.
+    pub fn cancel(&self) {
+        if self.state.running.replace(false) {
+            self.stop_ticking();
+            self.state.velocity.set(0.0);
+            self.state.emit_msg(&self.state.interrupted_msg);
+        }
+    }
+
+    /// Check if the spring is still moving.
+    pub fn is_running(&self) -> bool {
+        self.state.running.get()
+    }
+
+    /// Send the message created by `constructor` when the spring is interrupted by
+    /// [`set_target()`](#method.set_target) or [`cancel()`](#method.cancel) before it settled.
+    pub fn on_interrupted<F>(self, constructor: F) -> Self
+        where F: Fn() -> MSG + 'static,
+    {
+        *self.state.interrupted_msg.borrow_mut() = Some(Box::new(constructor));
+        self
+    }
+
+    /// Send the message created by `constructor` when the spring settles on its target.
+    pub fn on_settled<F>(self, constructor: F) -> Self
+        where F: Fn() -> MSG + 'static,
+    {
+        *self.state.settled_msg.borrow_mut() = Some(Box::new(constructor));
+        self
+    }
+
+    /// Get the current position of the spring.
+    pub fn position(&self) -> f64 {
+        self.state.position.get()
+    }
+
+    /// Move the target while keeping the current position and velocity, so that the movement
+    /// stays natural when the user changes their mind, for instance when a pane is dragged back.
+    /// The interrupted message is sent if the spring was still moving.
+    pub fn set_target(&self, target: f64) {
+        if self.state.running.get() {
+            self.state.emit_msg(&self.state.interrupted_msg);
+        }
+        self.state.target.set(target);
+        self.start();
+    }
+
+    fn start(&self) {
+        self.state.running.set(true);
+        if self.state.tick_callback.borrow().is_some() {
+            return;
+        }
+        if !self.state.widget.get_realized() {
+            // The frame clock of a widget which is not realized does not tick: jump to the target,
+            // after the messages are set.
+            let state = self.state.clone();
+            glib::idle_add_local(move || {
+                if state.running.get() {
+                    state.settle();
+                }
+                Continue(false)
+            });
+            return;
+        }
+        self.state.last_time.set(None);
+        let state = self.state.clone();
+        let tick_callback = self.state.widget.add_tick_callback(move |_, frame_clock| {
+            if state.tick(frame_clock.get_frame_time()) {
+                Continue(true)
+            }
+            else {
+                state.tick_callback.borrow_mut().take();
+                Continue(false)
+            }
+        });
+        *self.state.tick_callback.borrow_mut() = Some(tick_callback);
+    }
+
+    fn stop_ticking(&self) {
+        if let Some(tick_callback) = self.state.tick_callback.borrow_mut().take() {
+            tick_callback.remove();
+        }
+    }
+}
+
+/// Animate a value from `from` to `to` with a damped `spring` and send the message created by
+/// `constructor` with the position to `stream` at each frame of `widget`.
+///
+/// Unlike [`animate()`](fn.animate.html), the duration depends on the spring and the target can be
+/// moved while the animation runs with
+/// [`SpringAnimation::set_target()`](struct.SpringAnimation.html#method.set_target), which makes
+/// it suited to movements driven by the user, like panes, popovers or reordered list rows.
+/// The last message always contains the target.
+///
+/// ```ignore
+/// let animation = relm::animate_spring(&self.widgets.paned, self.model.relm.stream(), 0.0, 300.0,
+///     Spring::default(), PanePosition)
+///     .on_settled(|| PaneOpened);
+/// ```
+pub fn animate_spring<W, F, MSG>(widget: &W, stream: &StreamHandle<MSG>, from: f64, to: f64, spring: Spring,
+    constructor: F) -> SpringAnimation<MSG>
+    where W: IsA<gtk::Widget>,
+          F: Fn(f64) -> MSG + 'static,
+          MSG: 'static,
+{
+    let animation = SpringAnimation {
+        state: Rc::new(SpringState {
+            constructor: Box::new(constructor),
+            interrupted_msg: RefCell::new(None),
+            last_time: Cell::new(None),
+            position: Cell::new(from),
+            running: Cell::new(false),
+            settled_msg: RefCell::new(None),
+            spring,
+            stream: stream.clone(),
+            target: Cell::new(to),
+            tick_callback: RefCell::new(None),
+            velocity: Cell::new(0.0),
+            widget: widget.clone().upcast(),
+        }),
+    };
+    animation.start();
+    animation
+}
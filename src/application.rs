@@ -0,0 +1,226 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Integration with `gtk::Application`.
+
+use std::cell::RefCell;
+use std::ffi::OsString;
+use std::rc::Rc;
+
+use gio::{ApplicationCommandLineExt, ApplicationExt, ApplicationExtManual, ApplicationFlags, File};
+use glib::Cast;
+use gtk::{GtkApplicationExt, GtkWindowExt};
+
+use crate::component::Component;
+use crate::startup::RunOptions;
+use crate::state::{DisplayVariant, InitError};
+use crate::widget::Widget;
+use super::try_init;
+
+/// Trait to implement to run a relm widget as a `gtk::Application`.
+///
+/// Each method converts a signal of the application to a message sent to the root component.
+/// They all return `None` by default, i.e. the signal is ignored.
+pub trait ApplicationWidget: Widget {
+    /// Create the message to send when the application is activated, e.g. when it is launched a
+    /// second time.
+    fn activate_msg() -> Option<Self::Msg> {
+        None
+    }
+
+    /// Create the message to send when the application is asked to open `files`.
+    /// This requires the `ApplicationFlags::HANDLES_OPEN` flag.
+    fn open_msg(_files: Vec<File>, _hint: &str) -> Option<Self::Msg> {
+        None
+    }
+
+    /// Create the message to send when the application receives a command line.
+    /// This requires the `ApplicationFlags::HANDLES_COMMAND_LINE` flag.
+    fn command_line_msg(_arguments: Vec<OsString>) -> Option<Self::Msg> {
+        None
+    }
+}
+
+/// Create the specified relm `Widget` within a `gtk::Application` with the `app_id` and run it.
+///
+/// The component is created the first time the application is activated, opens files or
+/// receives a command line.
+/// Unless the `ApplicationFlags::NON_UNIQUE` flag is used, launching the application again
+/// forwards the signal to the first instance instead of creating another component.
+///
+/// Return the exit status of the application.
+pub fn run_app<WIDGET>(app_id: &str, flags: ApplicationFlags, model_param: WIDGET::ModelParam)
+    -> Result<i32, InitError>
+    where WIDGET: ApplicationWidget + 'static,
+          WIDGET::ModelParam: 'static,
+          WIDGET::Msg: DisplayVariant + 'static,
+{
+    run_app_with(app_id, flags, RunOptions::new(), model_param)
+}
+
+/// Same as [`run_app()`](fn.run_app.html), but with custom options.
+/// The `before_show` and `startup_msgs` callbacks are called when the component is created.
+/// When `startup_msgs` is set, the command line of the first instance is not given to
+/// [`command_line_msg()`](trait.ApplicationWidget.html#method.command_line_msg).
+pub fn run_app_with<WIDGET>(app_id: &str, flags: ApplicationFlags, options: RunOptions<WIDGET>,
+    model_param: WIDGET::ModelParam) -> Result<i32, InitError>
+    where WIDGET: ApplicationWidget + 'static,
+          WIDGET::ModelParam: 'static,
+          WIDGET::Msg: DisplayVariant + 'static,
+{
+    run_application(app_id, flags, options, model_param, false)
+}
+
+/// Run the specified relm `Widget` as a single-instance `gtk::Application`.
+///
+/// The first launch creates the component. The following launches, even from another
+/// directory, forward their command line to the component of the first instance, with
+/// [`command_line_msg()`](trait.ApplicationWidget.html#method.command_line_msg), and the files in
+/// their arguments, resolved relative to the directory where they were launched, with
+/// [`open_msg()`](trait.ApplicationWidget.html#method.open_msg).
+/// The window of the first instance is then presented to the user and the new instance exits.
+///
+/// The command line of the first instance is handled the same way, unless the options have a
+/// [`startup_msgs()`](struct.RunOptions.html#method.startup_msgs) callback, which then converts
+/// it instead.
+pub fn run_single_instance<WIDGET>(app_id: &str, options: RunOptions<WIDGET>, model_param: WIDGET::ModelParam)
+    -> Result<i32, InitError>
+    where WIDGET: ApplicationWidget + 'static,
+          WIDGET::ModelParam: 'static,
+          WIDGET::Msg: DisplayVariant + 'static,
+{
+    run_application(app_id, ApplicationFlags::HANDLES_COMMAND_LINE, options, model_param, true)
+}
+
+fn run_application<WIDGET>(app_id: &str, flags: ApplicationFlags, mut options: RunOptions<WIDGET>,
+    model_param: WIDGET::ModelParam, single_instance: bool) -> Result<i32, InitError>
+    where WIDGET: ApplicationWidget + 'static,
+          WIDGET::ModelParam: 'static,
+          WIDGET::Msg: DisplayVariant + 'static,
+{
+    options.before_init();
+    let application = gtk::Application::new(Some(app_id), flags)?;
+    if let Some(ref path) = options.resource_base_path {
+        application.set_resource_base_path(Some(path));
+    }
+    let arguments = options.arguments();
+    let app = Rc::new(App::<WIDGET> {
+        component: RefCell::new(None),
+        model_param: RefCell::new(Some(model_param)),
+        options: RefCell::new(options),
+    });
+
+    {
+        let app = app.clone();
+        let _ = application.connect_activate(move |application| {
+            app.emit(application, WIDGET::activate_msg());
+            app.present();
+        });
+    }
+    {
+        let app = app.clone();
+        let _ = application.connect_open(move |application, files, hint| {
+            app.emit(application, WIDGET::open_msg(files.to_vec(), hint));
+        });
+    }
+    {
+        let app = app.clone();
+        let _ = application.connect_command_line(move |application, command_line| {
+            let arguments = command_line.get_arguments();
+            // The command line of the first instance is only converted by startup_msgs() when it
+            // is set, so that its files are not opened twice.
+            let converted = !command_line.get_is_remote() && app.options.borrow().startup_msgs.is_some();
+            if !single_instance {
+                let msg = if converted { None } else { WIDGET::command_line_msg(arguments) };
+                app.emit(application, msg);
+                return 0;
+            }
+            if converted {
+                app.emit(application, None);
+            }
+            else {
+                let files: Vec<File> = arguments.iter()
+                    .skip(1)
+                    .filter(|argument| !argument.to_string_lossy().starts_with('-'))
+                    .map(|argument| command_line.create_file_for_arg(argument))
+                    .collect();
+                app.emit(application, WIDGET::command_line_msg(arguments));
+                if !files.is_empty() {
+                    app.emit(application, WIDGET::open_msg(files, ""));
+                }
+            }
+            app.present();
+            0
+        });
+    }
+
+    let status = application.run(&arguments);
+    // Destroy the component before the application.
+    let _ = app.component.borrow_mut().take();
+    Ok(status)
+}
+
+struct App<WIDGET: Widget> {
+    component: RefCell<Option<Component<WIDGET>>>,
+    model_param: RefCell<Option<WIDGET::ModelParam>>,
+    options: RefCell<RunOptions<WIDGET>>,
+}
+
+impl<WIDGET> App<WIDGET>
+    where WIDGET: Widget + 'static,
+          WIDGET::Msg: DisplayVariant + 'static,
+{
+    /// Create the component if it was not already created and send it `msg`.
+    fn emit(&self, application: &gtk::Application, msg: Option<WIDGET::Msg>) {
+        if self.component.borrow().is_none() {
+            let model_param =
+                match self.model_param.borrow_mut().take() {
+                    Some(model_param) => model_param,
+                    None => return,
+                };
+            match try_init::<WIDGET>(model_param) {
+                Ok(component) => {
+                    if let Ok(window) = component.widget().clone().upcast::<gtk::Widget>().downcast::<gtk::Window>() {
+                        application.add_window(&window);
+                    }
+                    self.options.borrow_mut().started(&component);
+                    *self.component.borrow_mut() = Some(component);
+                },
+                Err(error) => {
+                    log::error!("Cannot initialize widget {}: {}", std::any::type_name::<WIDGET>(), error);
+                    application.quit();
+                    return;
+                },
+            }
+        }
+        if let (Some(msg), Some(component)) = (msg, self.component.borrow().as_ref()) {
+            component.emit(msg);
+        }
+    }
+
+    fn present(&self) {
+        if let Some(ref component) = *self.component.borrow() {
+            if let Ok(window) = component.widget().clone().upcast::<gtk::Widget>().downcast::<gtk::Window>() {
+                window.present();
+            }
+        }
+    }
+}
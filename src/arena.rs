@@ -0,0 +1,203 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::mem;
+
+use glib::{IsA, Object};
+use gtk::WidgetExt;
+
+use crate::component::Component;
+use crate::container::ContainerWidget;
+use crate::state::DisplayVariant;
+use crate::widget::Widget;
+
+enum Entry<WIDGET: Widget> {
+    Free(Option<usize>),
+    Occupied(Component<WIDGET>),
+}
+
+struct Slot<WIDGET: Widget> {
+    entry: Entry<WIDGET>,
+    // Incremented each time the slot is freed, so that the keys of the previous components are not
+    // valid for the component reusing the slot.
+    generation: u32,
+}
+
+/// Key of a component stored in a [`ComponentArena`](struct.ComponentArena.html).
+///
+/// A key is only valid for the component it was returned for: once this component is removed, the
+/// key does not give access to another component stored in the same slot.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ComponentKey {
+    generation: u32,
+    index: usize,
+}
+
+/// Storage for many components of the same type, like the children of a list.
+///
+/// The components are stored contiguously and the slots of the removed components are reused, so
+/// that adding hundreds of components does not reallocate the storage when the capacity was
+/// reserved up front with [`with_capacity()`](#method.with_capacity) or
+/// [`reserve()`](#method.reserve).
+///
+/// The arena only avoids the reallocations of the collection: each component still allocates its
+/// own event stream when it is created.
+pub struct ComponentArena<WIDGET: Widget> {
+    free_head: Option<usize>,
+    len: usize,
+    slots: Vec<Slot<WIDGET>>,
+}
+
+impl<WIDGET: Widget> ComponentArena<WIDGET> {
+    /// Create an empty arena.
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Create an empty arena that can hold `capacity` components without reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        ComponentArena {
+            free_head: None,
+            len: 0,
+            slots: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Reserve capacity for at least `additional` more components.
+    /// Call this before a bulk add.
+    pub fn reserve(&mut self, additional: usize) {
+        let free = self.slots.len() - self.len;
+        if additional > free {
+            self.slots.reserve(additional - free);
+        }
+    }
+
+    /// Get the number of components the arena can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.slots.capacity()
+    }
+
+    /// Get the number of components in the arena.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check whether the arena contains no component.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Store a component in the arena.
+    pub fn insert(&mut self, component: Component<WIDGET>) -> ComponentKey {
+        self.len += 1;
+        match self.free_head {
+            Some(index) => {
+                let slot = &mut self.slots[index];
+                match mem::replace(&mut slot.entry, Entry::Occupied(component)) {
+                    Entry::Free(next) => self.free_head = next,
+                    Entry::Occupied(_) => unreachable!(),
+                }
+                ComponentKey {
+                    generation: slot.generation,
+                    index,
+                }
+            },
+            None => {
+                self.slots.push(Slot {
+                    entry: Entry::Occupied(component),
+                    generation: 0,
+                });
+                ComponentKey {
+                    generation: 0,
+                    index: self.slots.len() - 1,
+                }
+            },
+        }
+    }
+
+    /// Create a relm widget, add it to `container` and store it in the arena.
+    pub fn add_widget<CONTAINER>(&mut self, container: &CONTAINER, model_param: WIDGET::ModelParam) -> ComponentKey
+        where CONTAINER: ContainerWidget,
+              WIDGET: 'static,
+              WIDGET::Msg: DisplayVariant + 'static,
+              WIDGET::Root: IsA<gtk::Widget> + IsA<Object> + WidgetExt,
+    {
+        let component = container.add_widget::<WIDGET>(model_param);
+        self.insert(component)
+    }
+
+    /// Get the component with the `key`.
+    pub fn get(&self, key: ComponentKey) -> Option<&Component<WIDGET>> {
+        match self.slots.get(key.index) {
+            Some(Slot { entry: Entry::Occupied(component), generation }) if *generation == key.generation =>
+                Some(component),
+            _ => None,
+        }
+    }
+
+    /// Remove the component with the `key` from the arena and return it.
+    /// The component is destroyed when the returned value is dropped.
+    pub fn remove(&mut self, key: ComponentKey) -> Option<Component<WIDGET>> {
+        self.get(key)?;
+        self.len -= 1;
+        let entry = self.free(key.index);
+        match entry {
+            Entry::Occupied(component) => Some(component),
+            Entry::Free(_) => unreachable!(),
+        }
+    }
+
+    /// Remove all the components from the arena, keeping the allocated capacity.
+    pub fn clear(&mut self) {
+        // The slots are kept, instead of clearing the vector, to keep their generation.
+        for index in 0..self.slots.len() {
+            if let Entry::Occupied(_) = self.slots[index].entry {
+                let _ = self.free(index);
+            }
+        }
+        self.len = 0;
+    }
+
+    // Add the slot at `index` to the free list and return its previous entry.
+    fn free(&mut self, index: usize) -> Entry<WIDGET> {
+        let slot = &mut self.slots[index];
+        slot.generation = slot.generation.wrapping_add(1);
+        let entry = mem::replace(&mut slot.entry, Entry::Free(self.free_head));
+        self.free_head = Some(index);
+        entry
+    }
+
+    /// Iterate over the components of the arena with their keys.
+    pub fn iter(&self) -> impl Iterator<Item=(ComponentKey, &Component<WIDGET>)> {
+        self.slots.iter()
+            .enumerate()
+            .filter_map(|(index, slot)| match slot.entry {
+                Entry::Occupied(ref component) => Some((ComponentKey { generation: slot.generation, index }, component)),
+                Entry::Free(_) => None,
+            })
+    }
+}
+
+impl<WIDGET: Widget> Default for ComponentArena<WIDGET> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,132 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::cell::{Cell, Ref, RefCell};
+use std::rc::Rc;
+
+use glib::{IsA, Object, ObjectExt, ToValue, Value};
+
+struct Inner<T> {
+    bindings: RefCell<Vec<Box<dyn Fn(&T) -> bool>>>,
+    updating: Cell<bool>,
+    value: RefCell<T>,
+}
+
+/// Model field whose value is kept in sync with properties of widgets.
+///
+/// When [`set()`](#method.set) is called, the bound properties are updated through their transform
+/// function, so that the `update()` method does not need to set them explicitly.
+/// With [`bind_bidirectional()`](#method.bind_bidirectional), the value is also updated when the
+/// property of the widget changes.
+pub struct BoundValue<T> {
+    inner: Rc<Inner<T>>,
+}
+
+impl<T> Clone for BoundValue<T> {
+    fn clone(&self) -> Self {
+        BoundValue {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: 'static> BoundValue<T> {
+    /// Create a new value, not bound to any property yet.
+    pub fn new(value: T) -> Self {
+        BoundValue {
+            inner: Rc::new(Inner {
+                bindings: RefCell::new(vec![]),
+                updating: Cell::new(false),
+                value: RefCell::new(value),
+            }),
+        }
+    }
+
+    /// Borrow the current value.
+    pub fn get(&self) -> Ref<T> {
+        self.inner.value.borrow()
+    }
+
+    /// Change the value and update the bound properties.
+    pub fn set(&self, value: T) {
+        *self.inner.value.borrow_mut() = value;
+        self.sync();
+    }
+
+    /// Bind the `property` of `object` to this value: whenever the value changes, the property is
+    /// set to the result of `transform`.
+    /// The property is set immediately.
+    pub fn bind<O, V, F>(&self, object: &O, property: &'static str, transform: F)
+        where O: IsA<Object>,
+              V: ToValue,
+              F: Fn(&T) -> V + 'static,
+    {
+        let object = object.upcast_ref::<Object>().downgrade();
+        let binding = move |value: &T| {
+            match object.upgrade() {
+                Some(object) => {
+                    if let Err(error) = object.set_property(property, &transform(value)) {
+                        log::error!("Cannot set bound property {}: {}", property, error);
+                    }
+                    true
+                },
+                None => false,
+            }
+        };
+        let _ = binding(&*self.inner.value.borrow());
+        self.inner.bindings.borrow_mut().push(Box::new(binding));
+    }
+
+    /// Bind the `property` of `object` to this value in both directions: `transform_to` converts
+    /// the value to the property and `transform_from` converts the property to the value.
+    /// `transform_from` can return `None` to ignore a change of the property.
+    pub fn bind_bidirectional<O, V, F, G>(&self, object: &O, property: &'static str, transform_to: F,
+        transform_from: G)
+        where O: IsA<Object>,
+              V: ToValue,
+              F: Fn(&T) -> V + 'static,
+              G: Fn(&Value) -> Option<T> + 'static,
+    {
+        self.bind(object, property, transform_to);
+        let inner = Rc::downgrade(&self.inner);
+        let _ = object.upcast_ref::<Object>().connect_notify_local(Some(property), move |object, _| {
+            if let Some(inner) = inner.upgrade() {
+                if inner.updating.get() {
+                    return;
+                }
+                let new_value = object.get_property(property).ok()
+                    .and_then(|value| transform_from(&value));
+                if let Some(new_value) = new_value {
+                    *inner.value.borrow_mut() = new_value;
+                    BoundValue { inner }.sync();
+                }
+            }
+        });
+    }
+
+    fn sync(&self) {
+        // Avoid feedback loops with the bidirectional bindings.
+        self.inner.updating.set(true);
+        let value = self.inner.value.borrow();
+        self.inner.bindings.borrow_mut().retain(|binding| binding(&*value));
+        self.inner.updating.set(false);
+    }
+}
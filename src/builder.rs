@@ -0,0 +1,127 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Widgets loaded from a GtkBuilder `.ui` file.
+//!
+//! `#[derive(FromBuilder)]` gets each field of a struct from the object of the builder having the
+//! same id, or the id given with `#[builder(id = "...")]`, checking its type:
+//!
+//! ```ignore
+//! #[derive(Clone, FromBuilder)]
+//! struct Widgets {
+//!     #[builder(id = "inc_button")]
+//!     plus_button: gtk::Button,
+//!     label: gtk::Label,
+//!     window: gtk::Window,
+//! }
+//!
+//! fn try_view(relm: &Relm<Self>, model: Model) -> Result<Self, InitError> {
+//!     let widgets = Widgets::from_string(include_str!("window.ui"))?;
+//!     connect!(relm, widgets.plus_button, connect_clicked(_), Msg::Increment);
+//!     Ok(Win { model, widgets })
+//! }
+//! ```
+//!
+//! This is not a composite template: no GObject type is registered, and the root widget is a
+//! regular object of the builder, returned by [`Widget::root()`](../trait.Widget.html#tymethod.root).
+
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+use glib::{Cast, IsA, Object, StaticType, Type};
+use gtk::{Builder, BuilderExt};
+use gtk::prelude::BuilderExtManual;
+
+/// Error returned when the objects of a builder cannot be loaded.
+#[derive(Debug)]
+pub enum BuilderError {
+    /// The `.ui` definition is invalid.
+    Builder(glib::Error),
+    /// The builder has no object with this id.
+    MissingObject(String),
+    /// The object with this id does not have the type of the field.
+    WrongType {
+        /// The id of the object.
+        id: String,
+        /// The type of the field.
+        expected: Type,
+        /// The type of the object.
+        actual: Type,
+    },
+}
+
+impl Display for BuilderError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match *self {
+            BuilderError::Builder(ref error) => write!(formatter, "invalid .ui definition: {}", error),
+            BuilderError::MissingObject(ref id) => write!(formatter, "no object with id {} in the builder", id),
+            BuilderError::WrongType { ref id, expected, actual } =>
+                write!(formatter, "object {} is a {} instead of a {}", id, actual, expected),
+        }
+    }
+}
+
+impl Error for BuilderError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            BuilderError::Builder(ref error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Struct of widgets loaded from the objects of a `gtk::Builder`.
+/// This is implemented by `#[derive(FromBuilder)]`.
+pub trait FromBuilder: Sized {
+    /// Get the widgets from the objects of `builder`.
+    fn from_builder(builder: &Builder) -> Result<Self, BuilderError>;
+
+    /// Get the widgets from the `.ui` definition `ui`.
+    fn from_string(ui: &str) -> Result<Self, BuilderError> {
+        let builder = Builder::new();
+        builder.add_from_string(ui)
+            .map_err(BuilderError::Builder)?;
+        Self::from_builder(&builder)
+    }
+
+    /// Get the widgets from the `.ui` definition at `path` in the registered resources.
+    fn from_resource(path: &str) -> Result<Self, BuilderError> {
+        let builder = Builder::new();
+        builder.add_from_resource(path)
+            .map_err(BuilderError::Builder)?;
+        Self::from_builder(&builder)
+    }
+}
+
+/// Get the object with `id` from `builder`, checking that it is a `T`.
+pub fn builder_object<T>(builder: &Builder, id: &str) -> Result<T, BuilderError>
+    where T: IsA<Object> + StaticType,
+{
+    let object: Object = builder.get_object(id)
+        .ok_or_else(|| BuilderError::MissingObject(id.to_string()))?;
+    let actual = object.get_type();
+    object.downcast()
+        .map_err(|_| BuilderError::WrongType {
+            id: id.to_string(),
+            expected: T::static_type(),
+            actual,
+        })
+}
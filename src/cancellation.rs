@@ -0,0 +1,93 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::Waker;
+
+use gio::CancellableExt;
+
+/// Token to abort an in-flight asynchronous operation, like a future spawned with
+/// [`StreamHandle::spawn_local()`](struct.StreamHandle.html#method.spawn_local) or a request
+/// started with `connect_async_cancellable!`.
+///
+/// Once cancelled, the operation never emits its message. This is useful to abort the previous
+/// request before starting a new one:
+///
+/// ```ignore
+/// Search(query) => {
+///     if let Some(previous) = self.model.search.take() {
+///         previous.cancel();
+///     }
+///     self.model.search = Some(self.model.relm.stream().spawn_local(search(query)));
+/// },
+/// ```
+///
+/// The token can be cloned and sent to other threads.
+#[derive(Clone)]
+pub struct CancellationToken {
+    cancellable: gio::Cancellable,
+    // The wakers of the futures waiting on this token, to drop them as soon as it is cancelled.
+    wakers: Arc<Mutex<Vec<Waker>>>,
+}
+
+impl CancellationToken {
+    /// Create a token which is not cancelled.
+    pub fn new() -> Self {
+        CancellationToken {
+            cancellable: gio::Cancellable::new(),
+            wakers: Arc::new(Mutex::new(vec![])),
+        }
+    }
+
+    /// Abort the operations using this token.
+    pub fn cancel(&self) {
+        self.cancellable.cancel();
+        let wakers = std::mem::replace(&mut *self.wakers.lock().unwrap_or_else(PoisonError::into_inner), vec![]);
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
+    /// Check if the token was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellable.is_cancelled()
+    }
+
+    /// Get the `gio::Cancellable` cancelled with this token, to give to the asynchronous gio
+    /// functions.
+    pub fn cancellable(&self) -> &gio::Cancellable {
+        &self.cancellable
+    }
+
+    /// Wake the task of `waker` when the token is cancelled.
+    pub(crate) fn register(&self, waker: &Waker) {
+        let mut wakers = self.wakers.lock().unwrap_or_else(PoisonError::into_inner);
+        if !wakers.iter().any(|registered| registered.will_wake(waker)) {
+            wakers.push(waker.clone());
+        }
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
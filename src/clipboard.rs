@@ -0,0 +1,80 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Clipboard helpers sending the requested contents as messages.
+
+use gdk::Display;
+use gdk_pixbuf::Pixbuf;
+use gtk::Clipboard;
+
+use crate::core::StreamHandle;
+
+/// Get the default clipboard (the one used by Ctrl+C and Ctrl+V), if there's a display.
+pub fn get() -> Option<Clipboard> {
+    Display::get_default()
+        .and_then(|display| Clipboard::get_default(&display))
+}
+
+/// Request the text of `clipboard` and send it as a message built by `constructor` to `stream`.
+/// The text is `None` if the clipboard contains no text.
+pub fn request_text<F, MSG>(clipboard: &Clipboard, stream: &StreamHandle<MSG>, constructor: F)
+    where F: FnOnce(Option<String>) -> MSG + 'static,
+          MSG: 'static,
+{
+    let stream = stream.clone();
+    clipboard.request_text(move |_, text| {
+        stream.emit(constructor(text.map(ToString::to_string)));
+    });
+}
+
+/// Request the image of `clipboard` and send it as a message built by `constructor` to `stream`.
+/// The image is `None` if the clipboard contains no image.
+pub fn request_image<F, MSG>(clipboard: &Clipboard, stream: &StreamHandle<MSG>, constructor: F)
+    where F: FnOnce(Option<Pixbuf>) -> MSG + 'static,
+          MSG: 'static,
+{
+    let stream = stream.clone();
+    clipboard.request_image(move |_, image| {
+        stream.emit(constructor(image.cloned()));
+    });
+}
+
+/// Request the URIs of `clipboard`, like copied files, and send them as a message built by
+/// `constructor` to `stream`.
+pub fn request_uris<F, MSG>(clipboard: &Clipboard, stream: &StreamHandle<MSG>, constructor: F)
+    where F: FnOnce(Vec<String>) -> MSG + 'static,
+          MSG: 'static,
+{
+    let stream = stream.clone();
+    clipboard.request_uris(move |_, uris| {
+        stream.emit(constructor(uris.iter().map(ToString::to_string).collect()));
+    });
+}
+
+/// Set the text of `clipboard`.
+pub fn set_text(clipboard: &Clipboard, text: &str) {
+    clipboard.set_text(text);
+}
+
+/// Set the image of `clipboard`.
+pub fn set_image(clipboard: &Clipboard, image: &Pixbuf) {
+    clipboard.set_image(image);
+}
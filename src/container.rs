@@ -19,11 +19,15 @@
  * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
  */
 
-use glib::{Cast, IsA, Object};
-use gtk::{ContainerExt, WidgetExt};
+use std::cell::RefCell;
+use std::collections::HashMap;
 
+use glib::{Cast, IsA, Object, StaticType, Type};
+use gtk::{BoxExt, ContainerExt, FlowBoxExt, ListBoxExt, NotebookExt, WidgetExt};
+
+use crate::lazy::LazyComponent;
 use crate::state::EventStream;
-use super::{Component, DisplayVariant, StreamHandle, create_widget, init_component};
+use super::{Component, DisplayVariant, InitError, StreamHandle, create_widget, init_component, try_create_widget};
 use crate::widget::Widget;
 
 /// Struct for relm containers to add GTK+ and relm `Widget`s.
@@ -47,7 +51,7 @@ impl<WIDGET: Container + Widget> ContainerComponent<WIDGET> {
 
     /// Add a GTK+ widget to a relm container.
     pub fn add<CHILDWIDGET: IsA<gtk::Widget>>(&self, widget: &CHILDWIDGET) {
-        self.container.add(widget);
+        add_child(&self.container, widget);
     }
 
     /// Add a relm widget to a relm container.
@@ -63,6 +67,20 @@ impl<WIDGET: Container + Widget> ContainerComponent<WIDGET> {
         component
     }
 
+    /// Add a relm widget to a relm container, returning the error that occurred while
+    /// initializing it.
+    pub fn try_add_widget<CHILDWIDGET>(&self, model_param: CHILDWIDGET::ModelParam)
+        -> Result<Component<CHILDWIDGET>, InitError>
+        where CHILDWIDGET: Widget + 'static,
+              WIDGET::Container: ContainerExt + IsA<gtk::Widget> + IsA<Object>,
+    {
+        let (component, widget, child_relm) = try_create_widget::<CHILDWIDGET>(model_param)?;
+        let container = WIDGET::add_widget(self, &component);
+        widget.on_add(container);
+        init_component::<CHILDWIDGET>(component.owned_stream(), widget, &child_relm);
+        Ok(component)
+    }
+
     /// Emit a message of the widget stream.
     pub fn emit(&self, msg: WIDGET::Msg) {
         self.owned_stream().emit(msg);
@@ -101,7 +119,7 @@ pub trait Container: Widget {
     fn add_widget<WIDGET: Widget>(container: &ContainerComponent<Self>, component: &Component<WIDGET>)
         -> gtk::Container
     {
-        container.container.add(component.widget());
+        add_child(&container.container, component.widget());
         container.container.clone().upcast()
     }
 
@@ -140,13 +158,30 @@ pub trait ContainerWidget {
               CHILDWIDGET::Msg: DisplayVariant + 'static,
               CHILDWIDGET::Root: IsA<gtk::Widget> + IsA<Object> + WidgetExt;
 
+    /// Add a relm `Widget` to the current GTK+ container, but only create its view the first time
+    /// it becomes visible.
+    /// This is useful for the pages of a `gtk::Stack` or the tabs of a `gtk::Notebook`.
+    fn add_widget_lazy<CHILDWIDGET>(&self, model_param: CHILDWIDGET::ModelParam)
+            -> LazyComponent<CHILDWIDGET>
+        where CHILDWIDGET: Widget + 'static,
+              CHILDWIDGET::ModelParam: 'static,
+              CHILDWIDGET::Msg: DisplayVariant + 'static;
+
     /// Remove a relm `Widget` from the current GTK+ container.
     fn remove_widget<CHILDWIDGET>(&self, component: Component<CHILDWIDGET>)
         where CHILDWIDGET: Widget,
               CHILDWIDGET::Root: IsA<gtk::Widget>;
+
+    /// Add a relm `Widget` to the current GTK+ container, returning the error that occurred while
+    /// initializing it.
+    fn try_add_widget<CHILDWIDGET>(&self, model_param: CHILDWIDGET::ModelParam)
+            -> Result<Component<CHILDWIDGET>, InitError>
+        where CHILDWIDGET: Widget + 'static,
+              CHILDWIDGET::Msg: DisplayVariant + 'static,
+              CHILDWIDGET::Root: IsA<gtk::Widget> + IsA<Object> + WidgetExt;
 }
 
-impl<W: Clone + ContainerExt + IsA<gtk::Widget> + IsA<Object>> ContainerWidget for W {
+impl<W: Clone + ContainerExt + IsA<gtk::Container> + IsA<gtk::Widget> + IsA<Object>> ContainerWidget for W {
     fn add_container<CHILDWIDGET>(&self, model_param: CHILDWIDGET::ModelParam)
             -> ContainerComponent<CHILDWIDGET>
         where CHILDWIDGET: Container + Widget + 'static,
@@ -157,7 +192,7 @@ impl<W: Clone + ContainerExt + IsA<gtk::Widget> + IsA<Object>> ContainerWidget f
         let container = widget.container().clone();
         let containers = widget.other_containers();
         let root = widget.root().clone();
-        self.add(&root);
+        add_child(self, &root);
         widget.on_add(self.clone());
         init_component::<CHILDWIDGET>(component.owned_stream(), widget, &child_relm);
         ContainerComponent::new(component, container, containers)
@@ -170,18 +205,341 @@ impl<W: Clone + ContainerExt + IsA<gtk::Widget> + IsA<Object>> ContainerWidget f
               CHILDWIDGET::Root: IsA<gtk::Widget> + IsA<Object> + WidgetExt,
     {
         let (component, widget, child_relm) = create_widget::<CHILDWIDGET>(model_param);
-        self.add(component.widget());
+        add_child(self, component.widget());
         widget.on_add(self.clone());
         init_component::<CHILDWIDGET>(component.owned_stream(), widget, &child_relm);
         component
     }
 
+    fn add_widget_lazy<CHILDWIDGET>(&self, model_param: CHILDWIDGET::ModelParam)
+            -> LazyComponent<CHILDWIDGET>
+        where CHILDWIDGET: Widget + 'static,
+              CHILDWIDGET::ModelParam: 'static,
+              CHILDWIDGET::Msg: DisplayVariant + 'static,
+    {
+        let component = LazyComponent::new(model_param);
+        add_child(self, component.placeholder());
+        component
+    }
+
     // TODO: we're probably not calling remove_widget() when removing a relm widget from a gtk
     // widget.
     fn remove_widget<WIDGET>(&self, component: Component<WIDGET>)
         where WIDGET: Widget,
               WIDGET::Root: IsA<gtk::Widget>,
     {
-        self.remove(component.widget());
+        remove_child(self, component.widget());
+    }
+
+    fn try_add_widget<CHILDWIDGET>(&self, model_param: CHILDWIDGET::ModelParam)
+            -> Result<Component<CHILDWIDGET>, InitError>
+        where CHILDWIDGET: Widget + 'static,
+              CHILDWIDGET::Msg: DisplayVariant + 'static,
+              CHILDWIDGET::Root: IsA<gtk::Widget> + IsA<Object> + WidgetExt,
+    {
+        let (component, widget, child_relm) = try_create_widget::<CHILDWIDGET>(model_param)?;
+        add_child(self, component.widget());
+        widget.on_add(self.clone());
+        init_component::<CHILDWIDGET>(component.owned_stream(), widget, &child_relm);
+        Ok(component)
+    }
+}
+
+/// Extension trait for GTK+ containers whose children are ordered, to insert relm `Widget`s at a
+/// position and move them.
+/// This is useful for list-like views, to insert an item in the middle or to move an item up or
+/// down without recreating the other items.
+pub trait OrderedContainerWidget: ContainerWidget {
+    /// Add a relm `Widget` at position `index` in the current GTK+ container.
+    ///
+    /// # Note
+    ///
+    /// The returned `Component` must be stored in a `Widget`, like with
+    /// [`ContainerWidget::add_widget()`](trait.ContainerWidget.html#tymethod.add_widget).
+    fn insert_widget_at<CHILDWIDGET>(&self, index: usize, model_param: CHILDWIDGET::ModelParam)
+            -> Component<CHILDWIDGET>
+        where CHILDWIDGET: Widget + 'static,
+              CHILDWIDGET::Msg: DisplayVariant + 'static,
+              CHILDWIDGET::Root: IsA<gtk::Widget> + IsA<Object> + WidgetExt;
+
+    /// Move the relm `Widget` of `component` to position `index` in the current GTK+ container.
+    fn reorder_widget<CHILDWIDGET>(&self, component: &Component<CHILDWIDGET>, index: usize)
+        where CHILDWIDGET: Widget,
+              CHILDWIDGET::Root: IsA<gtk::Widget>;
+}
+
+fn insert_widget<CONTAINER, CHILDWIDGET, INSERT>(container: &CONTAINER, model_param: CHILDWIDGET::ModelParam,
+    insert: INSERT) -> Component<CHILDWIDGET>
+    where CONTAINER: Clone + IsA<gtk::Widget> + IsA<Object>,
+          CHILDWIDGET: Widget + 'static,
+          CHILDWIDGET::Msg: DisplayVariant + 'static,
+          INSERT: FnOnce(&gtk::Widget),
+{
+    let (component, widget, child_relm) = create_widget::<CHILDWIDGET>(model_param);
+    insert(component.widget().upcast_ref());
+    widget.on_add(container.clone());
+    init_component::<CHILDWIDGET>(component.owned_stream(), widget, &child_relm);
+    component
+}
+
+impl OrderedContainerWidget for gtk::Box {
+    fn insert_widget_at<CHILDWIDGET>(&self, index: usize, model_param: CHILDWIDGET::ModelParam)
+            -> Component<CHILDWIDGET>
+        where CHILDWIDGET: Widget + 'static,
+              CHILDWIDGET::Msg: DisplayVariant + 'static,
+              CHILDWIDGET::Root: IsA<gtk::Widget> + IsA<Object> + WidgetExt,
+    {
+        insert_widget(self, model_param, |child| {
+            self.add(child);
+            self.reorder_child(child, index as i32);
+        })
+    }
+
+    fn reorder_widget<CHILDWIDGET>(&self, component: &Component<CHILDWIDGET>, index: usize)
+        where CHILDWIDGET: Widget,
+              CHILDWIDGET::Root: IsA<gtk::Widget>,
+    {
+        self.reorder_child(component.widget(), index as i32);
+    }
+}
+
+impl OrderedContainerWidget for gtk::FlowBox {
+    fn insert_widget_at<CHILDWIDGET>(&self, index: usize, model_param: CHILDWIDGET::ModelParam)
+            -> Component<CHILDWIDGET>
+        where CHILDWIDGET: Widget + 'static,
+              CHILDWIDGET::Msg: DisplayVariant + 'static,
+              CHILDWIDGET::Root: IsA<gtk::Widget> + IsA<Object> + WidgetExt,
+    {
+        insert_widget(self, model_param, |child| self.insert(child, index as i32))
+    }
+
+    fn reorder_widget<CHILDWIDGET>(&self, component: &Component<CHILDWIDGET>, index: usize)
+        where CHILDWIDGET: Widget,
+              CHILDWIDGET::Root: IsA<gtk::Widget>,
+    {
+        // The flow box wraps its children in a gtk::FlowBoxChild, which is the widget to move.
+        let root = component.widget();
+        let child =
+            if root.is::<gtk::FlowBoxChild>() {
+                Some(root.clone().upcast::<gtk::Widget>())
+            }
+            else {
+                root.get_parent()
+            };
+        if let Some(child) = child {
+            self.remove(&child);
+            self.insert(&child, index as i32);
+        }
+    }
+}
+
+impl OrderedContainerWidget for gtk::ListBox {
+    fn insert_widget_at<CHILDWIDGET>(&self, index: usize, model_param: CHILDWIDGET::ModelParam)
+            -> Component<CHILDWIDGET>
+        where CHILDWIDGET: Widget + 'static,
+              CHILDWIDGET::Msg: DisplayVariant + 'static,
+              CHILDWIDGET::Root: IsA<gtk::Widget> + IsA<Object> + WidgetExt,
+    {
+        insert_widget(self, model_param, |child| self.insert(child, index as i32))
+    }
+
+    fn reorder_widget<CHILDWIDGET>(&self, component: &Component<CHILDWIDGET>, index: usize)
+        where CHILDWIDGET: Widget,
+              CHILDWIDGET::Root: IsA<gtk::Widget>,
+    {
+        // The list box wraps its children in a gtk::ListBoxRow, which is the widget to move.
+        let root = component.widget();
+        let row =
+            if root.is::<gtk::ListBoxRow>() {
+                Some(root.clone().upcast::<gtk::Widget>())
+            }
+            else {
+                root.get_parent()
+            };
+        if let Some(row) = row {
+            self.remove(&row);
+            self.insert(&row, index as i32);
+        }
+    }
+}
+
+impl OrderedContainerWidget for gtk::Notebook {
+    fn insert_widget_at<CHILDWIDGET>(&self, index: usize, model_param: CHILDWIDGET::ModelParam)
+            -> Component<CHILDWIDGET>
+        where CHILDWIDGET: Widget + 'static,
+              CHILDWIDGET::Msg: DisplayVariant + 'static,
+              CHILDWIDGET::Root: IsA<gtk::Widget> + IsA<Object> + WidgetExt,
+    {
+        insert_widget(self, model_param, |child| {
+            self.insert_page(child, None::<&gtk::Widget>, Some(index as u32));
+        })
+    }
+
+    fn reorder_widget<CHILDWIDGET>(&self, component: &Component<CHILDWIDGET>, index: usize)
+        where CHILDWIDGET: Widget,
+              CHILDWIDGET::Root: IsA<gtk::Widget>,
+    {
+        self.reorder_child(component.widget(), Some(index as u32));
+    }
+}
+
+/// Trait to implement for a GTK+ container that needs a specific method to add or remove its
+/// children, like the widgets of libhandy, so that it can be a parent in the `view!` macro and be
+/// used with [`ContainerWidget`](trait.ContainerWidget.html).
+///
+/// The `view!` macro dispatches statically to this implementation when the parent is declared
+/// with its concrete type, and registers it on first use. For the other paths, like
+/// [`ContainerWidget`](trait.ContainerWidget.html) on a parent created outside of a view, the
+/// implementation must be registered with [`register_container()`](fn.register_container.html)
+/// before adding children; the unregistered containers use `ContainerExt::add()` and
+/// `ContainerExt::remove()`.
+///
+/// ```ignore
+/// // In the crate defining the Sidebar container:
+/// impl RelmContainer for Sidebar {
+///     fn add_child(&self, child: &gtk::Widget) {
+///         self.add_item(child);
+///     }
+///
+///     fn remove_child(&self, child: &gtk::Widget) {
+///         self.remove_item(child);
+///     }
+/// }
+///
+/// relm::register_container::<Sidebar>();
+/// ```
+pub trait RelmContainer: IsA<gtk::Container> + IsA<gtk::Widget> + StaticType {
+    /// Add a child widget to this container.
+    fn add_child(&self, child: &gtk::Widget);
+
+    /// Remove a child widget from this container.
+    fn remove_child(&self, child: &gtk::Widget) {
+        self.remove(child);
+    }
+}
+
+type ChildFunction = fn(&gtk::Container, &gtk::Widget);
+
+thread_local! {
+    static CONTAINERS: RefCell<HashMap<Type, (ChildFunction, ChildFunction)>> = RefCell::new(HashMap::new());
+}
+
+/// Register the [`RelmContainer`](trait.RelmContainer.html) implementation of `CONTAINER`, so that
+/// it is used to add and remove the children of this container and of its subclasses.
+pub fn register_container<CONTAINER: RelmContainer>() {
+    fn add<CONTAINER: RelmContainer>(container: &gtk::Container, child: &gtk::Widget) {
+        container.downcast_ref::<CONTAINER>()
+            .expect("registered container type")
+            .add_child(child);
+    }
+
+    fn remove<CONTAINER: RelmContainer>(container: &gtk::Container, child: &gtk::Widget) {
+        container.downcast_ref::<CONTAINER>()
+            .expect("registered container type")
+            .remove_child(child);
+    }
+
+    CONTAINERS.with(|containers| {
+        containers.borrow_mut().insert(CONTAINER::static_type(), (add::<CONTAINER>, remove::<CONTAINER>));
+    });
+}
+
+fn is_registered<CONTAINER: RelmContainer>() -> bool {
+    CONTAINERS.with(|containers| containers.borrow().contains_key(&CONTAINER::static_type()))
+}
+
+/// Find the functions of the registered container type that is the closest ancestor of `typ`.
+fn container_functions(mut typ: Type) -> Option<(ChildFunction, ChildFunction)> {
+    CONTAINERS.with(|containers| {
+        let containers = containers.borrow();
+        if containers.is_empty() {
+            return None;
+        }
+        loop {
+            if let Some(&functions) = containers.get(&typ) {
+                return Some(functions);
+            }
+            typ = typ.parent()?;
+        }
+    })
+}
+
+/// Get the child of `parent` containing `widget`: `widget` itself, or the placeholder it was added
+/// to when it is the root of a [`LazyComponent`](struct.LazyComponent.html).
+#[doc(hidden)]
+pub fn direct_child<PARENT, WIDGET>(parent: &PARENT, widget: &WIDGET) -> gtk::Widget
+    where PARENT: IsA<gtk::Widget>,
+          WIDGET: IsA<gtk::Widget>,
+{
+    let parent = parent.upcast_ref::<gtk::Widget>();
+    let widget = widget.upcast_ref::<gtk::Widget>();
+    let mut child = widget.clone();
+    while let Some(child_parent) = child.get_parent() {
+        if child_parent == *parent {
+            return child;
+        }
+        child = child_parent;
+    }
+    widget.clone()
+}
+
+/// Add `child` to `container`, with its [`RelmContainer`](trait.RelmContainer.html)
+/// implementation if one is registered.
+#[doc(hidden)]
+pub fn add_child<CONTAINER, CHILD>(container: &CONTAINER, child: &CHILD)
+    where CONTAINER: IsA<gtk::Container>,
+          CHILD: IsA<gtk::Widget>,
+{
+    let container = container.upcast_ref::<gtk::Container>();
+    match container_functions(container.get_type()) {
+        Some((add, _)) => add(container, child.upcast_ref()),
+        None => container.add(child),
+    }
+}
+
+/// Remove `child` from `container`, with its [`RelmContainer`](trait.RelmContainer.html)
+/// implementation if one is registered.
+#[doc(hidden)]
+pub fn remove_child<CONTAINER, CHILD>(container: &CONTAINER, child: &CHILD)
+    where CONTAINER: IsA<gtk::Container>,
+          CHILD: IsA<gtk::Widget>,
+{
+    let container = container.upcast_ref::<gtk::Container>();
+    match container_functions(container.get_type()) {
+        Some((_, remove)) => remove(container, child.upcast_ref()),
+        None => container.remove(child),
+    }
+}
+
+/// Wrapper used by the `view!` macro to choose, at compile time, between the
+/// [`RelmContainer`](trait.RelmContainer.html) implementation of the parent and
+/// [`add_child()`](fn.add_child.html).
+#[doc(hidden)]
+pub struct ChildDispatch<'a, CONTAINER>(pub &'a CONTAINER);
+
+#[doc(hidden)]
+pub trait RelmContainerDispatch {
+    fn relm_add_child(&self, child: &gtk::Widget);
+}
+
+// Implemented on the wrapper itself, so that it takes precedence over the implementation on the
+// reference below when the method is called on `&ChildDispatch`.
+impl<'a, CONTAINER: RelmContainer> RelmContainerDispatch for ChildDispatch<'a, CONTAINER> {
+    fn relm_add_child(&self, child: &gtk::Widget) {
+        if !is_registered::<CONTAINER>() {
+            register_container::<CONTAINER>();
+        }
+        self.0.add_child(child);
+    }
+}
+
+#[doc(hidden)]
+pub trait GtkContainerDispatch {
+    fn relm_add_child(&self, child: &gtk::Widget);
+}
+
+impl<'a, CONTAINER: IsA<gtk::Container>> GtkContainerDispatch for &ChildDispatch<'a, CONTAINER> {
+    fn relm_add_child(&self, child: &gtk::Widget) {
+        add_child(self.0, child);
     }
 }
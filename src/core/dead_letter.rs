@@ -0,0 +1,90 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::any::type_name;
+use std::cell::RefCell;
+
+use super::StreamHandle;
+
+/// Information about a message that was emitted on a stream whose component was dropped.
+#[derive(Clone, Debug)]
+pub struct DeadLetter {
+    /// The name of the type of the message.
+    pub message_type: &'static str,
+}
+
+/// What to do when a message is emitted on a stream whose component was dropped.
+pub enum DeadLetterPolicy {
+    /// Send a message built from the `DeadLetter` to another stream.
+    Forward(Box<dyn Fn(DeadLetter)>),
+    /// Log an error.
+    Log,
+    /// Panic (this is the default).
+    Panic,
+    /// Panic in debug builds and log an error in release builds.
+    PanicInDebug,
+}
+
+thread_local! {
+    static POLICY: RefCell<DeadLetterPolicy> = RefCell::new(DeadLetterPolicy::Panic);
+}
+
+/// Set what to do when a message is emitted on a stream whose component was dropped.
+pub fn set_dead_letter_policy(policy: DeadLetterPolicy) {
+    POLICY.with(|current_policy| *current_policy.borrow_mut() = policy);
+}
+
+/// Send a message built by `constructor` to `stream` whenever a message is emitted on a stream
+/// whose component was dropped.
+pub fn forward_dead_letters<F, MSG>(stream: &StreamHandle<MSG>, constructor: F)
+    where F: Fn(DeadLetter) -> MSG + 'static,
+          MSG: 'static,
+{
+    let stream = stream.clone();
+    set_dead_letter_policy(DeadLetterPolicy::Forward(Box::new(move |dead_letter| {
+        if stream.pending_messages().is_some() {
+            stream.emit(constructor(dead_letter));
+        }
+        else {
+            log::error!("Message of type {} emitted on a dropped EventStream", dead_letter.message_type);
+        }
+    })));
+}
+
+pub(crate) fn dead_letter<MSG>() {
+    let dead_letter = DeadLetter {
+        message_type: type_name::<MSG>(),
+    };
+    POLICY.with(|policy| {
+        match *policy.borrow() {
+            DeadLetterPolicy::Forward(ref forward) => forward(dead_letter),
+            DeadLetterPolicy::Log => log::error!("Message of type {} emitted on a dropped EventStream",
+                dead_letter.message_type),
+            DeadLetterPolicy::Panic => panic!("Trying to call emit() on a dropped EventStream"),
+            DeadLetterPolicy::PanicInDebug => {
+                if cfg!(debug_assertions) {
+                    panic!("Trying to call emit() on a dropped EventStream");
+                }
+                log::error!("Message of type {} emitted on a dropped EventStream", dead_letter.message_type);
+            },
+        }
+    });
+}
@@ -32,23 +32,39 @@
     unused_qualifications,
 )]
 
+mod dead_letter;
 mod source;
 
 use std::cell::RefCell;
 use std::collections::VecDeque;
+use std::future::Future;
 use std::marker::PhantomData;
+use std::pin::Pin;
 use std::rc::{Rc, Weak};
+use std::sync::{Mutex, MutexGuard, PoisonError};
 use std::sync::mpsc::{self, Receiver, SendError};
+use std::task::{Context, Poll, Waker};
 
-use self::source::{SourceFuncs, new_source, source_get};
+pub use self::dead_letter::{DeadLetter, DeadLetterPolicy, forward_dead_letters, set_dead_letter_policy};
+use self::dead_letter::dead_letter;
+use self::source::{SourceFuncs, new_source, set_priority};
+use crate::cancellation::CancellationToken;
+use crate::debug::{stream_created, stream_dropped};
 
 use glib::{
+    Continue,
     MainContext,
+    Priority,
     Source,
     SourceId,
 };
 
 /// Handle to a EventStream to emit messages.
+///
+/// The handle is weak: it does not keep the stream, nor the component owning it, alive. Thus, it
+/// can be stored in long-lived closures, like timers, without creating reference cycles, and there
+/// is no need to downgrade it. Use [`is_dropped()`](#method.is_dropped) to know whether the
+/// component was destroyed.
 pub struct StreamHandle<MSG> {
     stream: Weak<RefCell<_EventStream<MSG>>>,
 }
@@ -73,16 +89,60 @@ impl<MSG> StreamHandle<MSG> {
         self.clone()
     }
 
+    /// Check whether the stream was dropped, i.e. whether the component owning it was destroyed.
+    pub fn is_dropped(&self) -> bool {
+        self.stream.strong_count() == 0
+    }
+
     /// Send the `event` message to the stream and the observers.
+    ///
+    /// If the stream was dropped, the message is handled according to the
+    /// [`DeadLetterPolicy`](enum.DeadLetterPolicy.html).
     pub fn emit(&self, msg: MSG) {
         if let Some(ref stream) = self.stream.upgrade() {
             emit(stream, msg);
         }
         else {
-            panic!("Trying to call emit() on a dropped EventStream");
+            dead_letter::<MSG>();
         }
     }
 
+    /// Send the `msg` message to the stream and the observers when the main loop is idle, i.e.
+    /// after the user input and the redraws were handled.
+    /// This is useful for low-importance work like cache warming.
+    pub fn emit_idle(&self, msg: MSG)
+        where MSG: 'static,
+    {
+        emit_idle(self.clone(), msg);
+    }
+
+    /// Spawn `future` on the default main context and emit the message it resolves to.
+    ///
+    /// The future is dropped without emitting anything when the stream is dropped, i.e. when its
+    /// component is destroyed, or when the returned token is cancelled.
+    pub fn spawn_local<FUTURE>(&self, future: FUTURE) -> CancellationToken
+        where FUTURE: Future<Output=MSG> + 'static,
+              MSG: 'static,
+    {
+        let token = CancellationToken::new();
+        let waker = Rc::new(RefCell::new(None));
+        if let Some(ref stream) = self.stream.upgrade() {
+            let mut stream = stream.borrow_mut();
+            stream.tasks.retain(|task| task.strong_count() > 0);
+            stream.tasks.push(Rc::downgrade(&waker));
+        }
+        else {
+            return token;
+        }
+        MainContext::default().spawn_local(StreamFuture {
+            future: Box::pin(future),
+            stream: self.clone(),
+            token: token.clone(),
+            waker,
+        });
+        token
+    }
+
     /// Lock the stream (don't emit message) until the `Lock` goes out of scope.
     pub fn lock(&self) -> Lock<MSG> {
         if let Some(ref stream) = self.stream.upgrade() {
@@ -96,16 +156,74 @@ impl<MSG> StreamHandle<MSG> {
         }
     }
 
+    /// Get the number of messages waiting to be processed, or None if the stream was dropped.
+    pub(crate) fn pending_messages(&self) -> Option<usize> {
+        self.stream.upgrade()
+            .map(|stream| stream.borrow().events.len())
+    }
+
+    /// Check whether a message of this stream is currently being handled by its component, i.e.
+    /// whether a message emitted now comes from its `update()` method.
+    pub(crate) fn is_updating(&self) -> bool {
+        self.stream.upgrade()
+            .map(|stream| stream.borrow().updating)
+            .unwrap_or(false)
+    }
+
+    /// Mark the stream as being updated by its component, returning whether it already was.
+    /// This is used by the components which handle their messages outside of the stream callback.
+    pub(crate) fn set_updating(&self, updating: bool) -> bool {
+        self.stream.upgrade()
+            .map(|stream| set_updating(&stream, updating))
+            .unwrap_or(false)
+    }
+
     /// Add an observer to the event stream.
     /// This callback will be called every time a message is emmited.
     pub fn observe<CALLBACK: Fn(&MSG) + 'static>(&self, callback: CALLBACK) {
         if let Some(ref stream) = self.stream.upgrade() {
-            stream.borrow_mut().observers.push(Rc::new(callback));
+            stream.borrow_mut().add_observer(Rc::new(callback));
         }
         else {
             panic!("Trying to call observe() on a dropped EventStream");
         }
     }
+
+    /// Add an observer to the event stream, which is removed when the returned guard is dropped.
+    pub fn observe_scoped<CALLBACK: Fn(&MSG) + 'static>(&self, callback: CALLBACK) -> ObserverGuard<MSG> {
+        if let Some(ref stream) = self.stream.upgrade() {
+            let id = stream.borrow_mut().add_observer(Rc::new(callback));
+            ObserverGuard {
+                id,
+                stream: Rc::downgrade(stream),
+            }
+        }
+        else {
+            panic!("Trying to call observe_scoped() on a dropped EventStream");
+        }
+    }
+}
+
+/// A guard removing an observer from its event stream when dropped.
+#[must_use]
+pub struct ObserverGuard<MSG> {
+    id: u64,
+    stream: Weak<RefCell<_EventStream<MSG>>>,
+}
+
+impl<MSG> ObserverGuard<MSG> {
+    /// Keep the observer for the whole lifetime of the stream.
+    pub fn forget(mut self) {
+        self.stream = Weak::new();
+    }
+}
+
+impl<MSG> Drop for ObserverGuard<MSG> {
+    fn drop(&mut self) {
+        if let Some(stream) = self.stream.upgrade() {
+            stream.borrow_mut().remove_observer(self.id);
+        }
+    }
 }
 
 /// A lock is used to temporarily stop emitting messages.
@@ -128,31 +246,42 @@ struct ChannelData<MSG> {
 
 /// A wrapper over a `std::sync::mpsc::Sender` to wakeup the glib event loop when sending a
 /// message.
+///
+/// The sender is `Send` and `Sync`, and cloning it is cheap, so that a clone can be moved to
+/// every producer thread.
 pub struct Sender<MSG> {
-    sender: mpsc::Sender<MSG>,
+    // Each clone has its own mutex, so the senders of different threads never contend.
+    sender: Mutex<mpsc::Sender<MSG>>,
 }
 
 impl<MSG> Clone for Sender<MSG> {
     fn clone(&self) -> Self {
         Self {
-            sender: self.sender.clone(),
+            sender: Mutex::new(self.lock().clone()),
         }
     }
 }
 
 impl<MSG> Sender<MSG> {
+    fn lock(&self) -> MutexGuard<mpsc::Sender<MSG>> {
+        // Sending never panics while holding the lock, so a poisoned mutex is still usable.
+        self.sender.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
     /// Send a message and wakeup the event loop.
+    /// When the `Channel` was dropped, e.g. because its component was destroyed, an error
+    /// containing the message is returned so that the producer thread can stop.
     pub fn send(&self, msg: MSG) -> Result<(), SendError<MSG>> {
-        let result = self.sender.send(msg);
+        self.lock().send(msg)?;
         let context = MainContext::default();
         context.wakeup();
-        result
+        Ok(())
     }
 }
 
 /// A channel to send a message to a relm widget from another thread.
 pub struct Channel<MSG> {
-    _source: Source,
+    source: Source,
     _phantom: PhantomData<MSG>,
 }
 
@@ -168,14 +297,21 @@ impl<MSG> Channel<MSG> {
         let main_context = MainContext::default();
         source.attach(Some(&main_context));
         (Self {
-            _source: source,
+            source,
             _phantom: PhantomData,
         }, Sender {
-            sender,
+            sender: Mutex::new(sender),
         })
     }
 }
 
+impl<MSG> Drop for Channel<MSG> {
+    fn drop(&mut self) {
+        // Destroying the source drops the receiver, so that the senders get an error.
+        self.source.destroy();
+    }
+}
+
 impl<MSG> SourceFuncs for RefCell<ChannelData<MSG>> {
     fn dispatch(&self) -> bool {
         // TODO: show errors.
@@ -203,14 +339,69 @@ impl<MSG> SourceFuncs for RefCell<ChannelData<MSG>> {
 struct _EventStream<MSG> {
     events: VecDeque<MSG>,
     locked: bool,
-    observers: Vec<Rc<dyn Fn(&MSG)>>,
+    next_observer_id: u64,
+    observers: Rc<Vec<(u64, Rc<dyn Fn(&MSG)>)>>,
+    // The wakers of the futures spawned with spawn_local(), to cancel them when the stream is dropped.
+    tasks: Vec<Weak<RefCell<Option<Waker>>>>,
+    // Whether a message of this stream is currently being handled by its component.
+    updating: bool,
+}
+
+/// Future emitting the message of `future` to `stream`, unless the stream was dropped or the
+/// token was cancelled.
+struct StreamFuture<MSG> {
+    future: Pin<Box<dyn Future<Output=MSG>>>,
+    stream: StreamHandle<MSG>,
+    token: CancellationToken,
+    waker: Rc<RefCell<Option<Waker>>>,
+}
+
+impl<MSG> Future for StreamFuture<MSG> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<()> {
+        if self.stream.stream.upgrade().is_none() || self.token.is_cancelled() {
+            return Poll::Ready(());
+        }
+        match self.future.as_mut().poll(context) {
+            Poll::Ready(msg) => {
+                self.stream.emit(msg);
+                Poll::Ready(())
+            },
+            Poll::Pending => {
+                self.token.register(context.waker());
+                *self.waker.borrow_mut() = Some(context.waker().clone());
+                Poll::Pending
+            },
+        }
+    }
+}
+
+impl<MSG> _EventStream<MSG> {
+    fn add_observer(&mut self, observer: Rc<dyn Fn(&MSG)>) -> u64 {
+        let id = self.next_observer_id;
+        self.next_observer_id += 1;
+        // The observers are shared with the emitters currently iterating over them, so only copy
+        // the list when it is in use.
+        Rc::make_mut(&mut self.observers).push((id, observer));
+        id
+    }
+
+    fn remove_observer(&mut self, id: u64) {
+        // An observer removed while a message is being emitted is still called for this message.
+        Rc::make_mut(&mut self.observers).retain(|&(observer_id, _)| observer_id != id);
+    }
 }
 
 impl<MSG> SourceFuncs for SourceData<MSG> {
     fn dispatch(&self) -> bool {
         let event = self.stream.borrow_mut().events.pop_front();
-        if let (Some(event), Some(callback)) = (event, self.callback.borrow_mut().as_mut()) {
-            callback(event);
+        if let Some(event) = event {
+            if let Some(callback) = self.callback.borrow_mut().as_mut() {
+                let was_updating = set_updating(&self.stream, true);
+                callback(event);
+                set_updating(&self.stream, was_updating);
+            }
         }
         true
     }
@@ -226,23 +417,52 @@ struct SourceData<MSG> {
     stream: Rc<RefCell<_EventStream<MSG>>>,
 }
 
+/// Mark the stream as being updated by its component, returning whether it already was.
+fn set_updating<MSG>(stream: &Rc<RefCell<_EventStream<MSG>>>, updating: bool) -> bool {
+    std::mem::replace(&mut stream.borrow_mut().updating, updating)
+}
+
 fn emit<MSG>(stream: &Rc<RefCell<_EventStream<MSG>>>, msg: MSG) {
-    if !stream.borrow().locked {
-        let len = stream.borrow().observers.len();
-        for i in 0..len {
-            let observer = stream.borrow().observers[i].clone();
+    // Only take a snapshot of the observers when there are some, so that the common case only
+    // borrows the stream twice and does not allocate.
+    let observers = {
+        let stream = stream.borrow();
+        if stream.locked {
+            return;
+        }
+        if stream.observers.is_empty() {
+            None
+        }
+        else {
+            Some(stream.observers.clone())
+        }
+    };
+    if let Some(observers) = observers {
+        for (_, observer) in observers.iter() {
             observer(&msg);
         }
-
-        stream.borrow_mut().events.push_back(msg);
     }
+
+    stream.borrow_mut().events.push_back(msg);
+}
+
+fn emit_idle<MSG: 'static>(stream: StreamHandle<MSG>, msg: MSG) {
+    let mut msg = Some(msg);
+    let _ = glib::idle_add_local(move || {
+        if let Some(msg) = msg.take() {
+            stream.emit(msg);
+        }
+        Continue(false)
+    });
 }
 
 /// A stream of messages to be used for widget/signal communication and inter-widget communication.
 /// EventStream cannot be send to another thread. Use a `Channel` `Sender` instead.
 pub struct EventStream<MSG> {
+    callback: Rc<RefCell<Option<Box<dyn FnMut(MSG)>>>>,
     source: Source,
     source_id: Option<SourceId>,
+    stream: Rc<RefCell<_EventStream<MSG>>>,
     _phantom: PhantomData<*mut MSG>,
 }
 
@@ -251,37 +471,48 @@ impl<MSG> Drop for EventStream<MSG> {
         // Ignore error since we're in a destructor.
         let _ = Source::remove(self.source_id.take().expect("source id"));
         self.close();
-    }
-}
-
-
-impl<MSG> EventStream<MSG> {
-    fn get_callback(&self) -> Rc<RefCell<Option<Box<dyn FnMut(MSG)>>>> {
-        source_get::<SourceData<MSG>>(&self.source).callback.clone()
-    }
-
-    fn get_stream(&self) -> Rc<RefCell<_EventStream<MSG>>> {
-        source_get::<SourceData<MSG>>(&self.source).stream.clone()
+        // Wake the spawned futures so that they see the stream was dropped and get cancelled.
+        let tasks = std::mem::replace(&mut self.stream.borrow_mut().tasks, vec![]);
+        for task in tasks {
+            if let Some(waker) = task.upgrade().and_then(|waker| waker.borrow_mut().take()) {
+                waker.wake();
+            }
+        }
+        stream_dropped::<MSG>();
     }
 }
 
 impl<MSG> EventStream<MSG> {
     /// Create a new event stream.
     pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Create a new event stream that can hold `capacity` pending messages without reallocating.
+    /// This is useful for streams receiving bursts of messages, like from data-acquisition threads.
+    pub fn with_capacity(capacity: usize) -> Self {
         let event_stream: _EventStream<MSG> = _EventStream {
-            events: VecDeque::new(),
+            events: VecDeque::with_capacity(capacity),
             locked: false,
-            observers: vec![],
+            next_observer_id: 0,
+            observers: Rc::new(vec![]),
+            tasks: vec![],
+            updating: false,
         };
+        let callback = Rc::new(RefCell::new(None));
+        let stream = Rc::new(RefCell::new(event_stream));
         let source = new_source(SourceData {
-            callback: Rc::new(RefCell::new(None)),
-            stream: Rc::new(RefCell::new(event_stream)),
+            callback: callback.clone(),
+            stream: stream.clone(),
         });
         let main_context = MainContext::default();
         let source_id = Some(source.attach(Some(&main_context)));
+        stream_created::<MSG>();
         EventStream {
+            callback,
             source,
             source_id,
+            stream,
             _phantom: PhantomData,
         }
     }
@@ -291,6 +522,14 @@ impl<MSG> EventStream<MSG> {
         self.source.destroy();
     }
 
+    /// Set the priority of the processing of the messages in the main loop.
+    /// The messages of the streams with a higher priority, i.e. a lower value, are processed
+    /// first, so that a stream receiving many messages with a low priority does not delay the
+    /// handling of the user input.
+    pub fn set_priority(&self, priority: Priority) {
+        set_priority(&self.source, priority);
+    }
+
     /// Synonym for downgrade().
     pub fn stream(&self) -> StreamHandle<MSG> {
         self.downgrade()
@@ -298,44 +537,72 @@ impl<MSG> EventStream<MSG> {
 
     /// Create a Clone-able EventStream handle.
     pub fn downgrade(&self) -> StreamHandle<MSG> {
-        StreamHandle::new(Rc::downgrade(&self.get_stream()))
+        StreamHandle::new(Rc::downgrade(&self.stream))
     }
 
     /// Send the `event` message to the stream and the observers.
     pub fn emit(&self, event: MSG) {
-        let stream = self.get_stream();
-        if !stream.borrow().locked {
-            let len = stream.borrow().observers.len();
-            for i in 0..len {
-                let observer = stream.borrow().observers[i].clone();
-                observer(&event);
-            }
+        emit(&self.stream, event);
+    }
 
-            stream.borrow_mut().events.push_back(event);
+    /// Put `events` at the front of the queue, without notifying the observers since they
+    /// already were when the messages were first emitted.
+    pub(crate) fn prepend(&self, events: Vec<MSG>) {
+        let mut stream = self.stream.borrow_mut();
+        for event in events.into_iter().rev() {
+            stream.events.push_front(event);
         }
     }
 
+    /// Send the `event` message to the stream and the observers when the main loop is idle.
+    /// See [`StreamHandle::emit_idle()`](struct.StreamHandle.html#method.emit_idle).
+    pub fn emit_idle(&self, event: MSG)
+        where MSG: 'static,
+    {
+        emit_idle(self.downgrade(), event);
+    }
+
+    /// Reserve capacity for at least `additional` more pending messages.
+    pub fn reserve(&self, additional: usize) {
+        self.stream.borrow_mut().events.reserve(additional);
+    }
+
+    /// Spawn `future` on the default main context and emit the message it resolves to.
+    /// See [`StreamHandle::spawn_local()`](struct.StreamHandle.html#method.spawn_local).
+    pub fn spawn_local<FUTURE>(&self, future: FUTURE) -> CancellationToken
+        where FUTURE: Future<Output=MSG> + 'static,
+              MSG: 'static,
+    {
+        self.downgrade().spawn_local(future)
+    }
+
     /// Lock the stream (don't emit message) until the `Lock` goes out of scope.
     pub fn lock(&self) -> Lock<MSG> {
-        let stream = self.get_stream();
-        stream.borrow_mut().locked = true;
+        self.stream.borrow_mut().locked = true;
         Lock {
-            stream: self.get_stream().clone(),
+            stream: self.stream.clone(),
         }
     }
 
     /// Add an observer to the event stream.
     /// This callback will be called every time a message is emmited.
     pub fn observe<CALLBACK: Fn(&MSG) + 'static>(&self, callback: CALLBACK) {
-        let stream = self.get_stream();
-        stream.borrow_mut().observers.push(Rc::new(callback));
+        self.stream.borrow_mut().add_observer(Rc::new(callback));
+    }
+
+    /// Add an observer to the event stream, which is removed when the returned guard is dropped.
+    pub fn observe_scoped<CALLBACK: Fn(&MSG) + 'static>(&self, callback: CALLBACK) -> ObserverGuard<MSG> {
+        let id = self.stream.borrow_mut().add_observer(Rc::new(callback));
+        ObserverGuard {
+            id,
+            stream: Rc::downgrade(&self.stream),
+        }
     }
 
     /// Add a callback to the event stream.
     /// This is the main callback and received a owned version of the message, in contrast to
     /// observe().
     pub fn set_callback<CALLBACK: FnMut(MSG) + 'static>(&self, callback: CALLBACK) {
-        let source_callback = self.get_callback();
-        *source_callback.borrow_mut() = Some(Box::new(callback));
+        *self.callback.borrow_mut() = Some(Box::new(callback));
     }
 }
@@ -23,9 +23,9 @@ use std::mem;
 use std::os::raw::c_int;
 use std::ptr;
 
-use glib::Source;
-use glib::translate::{ToGlibPtr, from_glib_full};
-use glib_sys::{GSource, GSourceFunc, GSourceFuncs, g_source_new};
+use glib::{Priority, Source};
+use glib::translate::{ToGlib, ToGlibPtr, from_glib_full};
+use glib_sys::{GSource, GSourceFunc, GSourceFuncs, g_source_new, g_source_set_priority};
 use libc;
 
 pub trait SourceFuncs {
@@ -58,8 +58,10 @@ pub fn new_source<T: SourceFuncs>(data: T) -> Source {
     }
 }
 
-pub fn source_get<T: SourceFuncs>(source: &Source) -> &T {
-    unsafe { &( *(source.to_glib_none().0 as *const SourceData<T>) ).data }
+pub fn set_priority(source: &Source, priority: Priority) {
+    unsafe {
+        g_source_set_priority(source.to_glib_none().0, priority.to_glib());
+    }
 }
 
 unsafe extern "C" fn check<T: SourceFuncs>(source: *mut GSource) -> c_int {
@@ -0,0 +1,233 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Sortable table bound to a `Vec` of rows.
+//!
+//! The columns are declared with `#[derive(GridRow)]` on the struct of the rows:
+//!
+//! ```ignore
+//! #[derive(Clone, GridRow)]
+//! struct Person {
+//!     #[column(title = "Name", editable)]
+//!     name: String,
+//!     #[column(title = "Age")]
+//!     age: u32,
+//! }
+//!
+//! let people = relm::create_component::<DataGrid<Person>>(self.model.people.clone());
+//! connect!(people@CellEdited(row, field, ref value), relm, Edit(row, field, value.clone()));
+//! ```
+//!
+//! Clicking a header sorts the rows. The grid does not change the rows itself: the parent applies
+//! the edits to its own `Vec` and sends it back with `SetRows`, so that it stays the source of
+//! truth. The row indexes of the messages are the indexes in this `Vec`, whatever the sort order.
+
+use glib::{StaticType, ToValue, Type, Value};
+use gtk::{
+    CellLayoutExt,
+    CellRendererTextExt,
+    CellRendererToggleExt,
+    ContainerExt,
+    GtkListStoreExt,
+    GtkListStoreExtManual,
+    ScrolledWindowExt,
+    TreeModelExt,
+    TreeViewColumnExt,
+    TreeViewExt,
+    WidgetExt,
+};
+
+use crate::state::{DisplayVariant, Relm, Update};
+use crate::widget::Widget;
+
+use self::DataGridMsg::*;
+
+/// A column of a [`DataGrid`](struct.DataGrid.html).
+#[derive(Clone, Debug)]
+pub struct GridColumn {
+    /// Whether the cells of the column can be edited.
+    pub editable: bool,
+    /// The name of the field shown in the column.
+    pub field: &'static str,
+    /// The title shown in the header.
+    pub title: &'static str,
+    /// The type of the values of the column.
+    pub typ: Type,
+}
+
+/// A row of a [`DataGrid`](struct.DataGrid.html).
+/// This is implemented by `#[derive(GridRow)]` for the structs whose fields have a
+/// `#[column(title = "Title")]` attribute, optionally with `editable`.
+pub trait GridRow: 'static {
+    /// The columns, in order.
+    fn columns() -> Vec<GridColumn>;
+    /// Get the values of the columns.
+    fn values(&self) -> Vec<Value>;
+}
+
+/// Messages of the [`DataGrid`](struct.DataGrid.html) component.
+///
+/// The first message is a command sent to the component, the others are events sent by the
+/// component.
+pub enum DataGridMsg<ROW> {
+    /// Replace the rows of the grid.
+    SetRows(Vec<ROW>),
+
+    /// The cell of the row at this index and of this field was edited with the new value.
+    /// The value of a boolean column is `"true"` or `"false"`.
+    CellEdited(usize, &'static str, String),
+    /// The row at this index was activated, e.g. by a double-click.
+    RowActivated(usize),
+}
+
+impl<ROW> DisplayVariant for DataGridMsg<ROW> {
+    fn display_variant(&self) -> &'static str {
+        match *self {
+            SetRows(_) => "SetRows",
+            CellEdited(_, _, _) => "CellEdited",
+            RowActivated(_) => "RowActivated",
+        }
+    }
+}
+
+/// Table backed by a `gtk::TreeView`, whose model parameter is the initial list of rows.
+pub struct DataGrid<ROW: GridRow> {
+    rows: Vec<ROW>,
+    root: gtk::ScrolledWindow,
+    store: gtk::ListStore,
+}
+
+// The last column of the store is the index of the row in the Vec.
+fn row_index(store: &gtk::ListStore, path: &gtk::TreePath) -> Option<usize> {
+    let index_column = store.get_n_columns() - 1;
+    store.get_iter(path)
+        .and_then(|iter| store.get_value(&iter, index_column).get::<u32>().ok().and_then(|index| index))
+        .map(|index| index as usize)
+}
+
+impl<ROW: GridRow> DataGrid<ROW> {
+    fn fill_store(&self) {
+        self.store.clear();
+        let column_count = ROW::columns().len() + 1;
+        let columns: Vec<u32> = (0..column_count as u32).collect();
+        for (index, row) in self.rows.iter().enumerate() {
+            let mut values = row.values();
+            values.push((index as u32).to_value());
+            let values: Vec<&dyn ToValue> = values.iter()
+                .map(|value| value as &dyn ToValue)
+                .collect();
+            self.store.insert_with_values(None, &columns, &values);
+        }
+    }
+}
+
+impl<ROW: GridRow> Update for DataGrid<ROW> {
+    type Model = Vec<ROW>;
+    type ModelParam = Vec<ROW>;
+    type Msg = DataGridMsg<ROW>;
+
+    fn model(_: &Relm<Self>, rows: Vec<ROW>) -> Vec<ROW> {
+        rows
+    }
+
+    fn update(&mut self, event: DataGridMsg<ROW>) {
+        match event {
+            SetRows(rows) => {
+                self.rows = rows;
+                self.fill_store();
+            },
+            // Events to be listened to by the parent.
+            CellEdited(_, _, _) | RowActivated(_) => (),
+        }
+    }
+}
+
+impl<ROW: GridRow> Widget for DataGrid<ROW> {
+    type Root = gtk::ScrolledWindow;
+
+    fn root(&self) -> Self::Root {
+        self.root.clone()
+    }
+
+    fn view(relm: &Relm<Self>, rows: Vec<ROW>) -> Self {
+        let columns = ROW::columns();
+        let mut types: Vec<Type> = columns.iter().map(|column| column.typ).collect();
+        types.push(u32::static_type());
+        let store = gtk::ListStore::new(&types);
+        let tree_view = gtk::TreeView::with_model(&store);
+
+        for (index, column) in columns.into_iter().enumerate() {
+            let view_column = gtk::TreeViewColumn::new();
+            view_column.set_title(column.title);
+            view_column.set_resizable(true);
+            view_column.set_sort_column_id(index as i32);
+            let field = column.field;
+            if column.typ == bool::static_type() {
+                let cell = gtk::CellRendererToggle::new();
+                cell.set_activatable(column.editable);
+                view_column.pack_start(&cell, true);
+                view_column.add_attribute(&cell, "active", index as i32);
+                let stream = relm.stream().clone();
+                let model = store.clone();
+                cell.connect_toggled(move |cell, path| {
+                    if let Some(row) = row_index(&model, &path) {
+                        stream.emit(CellEdited(row, field, (!cell.get_active()).to_string()));
+                    }
+                });
+            }
+            else {
+                let cell = gtk::CellRendererText::new();
+                cell.set_property_editable(column.editable);
+                view_column.pack_start(&cell, true);
+                view_column.add_attribute(&cell, "text", index as i32);
+                let stream = relm.stream().clone();
+                let model = store.clone();
+                cell.connect_edited(move |_, path, text| {
+                    if let Some(row) = row_index(&model, &path) {
+                        stream.emit(CellEdited(row, field, text.to_string()));
+                    }
+                });
+            }
+            tree_view.append_column(&view_column);
+        }
+
+        let stream = relm.stream().clone();
+        let model = store.clone();
+        tree_view.connect_row_activated(move |_, path, _| {
+            if let Some(row) = row_index(&model, path) {
+                stream.emit(RowActivated(row));
+            }
+        });
+
+        let root = gtk::ScrolledWindow::new(None::<&gtk::Adjustment>, None::<&gtk::Adjustment>);
+        root.set_policy(gtk::PolicyType::Automatic, gtk::PolicyType::Automatic);
+        root.add(&tree_view);
+        root.show_all();
+
+        let grid = DataGrid {
+            rows,
+            root,
+            store,
+        };
+        grid.fill_store();
+        grid
+    }
+}
@@ -0,0 +1,286 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! D-Bus services and clients delivering their calls and signals as messages.
+
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_uint};
+use std::ptr;
+
+use gio::{BusType, Cancellable, DBusCallFlags, DBusConnection, DBusMethodInvocation};
+use gio_sys::{
+    GDBusConnection,
+    GDBusInterfaceVTable,
+    GDBusMethodInvocation,
+    g_bus_own_name_on_connection,
+    g_bus_unown_name,
+    g_dbus_connection_register_object,
+    g_dbus_connection_signal_subscribe,
+    g_dbus_connection_signal_unsubscribe,
+    g_dbus_connection_unregister_object,
+    g_dbus_method_invocation_return_dbus_error,
+    g_dbus_method_invocation_return_value,
+    g_dbus_node_info_lookup_interface,
+    g_dbus_node_info_new_for_xml,
+    g_dbus_node_info_unref,
+    G_BUS_NAME_OWNER_FLAGS_NONE,
+    G_DBUS_SIGNAL_FLAGS_NONE,
+};
+use glib::{Error, Variant};
+use glib::translate::{FromGlibPtrNone, ToGlibPtr, from_glib_full};
+use glib_sys::{GError, GVariant, gpointer};
+
+use crate::core::StreamHandle;
+use crate::state::InitError;
+
+/// A method call received by a [`DBusService`](struct.DBusService.html).
+#[derive(Clone, Debug)]
+pub struct MethodCall {
+    /// The name of the method.
+    pub method: String,
+    /// The parameters of the call, as a tuple.
+    pub parameters: Variant,
+    /// The unique bus name of the caller.
+    pub sender: Option<String>,
+    invocation: DBusMethodInvocation,
+}
+
+impl MethodCall {
+    /// Reply to the call with `value`, which must be a tuple matching the out arguments of the
+    /// method (use `()` for no value).
+    pub fn reply(self, value: Option<&Variant>) {
+        unsafe {
+            g_dbus_method_invocation_return_value(self.invocation.to_glib_full(), value.to_glib_none().0);
+        }
+    }
+
+    /// Reply to the call with the D-Bus error `name`, like `org.freedesktop.DBus.Error.Failed`.
+    pub fn reply_error(self, name: &str, message: &str) {
+        unsafe {
+            g_dbus_method_invocation_return_dbus_error(self.invocation.to_glib_full(), name.to_glib_none().0,
+                message.to_glib_none().0);
+        }
+    }
+}
+
+/// A D-Bus signal received by a [`SignalSubscription`](struct.SignalSubscription.html).
+#[derive(Clone, Debug)]
+pub struct DBusSignal {
+    /// The name of the interface emitting the signal.
+    pub interface: String,
+    /// The path of the object emitting the signal.
+    pub object_path: String,
+    /// The parameters of the signal, as a tuple.
+    pub parameters: Variant,
+    /// The unique bus name of the emitter.
+    pub sender: Option<String>,
+    /// The name of the signal.
+    pub signal: String,
+}
+
+/// Object exported on the bus whose method calls are sent as messages to a component.
+///
+/// The object is unregistered and the name is released when the service is dropped, so it should
+/// be stored in the model.
+pub struct DBusService {
+    connection: DBusConnection,
+    owner_id: Option<c_uint>,
+    registration_id: c_uint,
+}
+
+impl DBusService {
+    /// Export the interface `interface_name`, described in the introspection `xml`, at
+    /// `object_path` on the session bus and send the method calls as messages built by
+    /// `constructor` to `stream`.
+    /// The messages should be replied to with [`MethodCall::reply()`](struct.MethodCall.html#method.reply).
+    ///
+    /// If `bus_name` is provided, this name is requested on the bus.
+    pub fn new<F, MSG>(bus_name: Option<&str>, object_path: &str, xml: &str, interface_name: &str,
+        stream: &StreamHandle<MSG>, constructor: F) -> Result<Self, InitError>
+        where F: Fn(MethodCall) -> MSG + 'static,
+              MSG: 'static,
+    {
+        let connection = gio::bus_get_sync(BusType::Session, None::<&Cancellable>)?;
+        Self::with_connection(connection, bus_name, object_path, xml, interface_name, stream, constructor)
+    }
+
+    /// Same as [`new()`](#method.new), but on the specified `connection`.
+    pub fn with_connection<F, MSG>(connection: DBusConnection, bus_name: Option<&str>, object_path: &str, xml: &str,
+        interface_name: &str, stream: &StreamHandle<MSG>, constructor: F) -> Result<Self, InitError>
+        where F: Fn(MethodCall) -> MSG + 'static,
+              MSG: 'static,
+    {
+        let handler: Box<Box<dyn Fn(MethodCall)>> = {
+            let stream = stream.clone();
+            Box::new(Box::new(move |call| stream.emit(constructor(call))))
+        };
+        let registration_id = unsafe {
+            let mut error: *mut GError = ptr::null_mut();
+            let node_info = g_dbus_node_info_new_for_xml(xml.to_glib_none().0, &mut error);
+            if node_info.is_null() {
+                let error: Error = from_glib_full(error);
+                return Err(error.into());
+            }
+            let interface_info = g_dbus_node_info_lookup_interface(node_info, interface_name.to_glib_none().0);
+            if interface_info.is_null() {
+                g_dbus_node_info_unref(node_info);
+                return Err(format!("Interface {} not found in the introspection data", interface_name).into());
+            }
+            let vtable = GDBusInterfaceVTable {
+                method_call: Some(method_call_trampoline),
+                get_property: None,
+                set_property: None,
+                padding: [ptr::null_mut(); 8],
+            };
+            let id = g_dbus_connection_register_object(connection.to_glib_none().0, object_path.to_glib_none().0,
+                interface_info, &vtable, Box::into_raw(handler) as gpointer, Some(destroy_handler::<MethodCall>),
+                &mut error);
+            g_dbus_node_info_unref(node_info);
+            if id == 0 {
+                let error: Error = from_glib_full(error);
+                return Err(error.into());
+            }
+            id
+        };
+        let owner_id = bus_name.map(|bus_name| unsafe {
+            g_bus_own_name_on_connection(connection.to_glib_none().0, bus_name.to_glib_none().0,
+                G_BUS_NAME_OWNER_FLAGS_NONE, None, None, ptr::null_mut(), None)
+        });
+        Ok(DBusService {
+            connection,
+            owner_id,
+            registration_id,
+        })
+    }
+
+    /// Get the connection on which the object is exported, e.g. to emit signals.
+    pub fn connection(&self) -> &DBusConnection {
+        &self.connection
+    }
+}
+
+impl Drop for DBusService {
+    fn drop(&mut self) {
+        unsafe {
+            if let Some(owner_id) = self.owner_id.take() {
+                g_bus_unown_name(owner_id);
+            }
+            let _ = g_dbus_connection_unregister_object(self.connection.to_glib_none().0, self.registration_id);
+        }
+    }
+}
+
+/// Subscription to a D-Bus signal whose emissions are sent as messages to a component.
+///
+/// The subscription is cancelled when this value is dropped, so it should be stored in the model.
+pub struct SignalSubscription {
+    connection: DBusConnection,
+    id: c_uint,
+}
+
+impl SignalSubscription {
+    /// Subscribe to the signals matching the `sender`, `interface`, `signal` and `object_path`
+    /// filters (`None` matches everything) and send them as messages built by `constructor` to
+    /// `stream`.
+    pub fn new<F, MSG>(connection: &DBusConnection, sender: Option<&str>, interface: Option<&str>,
+        signal: Option<&str>, object_path: Option<&str>, stream: &StreamHandle<MSG>, constructor: F) -> Self
+        where F: Fn(DBusSignal) -> MSG + 'static,
+              MSG: 'static,
+    {
+        let handler: Box<Box<dyn Fn(DBusSignal)>> = {
+            let stream = stream.clone();
+            Box::new(Box::new(move |signal| stream.emit(constructor(signal))))
+        };
+        let id = unsafe {
+            g_dbus_connection_signal_subscribe(connection.to_glib_none().0, sender.to_glib_none().0,
+                interface.to_glib_none().0, signal.to_glib_none().0, object_path.to_glib_none().0, ptr::null(),
+                G_DBUS_SIGNAL_FLAGS_NONE, Some(signal_trampoline), Box::into_raw(handler) as gpointer,
+                Some(destroy_handler::<DBusSignal>))
+        };
+        SignalSubscription {
+            connection: connection.clone(),
+            id,
+        }
+    }
+}
+
+impl Drop for SignalSubscription {
+    fn drop(&mut self) {
+        unsafe {
+            g_dbus_connection_signal_unsubscribe(self.connection.to_glib_none().0, self.id);
+        }
+    }
+}
+
+/// Call the `method` of a remote object on `connection` and send the result as a message built by
+/// `constructor` to `stream`.
+pub fn call<F, MSG>(connection: &DBusConnection, bus_name: &str, object_path: &str, interface: &str, method: &str,
+    parameters: Option<&Variant>, stream: &StreamHandle<MSG>, constructor: F)
+    where F: FnOnce(Result<Variant, Error>) -> MSG + 'static,
+          MSG: 'static,
+{
+    // TODO: remove any use of Fragile when gio callbacks stop requiring Send.
+    let stream = crate::vendor::fragile::Fragile::new(stream.clone());
+    connection.call(Some(bus_name), object_path, interface, method, parameters, None, DBusCallFlags::NONE, -1,
+        None::<&Cancellable>, move |result| {
+            stream.into_inner().emit(constructor(result));
+        });
+}
+
+unsafe extern "C" fn method_call_trampoline(_connection: *mut GDBusConnection, sender: *const c_char,
+    _object_path: *const c_char, _interface_name: *const c_char, method_name: *const c_char,
+    parameters: *mut GVariant, invocation: *mut GDBusMethodInvocation, user_data: gpointer)
+{
+    let handler = &*(user_data as *const Box<dyn Fn(MethodCall)>);
+    handler(MethodCall {
+        invocation: DBusMethodInvocation::from_glib_none(invocation),
+        method: to_string(method_name).unwrap_or_default(),
+        parameters: Variant::from_glib_none(parameters),
+        sender: to_string(sender),
+    });
+}
+
+unsafe extern "C" fn signal_trampoline(_connection: *mut GDBusConnection, sender: *const c_char,
+    object_path: *const c_char, interface_name: *const c_char, signal_name: *const c_char, parameters: *mut GVariant,
+    user_data: gpointer)
+{
+    let handler = &*(user_data as *const Box<dyn Fn(DBusSignal)>);
+    handler(DBusSignal {
+        interface: to_string(interface_name).unwrap_or_default(),
+        object_path: to_string(object_path).unwrap_or_default(),
+        parameters: Variant::from_glib_none(parameters),
+        sender: to_string(sender),
+        signal: to_string(signal_name).unwrap_or_default(),
+    });
+}
+
+unsafe extern "C" fn destroy_handler<T>(user_data: gpointer) {
+    let _ = Box::from_raw(user_data as *mut Box<dyn Fn(T)>);
+}
+
+unsafe fn to_string(string: *const c_char) -> Option<String> {
+    if string.is_null() {
+        None
+    }
+    else {
+        Some(CStr::from_ptr(string).to_string_lossy().into_owned())
+    }
+}
@@ -0,0 +1,336 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Time-travel debugging: record the messages received by a component together with snapshots of
+//! its state, so that the application can be rewound to any prior state and replayed forward.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk::{
+    Button,
+    ButtonExt,
+    ContainerExt,
+    GtkWindowExt,
+    Label,
+    LabelExt,
+    Orientation,
+    WidgetExt,
+    Window,
+    WindowType,
+};
+
+use glib::Priority;
+
+use crate::{Component, DisplayVariant, InitError, Relm, Update, Widget, init_component, try_create_widget};
+use crate::state::update_component_batch;
+
+/// Trait for components whose state can be captured and restored by the [`History`](struct.History.html)
+/// recorder.
+///
+/// When using the `#[widget]` attribute, implement `restore()` by assigning the model fields one
+/// by one (e.g. `self.model.counter = snapshot.counter;`) from a method of the widget so that the
+/// view bindings are refreshed.
+pub trait TimeTravel: Widget {
+    /// The type of the saved state, usually a clone of the model.
+    type Snapshot: Clone;
+
+    /// Capture the current state of the component.
+    fn snapshot(&self) -> Self::Snapshot;
+
+    /// Restore a state previously captured by `snapshot()`.
+    fn restore(&mut self, snapshot: Self::Snapshot);
+}
+
+struct HistoryData<WIDGET: TimeTravel> {
+    // The message at index i was received in the state at index i and produced the state at index
+    // i + 1.
+    messages: Vec<WIDGET::Msg>,
+    position: usize,
+    replaying: bool,
+    states: Vec<WIDGET::Snapshot>,
+}
+
+/// Recorder of the messages received by a component and of the states they produced.
+pub struct History<WIDGET: TimeTravel> {
+    component: Rc<RefCell<WIDGET>>,
+    data: Rc<RefCell<HistoryData<WIDGET>>>,
+}
+
+impl<WIDGET: TimeTravel> Clone for History<WIDGET> {
+    fn clone(&self) -> Self {
+        Self {
+            component: self.component.clone(),
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl<WIDGET> History<WIDGET>
+    where WIDGET: TimeTravel + 'static,
+          WIDGET::Msg: Clone + DisplayVariant + 'static,
+{
+    fn new(component: WIDGET) -> Self {
+        let initial_state = component.snapshot();
+        Self {
+            component: Rc::new(RefCell::new(component)),
+            data: Rc::new(RefCell::new(HistoryData {
+                messages: vec![],
+                position: 0,
+                replaying: false,
+                states: vec![initial_state],
+            })),
+        }
+    }
+
+    // Only call update(): after_update() is called by the caller once the messages are handled, so
+    // that the frame-synchronized widgets are refreshed once per batch.
+    fn record(&self, msg: WIDGET::Msg) {
+        let mut component = self.component.borrow_mut();
+        {
+            let mut data = self.data.borrow_mut();
+            if !data.replaying {
+                // Receiving a new message while rewound discards the messages that were undone.
+                let position = data.position;
+                data.messages.truncate(position);
+                data.states.truncate(position + 1);
+                data.messages.push(msg.clone());
+            }
+        }
+        component.update(msg);
+        let mut data = self.data.borrow_mut();
+        data.position += 1;
+        if !data.replaying {
+            data.states.push(component.snapshot());
+        }
+    }
+
+    /// Get the number of messages that were recorded.
+    pub fn len(&self) -> usize {
+        self.data.borrow().messages.len()
+    }
+
+    /// Check whether no message was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the name of the variants of the recorded messages.
+    pub fn message_names(&self) -> Vec<&'static str> {
+        self.data.borrow().messages.iter()
+            .map(|msg| msg.display_variant())
+            .collect()
+    }
+
+    /// Capture the current state of the component.
+    pub fn snapshot(&self) -> WIDGET::Snapshot {
+        self.component.borrow().snapshot()
+    }
+
+    /// Get the number of recorded messages that are currently applied to the component.
+    pub fn position(&self) -> usize {
+        self.data.borrow().position
+    }
+
+    /// Put the component in the state it was after receiving the first `position` messages.
+    ///
+    /// ## Panics
+    /// Panics if `position` is greater than the number of recorded messages.
+    pub fn go_to(&self, position: usize) {
+        let state = {
+            let data = self.data.borrow();
+            assert!(position < data.states.len(), "Cannot go to position {} in a history of {} messages", position,
+                data.messages.len());
+            data.states[position].clone()
+        };
+        self.component.borrow_mut().restore(state);
+        self.data.borrow_mut().position = position;
+    }
+
+    /// Undo the last applied message.
+    /// Return false if there is no message to undo.
+    pub fn step_back(&self) -> bool {
+        let position = self.position();
+        if position > 0 {
+            self.go_to(position - 1);
+            true
+        }
+        else {
+            false
+        }
+    }
+
+    /// Redo the next undone message.
+    /// Return false if there is no message to redo.
+    pub fn step_forward(&self) -> bool {
+        let position = self.position();
+        if position < self.len() {
+            self.go_to(position + 1);
+            true
+        }
+        else {
+            false
+        }
+    }
+
+    /// Send again the undone messages to the `update()` method of the component.
+    /// In contrast to `step_forward()`, this executes the update code instead of restoring
+    /// snapshots, which is useful to debug this code.
+    pub fn replay(&self) {
+        let messages: Vec<_> = {
+            let mut data = self.data.borrow_mut();
+            data.replaying = true;
+            data.messages[data.position..].to_vec()
+        };
+        let mut recorded = Recorded {
+            history: self.clone(),
+        };
+        let mut messages = messages.into_iter();
+        update_component_batch(&mut recorded, || messages.next());
+        self.data.borrow_mut().replaying = false;
+    }
+
+    /// Open a window to navigate in the history.
+    pub fn open_window(&self) -> Window {
+        let window = Window::new(WindowType::Toplevel);
+        window.set_title("relm history");
+        let vbox = gtk::Box::new(Orientation::Vertical, 0);
+        let label = Label::new(None);
+        vbox.add(&label);
+        let hbox = gtk::Box::new(Orientation::Horizontal, 0);
+        let back_button = Button::with_label("Step back");
+        hbox.add(&back_button);
+        let forward_button = Button::with_label("Step forward");
+        hbox.add(&forward_button);
+        let replay_button = Button::with_label("Replay");
+        hbox.add(&replay_button);
+        vbox.add(&hbox);
+        window.add(&vbox);
+
+        let refresh = {
+            let history = self.clone();
+            let label = label.clone();
+            move || {
+                let position = history.position();
+                let names = history.message_names();
+                let last_message = if position > 0 { names[position - 1] } else { "" };
+                label.set_text(&format!("{} / {} {}", position, names.len(), last_message));
+            }
+        };
+        refresh();
+        let refresh = Rc::new(refresh);
+
+        let history = self.clone();
+        let refresh_label = refresh.clone();
+        back_button.connect_clicked(move |_| {
+            history.step_back();
+            refresh_label();
+        });
+        let history = self.clone();
+        let refresh_label = refresh.clone();
+        forward_button.connect_clicked(move |_| {
+            history.step_forward();
+            refresh_label();
+        });
+        let history = self.clone();
+        replay_button.connect_clicked(move |_| {
+            history.replay();
+            refresh();
+        });
+
+        window.show_all();
+        window
+    }
+}
+
+/// Component handling the messages of a widget by recording them in its history, so that the
+/// widget is initialized like any other component.
+struct Recorded<WIDGET: TimeTravel> {
+    history: History<WIDGET>,
+}
+
+impl<WIDGET> Update for Recorded<WIDGET>
+    where WIDGET: TimeTravel + 'static,
+          WIDGET::Msg: Clone + DisplayVariant + 'static,
+{
+    type Model = ();
+    type ModelParam = ();
+    type Msg = WIDGET::Msg;
+
+    fn model(_: &Relm<Self>, _: ()) {
+    }
+
+    fn update(&mut self, msg: WIDGET::Msg) {
+        self.history.record(msg);
+    }
+
+    fn after_update(&mut self) {
+        self.history.component.borrow_mut().after_update();
+    }
+}
+
+impl<WIDGET> Widget for Recorded<WIDGET>
+    where WIDGET: TimeTravel + 'static,
+          WIDGET::Msg: Clone + DisplayVariant + 'static,
+{
+    type Root = WIDGET::Root;
+
+    fn init_messages(&self) -> Vec<WIDGET::Msg> {
+        self.history.component.borrow().init_messages()
+    }
+
+    fn frame_synchronized() -> bool {
+        WIDGET::frame_synchronized()
+    }
+
+    fn priority() -> Priority {
+        WIDGET::priority()
+    }
+
+    fn shutdown_msg() -> Option<WIDGET::Msg> {
+        WIDGET::shutdown_msg()
+    }
+
+    fn root(&self) -> Self::Root {
+        self.history.component.borrow().root()
+    }
+
+    fn view(_: &Relm<Self>, _: ()) -> Self {
+        unreachable!("the recorded widget is created by init_with_history()")
+    }
+}
+
+/// Initialize a widget while recording its messages in a [`History`](struct.History.html),
+/// returning the error that occurred while creating its model or its view.
+pub fn init_with_history<WIDGET>(model_param: WIDGET::ModelParam) -> Result<(Component<WIDGET>, History<WIDGET>), InitError>
+    where WIDGET: TimeTravel + 'static,
+          WIDGET::Msg: Clone + DisplayVariant + 'static,
+{
+    let (component, mut widget, relm) = try_create_widget::<WIDGET>(model_param)?;
+    widget.subscriptions(&relm);
+    let history = History::new(widget);
+    let recorded = Recorded {
+        history: history.clone(),
+    };
+    let stream = component.owned_stream();
+    init_component::<Recorded<WIDGET>>(stream, recorded, &Relm::new(stream));
+    Ok((component, history))
+}
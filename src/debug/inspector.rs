@@ -0,0 +1,153 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Window showing a live tree of the running components.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+
+use glib::{Continue, ObjectExt, ToValue, Type};
+use gtk::{
+    CellLayoutExt,
+    CellRendererText,
+    ContainerExt,
+    GtkWindowExt,
+    ScrolledWindow,
+    TreeIter,
+    TreeStore,
+    TreeStoreExt,
+    TreeStoreExtManual,
+    TreeView,
+    TreeViewColumn,
+    TreeViewColumnExt,
+    TreeViewExt,
+    WidgetExt,
+    Window,
+    WindowType,
+};
+
+use super::registry::{ComponentEntry, with_components};
+
+const COLUMNS: [&str; 4] = ["Component", "Root widget", "Pending messages", "Last message"];
+const REFRESH_INTERVAL: u32 = 500;
+
+/// Open a window showing a live tree of all running components, their widget types, the number of
+/// messages waiting in their stream and the last message they received since the window was
+/// opened.
+///
+/// Components are only tracked in debug builds.
+pub fn open_inspector() -> Window {
+    let window = Window::new(WindowType::Toplevel);
+    window.set_title("relm inspector");
+    window.set_default_size(600, 400);
+
+    let store = TreeStore::new(&[Type::String, Type::String, Type::U32, Type::String]);
+    let tree_view = TreeView::with_model(&store);
+    for (index, title) in COLUMNS.iter().enumerate() {
+        let column = TreeViewColumn::new();
+        column.set_title(title);
+        let cell = CellRendererText::new();
+        column.pack_start(&cell, true);
+        column.add_attribute(&cell, "text", index as i32);
+        tree_view.append_column(&column);
+    }
+    let scrolled_window = ScrolledWindow::new(None::<&gtk::Adjustment>, None::<&gtk::Adjustment>);
+    scrolled_window.add(&tree_view);
+    window.add(&scrolled_window);
+
+    // The observers tracking the last message of each component, removed when the window is
+    // closed.
+    let observers = RefCell::new(HashMap::new());
+    refresh(&store, &tree_view, &observers);
+    let weak_window = window.downgrade();
+    glib::timeout_add_local(REFRESH_INTERVAL, move || {
+        if weak_window.upgrade().is_some() {
+            refresh(&store, &tree_view, &observers);
+            Continue(true)
+        }
+        else {
+            Continue(false)
+        }
+    });
+
+    window.show_all();
+    window
+}
+
+fn refresh(store: &TreeStore, tree_view: &TreeView, observers: &RefCell<HashMap<u64, Box<dyn Any>>>) {
+    store.clear();
+    with_components(|components| {
+        let mut observers = observers.borrow_mut();
+        let ids: HashSet<_> = components.iter().map(|component| component.id).collect();
+        observers.retain(|id, _| ids.contains(id));
+        for component in components {
+            if !observers.contains_key(&component.id) {
+                if let Some(guard) = (component.observe)() {
+                    observers.insert(component.id, guard);
+                }
+            }
+        }
+
+        let roots: Vec<_> = components.iter()
+            .map(|component| component.root.upgrade())
+            .collect();
+        // Map each component to its closest parent component in the widget hierarchy.
+        let mut children: HashMap<Option<usize>, Vec<usize>> = HashMap::new();
+        for (index, root) in roots.iter().enumerate() {
+            if let Some(ref root) = *root {
+                let mut parent = None;
+                let mut ancestor = root.get_parent();
+                while let Some(widget) = ancestor {
+                    parent = roots.iter().position(|root| root.as_ref() == Some(&widget));
+                    if parent.is_some() {
+                        break;
+                    }
+                    ancestor = widget.get_parent();
+                }
+                children.entry(parent).or_insert_with(Vec::new).push(index);
+            }
+        }
+        insert_children(store, None, None, components, &roots, &children);
+    });
+    tree_view.expand_all();
+}
+
+fn insert_children(store: &TreeStore, parent_iter: Option<&TreeIter>, parent: Option<usize>,
+    components: &[ComponentEntry], roots: &[Option<gtk::Widget>], children: &HashMap<Option<usize>, Vec<usize>>)
+{
+    if let Some(indices) = children.get(&parent) {
+        for &index in indices {
+            let component = &components[index];
+            let widget_type = roots[index].as_ref()
+                .map(|root| root.get_type().name())
+                .unwrap_or_default();
+            let pending_messages = (component.pending_messages)().unwrap_or(0) as u32;
+            let iter = store.insert_with_values(parent_iter, None, &[0, 1, 2, 3], &[
+                &component.name as &dyn ToValue,
+                &widget_type,
+                &pending_messages,
+                &component.last_message.get(),
+            ]);
+            insert_children(store, Some(&iter), Some(index), components, roots, children);
+        }
+    }
+}
@@ -0,0 +1,106 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Detection of the components and streams that are kept alive longer than expected, for instance
+//! because of a reference cycle between closures and streams.
+//! The components and streams are only tracked in debug builds.
+
+use std::any::type_name;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use super::registry::with_components;
+
+thread_local! {
+    static LIVE_STREAMS: RefCell<HashMap<&'static str, usize>> = RefCell::new(HashMap::new());
+}
+
+pub(crate) fn stream_created<MSG>() {
+    if cfg!(debug_assertions) {
+        LIVE_STREAMS.with(|streams| {
+            *streams.borrow_mut().entry(type_name::<MSG>()).or_insert(0) += 1;
+        });
+    }
+}
+
+pub(crate) fn stream_dropped<MSG>() {
+    if cfg!(debug_assertions) {
+        LIVE_STREAMS.with(|streams| {
+            let mut streams = streams.borrow_mut();
+            let name = type_name::<MSG>();
+            let remove =
+                if let Some(count) = streams.get_mut(name) {
+                    *count -= 1;
+                    *count == 0
+                }
+                else {
+                    false
+                };
+            if remove {
+                let _ = streams.remove(name);
+            }
+        });
+    }
+}
+
+/// Get the number of live components for each component type.
+pub fn live_components() -> Vec<(&'static str, usize)> {
+    let mut counts = HashMap::new();
+    with_components(|components| {
+        for component in components {
+            *counts.entry(component.name).or_insert(0) += 1;
+        }
+    });
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    counts.sort();
+    counts
+}
+
+/// Get the number of live event streams for each message type.
+pub fn live_streams() -> Vec<(&'static str, usize)> {
+    let mut counts: Vec<_> = LIVE_STREAMS.with(|streams| {
+        streams.borrow().iter()
+            .map(|(&name, &count)| (name, count))
+            .collect()
+    });
+    counts.sort();
+    counts
+}
+
+/// Assert that all the components and event streams were dropped.
+/// This is meant to be called at the end of a test, after dropping the components.
+///
+/// ## Panics
+/// Panics with the list of the live components and streams if there are any.
+pub fn assert_no_leaks() {
+    let components = live_components();
+    let streams = live_streams();
+    if !components.is_empty() || !streams.is_empty() {
+        let components: Vec<_> = components.iter()
+            .map(|&(name, count)| format!("{} ({})", name, count))
+            .collect();
+        let streams: Vec<_> = streams.iter()
+            .map(|&(name, count)| format!("{} ({})", name, count))
+            .collect();
+        panic!("Leaked components: [{}], leaked streams of messages: [{}]", components.join(", "),
+            streams.join(", "));
+    }
+}
@@ -0,0 +1,65 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Debugging utilities for relm applications.
+//!
+//! These tools are opt-in, except for the bookkeeping of the live components and streams which is
+//! done in debug builds.
+
+mod history;
+mod inspector;
+mod leaks;
+mod profiler;
+mod registry;
+#[cfg(feature = "replay")]
+mod replay;
+mod watchdog;
+
+pub use self::history::{History, TimeTravel, init_with_history};
+pub use self::inspector::open_inspector;
+pub use self::leaks::{assert_no_leaks, live_components, live_streams};
+pub(crate) use self::leaks::{stream_created, stream_dropped};
+pub use self::profiler::{
+    ProfileEntry,
+    log_profile_report,
+    profile_report,
+    reset_profile,
+    set_frame_budget,
+    start_profiling,
+    stop_profiling,
+};
+pub(crate) use self::profiler::{is_profiling, record_update};
+pub(crate) use self::registry::register;
+#[cfg(feature = "replay")]
+pub use self::replay::{
+    Recorder,
+    Session,
+    Versioned,
+    deserialize_versioned,
+    record,
+    record_versioned,
+    replay,
+    replay_versioned,
+};
+#[cfg(feature = "replay")]
+pub use serde_json::Value;
+pub use self::watchdog::set_starvation_threshold;
+pub(crate) use self::watchdog::{check_starvation, starvation_threshold};
@@ -0,0 +1,124 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Profiler measuring the time spent in the `update()` method of the components.
+//!
+//! When using the `#[widget]` attribute, this time includes the refresh of the view bindings.
+
+use std::any::type_name;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Statistics about the handling of a message variant by a component.
+#[derive(Clone, Debug)]
+pub struct ProfileEntry {
+    /// The name of the component type.
+    pub component: &'static str,
+    /// The name of the message variant.
+    pub message: &'static str,
+    /// The number of times this message was handled.
+    pub count: u32,
+    /// The longest time the handling of this message took.
+    pub max: Duration,
+    /// The number of times the handling of this message exceeded the frame budget.
+    pub over_budget: u32,
+    /// The total time spent handling this message.
+    pub total: Duration,
+}
+
+impl ProfileEntry {
+    /// Get the average time spent handling this message.
+    pub fn average(&self) -> Duration {
+        self.total / self.count.max(1)
+    }
+}
+
+thread_local! {
+    static ENTRIES: RefCell<HashMap<(&'static str, &'static str), ProfileEntry>> = RefCell::new(HashMap::new());
+    static FRAME_BUDGET: Cell<Duration> = Cell::new(Duration::from_millis(16));
+    static PROFILING: Cell<bool> = Cell::new(false);
+}
+
+/// Start measuring the time spent in the `update()` methods.
+pub fn start_profiling() {
+    PROFILING.with(|profiling| profiling.set(true));
+}
+
+/// Stop measuring the time spent in the `update()` methods.
+/// The statistics collected so far are kept.
+pub fn stop_profiling() {
+    PROFILING.with(|profiling| profiling.set(false));
+}
+
+/// Clear the statistics collected so far.
+pub fn reset_profile() {
+    ENTRIES.with(|entries| entries.borrow_mut().clear());
+}
+
+/// Set the duration above which the handling of a message is reported as exceeding the frame
+/// budget. The default is 16ms.
+pub fn set_frame_budget(budget: Duration) {
+    FRAME_BUDGET.with(|frame_budget| frame_budget.set(budget));
+}
+
+pub(crate) fn is_profiling() -> bool {
+    PROFILING.with(Cell::get)
+}
+
+pub(crate) fn record_update<COMPONENT>(message: &'static str, duration: Duration) {
+    let component = type_name::<COMPONENT>();
+    let over_budget = duration > FRAME_BUDGET.with(Cell::get);
+    ENTRIES.with(|entries| {
+        let mut entries = entries.borrow_mut();
+        let entry = entries.entry((component, message)).or_insert_with(|| ProfileEntry {
+            component,
+            message,
+            count: 0,
+            max: Duration::from_secs(0),
+            over_budget: 0,
+            total: Duration::from_secs(0),
+        });
+        entry.count += 1;
+        entry.max = entry.max.max(duration);
+        if over_budget {
+            entry.over_budget += 1;
+        }
+        entry.total += duration;
+    });
+}
+
+/// Get the statistics collected so far, the slowest handlers first.
+pub fn profile_report() -> Vec<ProfileEntry> {
+    let mut report: Vec<_> = ENTRIES.with(|entries| entries.borrow().values().cloned().collect());
+    report.sort_by(|entry1, entry2| entry2.max.cmp(&entry1.max));
+    report
+}
+
+/// Log the `count` slowest handlers and those that exceeded the frame budget.
+pub fn log_profile_report(count: usize) {
+    for (index, entry) in profile_report().iter().enumerate() {
+        if index < count || entry.over_budget > 0 {
+            log::info!("{}::{}: {} calls, max {:?}, average {:?}, {} over budget", entry.component, entry.message,
+                entry.count, entry.max, entry.average(), entry.over_budget);
+        }
+    }
+}
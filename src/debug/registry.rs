@@ -0,0 +1,127 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Registry of the live components, used by the debugging tools.
+//! Components are only registered in debug builds.
+
+use std::any::{Any, type_name};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use glib::{Cast, ObjectExt, WeakRef};
+
+use crate::{DisplayVariant, EventStream, StreamHandle, Widget};
+
+// Minimum number of entries before the dropped components are forgotten.
+const MIN_PRUNE_LEN: usize = 64;
+
+pub struct ComponentEntry {
+    pub id: u64,
+    pub last_message: Rc<Cell<&'static str>>,
+    pub name: &'static str,
+    // Start tracking the last message of the component, until the returned guard is dropped.
+    // No observer is added at registration, so that the streams of the components stay on the
+    // fast path when no debugging tool is used.
+    pub observe: Box<dyn Fn() -> Option<Box<dyn Any>>>,
+    pub pending_messages: Box<dyn Fn() -> Option<usize>>,
+    pub root: WeakRef<gtk::Widget>,
+}
+
+impl ComponentEntry {
+    pub fn is_alive(&self) -> bool {
+        (self.pending_messages)().is_some()
+    }
+}
+
+struct Registry {
+    components: Vec<ComponentEntry>,
+    next_id: u64,
+    prune_len: usize,
+}
+
+impl Registry {
+    fn prune(&mut self) {
+        self.components.retain(ComponentEntry::is_alive);
+        self.prune_len = MIN_PRUNE_LEN.max(self.components.len() * 2);
+    }
+}
+
+thread_local! {
+    static REGISTRY: RefCell<Registry> = RefCell::new(Registry {
+        components: vec![],
+        next_id: 0,
+        prune_len: MIN_PRUNE_LEN,
+    });
+}
+
+/// Register a newly created widget.
+pub fn register<WIDGET>(stream: &EventStream<WIDGET::Msg>, root: &WIDGET::Root)
+    where WIDGET: Widget,
+          WIDGET::Msg: DisplayVariant + 'static,
+{
+    if !cfg!(debug_assertions) {
+        return;
+    }
+    let last_message = Rc::new(Cell::new(""));
+    let handle: StreamHandle<WIDGET::Msg> = stream.downgrade();
+    let observe = {
+        let handle = handle.clone();
+        let last_message = last_message.clone();
+        move || {
+            if handle.is_dropped() {
+                return None;
+            }
+            let last_message = last_message.clone();
+            let guard = handle.observe_scoped(move |msg: &WIDGET::Msg| {
+                last_message.set(msg.display_variant());
+            });
+            Some(Box::new(guard) as Box<dyn Any>)
+        }
+    };
+    let root: gtk::Widget = root.clone().upcast();
+    REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        // Only forget the components that were dropped once the registry doubled in size, so
+        // that registering stays cheap when many components are created.
+        if registry.components.len() >= registry.prune_len {
+            registry.prune();
+        }
+        let id = registry.next_id;
+        registry.next_id += 1;
+        registry.components.push(ComponentEntry {
+            id,
+            last_message,
+            name: type_name::<WIDGET>(),
+            observe: Box::new(observe),
+            pending_messages: Box::new(move || handle.pending_messages()),
+            root: root.downgrade(),
+        });
+    });
+}
+
+/// Call `callback` with the entries of the live components.
+pub fn with_components<F: FnOnce(&[ComponentEntry]) -> T, T>(callback: F) -> T {
+    REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        registry.prune();
+        callback(&registry.components)
+    })
+}
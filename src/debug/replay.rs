@@ -0,0 +1,214 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Record the messages of a session to a file and replay them later, for instance in a test.
+//! The messages must implement `serde::Serialize` and `serde::Deserialize`.
+//!
+//! To keep the recorded sessions loadable after the message enum changes, give it a version
+//! with `#[msg(version = 2, migrate = "migrate_msg")]` next to `#[derive(Msg)]` and record it
+//! with `record_versioned()`: the messages recorded with an older version are passed to the
+//! migration function, taking the recorded version and the serialized message, before being
+//! deserialized.
+
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, ErrorKind};
+use std::path::Path;
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::core::{ObserverGuard, StreamHandle};
+use crate::pump_messages;
+
+/// A serializable type whose format is versioned, so that values serialized with an older
+/// version can be migrated to the current format.
+/// This is implemented by `#[derive(Msg)]` for the messages having a `#[msg(version = N)]`
+/// attribute.
+pub trait Versioned {
+    /// The current version of the format.
+    const VERSION: u32;
+
+    /// Convert `value`, serialized with the older `version`, to the current format.
+    fn migrate(version: u32, value: Value) -> Result<Value, String> {
+        let _ = value;
+        Err(format!("no migration from version {} to version {}", version, Self::VERSION))
+    }
+}
+
+/// Deserialize `value`, serialized with `version`, migrating it first when the version is older
+/// than the current one.
+pub fn deserialize_versioned<T: DeserializeOwned + Versioned>(version: u32, value: Value) -> io::Result<T> {
+    let value =
+        if version < T::VERSION {
+            T::migrate(version, value)
+                .map_err(|error| io::Error::new(ErrorKind::InvalidData, error))?
+        }
+        else if version > T::VERSION {
+            return Err(io::Error::new(ErrorKind::InvalidData,
+                format!("version {} is newer than the supported version {}", version, T::VERSION)));
+        }
+        else {
+            value
+        };
+    T::deserialize(value)
+        .map_err(|error| io::Error::new(ErrorKind::InvalidData, error))
+}
+
+/// Messages recorded during a session.
+#[derive(Default, Deserialize, Serialize)]
+pub struct Session {
+    messages: Vec<Value>,
+    // The sessions saved before the versioning of the messages are at version 0.
+    #[serde(default)]
+    version: u32,
+}
+
+impl Session {
+    /// Load a session from a file created by `Recorder::save()`.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        serde_json::from_reader(BufReader::new(file))
+            .map_err(|error| io::Error::new(ErrorKind::InvalidData, error))
+    }
+
+    /// Get the number of messages in the session.
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Check whether the session contains no message.
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Deserialize the messages of the session.
+    pub fn messages<MSG: DeserializeOwned>(&self) -> io::Result<Vec<MSG>> {
+        self.messages.iter()
+            .map(|value| MSG::deserialize(value)
+                .map_err(|error| io::Error::new(ErrorKind::InvalidData, error)))
+            .collect()
+    }
+
+    /// Deserialize the messages of the session, migrating them when they were recorded with an
+    /// older version of the message enum.
+    pub fn migrated_messages<MSG: DeserializeOwned + Versioned>(&self) -> io::Result<Vec<MSG>> {
+        self.messages.iter()
+            .map(|value| deserialize_versioned(self.version, value.clone()))
+            .collect()
+    }
+
+    /// Get the version of the messages, as recorded by `record_versioned()`.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Save the session to a file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let file = File::create(path)?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)
+            .map_err(|error| io::Error::new(ErrorKind::InvalidData, error))
+    }
+}
+
+/// Recorder of the messages emitted on a stream.
+/// The recording stops when the recorder is dropped.
+pub struct Recorder<MSG> {
+    session: Rc<RefCell<Session>>,
+    _guard: ObserverGuard<MSG>,
+}
+
+impl<MSG> Recorder<MSG> {
+    /// Get a copy of the messages recorded so far.
+    pub fn session(&self) -> Session {
+        let session = self.session.borrow();
+        Session {
+            messages: session.messages.clone(),
+            version: session.version,
+        }
+    }
+
+    /// Save the messages recorded so far to a file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.session.borrow().save(path)
+    }
+}
+
+/// Start recording the messages emitted on `stream`.
+///
+/// Only the messages coming from outside of the component are recorded: the ones emitted by its
+/// `update()` method are emitted again when the session is replayed.
+pub fn record<MSG: Serialize + 'static>(stream: &StreamHandle<MSG>) -> Recorder<MSG> {
+    record_with_version(stream, 0)
+}
+
+/// Start recording the messages emitted on `stream`, with the version of the message enum, so
+/// that the session can be replayed with `replay_versioned()` after the enum changes.
+pub fn record_versioned<MSG: Serialize + Versioned + 'static>(stream: &StreamHandle<MSG>) -> Recorder<MSG> {
+    record_with_version(stream, MSG::VERSION)
+}
+
+fn record_with_version<MSG: Serialize + 'static>(stream: &StreamHandle<MSG>, version: u32) -> Recorder<MSG> {
+    let session = Rc::new(RefCell::new(Session {
+        messages: vec![],
+        version,
+    }));
+    let recorded_session = session.clone();
+    let recorded_stream = stream.clone();
+    let guard = stream.observe_scoped(move |msg| {
+        if recorded_stream.is_updating() {
+            return;
+        }
+        match serde_json::to_value(msg) {
+            Ok(value) => recorded_session.borrow_mut().messages.push(value),
+            Err(error) => log::error!("Cannot record message: {}", error),
+        }
+    });
+    Recorder {
+        session,
+        _guard: guard,
+    }
+}
+
+/// Emit the messages of `session` on `stream` and process them, so that the state of the
+/// component can be asserted right after this call.
+pub fn replay<MSG: DeserializeOwned>(stream: &StreamHandle<MSG>, session: &Session) -> io::Result<()> {
+    emit_and_process(stream, session.messages()?);
+    Ok(())
+}
+
+/// Same as [`replay()`](fn.replay.html), but migrate the messages recorded with an older version
+/// of the message enum.
+pub fn replay_versioned<MSG>(stream: &StreamHandle<MSG>, session: &Session) -> io::Result<()>
+    where MSG: DeserializeOwned + Versioned,
+{
+    emit_and_process(stream, session.migrated_messages()?);
+    Ok(())
+}
+
+fn emit_and_process<MSG>(stream: &StreamHandle<MSG>, messages: Vec<MSG>) {
+    for msg in messages {
+        stream.emit(msg);
+    }
+    pump_messages(stream);
+}
@@ -0,0 +1,63 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Watchdog warning when the `update()` method of a component blocks the main loop for too long.
+
+use std::any::type_name;
+use std::cell::Cell;
+use std::time::Duration;
+
+thread_local! {
+    static THRESHOLD: Cell<Option<Duration>> = Cell::new(
+        if cfg!(debug_assertions) {
+            Some(Duration::from_millis(16))
+        }
+        else {
+            None
+        });
+}
+
+/// Set the duration above which a warning is logged when the `update()` method of a component
+/// blocks the main loop.
+/// `None` disables the watchdog.
+/// The default is 16ms in debug builds and `None` in release builds.
+pub fn set_starvation_threshold(threshold: Option<Duration>) {
+    THRESHOLD.with(|current_threshold| current_threshold.set(threshold));
+}
+
+pub(crate) fn starvation_threshold() -> Option<Duration> {
+    THRESHOLD.with(Cell::get)
+}
+
+pub(crate) fn check_starvation<COMPONENT>(message: &'static str, duration: Duration, threshold: Duration) {
+    if duration >= threshold {
+        // Truncate on a character boundary, since a variant name can contain non-ASCII characters.
+        let message =
+            match message.char_indices().nth(100) {
+                Some((index, _)) => format!("{}…", &message[..index]),
+                None => message.to_string(),
+            };
+        let ms = duration.subsec_nanos() as u64 / 1_000_000 + duration.as_secs() * 1000;
+        log::warn!("The update function of {} was slow to execute for message {}: {}ms. Consider moving the heavy \
+            work to another thread and send the result back with a relm::Channel.", type_name::<COMPONENT>(), message,
+            ms);
+    }
+}
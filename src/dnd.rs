@@ -0,0 +1,95 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Drag-and-drop sources and targets without the `drag_data_*` plumbing, to call from
+//! `init_view()`.
+//!
+//! The source functions take a closure called when a drag starts, so the data must be readable
+//! outside of `update()`: share it with the model through a `BoundValue` or a `Rc<RefCell<_>>`.
+
+use gdk::{DragAction, ModifierType};
+use glib::IsA;
+use gtk::{DestDefaults, WidgetExt};
+
+use crate::core::StreamHandle;
+
+/// Data dropped on a target.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DropData {
+    /// Text, like a selection dragged from an entry.
+    Text(String),
+    /// URIs, like files dragged from a file manager.
+    Uris(Vec<String>),
+}
+
+/// Make `widget` a drag source providing the text returned by `provider`.
+pub fn source_text<W, F>(widget: &W, provider: F)
+    where W: IsA<gtk::Widget>,
+          F: Fn() -> String + 'static,
+{
+    widget.drag_source_set(ModifierType::BUTTON1_MASK, &[], DragAction::COPY);
+    widget.drag_source_add_text_targets();
+    widget.connect_drag_data_get(move |_, _, selection, _, _| {
+        let text = provider();
+        selection.set_text(&text, text.len() as i32);
+    });
+}
+
+/// Make `widget` a drag source providing the URIs returned by `provider`.
+pub fn source_uris<W, F>(widget: &W, provider: F)
+    where W: IsA<gtk::Widget>,
+          F: Fn() -> Vec<String> + 'static,
+{
+    widget.drag_source_set(ModifierType::BUTTON1_MASK, &[], DragAction::COPY);
+    widget.drag_source_add_uri_targets();
+    widget.connect_drag_data_get(move |_, _, selection, _, _| {
+        let uris = provider();
+        let uris: Vec<&str> = uris.iter().map(String::as_str).collect();
+        selection.set_uris(&uris);
+    });
+}
+
+/// Make `widget` a drop target accepting text and URIs, sending the message built by
+/// `constructor` to `stream` for each drop.
+pub fn target<W, F, MSG>(widget: &W, stream: &StreamHandle<MSG>, constructor: F)
+    where W: IsA<gtk::Widget>,
+          F: Fn(DropData) -> MSG + 'static,
+          MSG: 'static,
+{
+    widget.drag_dest_set(DestDefaults::ALL, &[], DragAction::COPY);
+    widget.drag_dest_add_text_targets();
+    widget.drag_dest_add_uri_targets();
+    let stream = stream.clone();
+    widget.connect_drag_data_received(move |_, _, _, _, selection, _, _| {
+        let uris = selection.get_uris();
+        let data =
+            if !uris.is_empty() {
+                DropData::Uris(uris.iter().map(ToString::to_string).collect())
+            }
+            else if let Some(text) = selection.get_text() {
+                DropData::Text(text.to_string())
+            }
+            else {
+                return;
+            };
+        stream.emit(constructor(data));
+    });
+}
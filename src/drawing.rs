@@ -4,7 +4,7 @@
 
 // TODO: check if clip has the intended behavior.
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::ops::Deref;
 use std::rc::Rc;
 
@@ -13,12 +13,15 @@ use cairo::{
     Context,
     Format,
     ImageSurface,
+    Operator,
 };
 use gtk::{
     Inhibit,
     WidgetExt,
 };
 
+use crate::core::StreamHandle;
+
 #[derive(Clone)]
 struct Surface {
     surface: Rc<RefCell<ImageSurface>>,
@@ -40,22 +43,61 @@ impl Surface {
     }
 }
 
+/// Area of the widget invalidated by a drawing, in widget coordinates.
+#[derive(Clone, Copy)]
+struct Area {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
+
+impl Area {
+    fn union(self, other: Area) -> Area {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        Area {
+            x,
+            y,
+            width: (self.x + self.width).max(other.x + other.width) - x,
+            height: (self.y + self.height).max(other.y + other.height) - y,
+        }
+    }
+}
+
+/// Context to draw on the back buffer of a [`DrawHandler`](struct.DrawHandler.html).
+///
+/// The back buffer starts with the content currently shown, and is shown when the context is
+/// dropped.
 pub struct DrawContext<W: WidgetExt> {
+    back_surface: Surface,
     context: Context,
-    draw_surface: Surface,
+    dirty_area: Option<Area>,
     edit_surface: ImageSurface,
+    front_surface: Surface,
     widget: W,
 }
 
 impl<W: Clone + WidgetExt> DrawContext<W> {
-    fn new(draw_surface: &Surface, edit_surface: &ImageSurface, widget: &W) -> Self {
+    fn new(front_surface: &Surface, back_surface: &Surface, widget: &W) -> Self {
+        let edit_surface = back_surface.get();
         Self {
+            back_surface: back_surface.clone(),
             context: Context::new(&edit_surface),
-            draw_surface: draw_surface.clone(),
-            edit_surface: edit_surface.clone(),
+            dirty_area: None,
+            edit_surface,
+            front_surface: front_surface.clone(),
             widget: widget.clone(),
         }
     }
+
+    /// Only redraw this area of the widget when the context is dropped, instead of the whole
+    /// widget.
+    /// When called many times, the union of the areas is redrawn.
+    pub fn invalidate_area(&mut self, x: i32, y: i32, width: i32, height: i32) {
+        let area = Area { x, y, width, height };
+        self.dirty_area = Some(self.dirty_area.map_or(area, |dirty_area| dirty_area.union(area)));
+    }
 }
 
 impl<W: WidgetExt> Deref for DrawContext<W> {
@@ -68,15 +110,26 @@ impl<W: WidgetExt> Deref for DrawContext<W> {
 
 impl<W: WidgetExt> Drop for DrawContext<W> {
     fn drop(&mut self) {
-        self.draw_surface.set(&self.edit_surface);
-        self.widget.queue_draw();
+        // Swap the buffers so that the next drawing does not write on the shown surface.
+        let front = self.front_surface.get();
+        self.front_surface.set(&self.edit_surface);
+        self.back_surface.set(&front);
+        match self.dirty_area {
+            Some(Area { x, y, width, height }) => self.widget.queue_draw_area(x, y, width, height),
+            None => self.widget.queue_draw(),
+        }
     }
 }
 
 /// Manager for drawing operations.
+///
+/// The drawing is double-buffered: the drawing is done on a back buffer which replaces the shown
+/// buffer when the [`DrawContext`](struct.DrawContext.html) is dropped, so that a partially
+/// drawn frame is never shown. The buffers are resized with the widget.
 pub struct DrawHandler<W> {
-    draw_surface: Surface,
-    edit_surface: ImageSurface,
+    back_surface: Surface,
+    front_surface: Surface,
+    needs_redraw: Rc<Cell<bool>>,
     widget: Option<W>,
 }
 
@@ -84,8 +137,9 @@ impl<W: Clone + WidgetExt> DrawHandler<W> {
     /// Create a new DrawHandler.
     pub fn new() -> Result<Self, cairo::Error> {
         Ok(Self {
-            draw_surface: Surface::new(ImageSurface::create(Format::ARgb32, 100, 100)?),
-            edit_surface: ImageSurface::create(Format::ARgb32, 100, 100)?,
+            back_surface: Surface::new(ImageSurface::create(Format::ARgb32, 100, 100)?),
+            front_surface: Surface::new(ImageSurface::create(Format::ARgb32, 100, 100)?),
+            needs_redraw: Rc::new(Cell::new(true)),
             widget: None,
         })
     }
@@ -101,20 +155,25 @@ impl<W: Clone + WidgetExt> DrawHandler<W> {
             };
             let width = allocation.width * scale;
             let height = allocation.height * scale;
-            if (width, height) != (self.edit_surface.get_width(), self.edit_surface.get_height()) {
-                // TODO: also copy the old small surface to the new bigger one?
+            let back = self.back_surface.get();
+            if (width, height) != (back.get_width(), back.get_height()) {
                 match ImageSurface::create(Format::ARgb32, width, height) {
                     Ok(surface) => {
                         {
                             #[cfg(feature = "hidpi")]
                             surface.set_device_scale(scale as f64, scale as f64);
                         }
-                        self.edit_surface = surface
+                        self.back_surface.set(&surface);
                     }
                     Err(error) => eprintln!("Cannot resize image surface: {:?}", error),
                 }
             }
-            DrawContext::new(&self.draw_surface, &self.edit_surface, widget)
+            // Start from the content currently shown to allow drawing only what changed.
+            let context = Context::new(&self.back_surface.get());
+            context.set_operator(Operator::Source);
+            context.set_source_surface(&self.front_surface.get(), 0.0, 0.0);
+            context.paint();
+            DrawContext::new(&self.front_surface, &self.back_surface, widget)
         }
         else {
             panic!("Call DrawHandler::init() before DrawHandler::get_context().");
@@ -126,12 +185,46 @@ impl<W: Clone + WidgetExt> DrawHandler<W> {
     pub fn init(&mut self, widget: &W) {
         widget.set_app_paintable(true);
         self.widget = Some(widget.clone());
-        let draw_surface = self.draw_surface.clone();
+        let front_surface = self.front_surface.clone();
         widget.connect_draw(move |_, context| {
-            // TODO: only copy the area that was exposed?
-            context.set_source_surface(&draw_surface.get(), 0.0, 0.0);
+            // Only the exposed area is painted since the context is clipped to it.
+            context.set_source_surface(&front_surface.get(), 0.0, 0.0);
             context.paint();
             Inhibit(false)
         });
+        let needs_redraw = self.needs_redraw.clone();
+        widget.connect_size_allocate(move |_, _| {
+            needs_redraw.set(true);
+        });
+    }
+
+    /// Request a redraw at the next frame, which sends the message given to
+    /// [`connect_redraw()`](#method.connect_redraw).
+    pub fn queue_redraw(&self) {
+        self.needs_redraw.set(true);
+    }
+
+    /// Send the message created by `constructor` to `stream` at most once per frame, when a redraw
+    /// was requested with [`queue_redraw()`](#method.queue_redraw) or the widget was resized.
+    /// Draw in the handler of this message to draw at the pace of the display.
+    ///
+    /// Call this method after [`init()`](#method.init).
+    pub fn connect_redraw<F, MSG>(&self, stream: &StreamHandle<MSG>, constructor: F)
+        where F: Fn() -> MSG + 'static,
+              MSG: 'static,
+    {
+        if let Some(ref widget) = self.widget {
+            let needs_redraw = self.needs_redraw.clone();
+            let stream = stream.clone();
+            widget.add_tick_callback(move |_, _| {
+                if needs_redraw.replace(false) {
+                    stream.emit(constructor());
+                }
+                glib::Continue(true)
+            });
+        }
+        else {
+            panic!("Call DrawHandler::init() before DrawHandler::connect_redraw().");
+        }
     }
 }
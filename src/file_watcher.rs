@@ -0,0 +1,128 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Subscriptions sending a message when a watched file or directory changes.
+
+use std::path::{Path, PathBuf};
+
+use gio::{Cancellable, FileExt, FileMonitor, FileMonitorEvent, FileMonitorExt, FileMonitorFlags};
+use glib::{Error, ObjectExt, SignalHandlerId};
+
+use crate::core::StreamHandle;
+
+/// The kind of change reported by a [`FileWatcher`](struct.FileWatcher.html).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FileChange {
+    /// The metadata (permissions, modification time, …) changed.
+    AttributesChanged,
+    /// The file was created or moved into the watched directory.
+    Created,
+    /// The file was deleted or moved out of the watched directory.
+    Deleted,
+    /// The content of the file changed.
+    Modified,
+    /// The file was renamed to the specified path.
+    Renamed(PathBuf),
+}
+
+impl FileChange {
+    fn from_event(event: FileMonitorEvent, other_path: Option<PathBuf>) -> Option<Self> {
+        let change =
+            match event {
+                FileMonitorEvent::AttributeChanged => FileChange::AttributesChanged,
+                FileMonitorEvent::Changed => FileChange::Modified,
+                FileMonitorEvent::Created | FileMonitorEvent::MovedIn => FileChange::Created,
+                FileMonitorEvent::Deleted | FileMonitorEvent::MovedOut => FileChange::Deleted,
+                FileMonitorEvent::Renamed | FileMonitorEvent::Moved => FileChange::Renamed(other_path?),
+                _ => return None,
+            };
+        Some(change)
+    }
+}
+
+/// Subscription watching files and directories with `gio::FileMonitor`.
+///
+/// Create it in `subscriptions()` and store it in the model: the paths are not watched anymore
+/// when it is dropped.
+/// When a directory is watched, the changes of its direct children are reported with the path of
+/// the child.
+pub struct FileWatcher {
+    monitors: Vec<(FileMonitor, SignalHandlerId)>,
+}
+
+impl FileWatcher {
+    /// Watch `paths` and send the message built by `constructor` from the changed path and the
+    /// kind of change to `stream`.
+    /// The paths do not need to exist: a `Created` change is sent when they are created.
+    pub fn new<F, MSG, P>(stream: &StreamHandle<MSG>, paths: &[P], constructor: F) -> Result<Self, Error>
+        where F: Fn(PathBuf, FileChange) -> MSG + Clone + 'static,
+              MSG: 'static,
+              P: AsRef<Path>,
+    {
+        let mut watcher = FileWatcher {
+            monitors: vec![],
+        };
+        for path in paths {
+            watcher.watch(stream, path.as_ref(), constructor.clone())?;
+        }
+        Ok(watcher)
+    }
+
+    /// Also watch `path`.
+    pub fn watch<F, MSG>(&mut self, stream: &StreamHandle<MSG>, path: &Path, constructor: F) -> Result<(), Error>
+        where F: Fn(PathBuf, FileChange) -> MSG + 'static,
+              MSG: 'static,
+    {
+        let file = gio::File::new_for_path(path);
+        let monitor = file.monitor(FileMonitorFlags::WATCH_MOVES, None::<&Cancellable>)?;
+        let stream = stream.clone();
+        let handler = monitor.connect_changed(move |_, file, other_file, event| {
+            let path =
+                match file.get_path() {
+                    Some(path) => path,
+                    None => return,
+                };
+            let other_path = other_file.and_then(|file| file.get_path());
+            if let Some(change) = FileChange::from_event(event, other_path) {
+                stream.emit(constructor(path, change));
+            }
+        });
+        self.monitors.push((monitor, handler));
+        Ok(())
+    }
+
+    /// Set the minimum number of milliseconds between two `Modified` changes of the same file.
+    /// The default is 800.
+    pub fn set_rate_limit(&self, limit: i32) {
+        for (monitor, _) in &self.monitors {
+            monitor.set_rate_limit(limit);
+        }
+    }
+}
+
+impl Drop for FileWatcher {
+    fn drop(&mut self) {
+        for (monitor, handler) in self.monitors.drain(..) {
+            monitor.disconnect(handler);
+            monitor.cancel();
+        }
+    }
+}
@@ -0,0 +1,108 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Keyboard focus driven by the model.
+
+use glib::{Cast, IsA, ObjectExt, SignalHandlerId};
+use gtk::{ContainerExt, GtkWindowExt, WidgetExt};
+
+use crate::core::StreamHandle;
+use crate::state::{Relm, Update};
+
+// Find the widget named `name` in the tree of `root`, depth first.
+fn find_named(root: &gtk::Widget, name: &str) -> Option<gtk::Widget> {
+    let mut stack = vec![root.clone()];
+    while let Some(widget) = stack.pop() {
+        if widget.get_widget_name().as_str() == name {
+            return Some(widget);
+        }
+        if let Some(container) = widget.dynamic_cast_ref::<gtk::Container>() {
+            stack.extend(container.get_children().into_iter().rev());
+        }
+    }
+    None
+}
+
+impl<UPDATE: Update> Relm<UPDATE> {
+    /// Give the keyboard focus to the widget whose name is `widget_name`, as set with the
+    /// `widget_name` property in the `view!` macro:
+    ///
+    /// ```ignore
+    /// InvalidEmail => self.model.relm.grab_focus("email"),
+    /// ...
+    /// gtk::Entry {
+    ///     widget_name: "email",
+    /// },
+    /// ```
+    ///
+    /// The widget is searched in the active window first, then in the other toplevel windows.
+    /// Return `false` if no such widget was found.
+    pub fn grab_focus(&self, widget_name: &str) -> bool {
+        let mut windows = gtk::Window::list_toplevels();
+        // Search the active window first.
+        windows.sort_by_key(|window| {
+            !window.downcast_ref::<gtk::Window>().map_or(false, GtkWindowExt::is_active)
+        });
+        let widget = windows.iter().find_map(|window| find_named(window, widget_name));
+        match widget {
+            Some(widget) => {
+                widget.grab_focus();
+                true
+            },
+            None => false,
+        }
+    }
+}
+
+/// Subscription sending a message when the keyboard focus moves to another widget of a window.
+///
+/// The subscription is removed when this value is dropped, so it should be stored in the model.
+pub struct FocusTracker {
+    handler: Option<SignalHandlerId>,
+    window: gtk::Window,
+}
+
+impl FocusTracker {
+    /// Send the message created by `constructor` with the newly focused widget, or `None` when no
+    /// widget has the focus anymore, to `stream` whenever the focus changes in `window`.
+    pub fn new<W, F, MSG>(window: &W, stream: &StreamHandle<MSG>, constructor: F) -> Self
+        where W: IsA<gtk::Window>,
+              F: Fn(Option<gtk::Widget>) -> MSG + 'static,
+              MSG: 'static,
+    {
+        let stream = stream.clone();
+        let handler = window.connect_set_focus(move |_, widget| {
+            stream.emit(constructor(widget.cloned()));
+        });
+        FocusTracker {
+            handler: Some(handler),
+            window: window.clone().upcast(),
+        }
+    }
+}
+
+impl Drop for FocusTracker {
+    fn drop(&mut self) {
+        if let Some(handler) = self.handler.take() {
+            self.window.disconnect(handler);
+        }
+    }
+}
@@ -0,0 +1,149 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Coalesce the view refreshes of a component to at most once per frame.
+
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::rc::{Rc, Weak};
+
+use glib::Continue;
+use gtk::WidgetExt;
+
+use crate::core::{EventStream, StreamHandle};
+use crate::state::{Relm, update_component_batch};
+use crate::widget::Widget;
+
+struct Batch<WIDGET: Widget> {
+    component: RefCell<WIDGET>,
+    idle_scheduled: Cell<bool>,
+    pending: RefCell<VecDeque<WIDGET::Msg>>,
+    stream: StreamHandle<WIDGET::Msg>,
+    tick_scheduled: Cell<bool>,
+}
+
+impl<WIDGET: Widget + 'static> Batch<WIDGET> {
+    /// Handle the pending messages, calling `after_update()` only once.
+    /// Return false when the component is already being updated in a nested main loop.
+    fn flush(&self) -> bool {
+        match self.component.try_borrow_mut() {
+            Ok(mut component) => {
+                // Mark the stream as updating so that the messages emitted by update() are known
+                // to come from the component.
+                let was_updating = self.stream.set_updating(true);
+                // Do not keep the queue borrowed while updating since update() might emit new
+                // messages.
+                update_component_batch(&mut *component, || self.pending.borrow_mut().pop_front());
+                self.stream.set_updating(was_updating);
+                true
+            },
+            Err(_) => false,
+        }
+    }
+
+    /// Handle the pending messages when the main loop is idle, since the frame clock does not tick
+    /// for an unrealized widget.
+    fn flush_when_idle(batch: &Rc<Self>) {
+        if batch.idle_scheduled.get() {
+            return;
+        }
+        batch.idle_scheduled.set(true);
+        let batch = Rc::downgrade(batch);
+        let _ = glib::idle_add_local(move || {
+            match batch.upgrade() {
+                Some(batch) =>
+                    if batch.flush() {
+                        batch.idle_scheduled.set(false);
+                        Continue(false)
+                    }
+                    else {
+                        Continue(true)
+                    },
+                None => Continue(false),
+            }
+        });
+    }
+
+    fn flush_on_next_frame(batch: &Rc<Self>, root: &WIDGET::Root) {
+        if batch.tick_scheduled.get() {
+            return;
+        }
+        batch.tick_scheduled.set(true);
+        // Only keep a weak reference to avoid a cycle between the root widget and the component.
+        let batch = Rc::downgrade(batch);
+        let _ = root.add_tick_callback(move |_, _| {
+            match batch.upgrade() {
+                Some(batch) =>
+                    if batch.flush() {
+                        batch.tick_scheduled.set(false);
+                        Continue(false)
+                    }
+                    else {
+                        // The component is being updated in a nested main loop: try again on the
+                        // next frame.
+                        Continue(true)
+                    },
+                None => Continue(false),
+            }
+        });
+    }
+}
+
+/// Initialize a component whose messages are buffered and handled right before the next frame of
+/// its root widget is drawn, so that a burst of messages only refreshes the view once.
+pub(crate) fn init_frame_synchronized<WIDGET>(stream: &EventStream<WIDGET::Msg>, mut component: WIDGET,
+    relm: &Relm<WIDGET>)
+    where WIDGET: Widget + 'static,
+{
+    component.subscriptions(relm);
+    let root = component.root();
+    let batch = Rc::new(Batch {
+        component: RefCell::new(component),
+        idle_scheduled: Cell::new(false),
+        pending: RefCell::new(VecDeque::new()),
+        stream: stream.downgrade(),
+        tick_scheduled: Cell::new(false),
+    });
+    {
+        // The messages waiting for a frame that will not come are handled when idle.
+        let batch: Weak<Batch<WIDGET>> = Rc::downgrade(&batch);
+        root.connect_unrealize(move |_| {
+            if let Some(batch) = batch.upgrade() {
+                if !batch.pending.borrow().is_empty() {
+                    Batch::flush_when_idle(&batch);
+                }
+            }
+        });
+    }
+    stream.set_callback(move |event| {
+        batch.pending.borrow_mut().push_back(event);
+        // The frame clock only ticks for realized widgets, so handle the message right away when
+        // the component is not shown.
+        if !root.get_realized() {
+            if !batch.flush() {
+                Batch::flush_when_idle(&batch);
+            }
+        }
+        else {
+            Batch::flush_on_next_frame(&batch, &root);
+        }
+    });
+}
@@ -0,0 +1,244 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! HTTP requests whose responses are delivered as messages.
+//!
+//! The requests are made with `ureq` in a worker thread, so no async runtime is needed:
+//!
+//! ```ignore
+//! FetchUrl => {
+//!     let request = Request::get("https://example.com/api/cats").header("Accept", "application/json");
+//!     self.model.request = Some(self.model.relm.fetch(request, Fetched));
+//! },
+//! Fetched(HttpEvent::Completed(response)) => self.model.text = response.text(),
+//! Fetched(HttpEvent::Failed(error)) => self.model.text = error,
+//! ```
+
+use std::io::Read;
+use std::thread;
+
+use glib::{Continue, MainContext, PRIORITY_DEFAULT};
+
+use crate::cancellation::CancellationToken;
+use crate::core::StreamHandle;
+use crate::progress::Progress;
+use crate::state::{Relm, Update};
+
+const CHUNK_SIZE: usize = 16 * 1024;
+
+/// An HTTP request to send with [`fetch()`](fn.fetch.html).
+#[derive(Clone, Debug)]
+pub struct Request {
+    body: Option<Vec<u8>>,
+    headers: Vec<(String, String)>,
+    method: String,
+    report_progress: bool,
+    url: String,
+}
+
+impl Request {
+    /// Create a request with the HTTP `method`, like `PUT`, to `url`.
+    pub fn new(method: &str, url: &str) -> Self {
+        Request {
+            body: None,
+            headers: vec![],
+            method: method.to_string(),
+            report_progress: false,
+            url: url.to_string(),
+        }
+    }
+
+    /// Create a `GET` request to `url`.
+    pub fn get(url: &str) -> Self {
+        Self::new("GET", url)
+    }
+
+    /// Create a `POST` request to `url` sending `body`.
+    pub fn post(url: &str, body: Vec<u8>) -> Self {
+        Self::new("POST", url).body(body)
+    }
+
+    /// Set the body of the request.
+    pub fn body(mut self, body: Vec<u8>) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    /// Add the header `name`.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Send a [`HttpEvent::Progress`](enum.HttpEvent.html#variant.Progress) while the body of the
+    /// response is downloaded, for large downloads.
+    pub fn report_progress(mut self) -> Self {
+        self.report_progress = true;
+        self
+    }
+
+    fn send(&self) -> Result<ureq::Response, String> {
+        let mut request = ureq::request(&self.method, &self.url);
+        for (name, value) in &self.headers {
+            request = request.set(name, value);
+        }
+        let result =
+            match self.body {
+                Some(ref body) => request.send_bytes(body),
+                None => request.call(),
+            };
+        match result {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => Ok(response),
+            Err(error) => Err(error.to_string()),
+        }
+    }
+}
+
+/// The response to a [`Request`](struct.Request.html).
+///
+/// Responses with an error status, like 404, are responses too: check
+/// [`is_success()`](#method.is_success).
+#[derive(Clone, Debug)]
+pub struct Response {
+    /// The body.
+    pub body: Vec<u8>,
+    /// The headers, in the order they were received.
+    pub headers: Vec<(String, String)>,
+    /// The status code.
+    pub status: u16,
+}
+
+impl Response {
+    /// Get the value of the first header `name`, ignoring the case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Check if the status is 2xx.
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Get the body as text, replacing the invalid UTF-8 sequences.
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+/// Event of a request sent with [`fetch()`](fn.fetch.html).
+#[derive(Clone, Debug)]
+pub enum HttpEvent {
+    /// The response was fully received.
+    Completed(Response),
+    /// The request failed, because of a network or a TLS error for instance.
+    Failed(String),
+    /// Part of the body was received. Only sent if the request was created with
+    /// [`report_progress()`](struct.Request.html#method.report_progress).
+    Progress(Progress),
+}
+
+/// Send `request` in a worker thread and send the events built by `constructor` to `stream`.
+///
+/// The last event is either `Completed` or `Failed`. When the returned token is cancelled, the
+/// download stops and no more events are sent.
+pub fn fetch<F, MSG>(stream: &StreamHandle<MSG>, request: Request, constructor: F) -> CancellationToken
+    where F: Fn(HttpEvent) -> MSG + 'static,
+          MSG: 'static,
+{
+    let token = CancellationToken::new();
+    let (sender, receiver) = MainContext::channel(PRIORITY_DEFAULT);
+    let stream = stream.clone();
+    let receiver_token = token.clone();
+    receiver.attach(None, move |event| {
+        if receiver_token.is_cancelled() || stream.is_dropped() {
+            return Continue(false);
+        }
+        let finished = !matches!(event, HttpEvent::Progress(_));
+        stream.emit(constructor(event));
+        Continue(!finished)
+    });
+    let worker_token = token.clone();
+    thread::spawn(move || {
+        let event =
+            match download(&request, &worker_token, |progress| {
+                let _ = sender.send(HttpEvent::Progress(progress));
+            })
+            {
+                Ok(Some(response)) => HttpEvent::Completed(response),
+                Ok(None) => return,
+                Err(error) => HttpEvent::Failed(error),
+            };
+        let _ = sender.send(event);
+    });
+    token
+}
+
+// Return None when the token is cancelled.
+fn download<F: Fn(Progress)>(request: &Request, token: &CancellationToken, on_progress: F)
+    -> Result<Option<Response>, String>
+{
+    let response = request.send()?;
+    let status = response.status();
+    let headers = response.headers_names().into_iter()
+        .filter_map(|name| {
+            let value = response.header(&name)?.to_string();
+            Some((name, value))
+        })
+        .collect();
+    let total = response.header("Content-Length").and_then(|length| length.parse().ok());
+    let mut reader = response.into_reader();
+    let mut body = vec![];
+    let mut chunk = vec![0; CHUNK_SIZE];
+    loop {
+        if token.is_cancelled() {
+            return Ok(None);
+        }
+        let size = reader.read(&mut chunk).map_err(|error| error.to_string())?;
+        if size == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..size]);
+        if request.report_progress {
+            on_progress(Progress {
+                done: body.len() as u64,
+                label: request.url.clone(),
+                total,
+            });
+        }
+    }
+    Ok(Some(Response {
+        body,
+        headers,
+        status,
+    }))
+}
+
+impl<UPDATE: Update> Relm<UPDATE> {
+    /// Send `request` and send the events built by `constructor` to this component.
+    /// See [`http::fetch()`](http/fn.fetch.html).
+    pub fn fetch<F>(&self, request: Request, constructor: F) -> CancellationToken
+        where F: Fn(HttpEvent) -> UPDATE::Msg + 'static,
+    {
+        fetch(self.stream(), request, constructor)
+    }
+}
@@ -0,0 +1,101 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Translation of the strings of the views with gettext.
+//!
+//! When the `i18n` feature of `relm` is enabled, which also enables it in `relm-derive`, the string
+//! literals given to properties in `view!` are translated with `gettext()` and re-translated by
+//! [`set_language()`](fn.set_language.html).
+//! Wrap a literal in [`untranslated()`](fn.untranslated.html) to opt out of the translation.
+
+use std::cell::RefCell;
+
+use gettextrs::{LocaleCategory, bind_textdomain_codeset, bindtextdomain, gettext, setlocale, textdomain};
+use glib::{IsA, ObjectExt, WeakRef};
+
+thread_local! {
+    static BINDINGS: RefCell<Vec<Box<dyn Fn() -> bool>>> = RefCell::new(vec![]);
+}
+
+/// Initialize gettext with the locale of the environment, using the translations of the text
+/// `domain` found in `directory`.
+pub fn init(domain: &str, directory: &str) {
+    setlocale(LocaleCategory::LcAll, "");
+    bindtextdomain(domain, directory);
+    bind_textdomain_codeset(domain, "UTF-8");
+    textdomain(domain);
+}
+
+/// Switch the language of the translations to `language` (e.g. `fr_CA`) and translate again all
+/// the properties of the living widgets.
+pub fn set_language(language: &str) {
+    std::env::set_var("LANGUAGE", language);
+    // Calling setlocale() invalidates the cached translations.
+    setlocale(LocaleCategory::LcMessages, "");
+    retranslate();
+}
+
+/// Translate again all the properties of the living widgets, for instance after the text domain
+/// was changed.
+pub fn retranslate() {
+    // Take the bindings so that a setter creating a widget can register a new binding.
+    let bindings = BINDINGS.with(|bindings| bindings.replace(vec![]));
+    let mut bindings: Vec<_> = bindings.into_iter()
+        .filter(|binding| binding())
+        .collect();
+    BINDINGS.with(|new_bindings| {
+        bindings.append(&mut new_bindings.borrow_mut());
+        *new_bindings.borrow_mut() = bindings;
+    });
+}
+
+/// Translate `msgid` with gettext.
+pub fn tr(msgid: &str) -> String {
+    gettext(msgid)
+}
+
+/// Mark a string literal in `view!` as not translatable.
+pub fn untranslated(text: &str) -> &str {
+    text
+}
+
+/// Set the translation of `msgid` on `widget` with `setter` and do it again when the language
+/// changes, for as long as the widget lives.
+/// This is used by the code generated by the `#[widget]` attribute.
+#[doc(hidden)]
+pub fn bind<WIDGET, F>(widget: &WIDGET, msgid: &'static str, setter: F)
+    where WIDGET: IsA<glib::Object>,
+          F: Fn(&WIDGET, &str) + 'static,
+{
+    setter(widget, &gettext(msgid));
+    let widget: WeakRef<WIDGET> = widget.downgrade();
+    BINDINGS.with(move |bindings| {
+        bindings.borrow_mut().push(Box::new(move || {
+            match widget.upgrade() {
+                Some(widget) => {
+                    setter(&widget, &gettext(msgid));
+                    true
+                },
+                None => false,
+            }
+        }));
+    });
+}
@@ -0,0 +1,64 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Run closures on the GTK thread from other threads.
+//!
+//! Sending a message through a `Channel` is the usual way to
+//! communicate with widgets from a worker thread, but it is sometimes simpler to run a closure
+//! doing a quick read directly on the GTK thread.
+//! Since widgets cannot be sent to other threads, the closures usually access them through a
+//! `thread_local!` or a `glib::SendWeakRef`.
+
+use std::sync::mpsc;
+
+use glib::MainContext;
+
+/// Run `func` on the GTK thread, i.e. the thread owning the default main context, the next time
+/// the main loop is idle.
+/// When called from the GTK thread itself, `func` is run immediately.
+pub fn invoke<F: FnOnce() + Send + 'static>(func: F) {
+    MainContext::default().invoke(func);
+}
+
+/// Run `func` on the GTK thread and block until it returns its result.
+/// When called from the GTK thread itself, `func` is run immediately, so this never deadlocks
+/// by waiting for its own thread.
+///
+/// ## Panics
+///
+/// Panics if `func` panics or if the main loop stops before running `func`.
+/// Note that this blocks forever if the main loop is not running on the GTK thread.
+pub fn invoke_and_wait<F, T>(func: F) -> T
+    where F: FnOnce() -> T + Send + 'static,
+          T: Send + 'static,
+{
+    let context = MainContext::default();
+    if context.is_owner() || gtk::is_initialized_main_thread() {
+        return func();
+    }
+    let (sender, receiver) = mpsc::sync_channel(1);
+    context.invoke(move || {
+        // Ignore error since the waiting thread cannot go away.
+        let _ = sender.send(func());
+    });
+    receiver.recv()
+        .expect("invoke_and_wait(): the closure was dropped without being run on the GTK thread")
+}
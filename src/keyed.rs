@@ -0,0 +1,506 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::rc::Rc;
+
+use gdk::{DragAction, ModifierType};
+use gdk::keys::constants as key;
+use glib::{Cast, IsA, Object, StaticType};
+use gtk::{
+    AdjustmentExt,
+    BinExt,
+    BoxExt,
+    ContainerExt,
+    DestDefaults,
+    Inhibit,
+    OrientableExt,
+    Orientation,
+    ScrolledWindowExt,
+    StateFlags,
+    TargetEntry,
+    TargetFlags,
+    WidgetExt,
+};
+
+use crate::component::Component;
+use crate::container::ContainerWidget;
+use crate::core::StreamHandle;
+use crate::state::DisplayVariant;
+use crate::widget::Widget;
+
+/// Number of operations applied to the container by
+/// [`KeyedChildren::update()`](struct.KeyedChildren.html#method.update).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ChildrenDiff {
+    /// Number of components created.
+    pub inserted: usize,
+    /// Number of components moved to another position.
+    pub moved: usize,
+    /// Number of components removed.
+    pub removed: usize,
+    /// Number of messages sent to the kept components.
+    pub updated: usize,
+}
+
+/// Event of the keyboard navigation enabled with
+/// [`KeyedChildren::enable_keyboard_navigation()`](struct.KeyedChildren.html#method.enable_keyboard_navigation).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ListNavigation {
+    /// Enter was pressed on the selected child, at this index.
+    Activated(usize),
+    /// The child at this index was selected with the keyboard.
+    SelectionMoved(usize),
+}
+
+/// Child components of a `gtk::Box` bound to a collection, where each item is identified by a key.
+///
+/// When the collection changes, only the components of the items that were inserted, removed or
+/// moved are touched, instead of rebuilding all the children.
+/// The box must not contain other children.
+pub struct KeyedChildren<KEY, WIDGET: Widget> {
+    children: Vec<(KEY, Component<WIDGET>)>,
+    container: gtk::Box,
+    // The child being dragged, when reordering is enabled.
+    dragged: Option<Rc<RefCell<Option<gtk::Widget>>>>,
+    selected: Rc<Cell<Option<usize>>>,
+}
+
+const REORDER_TARGET: &str = "relm-keyed-child";
+
+fn reorder_targets() -> [TargetEntry; 1] {
+    [TargetEntry::new(REORDER_TARGET, TargetFlags::SAME_WIDGET, 0)]
+}
+
+fn make_draggable(widget: &gtk::Widget, dragged: &Rc<RefCell<Option<gtk::Widget>>>) {
+    widget.drag_source_set(ModifierType::BUTTON1_MASK, &reorder_targets(), DragAction::MOVE);
+    let dragged = dragged.clone();
+    widget.connect_drag_begin(move |widget, _| {
+        *dragged.borrow_mut() = Some(widget.clone());
+    });
+    widget.connect_drag_data_get(|_, _, selection, _, _| {
+        // The dragged child is shared with the target, so the data is not used.
+        selection.set(&selection.get_target(), 8, &[]);
+    });
+}
+
+// Get the visible children, with their index among all the children, which is their index in the
+// model.
+fn visible_children(container: &gtk::Box) -> Vec<(usize, gtk::Widget)> {
+    container.get_children().into_iter()
+        .enumerate()
+        .filter(|(_, child)| child.get_visible())
+        .collect()
+}
+
+fn scrolled_window(container: &gtk::Box) -> Option<gtk::ScrolledWindow> {
+    container.get_ancestor(gtk::ScrolledWindow::static_type())
+        .and_then(|widget| widget.downcast().ok())
+}
+
+// Highlight the child at `index`, unhighlighting the previous one, and scroll to it.
+fn select_child(container: &gtk::Box, previous: Option<usize>, index: Option<usize>) {
+    let children = container.get_children();
+    if let Some(child) = previous.and_then(|previous| children.get(previous)) {
+        child.unset_state_flags(StateFlags::SELECTED);
+    }
+    let child =
+        match index.and_then(|index| children.get(index)) {
+            Some(child) => child,
+            None => return,
+        };
+    child.set_state_flags(StateFlags::SELECTED, false);
+    let scrolled_window =
+        match scrolled_window(container) {
+            Some(scrolled_window) => scrolled_window,
+            None => return,
+        };
+    let vertical = container.get_orientation() == Orientation::Vertical;
+    let adjustment =
+        if vertical {
+            scrolled_window.get_vadjustment()
+        }
+        else {
+            scrolled_window.get_hadjustment()
+        };
+    let viewport = scrolled_window.get_child();
+    if let (Some(adjustment), Some(viewport)) = (adjustment, viewport) {
+        // The coordinates in the viewport are relative to the visible part.
+        if let Some((x, y)) = child.translate_coordinates(&viewport, 0, 0) {
+            let allocation = child.get_allocation();
+            let (start, size) = if vertical { (y, allocation.height) } else { (x, allocation.width) };
+            let start = adjustment.get_value() + start as f64;
+            adjustment.clamp_page(start, start + size as f64);
+        }
+    }
+}
+
+// Get the number of children visible in the scrolled window, to move by a page.
+fn page_length(container: &gtk::Box, children: &[(usize, gtk::Widget)]) -> usize {
+    const DEFAULT_PAGE_LENGTH: usize = 10;
+    let vertical = container.get_orientation() == Orientation::Vertical;
+    let adjustment = scrolled_window(container)
+        .and_then(|scrolled_window|
+            if vertical {
+                scrolled_window.get_vadjustment()
+            }
+            else {
+                scrolled_window.get_hadjustment()
+            });
+    let child_size = children.first().map_or(0, |(_, child)| {
+        let allocation = child.get_allocation();
+        if vertical { allocation.height } else { allocation.width }
+    });
+    match adjustment {
+        Some(adjustment) if child_size > 0 => ((adjustment.get_page_size() / child_size as f64) as usize).max(1),
+        _ => DEFAULT_PAGE_LENGTH,
+    }
+}
+
+// Get the index, among all the children other than `dragged`, where a child dropped at `position`,
+// in the coordinates of the container, goes: it is inserted before the first visible child after
+// the drop position, or after the last visible child.
+fn drop_index(container: &gtk::Box, dragged: &gtk::Widget, position: i32) -> usize {
+    let origin = container.get_allocation();
+    let vertical = container.get_orientation() == Orientation::Vertical;
+    let others: Vec<_> = container.get_children().into_iter()
+        .filter(|child| child != dragged)
+        .collect();
+    let visible_before = others.iter()
+        .filter(|child| child.get_visible())
+        .filter(|child| {
+            let allocation = child.get_allocation();
+            let middle =
+                if vertical {
+                    allocation.y - origin.y + allocation.height / 2
+                }
+                else {
+                    allocation.x - origin.x + allocation.width / 2
+                };
+            middle < position
+        })
+        .count();
+    let mut visible = others.iter()
+        .enumerate()
+        .filter(|(_, child)| child.get_visible())
+        .map(|(index, _)| index);
+    match visible.nth(visible_before) {
+        Some(index) => index,
+        None => others.iter().rposition(WidgetExt::get_visible).map_or(0, |index| index + 1),
+    }
+}
+
+// Get, for each value, whether it is part of a longest strictly increasing subsequence of `values`,
+// in O(n log n).
+fn longest_increasing_subsequence(values: &[usize]) -> Vec<bool> {
+    // The index of the last value of the best subsequence of each length.
+    let mut tails: Vec<usize> = vec![];
+    let mut previous = vec![None; values.len()];
+    for (index, &value) in values.iter().enumerate() {
+        let length = tails.binary_search_by(|&tail| values[tail].cmp(&value))
+            .unwrap_or_else(|length| length);
+        if length > 0 {
+            previous[index] = Some(tails[length - 1]);
+        }
+        if length == tails.len() {
+            tails.push(index);
+        }
+        else {
+            tails[length] = index;
+        }
+    }
+    let mut in_subsequence = vec![false; values.len()];
+    let mut current = tails.last().copied();
+    while let Some(index) = current {
+        in_subsequence[index] = true;
+        current = previous[index];
+    }
+    in_subsequence
+}
+
+impl<KEY, WIDGET> KeyedChildren<KEY, WIDGET>
+    where KEY: Clone + Eq + Hash,
+          WIDGET: Widget + 'static,
+          WIDGET::Msg: DisplayVariant + 'static,
+          WIDGET::Root: IsA<gtk::Widget> + IsA<Object> + WidgetExt,
+{
+    /// Manage the children of `container`.
+    pub fn new<CONTAINER: IsA<gtk::Box>>(container: &CONTAINER) -> Self {
+        KeyedChildren {
+            children: vec![],
+            container: container.clone().upcast(),
+            dragged: None,
+            selected: Rc::new(Cell::new(None)),
+        }
+    }
+
+    /// Let the user move a selection among the visible children with the arrow keys, Home, End,
+    /// Page Up and Page Down, and activate the selected child with Enter, sending the messages
+    /// built by `constructor` to `stream`.
+    /// The indices in the messages are the indices of the items in the collection, including the
+    /// hidden ones.
+    ///
+    /// The selected child has the `selected` state, to be highlighted by the theme, and is
+    /// scrolled into view when the box is in a `gtk::ScrolledWindow`.
+    /// The box is made focusable, since it receives the key presses when it or one of its
+    /// children has the focus.
+    pub fn enable_keyboard_navigation<F, MSG>(&mut self, stream: &StreamHandle<MSG>, constructor: F)
+        where F: Fn(ListNavigation) -> MSG + 'static,
+              MSG: 'static,
+    {
+        self.container.set_can_focus(true);
+        let selected = self.selected.clone();
+        let stream = stream.clone();
+        self.container.connect_key_press_event(move |container, event| {
+            let children = visible_children(container);
+            if children.is_empty() {
+                return Inhibit(false);
+            }
+            let last = children.len() - 1;
+            // The position of the selection among the visible children.
+            let current = selected.get()
+                .and_then(|selected| children.iter().position(|&(index, _)| index == selected));
+            let vertical = container.get_orientation() == Orientation::Vertical;
+            let page = page_length(container, &children);
+            let keyval = event.get_keyval();
+            let previous = if vertical { key::Up } else { key::Left };
+            let next = if vertical { key::Down } else { key::Right };
+            let index =
+                if keyval == previous {
+                    current.map_or(0, |index| index.saturating_sub(1))
+                }
+                else if keyval == next {
+                    current.map_or(0, |index| (index + 1).min(last))
+                }
+                else if keyval == key::Home {
+                    0
+                }
+                else if keyval == key::End {
+                    last
+                }
+                else if keyval == key::Page_Up {
+                    current.map_or(0, |index| index.saturating_sub(page))
+                }
+                else if keyval == key::Page_Down {
+                    current.map_or(0, |index| (index + page).min(last))
+                }
+                else if keyval == key::Return || keyval == key::KP_Enter {
+                    return match current {
+                        Some(position) => {
+                            let index = children[position].0;
+                            stream.emit(constructor(ListNavigation::Activated(index)));
+                            Inhibit(true)
+                        },
+                        None => Inhibit(false),
+                    };
+                }
+                else {
+                    return Inhibit(false);
+                };
+            let index = children[index].0;
+            if selected.get() != Some(index) {
+                select_child(container, selected.get(), Some(index));
+                selected.set(Some(index));
+                stream.emit(constructor(ListNavigation::SelectionMoved(index)));
+            }
+            Inhibit(true)
+        });
+    }
+
+    /// Get the index, in the collection, of the item selected with the keyboard.
+    /// The selection follows its item when [`update()`](#method.update) moves it, and is removed
+    /// with it.
+    pub fn selected(&self) -> Option<usize> {
+        self.selected.get()
+    }
+
+    /// Select the child of the item at `index` in the collection, or remove the selection with
+    /// `None`, without sending a message.
+    pub fn select(&self, index: Option<usize>) {
+        let index = index.filter(|&index| index < self.children.len());
+        select_child(&self.container, self.selected.get(), index);
+        self.selected.set(index);
+    }
+
+    /// Let the user drag the children to reorder them, sending the message built by `constructor`
+    /// with the previous and the new index of the item to `stream` when a child is dropped at
+    /// another position.
+    /// Like for the keyboard navigation, these are the indices in the collection, including the
+    /// hidden items.
+    ///
+    /// The children are not moved: move the item in the model and call
+    /// [`update()`](#method.update), which only moves the dropped child, not the ones between its
+    /// previous and its new position:
+    ///
+    /// ```ignore
+    /// Reordered(from, to) => {
+    ///     let item = self.model.items.remove(from);
+    ///     self.model.items.insert(to, item);
+    ///     self.model.rows.update(&self.model.items, |item| item.id, |item| item.clone(), |_| None);
+    /// },
+    /// ```
+    ///
+    /// The root widget of the children must receive the button events, like a `gtk::EventBox`
+    /// or a `gtk::Button`, to be dragged.
+    pub fn enable_reordering<F, MSG>(&mut self, stream: &StreamHandle<MSG>, constructor: F)
+        where F: Fn(usize, usize) -> MSG + 'static,
+              MSG: 'static,
+    {
+        if self.dragged.is_some() {
+            return;
+        }
+        let dragged = Rc::new(RefCell::new(None));
+        for (_, component) in &self.children {
+            make_draggable(component.widget().upcast_ref(), &dragged);
+        }
+        self.container.drag_dest_set(DestDefaults::ALL, &reorder_targets(), DragAction::MOVE);
+        let stream = stream.clone();
+        let dragged_child = dragged.clone();
+        self.container.connect_drag_data_received(move |container, _, x, y, _, _, _| {
+            let child =
+                match dragged_child.borrow_mut().take() {
+                    Some(child) => child,
+                    None => return,
+                };
+            let from = container.get_children().iter()
+                .position(|widget| *widget == child);
+            if let Some(from) = from {
+                let position = if container.get_orientation() == Orientation::Vertical { y } else { x };
+                let to = drop_index(container, &child, position);
+                if from != to {
+                    stream.emit(constructor(from, to));
+                }
+            }
+        });
+        self.dragged = Some(dragged);
+    }
+
+    /// Get the component for the item with the `key`.
+    pub fn get(&self, key: &KEY) -> Option<&Component<WIDGET>> {
+        self.children.iter()
+            .find(|(child_key, _)| child_key == key)
+            .map(|(_, component)| component)
+    }
+
+    /// Get the number of children.
+    pub fn len(&self) -> usize {
+        self.children.len()
+    }
+
+    /// Check whether there is no children.
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Make the children match `items`.
+    ///
+    /// `key` identifies an item, `model_param` creates the model parameter of a new component and
+    /// `message` optionally creates a message to send to the component of an item that was already
+    /// there, to update it.
+    ///
+    /// The kept children in the longest sequence that is already in the right order are not
+    /// touched: only the other ones are moved, so that moving one item moves one child.
+    pub fn update<ITEM, KEYFN, PARAMFN, MSGFN>(&mut self, items: &[ITEM], key: KEYFN, model_param: PARAMFN,
+        message: MSGFN) -> ChildrenDiff
+        where KEYFN: Fn(&ITEM) -> KEY,
+              PARAMFN: Fn(&ITEM) -> WIDGET::ModelParam,
+              MSGFN: Fn(&ITEM) -> Option<WIDGET::Msg>,
+    {
+        // The selection follows its item, wherever it is moved.
+        let selected_key = self.selected.get()
+            .and_then(|index| self.children.get(index))
+            .map(|(key, component)| {
+                component.widget().unset_state_flags(StateFlags::SELECTED);
+                key.clone()
+            });
+
+        let mut diff = ChildrenDiff::default();
+        let keys: Vec<KEY> = items.iter().map(&key).collect();
+        let new_indices: HashMap<&KEY, usize> = keys.iter()
+            .enumerate()
+            .map(|(index, key)| (key, index))
+            .collect();
+
+        let container = &self.container;
+        let mut removed = 0;
+        self.children.retain(|(key, component)| {
+            let keep = new_indices.contains_key(key);
+            if !keep {
+                container.remove(component.widget());
+                removed += 1;
+            }
+            keep
+        });
+        diff.removed = removed;
+
+        // The children that are not in the longest increasing subsequence of new indices are moved
+        // to the end first, so that the other ones stay in order before the moved and new children,
+        // which are then moved to their position one by one.
+        let old_indices: Vec<usize> = self.children.iter()
+            .map(|(key, _)| new_indices[key])
+            .collect();
+        let in_order = longest_increasing_subsequence(&old_indices);
+        let kept_count = old_indices.len();
+        let mut old_children: HashMap<KEY, (Component<WIDGET>, bool)> = HashMap::with_capacity(self.children.len());
+        for ((key, component), kept) in self.children.drain(..).zip(in_order) {
+            if !kept {
+                container.reorder_child(component.widget(), -1);
+            }
+            let _ = old_children.insert(key, (component, kept));
+        }
+
+        for (position, (item, key)) in items.iter().zip(keys.iter()).enumerate() {
+            match old_children.remove(key) {
+                Some((component, kept)) => {
+                    if !kept {
+                        self.container.reorder_child(component.widget(), position as i32);
+                        diff.moved += 1;
+                    }
+                    if let Some(msg) = message(item) {
+                        component.emit(msg);
+                        diff.updated += 1;
+                    }
+                    self.children.push((key.clone(), component));
+                },
+                None => {
+                    let component = self.container.add_widget::<WIDGET>(model_param(item));
+                    if let Some(ref dragged) = self.dragged {
+                        make_draggable(component.widget().upcast_ref(), dragged);
+                    }
+                    // The new component was added after the kept and the previously inserted ones.
+                    if position != kept_count + diff.inserted {
+                        self.container.reorder_child(component.widget(), position as i32);
+                    }
+                    self.children.push((key.clone(), component));
+                    diff.inserted += 1;
+                },
+            }
+        }
+        if let Some(selected_key) = selected_key {
+            let index = new_indices.get(&selected_key).copied();
+            select_child(&self.container, None, index);
+            self.selected.set(index);
+        }
+        diff
+    }
+}
@@ -0,0 +1,133 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::any::type_name;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk::{ContainerExt, WidgetExt};
+use gtk::Orientation::Vertical;
+
+use crate::core::{EventStream, StreamHandle};
+use crate::state::DisplayVariant;
+use crate::widget::Widget;
+use super::{init_component, try_build_widget};
+
+/// Relm widget whose view is only created the first time it becomes visible, like a page of a
+/// `gtk::Stack` or a tab of a `gtk::Notebook`.
+///
+/// The messages sent to the component before its view is created are buffered and handled by
+/// [`update()`](trait.Update.html#tymethod.update) once it is.
+///
+/// In `view!`, a relm widget added to a GTK+ container is created lazily with the `#[lazy]`
+/// attribute; its field in the widgets is then its placeholder.
+#[must_use]
+pub struct LazyComponent<WIDGET: Widget> {
+    placeholder: gtk::Box,
+    stream: Rc<EventStream<WIDGET::Msg>>,
+    widget: Rc<RefCell<Option<WIDGET::Root>>>,
+}
+
+impl<WIDGET: Widget> LazyComponent<WIDGET> {
+    pub(crate) fn new(model_param: WIDGET::ModelParam) -> Self
+        where WIDGET: 'static,
+              WIDGET::Msg: DisplayVariant + 'static,
+              WIDGET::ModelParam: 'static,
+    {
+        let stream = Rc::new(EventStream::new());
+        let buffer = Rc::new(RefCell::new(vec![]));
+        {
+            let buffer = buffer.clone();
+            stream.set_callback(move |msg| buffer.borrow_mut().push(msg));
+        }
+
+        let placeholder = gtk::Box::new(Vertical, 0);
+        placeholder.show();
+        let widget = Rc::new(RefCell::new(None));
+        let model_param = RefCell::new(Some(model_param));
+        {
+            let stream = Rc::downgrade(&stream);
+            let widget = widget.clone();
+            let _ = placeholder.connect_map(move |placeholder| {
+                let model_param =
+                    match model_param.borrow_mut().take() {
+                        Some(model_param) => model_param,
+                        None => return,
+                    };
+                let stream =
+                    match stream.upgrade() {
+                        Some(stream) => stream,
+                        None => return,
+                    };
+                match try_build_widget::<WIDGET>(&stream, model_param) {
+                    Ok((child, relm)) => {
+                        let root = child.root();
+                        placeholder.add(&root);
+                        // The child properties are set on the placeholder, in the container it was
+                        // added to.
+                        if let Some(parent) = placeholder.get_parent() {
+                            child.on_add(parent);
+                        }
+                        root.show();
+                        init_component::<WIDGET>(&stream, child, &relm);
+                        stream.prepend(buffer.borrow_mut().drain(..).collect());
+                        *widget.borrow_mut() = Some(root);
+                    },
+                    Err(error) => log::error!("Cannot create lazy widget {}: {}", type_name::<WIDGET>(), error),
+                }
+            });
+        }
+
+        LazyComponent {
+            placeholder,
+            stream,
+            widget,
+        }
+    }
+
+    /// Emit a message of the widget stream.
+    /// The message is buffered if the view was not created yet.
+    pub fn emit(&self, msg: WIDGET::Msg) {
+        self.stream.emit(msg);
+    }
+
+    /// Check whether the view of the component was created.
+    pub fn is_created(&self) -> bool {
+        self.widget.borrow().is_some()
+    }
+
+    /// Get the widget added to the container in place of the root widget of the component.
+    /// The root widget is added to it when the view is created.
+    pub fn placeholder(&self) -> &gtk::Box {
+        &self.placeholder
+    }
+
+    /// Get the event stream of the component.
+    /// This is used internally by the library.
+    pub fn stream(&self) -> StreamHandle<WIDGET::Msg> {
+        self.stream.downgrade()
+    }
+
+    /// Get the root widget of the component, or None if the view was not created yet.
+    pub fn widget(&self) -> Option<WIDGET::Root> {
+        self.widget.borrow().clone()
+    }
+}
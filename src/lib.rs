@@ -93,15 +93,74 @@
  * TODO: optionnaly multi-threaded.
  */
 
+mod accel;
+mod action;
+mod animation;
+mod application;
+mod arena;
+mod binding;
+pub mod builder;
+mod cancellation;
+pub mod clipboard;
 mod component;
 mod container;
 mod core;
+pub mod data_grid;
+#[cfg(feature = "dbus")]
+pub mod dbus;
+pub mod debug;
+pub mod dnd;
 mod drawing;
+mod file_watcher;
+mod focus;
+mod frame;
+#[cfg(feature = "http")]
+pub mod http;
+#[cfg(feature = "i18n")]
+pub mod i18n;
+mod invoke;
+mod keyed;
+mod lazy;
 mod macros;
+pub mod master_detail;
+pub mod net;
+mod notification;
+#[cfg(feature = "persist")]
+pub mod persist;
+pub mod preferences;
+pub mod print;
+mod progress;
+mod retry;
+pub mod searchable_list;
+mod settings;
+mod shutdown;
+mod splash;
+mod startup;
 mod state;
+pub mod style;
+mod task;
+mod ticker;
+pub mod timer;
+pub mod toast;
+mod transition;
+#[cfg(feature = "tray")]
+mod tray;
+#[cfg(all(unix, feature = "unix-signals"))]
+mod unix_signal;
+pub mod validation;
 #[doc(hidden)]
 pub mod vendor;
+#[cfg(feature = "video")]
+pub mod video;
+#[cfg(feature = "webkit")]
+pub mod webkit;
+#[cfg(feature = "websocket")]
+pub mod websocket;
 mod widget;
+mod window_geometry;
+mod window_manager;
+pub mod wizard;
+mod xdg;
 
 #[doc(hidden)]
 pub use glib::{
@@ -116,24 +175,87 @@ pub use glib::{
 pub use glib::translate::{FromGlibPtrNone, ToGlib, ToGlibPtr};
 #[doc(hidden)]
 pub use gobject_sys::{GParameter, g_object_newv};
-use glib::Continue;
+use glib::{Continue, MainContext};
 
-pub use crate::core::{Channel, EventStream, Sender, StreamHandle};
+pub use crate::core::{
+    Channel,
+    DeadLetter,
+    DeadLetterPolicy,
+    EventStream,
+    ObserverGuard,
+    Sender,
+    StreamHandle,
+    forward_dead_letters,
+    set_dead_letter_policy,
+};
 pub use crate::state::{
+    ComponentPanicked,
     DisplayVariant,
+    InitError,
     IntoOption,
     IntoPair,
     Relm,
     Update,
     UpdateNew,
     execute,
+    isolate_panics,
+    stop_isolating_panics,
 };
-use state::init_component;
+use frame::init_frame_synchronized;
 
+pub use accel::Accelerators;
+pub use action::{Action, Actions, action_menu, add_actions};
+pub use animation::{Animation, Easing, Interpolate, Spring, SpringAnimation, animate, animate_spring};
+pub use application::{ApplicationWidget, run_app, run_app_with, run_single_instance};
+pub use arena::{ComponentArena, ComponentKey};
+pub use binding::BoundValue;
+pub use cancellation::CancellationToken;
 pub use component::Component;
-pub use container::{Container, ContainerComponent, ContainerWidget};
-pub use drawing::DrawHandler;
+pub use container::{
+    ChildDispatch,
+    Container,
+    ContainerComponent,
+    ContainerWidget,
+    GtkContainerDispatch,
+    OrderedContainerWidget,
+    RelmContainer,
+    RelmContainerDispatch,
+    add_child,
+    direct_child,
+    register_container,
+    remove_child,
+};
+pub use drawing::{DrawContext, DrawHandler};
+pub use file_watcher::{FileChange, FileWatcher};
+pub use focus::FocusTracker;
+pub use invoke::{invoke, invoke_and_wait};
+pub use keyed::{ChildrenDiff, KeyedChildren, ListNavigation};
+pub use lazy::LazyComponent;
+pub use notification::{notify, withdraw_notification};
+pub use progress::{Progress, ProgressBarBinding};
+pub use retry::{Backoff, RetryEvent, retry};
+pub use settings::{Setting, SettingValue, SettingsModel};
+pub use shutdown::{ShutdownDelay, delay_shutdown, is_shutting_down, shutdown};
+pub use splash::run_with_splash;
+pub use startup::{RunOptions, run_with};
+pub use task::TaskScope;
+pub use ticker::{FrameTick, FrameTicker};
+pub use transition::TransitionWidget;
+#[cfg(feature = "tray")]
+pub use tray::TrayIcon;
+#[cfg(all(unix, feature = "unix-signals"))]
+pub use unix_signal::{UnixSignal, UnixSignals};
 pub use widget::{Widget, WidgetTest};
+pub use window_geometry::persist_geometry;
+pub use window_manager::WindowManager;
+pub use xdg::{set_state_dir, state_dir};
+
+/// Check whether the text of a widget is already `new_text`.
+/// This is used by the code generated by the `#[widget]` attribute to skip redundant updates.
+#[doc(hidden)]
+pub fn same_text<TEXT: AsRef<str> + ?Sized>(current_text: &str, new_text: &TEXT) -> bool {
+    current_text == new_text.as_ref()
+}
 
 /// Dummy macro to be used with `#[derive(Widget)]`.
 #[macro_export]
@@ -151,6 +273,26 @@ macro_rules! use_impl_self_type {
     };
 }
 
+/// Initialize a widget component, handling its messages once per frame if it asks to.
+fn init_component<WIDGET>(stream: &EventStream<WIDGET::Msg>, mut widget: WIDGET, relm: &Relm<WIDGET>)
+    where WIDGET: Widget + 'static,
+          WIDGET::Msg: DisplayVariant + 'static,
+{
+    for message in widget.init_messages() {
+        state::update_component(&mut widget, message);
+    }
+    shutdown::register::<WIDGET>(stream);
+    if WIDGET::priority() != glib::PRIORITY_DEFAULT {
+        stream.set_priority(WIDGET::priority());
+    }
+    if WIDGET::frame_synchronized() {
+        init_frame_synchronized::<WIDGET>(stream, widget, relm);
+    }
+    else {
+        state::init_component::<WIDGET>(stream, widget, relm);
+    }
+}
+
 fn create_widget_test<WIDGET>(model_param: WIDGET::ModelParam) -> (Component<WIDGET>, WIDGET::Streams, WIDGET::Widgets)
     where WIDGET: Widget + WidgetTest + 'static,
           WIDGET::Msg: DisplayVariant + 'static,
@@ -193,20 +335,61 @@ fn create_widget<WIDGET>(model_param: WIDGET::ModelParam)
     -> (Component<WIDGET>, WIDGET, Relm<WIDGET>)
     where WIDGET: Widget + 'static,
           WIDGET::Msg: DisplayVariant + 'static,
+{
+    match try_create_widget::<WIDGET>(model_param) {
+        Ok(result) => result,
+        Err(error) => panic!("Cannot create widget {}: {}", std::any::type_name::<WIDGET>(), error),
+    }
+}
+
+/// Create a new relm widget with `model_param` as initialization value, or return the error that
+/// occurred while creating its model or its view.
+fn try_create_widget<WIDGET>(model_param: WIDGET::ModelParam)
+    -> Result<(Component<WIDGET>, WIDGET, Relm<WIDGET>), InitError>
+    where WIDGET: Widget + 'static,
+          WIDGET::Msg: DisplayVariant + 'static,
 {
     let stream = EventStream::new();
+    let (widget, relm) = try_build_widget::<WIDGET>(&stream, model_param)?;
+    let root = widget.root().clone();
+    Ok((Component::new(stream, root), widget, relm))
+}
 
-    let relm = Relm::new(&stream);
-    let model = WIDGET::model(&relm, model_param);
-    let mut widget = WIDGET::view(&relm, model);
+/// Create the model and the view of a relm widget whose messages are sent to `stream`.
+fn try_build_widget<WIDGET>(stream: &EventStream<WIDGET::Msg>, model_param: WIDGET::ModelParam)
+    -> Result<(WIDGET, Relm<WIDGET>), InitError>
+    where WIDGET: Widget + 'static,
+          WIDGET::Msg: DisplayVariant + 'static,
+{
+    let relm = Relm::new(stream);
+    let model = WIDGET::try_model(&relm, model_param)?;
+    let mut widget = WIDGET::try_view(&relm, model)?;
     widget.init_view();
 
-    let root = widget.root().clone();
-    (Component::new(stream, root), widget, relm)
+    debug::register::<WIDGET>(stream, &widget.root());
+    Ok((widget, relm))
+}
+
+/// Same as [`create_component()`](fn.create_component.html), but return the error that occurred
+/// while initializing the component instead of panicking.
+pub fn try_create_component<CHILDWIDGET>(model_param: CHILDWIDGET::ModelParam)
+        -> Result<Component<CHILDWIDGET>, InitError>
+    where CHILDWIDGET: Widget + 'static,
+          CHILDWIDGET::Msg: DisplayVariant + 'static,
+{
+    let (component, widget, child_relm) = try_create_widget::<CHILDWIDGET>(model_param)?;
+    init_component::<CHILDWIDGET>(component.owned_stream(), widget, &child_relm);
+    Ok(component)
 }
 
 /// Initialize a widget for a test.
 ///
+/// The main loop is not started: the component, the streams of its child components and its
+/// widgets are returned so that the test can send messages, with `component.emit()` or
+/// `component.stream()`, and inspect the widgets.
+/// The messages are only handled when the main context is iterated, which can be done with the
+/// `settle()` and `send_and_settle()` functions of the `relm-test` crate.
+///
 /// It is to be used this way:
 ///
 /// ```
@@ -272,12 +455,51 @@ pub fn init_test<WIDGET>(model_param: WIDGET::ModelParam) ->
     Ok(component)
 }
 
+/// Initialize a widget for a test, returning the component, its widgets and a handle to its stream.
+///
+/// Like [`init_test()`](fn.init_test.html), the main loop is not started: the messages emitted on
+/// the stream are handled by [`pump_messages()`](fn.pump_messages.html).
+///
+/// ```ignore
+/// let (component, widgets, stream) = relm::init_test_with_stream::<Win>(()).expect("init_test failed");
+/// stream.emit(Increment);
+/// relm::pump_messages(&stream);
+/// assert_text!(widgets.label, "1");
+/// ```
+pub fn init_test_with_stream<WIDGET>(model_param: WIDGET::ModelParam) ->
+    Result<(Component<WIDGET>, WIDGET::Widgets, StreamHandle<WIDGET::Msg>), ()>
+    where WIDGET: Widget + WidgetTest + 'static,
+          WIDGET::Msg: DisplayVariant + 'static,
+{
+    let (component, _, widgets) = init_test::<WIDGET>(model_param)?;
+    let stream = component.stream();
+    Ok((component, widgets, stream))
+}
+
+/// Handle the messages waiting in `stream`, including the ones emitted while handling them, so
+/// that their effects can be asserted right after this call.
+pub fn pump_messages<MSG>(stream: &StreamHandle<MSG>) {
+    let context = MainContext::default();
+    while stream.pending_messages().unwrap_or(0) > 0 {
+        context.iteration(true);
+    }
+}
+
 /// Initialize a widget.
 pub fn init<WIDGET>(model_param: WIDGET::ModelParam) -> Result<Component<WIDGET>, ()>
     where WIDGET: Widget + 'static,
           WIDGET::Msg: DisplayVariant + 'static
 {
-    let (component, widget, relm) = create_widget::<WIDGET>(model_param);
+    try_init::<WIDGET>(model_param)
+        .map_err(|error| log::error!("Cannot initialize widget {}: {}", std::any::type_name::<WIDGET>(), error))
+}
+
+/// Initialize a widget, returning the error that occurred while creating its model or its view.
+pub fn try_init<WIDGET>(model_param: WIDGET::ModelParam) -> Result<Component<WIDGET>, InitError>
+    where WIDGET: Widget + 'static,
+          WIDGET::Msg: DisplayVariant + 'static
+{
+    let (component, widget, relm) = try_create_widget::<WIDGET>(model_param)?;
     init_component::<WIDGET>(component.owned_stream(), widget, &relm);
     Ok(component)
 }
@@ -334,16 +556,28 @@ pub fn init<WIDGET>(model_param: WIDGET::ModelParam) -> Result<Component<WIDGET>
 pub fn run<WIDGET>(model_param: WIDGET::ModelParam) -> Result<(), ()>
     where WIDGET: Widget + 'static,
 {
-    gtk::init().map_err(|_| ())?;
-    let _component = init::<WIDGET>(model_param)?;
+    try_run::<WIDGET>(model_param)
+        .map_err(|error| log::error!("Cannot run widget {}: {}", std::any::type_name::<WIDGET>(), error))
+}
+
+/// Same as [`run()`](fn.run.html), but return the error that occurred while initializing GTK+ or
+/// the widget.
+pub fn try_run<WIDGET>(model_param: WIDGET::ModelParam) -> Result<(), InitError>
+    where WIDGET: Widget + 'static,
+{
+    gtk::init()?;
+    let _component = try_init::<WIDGET>(model_param)?;
     gtk::main();
     Ok(())
 }
 
-/// Emit the `msg` every `duration` ms.
+/// Emit the `msg` every `duration` ms, until the stream is dropped.
 pub fn interval<F: Fn() -> MSG + 'static, MSG: 'static>(stream: &StreamHandle<MSG>, duration: u32, constructor: F) {
     let stream = stream.clone();
     glib::timeout_add_local(duration, move || {
+        if stream.is_dropped() {
+            return Continue(false);
+        }
         let msg = constructor();
         stream.emit(msg);
         Continue(true)
@@ -23,6 +23,7 @@
 ///
 /// ## Rules
 /// 1. Send `$msg` to `$other_component` when the GTK+ `$event` is emitted on `$widget`.
+/// When `$msg` is preceded by `return`, it returns `(Option<MSG>, ReturnValue)` like in rule 2.
 ///
 /// 2. Optionally send `$msg.0` when the GTK+ `$event` is emitted on `$widget`.
 /// Return `$msg.1` in the GTK+ callback.
@@ -31,10 +32,19 @@
 /// Option<MSG> can be None if no message needs to be emitted.
 ///
 /// 3. Send `$msg` when the GTK+ `$event` is emitted on `$widget`.
+/// When `$msg` is an `async` block, it is spawned on the main context and the message it resolves
+/// to is sent, unless the component was destroyed in the meantime.
 ///
 /// 4. Send `$msg` to `$widget` when the `$message` is received on `$stream`.
 #[macro_export]
 macro_rules! connect {
+    // Connect to a GTK+ widget event, sending a message to another widget.
+    // Like the return variant below, `$msg` returns (Option<MSG>, ReturnValue), so that the value
+    // returned in the GTK+ callback, like an Inhibit, can be computed from the event.
+    ($widget:expr, $event:ident($($args:pat),*), $other_component:expr, return $msg:expr) => {
+        $crate::connect_stream!(return $other_component.stream(), $widget, $event($($args),*), $msg);
+    };
+
     // Connect to a GTK+ widget event, sending a message to another widget.
     ($widget:expr, $event:ident($($args:pat),*), $other_component:expr, $msg:expr) => {
         $crate::connect_stream!($widget, $event($($args),*), $other_component.stream(), $msg);
@@ -48,6 +58,14 @@ macro_rules! connect {
         $crate::connect_stream!(return $relm.stream(), $widget, $event($($args),*), $msg);
     }};
 
+    // Connect to a GTK+ widget event, spawning an async block resolving to the message.
+    ($relm:expr, $widget:expr, $event:ident($($args:pat),*), async $($block:tt)+) => {{
+        let stream = $relm.stream().clone();
+        let _ = $widget.$event(move |$($args),*| {
+            stream.spawn_local(async $($block)+);
+        });
+    }};
+
     // Connect to a GTK+ widget event.
     ($relm:expr, $widget:expr, $event:ident($($args:pat),*), $msg:expr) => {{
         let stream = $relm.stream().clone();
@@ -67,6 +85,21 @@ macro_rules! connect {
     };
 }
 
+/// Declare keyboard accelerators sending messages to the component of `$relm`.
+///
+/// ```ignore
+/// let accelerators = relm::Accelerators::new(&window);
+/// accel!(accelerators, relm, "<Ctrl>N" => Msg::New, "<Ctrl>Q" => Msg::Quit);
+/// ```
+#[macro_export]
+macro_rules! accel {
+    ($accelerators:expr, $relm:expr, $($accel:expr => $msg:expr),* $(,)*) => {{
+        $(
+            $accelerators.add($accel, $relm.stream(), $msg);
+        )*
+    }};
+}
+
 /// Connect events to sending a message.
 /// Similar to `connect!` but wants a stream instead of a component.
 ///
@@ -186,6 +219,92 @@ macro_rules! connect_async_func {
     }};
 }
 
+/// Same as [`connect_async!`](macro.connect_async.html), but return a
+/// [`CancellationToken`](struct.CancellationToken.html) to abort the call: no message is sent once
+/// it is cancelled.
+#[macro_export]
+macro_rules! connect_async_cancellable {
+    ($object:expr, $async_method:ident, $relm:expr, $msg:expr) => {
+        connect_async_cancellable!($object, $async_method(), $relm, $msg)
+    };
+    ($object:expr, $async_method:ident ( $($args:expr),* ), $relm:expr, $msg:expr) => {{
+        // TODO: remove any use of Fragile when gio callbacks stop requiring Send.
+        let stream = ::relm::vendor::fragile::Fragile::new($relm.stream().clone());
+        let token = ::relm::CancellationToken::new();
+        let cancelled = token.clone();
+        $object.$async_method($($args,)* Some(token.cancellable()), move |result| {
+            if cancelled.is_cancelled() {
+                return;
+            }
+            if let Ok(result) = result {
+                stream.into_inner().emit($msg(result));
+            }
+        });
+        token
+    }};
+    ($object:expr, $async_method:ident, $relm:expr, $msg:expr, $fail_msg:expr) => {
+        connect_async_cancellable!($object, $async_method(), $relm, $msg, $fail_msg)
+    };
+    ($object:expr, $async_method:ident ( $($args:expr),* ), $relm:expr, $msg:expr, $fail_msg:expr) => {{
+        let event_stream = ::relm::vendor::fragile::Fragile::new($relm.stream().clone());
+        let fail_event_stream = ::relm::vendor::fragile::Fragile::new($relm.stream().clone());
+        let token = ::relm::CancellationToken::new();
+        let cancelled = token.clone();
+        $object.$async_method($($args,)* Some(token.cancellable()), move |result| {
+            if cancelled.is_cancelled() {
+                return;
+            }
+            match result {
+                Ok(value) => event_stream.into_inner().emit($msg(value)),
+                Err(error) => fail_event_stream.into_inner().emit($fail_msg(error)),
+            }
+        });
+        token
+    }};
+}
+
+/// Same as [`connect_async_func!`](macro.connect_async_func.html), but return a
+/// [`CancellationToken`](struct.CancellationToken.html) to abort the call.
+#[macro_export]
+macro_rules! connect_async_func_cancellable {
+    ($class:ident :: $async_func:ident, $relm:expr, $msg:expr) => {
+        connect_async_func_cancellable!($class::$async_func(), $relm, $msg)
+    };
+    ($class:ident :: $async_func:ident ( $($args:expr),* ), $relm:expr, $msg:expr) => {{
+        let stream = ::relm::vendor::fragile::Fragile::new($relm.stream().clone());
+        let token = ::relm::CancellationToken::new();
+        let cancelled = token.clone();
+        $class::$async_func($($args,)* Some(token.cancellable()), move |result| {
+            if cancelled.is_cancelled() {
+                return;
+            }
+            if let Ok(result) = result {
+                stream.into_inner().emit($msg(result));
+            }
+        });
+        token
+    }};
+    ($class:ident :: $async_func:ident, $relm:expr, $msg:expr, $fail_msg:expr) => {
+        connect_async_func_cancellable!($class::$async_func(), $relm, $msg, $fail_msg)
+    };
+    ($class:ident :: $async_func:ident ( $($args:expr),* ), $relm:expr, $msg:expr, $fail_msg:expr) => {{
+        let event_stream = ::relm::vendor::fragile::Fragile::new($relm.stream().clone());
+        let fail_event_stream = ::relm::vendor::fragile::Fragile::new($relm.stream().clone());
+        let token = ::relm::CancellationToken::new();
+        let cancelled = token.clone();
+        $class::$async_func($($args,)* Some(token.cancellable()), move |result| {
+            if cancelled.is_cancelled() {
+                return;
+            }
+            match result {
+                Ok(value) => event_stream.into_inner().emit($msg(value)),
+                Err(error) => fail_event_stream.into_inner().emit($fail_msg(error)),
+            }
+        });
+        token
+    }};
+}
+
 /// Like `connect_async!`, but also return a `Cancellable` to control the asynchronous request.
 #[macro_export]
 macro_rules! connect_async_full {
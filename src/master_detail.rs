@@ -0,0 +1,286 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Split pane showing a list of items in a sidebar and a detail component for the selected item.
+//!
+//! ```ignore
+//! impl Detail<Contact> for ContactView {
+//!     fn model_param(contact: &Contact) -> Contact {
+//!         contact.clone()
+//!     }
+//!
+//!     fn refresh(contact: &Contact) -> Option<ContactMsg> {
+//!         Some(ContactMsg::Set(contact.clone()))
+//!     }
+//! }
+//!
+//! let contacts = relm::create_component::<MasterDetail<Contact, ContactView>>(vec![]);
+//! contacts.emit(SetItems(self.model.contacts.clone()));
+//! connect!(contacts@SelectionChanged(index), relm, ContactSelected(index));
+//! ```
+
+use std::cell::Cell;
+use std::fmt::Display;
+use std::rc::Rc;
+
+use glib::Cast;
+use gtk::{
+    BoxExt,
+    ContainerExt,
+    LabelExt,
+    ListBoxExt,
+    ListBoxRowExt,
+    PanedExt,
+    ScrolledWindowExt,
+    WidgetExt,
+};
+
+use crate::component::Component;
+use crate::core::StreamHandle;
+use crate::state::{DisplayVariant, Relm, Update};
+use crate::widget::Widget;
+
+use self::MasterDetailMsg::*;
+
+/// Component showing the details of the selected item of a
+/// [`MasterDetail`](struct.MasterDetail.html).
+pub trait Detail<ITEM>: Widget {
+    /// Create the model parameter of the component when `item` is selected.
+    fn model_param(item: &ITEM) -> Self::ModelParam;
+
+    /// Create the message sent to the component when the selected `item` was changed by
+    /// `SetItems`, or `None` to create a new component.
+    fn refresh(item: &ITEM) -> Option<Self::Msg> {
+        let _ = item;
+        None
+    }
+}
+
+/// Messages of the [`MasterDetail`](struct.MasterDetail.html) component.
+///
+/// The first messages are commands sent to the component, the last one is an event sent by the
+/// component.
+pub enum MasterDetailMsg<ITEM> {
+    #[doc(hidden)]
+    RowSelected(Option<usize>),
+    /// Select the item at this index, or clear the selection.
+    Select(Option<usize>),
+    /// Replace the items of the list, keeping the selected index if it still exists.
+    SetItems(Vec<ITEM>),
+
+    /// The selected item changed.
+    SelectionChanged(Option<usize>),
+}
+
+impl<ITEM> DisplayVariant for MasterDetailMsg<ITEM> {
+    fn display_variant(&self) -> &'static str {
+        match *self {
+            RowSelected(_) => "RowSelected",
+            Select(_) => "Select",
+            SetItems(_) => "SetItems",
+            SelectionChanged(_) => "SelectionChanged",
+        }
+    }
+}
+
+#[doc(hidden)]
+pub struct MasterDetailModel<ITEM> {
+    items: Vec<ITEM>,
+    selected: Option<usize>,
+}
+
+/// Split pane whose sidebar lists the items, shown with their `Display` implementation, and whose
+/// other side shows the `DETAIL` component of the selected item.
+/// The model parameter is the initial list of items.
+pub struct MasterDetail<ITEM: Display + 'static, DETAIL: Detail<ITEM>> {
+    detail: Option<Component<DETAIL>>,
+    detail_box: gtk::Box,
+    list: gtk::ListBox,
+    model: MasterDetailModel<ITEM>,
+    root: gtk::Paned,
+    stream: StreamHandle<MasterDetailMsg<ITEM>>,
+    // Whether the list is being changed by the component, in which case the selection signals are
+    // ignored.
+    updating: Rc<Cell<bool>>,
+}
+
+struct UpdatingGuard {
+    previous: bool,
+    updating: Rc<Cell<bool>>,
+}
+
+impl UpdatingGuard {
+    fn new(updating: &Rc<Cell<bool>>) -> Self {
+        let previous = updating.replace(true);
+        Self {
+            previous,
+            updating: updating.clone(),
+        }
+    }
+}
+
+impl Drop for UpdatingGuard {
+    fn drop(&mut self) {
+        self.updating.set(self.previous);
+    }
+}
+
+impl<ITEM, DETAIL> MasterDetail<ITEM, DETAIL>
+    where ITEM: Display + 'static,
+          DETAIL: Detail<ITEM> + 'static,
+          DETAIL::Msg: DisplayVariant + 'static,
+{
+    fn fill_list(&self) {
+        let _updating = UpdatingGuard::new(&self.updating);
+        for row in self.list.get_children() {
+            self.list.remove(&row);
+        }
+        for item in &self.model.items {
+            let label = gtk::Label::new(Some(&item.to_string()));
+            label.set_halign(gtk::Align::Start);
+            label.show();
+            self.list.add(&label);
+        }
+        self.select_row();
+    }
+
+    fn select_row(&self) {
+        let _updating = UpdatingGuard::new(&self.updating);
+        match self.model.selected.and_then(|index| self.list.get_row_at_index(index as i32)) {
+            Some(row) => self.list.select_row(Some(&row)),
+            None => self.list.unselect_all(),
+        }
+    }
+
+    fn set_selection(&mut self, selected: Option<usize>) {
+        let selected = selected.filter(|&index| index < self.model.items.len());
+        if selected == self.model.selected {
+            return;
+        }
+        self.model.selected = selected;
+        self.select_row();
+        if let Some(detail) = self.detail.take() {
+            self.detail_box.remove(detail.widget());
+        }
+        if let Some(item) = selected.and_then(|index| self.model.items.get(index)) {
+            self.show_detail(crate::create_component::<DETAIL>(DETAIL::model_param(item)));
+        }
+        self.stream.emit(SelectionChanged(selected));
+    }
+
+    fn show_detail(&mut self, detail: Component<DETAIL>) {
+        let widget: gtk::Widget = detail.widget().clone().upcast();
+        self.detail_box.pack_start(&widget, true, true, 0);
+        widget.show();
+        self.detail = Some(detail);
+    }
+}
+
+impl<ITEM, DETAIL> Update for MasterDetail<ITEM, DETAIL>
+    where ITEM: Display + 'static,
+          DETAIL: Detail<ITEM> + 'static,
+          DETAIL::Msg: DisplayVariant + 'static,
+{
+    type Model = MasterDetailModel<ITEM>;
+    type ModelParam = Vec<ITEM>;
+    type Msg = MasterDetailMsg<ITEM>;
+
+    fn model(_: &Relm<Self>, items: Vec<ITEM>) -> MasterDetailModel<ITEM> {
+        MasterDetailModel {
+            items,
+            selected: None,
+        }
+    }
+
+    fn update(&mut self, event: MasterDetailMsg<ITEM>) {
+        match event {
+            RowSelected(selected) | Select(selected) => self.set_selection(selected),
+            SetItems(items) => {
+                self.model.items = items;
+                let selected = self.model.selected.filter(|&index| index < self.model.items.len());
+                if selected.is_none() {
+                    self.set_selection(None);
+                }
+                else if let Some(item) = selected.and_then(|index| self.model.items.get(index)) {
+                    match (DETAIL::refresh(item), self.detail.as_ref()) {
+                        (Some(msg), Some(detail)) => detail.emit(msg),
+                        _ => {
+                            let detail = crate::create_component::<DETAIL>(DETAIL::model_param(item));
+                            if let Some(old_detail) = self.detail.take() {
+                                self.detail_box.remove(old_detail.widget());
+                            }
+                            self.show_detail(detail);
+                        },
+                    }
+                }
+                self.fill_list();
+            },
+            // Event to be listened to by the parent.
+            SelectionChanged(_) => (),
+        }
+    }
+}
+
+impl<ITEM, DETAIL> Widget for MasterDetail<ITEM, DETAIL>
+    where ITEM: Display + 'static,
+          DETAIL: Detail<ITEM> + 'static,
+          DETAIL::Msg: DisplayVariant + 'static,
+{
+    type Root = gtk::Paned;
+
+    fn root(&self) -> Self::Root {
+        self.root.clone()
+    }
+
+    fn view(relm: &Relm<Self>, model: MasterDetailModel<ITEM>) -> Self {
+        let root = gtk::Paned::new(gtk::Orientation::Horizontal);
+        let scrolled_window = gtk::ScrolledWindow::new(None::<&gtk::Adjustment>, None::<&gtk::Adjustment>);
+        scrolled_window.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+        scrolled_window.set_size_request(200, -1);
+        let list = gtk::ListBox::new();
+        scrolled_window.add(&list);
+        root.pack1(&scrolled_window, false, false);
+        let detail_box = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        root.pack2(&detail_box, true, false);
+
+        let updating = Rc::new(Cell::new(false));
+        let stream = relm.stream().clone();
+        let list_updating = updating.clone();
+        list.connect_row_selected(move |_, row| {
+            if !list_updating.get() {
+                stream.emit(RowSelected(row.map(|row| row.get_index() as usize)));
+            }
+        });
+        root.show_all();
+
+        let master_detail = MasterDetail {
+            detail: None,
+            detail_box,
+            list,
+            model,
+            root,
+            stream: relm.stream().clone(),
+            updating,
+        };
+        master_detail.fill_list();
+        master_detail
+    }
+}
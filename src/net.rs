@@ -0,0 +1,393 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! TCP clients and servers delivering the received data and the connection changes as messages.
+//!
+//! The data is sent from `update()` with [`TcpClient::send()`](struct.TcpClient.html#method.send)
+//! and [`TcpServer::send()`](struct.TcpServer.html#method.send), so a network tool can be written
+//! entirely in the model.
+
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+
+use gio::{
+    Cancellable,
+    CancellableExt,
+    InputStreamExt,
+    IOStreamExt,
+    OutputStreamExt,
+    SocketClient,
+    SocketClientExt,
+    SocketConnection,
+    SocketConnectionExt,
+    SocketExt,
+    SocketListenerExt,
+    SocketService,
+    SocketServiceExt,
+};
+use glib::{Bytes, Error, MainContext, PRIORITY_DEFAULT};
+
+use crate::core::StreamHandle;
+
+const BUFFER_SIZE: usize = 4096;
+
+/// Event of a [`TcpClient`](struct.TcpClient.html).
+#[derive(Debug)]
+pub enum TcpEvent {
+    /// The peer closed the connection.
+    Closed,
+    /// The connection is established: the data sent before was flushed.
+    Connected,
+    /// The connection could not be established or failed. It is closed.
+    Error(Error),
+    /// Data received from the peer.
+    Received(Vec<u8>),
+}
+
+/// Identifier of a connection accepted by a [`TcpServer`](struct.TcpServer.html).
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ConnectionId(u64);
+
+/// Event of a [`TcpServer`](struct.TcpServer.html).
+#[derive(Debug)]
+pub enum ServerEvent {
+    /// A client connected.
+    Accepted(ConnectionId),
+    /// The client closed the connection.
+    Closed(ConnectionId),
+    /// The connection failed. It is closed.
+    Error(ConnectionId, Error),
+    /// Data received from a client.
+    Received(ConnectionId, Vec<u8>),
+}
+
+enum Event {
+    Closed,
+    Error(Error),
+    Received(Vec<u8>),
+}
+
+// A connection reading continuously and writing the queued data in order, since gio does not
+// allow concurrent operations on a stream.
+struct Connection {
+    cancellable: Cancellable,
+    closed: Cell<bool>,
+    connection: RefCell<Option<SocketConnection>>,
+    queue: RefCell<VecDeque<Vec<u8>>>,
+    writing: Cell<bool>,
+}
+
+impl Connection {
+    fn new() -> Rc<Self> {
+        Rc::new(Connection {
+            cancellable: Cancellable::new(),
+            closed: Cell::new(false),
+            connection: RefCell::new(None),
+            queue: RefCell::new(VecDeque::new()),
+            writing: Cell::new(false),
+        })
+    }
+
+    fn start(self: &Rc<Self>, connection: SocketConnection, on_event: Rc<dyn Fn(Event)>) {
+        *self.connection.borrow_mut() = Some(connection);
+        self.read(on_event.clone());
+        self.flush(on_event);
+    }
+
+    fn close(&self) {
+        if self.closed.replace(true) {
+            return;
+        }
+        self.cancellable.cancel();
+        self.queue.borrow_mut().clear();
+        if let Some(connection) = self.connection.borrow_mut().take() {
+            // Close the socket directly since the streams have pending operations.
+            let _ = connection.get_socket().close();
+        }
+    }
+
+    fn fail(&self, event: Event, on_event: &Rc<dyn Fn(Event)>) {
+        if !self.closed.get() {
+            self.close();
+            on_event(event);
+        }
+    }
+
+    fn read(self: &Rc<Self>, on_event: Rc<dyn Fn(Event)>) {
+        let input =
+            match *self.connection.borrow() {
+                Some(ref connection) => connection.get_input_stream(),
+                None => return,
+            };
+        let this = self.clone();
+        input.read_bytes_async(BUFFER_SIZE, PRIORITY_DEFAULT, Some(&self.cancellable), move |result| {
+            match result {
+                Ok(ref bytes) if bytes.is_empty() => this.fail(Event::Closed, &on_event),
+                Ok(bytes) => {
+                    if !this.closed.get() {
+                        on_event(Event::Received(bytes.to_vec()));
+                        this.read(on_event);
+                    }
+                },
+                Err(error) => this.fail(Event::Error(error), &on_event),
+            }
+        });
+    }
+
+    fn send(self: &Rc<Self>, data: &[u8], on_event: Rc<dyn Fn(Event)>) {
+        if self.closed.get() || data.is_empty() {
+            return;
+        }
+        self.queue.borrow_mut().push_back(data.to_vec());
+        self.flush(on_event);
+    }
+
+    fn flush(self: &Rc<Self>, on_event: Rc<dyn Fn(Event)>) {
+        if self.writing.get() {
+            return;
+        }
+        let output =
+            match *self.connection.borrow() {
+                Some(ref connection) => connection.get_output_stream(),
+                None => return,
+            };
+        let data =
+            match self.queue.borrow_mut().pop_front() {
+                Some(data) => data,
+                None => return,
+            };
+        self.writing.set(true);
+        let this = self.clone();
+        output.write_bytes_async(&Bytes::from(&data), PRIORITY_DEFAULT, Some(&self.cancellable), move |result| {
+            this.writing.set(false);
+            match result {
+                Ok(written) => {
+                    let written = written as usize;
+                    if written < data.len() {
+                        this.queue.borrow_mut().push_front(data[written..].to_vec());
+                    }
+                    this.flush(on_event);
+                },
+                Err(error) => this.fail(Event::Error(error), &on_event),
+            }
+        });
+    }
+}
+
+/// TCP client sending the received data and the connection changes as messages.
+///
+/// The connection is closed when this value is dropped, so it should be stored in the model.
+pub struct TcpClient {
+    connection: Rc<Connection>,
+    on_event: Rc<dyn Fn(Event)>,
+}
+
+impl TcpClient {
+    /// Connect to `host_and_port`, like `example.com:80`, using `default_port` if there's no port
+    /// in the address, and send the events built by `constructor` to `stream`.
+    pub fn connect<F, MSG>(host_and_port: &str, default_port: u16, stream: &StreamHandle<MSG>, constructor: F)
+        -> Self
+        where F: Fn(TcpEvent) -> MSG + 'static,
+              MSG: 'static,
+    {
+        let connection = Connection::new();
+        let stream = stream.clone();
+        let constructor = Rc::new(constructor);
+        let on_event: Rc<dyn Fn(Event)> = {
+            let constructor = constructor.clone();
+            let stream = stream.clone();
+            Rc::new(move |event| {
+                let event =
+                    match event {
+                        Event::Closed => TcpEvent::Closed,
+                        Event::Error(error) => TcpEvent::Error(error),
+                        Event::Received(data) => TcpEvent::Received(data),
+                    };
+                stream.emit(constructor(event));
+            })
+        };
+        let client = SocketClient::new();
+        let handler = on_event.clone();
+        let pending = connection.clone();
+        client.connect_to_host_async(host_and_port, default_port, Some(&connection.cancellable), move |result| {
+            if pending.closed.get() {
+                return;
+            }
+            match result {
+                Ok(socket_connection) => {
+                    stream.emit(constructor(TcpEvent::Connected));
+                    pending.start(socket_connection, handler);
+                },
+                Err(error) => pending.fail(Event::Error(error), &handler),
+            }
+        });
+        TcpClient {
+            connection,
+            on_event,
+        }
+    }
+
+    /// Close the connection. No `Closed` event is sent.
+    pub fn close(&self) {
+        self.connection.close();
+    }
+
+    /// Check if the connection is closed.
+    pub fn is_closed(&self) -> bool {
+        self.connection.closed.get()
+    }
+
+    /// Send `data` to the peer. The data sent before the connection is established is queued.
+    pub fn send(&self, data: &[u8]) {
+        self.connection.send(data, self.on_event.clone());
+    }
+}
+
+impl Drop for TcpClient {
+    fn drop(&mut self) {
+        self.connection.close();
+    }
+}
+
+struct ServerState {
+    connections: RefCell<HashMap<ConnectionId, (Rc<Connection>, Rc<dyn Fn(Event)>)>>,
+    next_id: Cell<u64>,
+}
+
+/// TCP server sending the accepted connections and the received data as messages.
+///
+/// The server stops listening and the connections are closed when this value is dropped, so it
+/// should be stored in the model.
+pub struct TcpServer {
+    port: u16,
+    service: SocketService,
+    state: Rc<ServerState>,
+}
+
+impl TcpServer {
+    /// Listen on `port` on all the interfaces, or on any free port if `port` is 0, and send the
+    /// events built by `constructor` to `stream`.
+    pub fn listen<F, MSG>(port: u16, stream: &StreamHandle<MSG>, constructor: F) -> Result<Self, Error>
+        where F: Fn(ServerEvent) -> MSG + 'static,
+              MSG: 'static,
+    {
+        let service = SocketService::new();
+        let port =
+            if port == 0 {
+                service.add_any_inet_port(None::<&glib::Object>)?
+            }
+            else {
+                service.add_inet_port(port, None::<&glib::Object>)?;
+                port
+            };
+        let state = Rc::new(ServerState {
+            connections: RefCell::new(HashMap::new()),
+            next_id: Cell::new(0),
+        });
+        let stream = stream.clone();
+        let constructor = Rc::new(constructor);
+        let weak_state = Rc::downgrade(&state);
+        let _ = service.connect_incoming(move |_, socket_connection, _| {
+            let state =
+                match weak_state.upgrade() {
+                    Some(state) => state,
+                    None => return false,
+                };
+            let id = ConnectionId(state.next_id.get());
+            state.next_id.set(id.0 + 1);
+            let on_event: Rc<dyn Fn(Event)> = {
+                let constructor = constructor.clone();
+                let state = Rc::downgrade(&state);
+                let stream = stream.clone();
+                Rc::new(move |event| {
+                    let event =
+                        match event {
+                            Event::Closed => ServerEvent::Closed(id),
+                            Event::Error(error) => ServerEvent::Error(id, error),
+                            Event::Received(data) => ServerEvent::Received(id, data),
+                        };
+                    if let ServerEvent::Closed(_) | ServerEvent::Error(_, _) = event {
+                        if let Some(state) = state.upgrade() {
+                            state.connections.borrow_mut().remove(&id);
+                        }
+                    }
+                    stream.emit(constructor(event));
+                })
+            };
+            let connection = Connection::new();
+            state.connections.borrow_mut().insert(id, (connection.clone(), on_event.clone()));
+            stream.emit(constructor(ServerEvent::Accepted(id)));
+            connection.start(socket_connection.clone(), on_event);
+            true
+        });
+        service.start();
+        Ok(TcpServer {
+            port,
+            service,
+            state,
+        })
+    }
+
+    /// Close the connection `id`. No `Closed` event is sent.
+    pub fn close(&self, id: ConnectionId) {
+        if let Some((connection, _)) = self.state.connections.borrow_mut().remove(&id) {
+            connection.close();
+        }
+    }
+
+    /// Get the identifiers of the open connections.
+    pub fn connections(&self) -> Vec<ConnectionId> {
+        let mut connections: Vec<_> = self.state.connections.borrow().keys().cloned().collect();
+        connections.sort();
+        connections
+    }
+
+    /// Get the port the server is listening on.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Send `data` to the client of the connection `id`.
+    pub fn send(&self, id: ConnectionId, data: &[u8]) {
+        let connection = self.state.connections.borrow().get(&id).cloned();
+        if let Some((connection, on_event)) = connection {
+            connection.send(data, on_event);
+        }
+    }
+
+    /// Send `data` to all the connected clients.
+    pub fn broadcast(&self, data: &[u8]) {
+        for id in self.connections() {
+            self.send(id, data);
+        }
+    }
+}
+
+impl Drop for TcpServer {
+    fn drop(&mut self) {
+        self.service.stop();
+        self.service.close();
+        for (_, (connection, _)) in self.state.connections.borrow_mut().drain() {
+            connection.close();
+        }
+    }
+}
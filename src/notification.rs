@@ -0,0 +1,76 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Desktop notifications whose buttons send messages.
+
+use gio::{ActionMapExt, ApplicationExt, Notification, SimpleAction, SimpleActionExt};
+use log::error;
+
+use crate::core::StreamHandle;
+
+/// Send a desktop notification with the identifier `id` through the default `gio::Application`,
+/// replacing any previous notification with the same identifier.
+///
+/// Each action is a button label with the message to send to `stream` when it is clicked.
+/// Returns `false` if there's no default application, as required by `gio::Notification`:
+/// use `relm::run_app()` or register a `gtk::Application` before calling this function.
+pub fn notify<MSG>(id: &str, summary: &str, body: &str, actions: &[(&str, MSG)], stream: &StreamHandle<MSG>)
+    -> bool
+    where MSG: Clone + 'static,
+{
+    let application =
+        match gio::Application::get_default() {
+            Some(application) => application,
+            None => {
+                error!("Cannot send notification {}: there's no default application", id);
+                return false;
+            },
+        };
+    let notification = Notification::new(summary);
+    notification.set_body(Some(body));
+    for (index, &(label, ref msg)) in actions.iter().enumerate() {
+        let name = action_name(id, index);
+        let action = SimpleAction::new(&name, None);
+        let stream = stream.clone();
+        let msg = msg.clone();
+        let _ = action.connect_activate(move |_, _| {
+            stream.emit(msg.clone());
+        });
+        application.add_action(&action);
+        notification.add_button(label, &format!("app.{}", name));
+    }
+    application.send_notification(Some(id), &notification);
+    true
+}
+
+/// Withdraw the notification with the identifier `id`.
+pub fn withdraw_notification(id: &str) {
+    if let Some(application) = gio::Application::get_default() {
+        application.withdraw_notification(id);
+    }
+}
+
+fn action_name(id: &str, index: usize) -> String {
+    let id: String = id.chars()
+        .map(|character| if character.is_ascii_alphanumeric() { character } else { '-' })
+        .collect();
+    format!("relm-notification-{}-{}", id, index)
+}
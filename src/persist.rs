@@ -0,0 +1,103 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Save the model of a component when the application quits and restore it at startup.
+//!
+//! The model must implement `serde::Serialize` and `serde::Deserialize`, and
+//! `#[derive(PersistentModel)]`, which names the file `model_name.json` by default or the
+//! file given with `#[persist(file = "state.json")]`.
+//! The file is stored in the directory of the application in the XDG state directory, i.e.
+//! `$XDG_STATE_HOME/prgname/`.
+//!
+//! Since the model is owned by its widget, it is saved from `update()`:
+//!
+//! ```ignore
+//! fn model() -> Model {
+//!     relm::persist::load().unwrap_or_default()
+//! }
+//!
+//! fn update(&mut self, event: Msg) {
+//!     match event {
+//!         Quit => {
+//!             if let Err(error) = relm::persist::save(&self.model) {
+//!                 eprintln!("Cannot save the state: {}", error);
+//!             }
+//!             gtk::main_quit();
+//!         },
+//!     }
+//! }
+//! ```
+
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+pub use crate::xdg::{set_state_dir, state_dir};
+
+/// A model which can be saved to the state directory of the application.
+/// This is implemented by `#[derive(PersistentModel)]`.
+pub trait PersistentModel: Serialize + DeserializeOwned {
+    /// The name of the file, in the state directory, where the model is saved.
+    fn file_name() -> &'static str;
+}
+
+/// Get the path of the file where `MODEL` is saved.
+pub fn state_path<MODEL: PersistentModel>() -> PathBuf {
+    state_dir().join(MODEL::file_name())
+}
+
+/// Load the model saved by `save()`.
+/// An error of kind `NotFound` is returned when the model was never saved.
+pub fn load<MODEL: PersistentModel>() -> io::Result<MODEL> {
+    load_from(state_path::<MODEL>())
+}
+
+/// Load a model from the file at `path`.
+pub fn load_from<MODEL: DeserializeOwned, P: AsRef<Path>>(path: P) -> io::Result<MODEL> {
+    let file = File::open(path)?;
+    serde_json::from_reader(BufReader::new(file))
+        .map_err(|error| io::Error::new(ErrorKind::InvalidData, error))
+}
+
+/// Save `model` in the state directory of the application.
+pub fn save<MODEL: PersistentModel>(model: &MODEL) -> io::Result<()> {
+    save_to(state_path::<MODEL>(), model)
+}
+
+/// Save `model` to the file at `path`, creating its directory if needed.
+/// The file is replaced atomically, so that it is not corrupted if the application is killed
+/// while saving.
+pub fn save_to<MODEL: Serialize, P: AsRef<Path>>(path: P, model: &MODEL) -> io::Result<()> {
+    let path = path.as_ref();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let temp_path = path.with_extension("tmp");
+    let mut writer = BufWriter::new(File::create(&temp_path)?);
+    serde_json::to_writer_pretty(&mut writer, model)
+        .map_err(|error| io::Error::new(ErrorKind::InvalidData, error))?;
+    writer.flush()?;
+    drop(writer);
+    fs::rename(temp_path, path)
+}
@@ -0,0 +1,423 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Preferences window generated from a settings struct.
+//!
+//! The fields of a struct deriving `Preferences` are shown in a form, where each field has a
+//! widget depending on its type: a switch for `bool`, a spin button for the numbers, an entry for
+//! `String` and a combo box for the enums deriving `PreferenceChoice`.
+//!
+//! ```ignore
+//! #[derive(Clone, Preferences)]
+//! struct Prefs {
+//!     #[preference(label = "Dark theme")]
+//!     dark_theme: bool,
+//!     #[preference(label = "Font size", range(min = 6, max = 72))]
+//!     font_size: u32,
+//!     #[preference(label = "Layout")]
+//!     layout: Layout,
+//!     #[preference(label = "User name")]
+//!     user_name: String,
+//! }
+//!
+//! #[derive(Clone, PreferenceChoice)]
+//! enum Layout {
+//!     Compact,
+//!     Wide,
+//! }
+//!
+//! // In the parent widget:
+//! let dialog = relm::create_component::<PreferencesDialog<Prefs>>(self.model.prefs.clone());
+//! connect!(dialog@Changed(_, ref prefs), relm, PrefsChanged(prefs.clone()));
+//! ```
+//!
+//! The dialog emits `Changed` whenever the user edits a field, and `Set` can be sent to update
+//! the form when the preferences are changed elsewhere.
+
+use std::collections::HashMap;
+
+use glib::Cast;
+use gtk::{
+    ComboBoxExt,
+    ComboBoxTextExt,
+    ContainerExt,
+    EditableSignals,
+    EntryExt,
+    GridExt,
+    GtkWindowExt,
+    Inhibit,
+    LabelExt,
+    SpinButtonExt,
+    SpinButtonSignals,
+    SwitchExt,
+    WidgetExt,
+};
+
+use crate::core::StreamHandle;
+use crate::state::{DisplayVariant, Relm, Update};
+use crate::widget::Widget;
+
+use self::PreferencesMsg::*;
+
+/// The kind of widget used to edit a preference.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PreferenceKind {
+    /// A switch.
+    Bool,
+    /// A combo box with the names of the choices.
+    Choice(&'static [&'static str]),
+    /// A spin button.
+    Range {
+        /// The minimum value.
+        min: f64,
+        /// The maximum value.
+        max: f64,
+        /// The increment of the buttons.
+        step: f64,
+        /// The number of decimal digits shown.
+        digits: u32,
+    },
+    /// An entry.
+    Text,
+}
+
+impl PreferenceKind {
+    /// Use a spin button between `min` and `max`, keeping the number of digits of a range.
+    pub fn with_range(self, min: f64, max: f64, step: f64) -> Self {
+        let digits =
+            match self {
+                PreferenceKind::Range { digits, .. } => digits,
+                _ => 0,
+            };
+        PreferenceKind::Range {
+            min,
+            max,
+            step,
+            digits,
+        }
+    }
+}
+
+/// The value of a preference, as edited in the form.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PreferenceValue {
+    /// The value of a switch.
+    Bool(bool),
+    /// The index of a choice.
+    Choice(usize),
+    /// The value of a spin button.
+    Number(f64),
+    /// The text of an entry.
+    Text(String),
+}
+
+/// A field of the preferences.
+#[derive(Clone, Debug)]
+pub struct PreferenceField {
+    /// The widget used to edit the field.
+    pub kind: PreferenceKind,
+    /// The label shown next to the widget.
+    pub label: &'static str,
+    /// The name of the field in the struct.
+    pub name: &'static str,
+}
+
+/// Type of a field of the preferences.
+pub trait PreferenceType: Sized {
+    /// The kind of widget used to edit values of this type.
+    fn kind() -> PreferenceKind;
+    /// Convert the value to be shown in the form.
+    fn to_value(&self) -> PreferenceValue;
+    /// Convert the value edited in the form.
+    fn from_value(value: PreferenceValue) -> Option<Self>;
+}
+
+/// An enum shown as a combo box in the preferences.
+/// This is implemented by `#[derive(PreferenceChoice)]` for enums with unit variants.
+pub trait PreferenceChoice: Sized {
+    /// The labels of the choices, in declaration order.
+    fn choices() -> &'static [&'static str];
+    /// Get the index of the current choice.
+    fn choice_index(&self) -> usize;
+    /// Create the choice at `index`.
+    fn from_choice_index(index: usize) -> Option<Self>;
+}
+
+/// A settings struct from which a preferences window is generated.
+/// This is implemented by `#[derive(Preferences)]` for the structs whose fields have a
+/// `#[preference(label = "Label")]` attribute.
+pub trait Preferences: Clone + 'static {
+    /// The fields shown in the form, in order.
+    fn fields() -> Vec<PreferenceField>;
+    /// Get the value of the field `name`.
+    fn get(&self, name: &str) -> Option<PreferenceValue>;
+    /// Set the value of the field `name`, returning false if the value is invalid.
+    fn set(&mut self, name: &str, value: PreferenceValue) -> bool;
+}
+
+impl PreferenceType for bool {
+    fn kind() -> PreferenceKind {
+        PreferenceKind::Bool
+    }
+
+    fn to_value(&self) -> PreferenceValue {
+        PreferenceValue::Bool(*self)
+    }
+
+    fn from_value(value: PreferenceValue) -> Option<Self> {
+        match value {
+            PreferenceValue::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl PreferenceType for String {
+    fn kind() -> PreferenceKind {
+        PreferenceKind::Text
+    }
+
+    fn to_value(&self) -> PreferenceValue {
+        PreferenceValue::Text(self.clone())
+    }
+
+    fn from_value(value: PreferenceValue) -> Option<Self> {
+        match value {
+            PreferenceValue::Text(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! impl_number_preference {
+    ($($typ:ty => $digits:expr),*) => {
+        $(
+            impl PreferenceType for $typ {
+                fn kind() -> PreferenceKind {
+                    PreferenceKind::Range {
+                        min: 0.0,
+                        max: 100.0,
+                        step: 1.0,
+                        digits: $digits,
+                    }
+                }
+
+                fn to_value(&self) -> PreferenceValue {
+                    PreferenceValue::Number(*self as f64)
+                }
+
+                fn from_value(value: PreferenceValue) -> Option<Self> {
+                    match value {
+                        PreferenceValue::Number(value) => Some(value as $typ),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_number_preference!(i32 => 0, i64 => 0, u8 => 0, u32 => 0, u64 => 0, f32 => 2, f64 => 2);
+
+/// Messages of the [`PreferencesDialog`](struct.PreferencesDialog.html) component.
+#[derive(Clone)]
+pub enum PreferencesMsg<PREFS> {
+    /// Replace the preferences shown in the form.
+    Set(PREFS),
+
+    /// A field, whose name is the first value, was edited by the user.
+    Changed(&'static str, PREFS),
+    #[doc(hidden)]
+    Edited(&'static str, PreferenceValue),
+}
+
+impl<PREFS> DisplayVariant for PreferencesMsg<PREFS> {
+    fn display_variant(&self) -> &'static str {
+        match *self {
+            Set(_) => "Set",
+            Changed(_, _) => "Changed",
+            Edited(_, _) => "Edited",
+        }
+    }
+}
+
+#[derive(Clone)]
+enum FieldWidget {
+    Choice(gtk::ComboBoxText),
+    Spin(gtk::SpinButton),
+    Switch(gtk::Switch),
+    Text(gtk::Entry),
+}
+
+impl FieldWidget {
+    fn set_value(&self, value: &PreferenceValue) {
+        match (self, value) {
+            (FieldWidget::Choice(combo), PreferenceValue::Choice(index)) =>
+                combo.set_active(Some(*index as u32)),
+            (FieldWidget::Spin(spin), PreferenceValue::Number(number)) => spin.set_value(*number),
+            (FieldWidget::Switch(switch), PreferenceValue::Bool(active)) => switch.set_active(*active),
+            (FieldWidget::Text(entry), PreferenceValue::Text(text)) => {
+                if entry.get_text().as_str() != text {
+                    entry.set_text(text);
+                }
+            },
+            _ => (),
+        }
+    }
+}
+
+/// Preferences window whose form is generated from `PREFS`.
+/// The window is hidden instead of being destroyed when it is closed.
+pub struct PreferencesDialog<PREFS: Preferences> {
+    fields: HashMap<&'static str, FieldWidget>,
+    prefs: PREFS,
+    root: gtk::Window,
+    stream: StreamHandle<PreferencesMsg<PREFS>>,
+}
+
+impl<PREFS: Preferences> PreferencesDialog<PREFS> {
+    fn refresh(&self) {
+        for (name, widget) in &self.fields {
+            if let Some(value) = self.prefs.get(name) {
+                widget.set_value(&value);
+            }
+        }
+    }
+}
+
+impl<PREFS: Preferences> Update for PreferencesDialog<PREFS> {
+    type Model = PREFS;
+    type ModelParam = PREFS;
+    type Msg = PreferencesMsg<PREFS>;
+
+    fn model(_: &Relm<Self>, prefs: PREFS) -> PREFS {
+        prefs
+    }
+
+    fn update(&mut self, event: PreferencesMsg<PREFS>) {
+        match event {
+            Set(prefs) => {
+                self.prefs = prefs;
+                self.refresh();
+            },
+            Edited(name, value) => {
+                // Setting the widgets in refresh() also triggers their signals.
+                if self.prefs.get(name).as_ref() != Some(&value) && self.prefs.set(name, value) {
+                    self.stream.emit(Changed(name, self.prefs.clone()));
+                }
+            },
+            // Event to be listened to by the parent.
+            Changed(_, _) => (),
+        }
+    }
+}
+
+impl<PREFS: Preferences> Widget for PreferencesDialog<PREFS> {
+    type Root = gtk::Window;
+
+    fn root(&self) -> Self::Root {
+        self.root.clone()
+    }
+
+    fn view(relm: &Relm<Self>, prefs: PREFS) -> Self {
+        let root = gtk::Window::new(gtk::WindowType::Toplevel);
+        root.set_title("Preferences");
+        root.connect_delete_event(|window, _| {
+            window.hide();
+            Inhibit(true)
+        });
+
+        let grid = gtk::Grid::new();
+        grid.set_border_width(12);
+        grid.set_column_spacing(12);
+        grid.set_row_spacing(6);
+        root.add(&grid);
+
+        let mut fields = HashMap::new();
+        for (row, field) in PREFS::fields().into_iter().enumerate() {
+            let label = gtk::Label::new(Some(field.label));
+            label.set_halign(gtk::Align::Start);
+            grid.attach(&label, 0, row as i32, 1, 1);
+
+            let name = field.name;
+            let stream = relm.stream().clone();
+            let widget =
+                match field.kind {
+                    PreferenceKind::Bool => {
+                        let switch = gtk::Switch::new();
+                        switch.set_halign(gtk::Align::End);
+                        switch.connect_property_active_notify(move |switch| {
+                            stream.emit(Edited(name, PreferenceValue::Bool(switch.get_active())));
+                        });
+                        FieldWidget::Switch(switch)
+                    },
+                    PreferenceKind::Choice(choices) => {
+                        let combo = gtk::ComboBoxText::new();
+                        for choice in choices {
+                            combo.append_text(choice);
+                        }
+                        combo.connect_changed(move |combo| {
+                            if let Some(index) = combo.get_active() {
+                                stream.emit(Edited(name, PreferenceValue::Choice(index as usize)));
+                            }
+                        });
+                        FieldWidget::Choice(combo)
+                    },
+                    PreferenceKind::Range { min, max, step, digits } => {
+                        let spin = gtk::SpinButton::with_range(min, max, step);
+                        spin.set_digits(digits);
+                        spin.connect_value_changed(move |spin| {
+                            stream.emit(Edited(name, PreferenceValue::Number(spin.get_value())));
+                        });
+                        FieldWidget::Spin(spin)
+                    },
+                    PreferenceKind::Text => {
+                        let entry = gtk::Entry::new();
+                        entry.connect_changed(move |entry| {
+                            stream.emit(Edited(name, PreferenceValue::Text(entry.get_text().to_string())));
+                        });
+                        FieldWidget::Text(entry)
+                    },
+                };
+            if let Some(value) = prefs.get(name) {
+                widget.set_value(&value);
+            }
+            let gtk_widget: gtk::Widget =
+                match widget {
+                    FieldWidget::Choice(ref combo) => combo.clone().upcast(),
+                    FieldWidget::Spin(ref spin) => spin.clone().upcast(),
+                    FieldWidget::Switch(ref switch) => switch.clone().upcast(),
+                    FieldWidget::Text(ref entry) => entry.clone().upcast(),
+                };
+            gtk_widget.set_hexpand(true);
+            grid.attach(&gtk_widget, 1, row as i32, 1, 1);
+            fields.insert(name, widget);
+        }
+        grid.show_all();
+
+        PreferencesDialog {
+            fields,
+            prefs,
+            root,
+            stream: relm.stream().clone(),
+        }
+    }
+}
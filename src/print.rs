@@ -0,0 +1,156 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Printing with the pagination and the drawing of the pages done in `update()`.
+//!
+//! ```ignore
+//! fn update(&mut self, event: Msg) {
+//!     match event {
+//!         Print => {
+//!             let operation = gtk::PrintOperation::new();
+//!             let _ = relm::print::run(&operation, Some(&self.widgets.window), self.model.relm.stream(), Printing);
+//!         },
+//!         Printing(PrintEvent::Paginate(pagination)) => pagination.set_n_pages(self.model.pages.len() as i32),
+//!         Printing(PrintEvent::DrawPage(page)) => self.draw_page(&page.context().get_cairo_context(), page.number()),
+//!         Printing(PrintEvent::Done(result)) => println!("Printing done: {:?}", result),
+//!     }
+//! }
+//! ```
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use gtk::{PrintContext, PrintOperationAction, PrintOperationExt, PrintOperationResult};
+
+use crate::core::StreamHandle;
+
+/// Event of a print operation sent as a message.
+pub enum PrintEvent {
+    /// The pages must be counted with `Pagination::set_n_pages()`.
+    Paginate(Pagination),
+    /// A page must be drawn on its context.
+    DrawPage(PrintPage),
+    /// The print operation is finished.
+    Done(PrintOperationResult),
+}
+
+/// Request to count the pages to print, sent when the print context is known.
+/// The pagination is considered done when this value is dropped.
+pub struct Pagination {
+    context: PrintContext,
+    operation: gtk::PrintOperation,
+    paginated: Rc<Cell<bool>>,
+}
+
+impl Pagination {
+    /// Get the print context, to compute the layout of the pages.
+    pub fn context(&self) -> &PrintContext {
+        &self.context
+    }
+
+    /// Set the number of pages to print.
+    pub fn set_n_pages(self, pages: i32) {
+        self.operation.set_n_pages(pages);
+    }
+}
+
+impl Drop for Pagination {
+    fn drop(&mut self) {
+        self.paginated.set(true);
+    }
+}
+
+/// Request to draw a page.
+/// The page is considered drawn when this value is dropped.
+pub struct PrintPage {
+    context: PrintContext,
+    number: i32,
+    operation: gtk::PrintOperation,
+}
+
+impl PrintPage {
+    /// Get the print context, whose cairo context is where the page must be drawn.
+    pub fn context(&self) -> &PrintContext {
+        &self.context
+    }
+
+    /// Get the number of the page, starting at 0.
+    pub fn number(&self) -> i32 {
+        self.number
+    }
+}
+
+impl Drop for PrintPage {
+    fn drop(&mut self) {
+        self.operation.draw_page_finish();
+    }
+}
+
+/// Show the print dialog for `operation` and print, sending the print events as messages created
+/// by `constructor` to `stream`.
+///
+/// Use a new `gtk::PrintOperation` for each call, configured with the print settings and the
+/// default page setup if needed.
+pub fn run<F, MSG>(operation: &gtk::PrintOperation, parent: Option<&gtk::Window>, stream: &StreamHandle<MSG>,
+    constructor: F) -> Result<PrintOperationResult, glib::Error>
+    where F: Fn(PrintEvent) -> MSG + 'static,
+          MSG: 'static,
+{
+    let constructor = Rc::new(constructor);
+    let paginated = Rc::new(Cell::new(false));
+    let requested = Cell::new(false);
+    {
+        let constructor = constructor.clone();
+        let stream = stream.clone();
+        operation.connect_paginate(move |operation, context| {
+            // Paginate is called until it returns true, so the pagination is requested only once.
+            if !requested.replace(true) {
+                stream.emit(constructor(PrintEvent::Paginate(Pagination {
+                    context: context.clone(),
+                    operation: operation.clone(),
+                    paginated: paginated.clone(),
+                })));
+            }
+            paginated.get()
+        });
+    }
+    {
+        let constructor = constructor.clone();
+        let stream = stream.clone();
+        operation.connect_draw_page(move |operation, context, number| {
+            // Drawing is deferred until the page is drawn in update().
+            operation.set_defer_drawing();
+            stream.emit(constructor(PrintEvent::DrawPage(PrintPage {
+                context: context.clone(),
+                number,
+                operation: operation.clone(),
+            })));
+        });
+    }
+    {
+        let stream = stream.clone();
+        operation.connect_done(move |_, result| {
+            stream.emit(constructor(PrintEvent::Done(result)));
+        });
+    }
+    operation.set_allow_async(true);
+    operation.run(PrintOperationAction::PrintDialog, parent)
+}
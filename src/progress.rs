@@ -0,0 +1,185 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use glib::{Continue, SourceId};
+use gtk::{ButtonExt, ProgressBarExt, WidgetExt};
+
+use crate::core::{ObserverGuard, StreamHandle};
+
+/// Progress of a background job, to be sent in a message by the worker thread or the future
+/// doing the job.
+///
+/// The convention is to wrap it in a message variant, for instance `Msg::Progress(Progress)`, and
+/// to bind this message to a progress bar with a
+/// [`ProgressBarBinding`](struct.ProgressBarBinding.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Progress {
+    /// The number of units of work already done.
+    pub done: u64,
+    /// A description of the current step of the job.
+    pub label: String,
+    /// The total number of units of work, if known.
+    pub total: Option<u64>,
+}
+
+impl Progress {
+    /// Create a progress of `done` units of work out of `total`.
+    pub fn new(done: u64, total: u64, label: &str) -> Self {
+        Progress {
+            done,
+            label: label.to_string(),
+            total: Some(total),
+        }
+    }
+
+    /// Create a progress whose total is not known yet.
+    pub fn indeterminate(label: &str) -> Self {
+        Progress {
+            done: 0,
+            label: label.to_string(),
+            total: None,
+        }
+    }
+
+    /// Get the fraction of the job that is done, between 0 and 1, or `None` if the total is not
+    /// known.
+    pub fn fraction(&self) -> Option<f64> {
+        self.total.map(|total|
+            if total == 0 {
+                1.0
+            }
+            else {
+                (self.done as f64 / total as f64).min(1.0)
+            })
+    }
+
+    /// Check if the job is done.
+    pub fn is_finished(&self) -> bool {
+        match self.total {
+            Some(total) => self.done >= total,
+            None => false,
+        }
+    }
+}
+
+struct State {
+    bar: gtk::ProgressBar,
+    cancel_button: RefCell<Option<gtk::Button>>,
+    pulse_source: RefCell<Option<SourceId>>,
+}
+
+impl State {
+    fn update(&self, progress: &Progress) {
+        match progress.fraction() {
+            Some(fraction) => {
+                self.stop_pulsing();
+                self.bar.set_fraction(fraction);
+                let total = progress.total.unwrap_or_default();
+                self.bar.set_text(Some(&format!("{} ({}/{})", progress.label, progress.done, total)));
+            },
+            None => {
+                self.start_pulsing();
+                self.bar.set_text(Some(&progress.label));
+            },
+        }
+        self.bar.set_show_text(true);
+        if let Some(ref button) = *self.cancel_button.borrow() {
+            button.set_sensitive(!progress.is_finished());
+        }
+    }
+
+    fn start_pulsing(&self) {
+        let mut pulse_source = self.pulse_source.borrow_mut();
+        if pulse_source.is_none() {
+            let bar = self.bar.clone();
+            *pulse_source = Some(glib::timeout_add_local(100, move || {
+                bar.pulse();
+                Continue(true)
+            }));
+        }
+    }
+
+    fn stop_pulsing(&self) {
+        if let Some(source) = self.pulse_source.borrow_mut().take() {
+            glib::source_remove(source);
+        }
+    }
+}
+
+/// Binding updating a progress bar, and optionally a cancel button, from the
+/// [`Progress`](struct.Progress.html) found in the messages of a stream.
+///
+/// When the total is unknown, the progress bar pulses until a progress with a total is received.
+/// The cancel button is only sensitive while the job is not finished.
+/// The binding is removed when this value is dropped, so it should be kept in the model.
+pub struct ProgressBarBinding<MSG> {
+    _guard: ObserverGuard<MSG>,
+    state: Rc<State>,
+    stream: StreamHandle<MSG>,
+}
+
+impl<MSG: 'static> ProgressBarBinding<MSG> {
+    /// Bind `bar` to the progress returned by `extract` for the messages of `stream`.
+    pub fn new<F>(stream: &StreamHandle<MSG>, bar: &gtk::ProgressBar, extract: F) -> Self
+        where F: Fn(&MSG) -> Option<&Progress> + 'static,
+    {
+        let state = Rc::new(State {
+            bar: bar.clone(),
+            cancel_button: RefCell::new(None),
+            pulse_source: RefCell::new(None),
+        });
+        let observer_state = Rc::downgrade(&state);
+        let guard = stream.observe_scoped(move |msg| {
+            if let (Some(progress), Some(state)) = (extract(msg), observer_state.upgrade()) {
+                state.update(progress);
+            }
+        });
+        ProgressBarBinding {
+            _guard: guard,
+            state,
+            stream: stream.clone(),
+        }
+    }
+
+    /// Send the message created by `cancel` when `button` is clicked.
+    pub fn with_cancel_button<F>(self, button: &gtk::Button, cancel: F) -> Self
+        where F: Fn() -> MSG + 'static,
+    {
+        let stream = self.stream.clone();
+        button.connect_clicked(move |_| stream.emit(cancel()));
+        *self.state.cancel_button.borrow_mut() = Some(button.clone());
+        self
+    }
+
+    /// Update the progress bar and the cancel button directly, without sending a message.
+    pub fn update(&self, progress: &Progress) {
+        self.state.update(progress);
+    }
+}
+
+impl<MSG> Drop for ProgressBarBinding<MSG> {
+    fn drop(&mut self) {
+        self.state.stop_pulsing();
+    }
+}
@@ -0,0 +1,129 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::future::Future;
+
+use crate::cancellation::CancellationToken;
+use crate::core::StreamHandle;
+
+use self::RetryEvent::*;
+
+/// Delays between the attempts of [`retry()`](fn.retry.html): the first retry happens after the
+/// initial delay, which is then multiplied by the factor after each failure, up to the maximum
+/// delay.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Backoff {
+    factor: f64,
+    initial_delay: u32,
+    max_attempts: Option<u32>,
+    max_delay: u32,
+}
+
+impl Backoff {
+    /// Create an exponential backoff starting with a delay of `initial_delay` ms, doubling after
+    /// each failure up to a minute, and retrying forever.
+    pub fn new(initial_delay: u32) -> Self {
+        Backoff {
+            factor: 2.0,
+            initial_delay,
+            max_attempts: None,
+            max_delay: 60_000,
+        }
+    }
+
+    /// Multiply the delay by `factor` after each failure.
+    pub fn factor(mut self, factor: f64) -> Self {
+        self.factor = factor;
+        self
+    }
+
+    /// Give up after `max_attempts` failed attempts, including the first one.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    /// Never wait more than `max_delay` ms between two attempts.
+    pub fn max_delay(mut self, max_delay: u32) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Get the delay in ms before the retry following the failed attempt number `attempt`,
+    /// starting at 1.
+    pub fn delay(&self, attempt: u32) -> u32 {
+        let delay = self.initial_delay as f64 * self.factor.powi(attempt.saturating_sub(1) as i32);
+        delay.min(self.max_delay as f64) as u32
+    }
+
+    pub(crate) fn gives_up(&self, attempt: u32) -> bool {
+        self.max_attempts.map_or(false, |max_attempts| attempt >= max_attempts)
+    }
+}
+
+/// Events sent by [`retry()`](fn.retry.html).
+#[derive(Debug)]
+pub enum RetryEvent<T, E> {
+    /// The last attempt failed with this error and no more attempt will be made.
+    GaveUp(E),
+    /// The attempt of this number failed and the operation will be retried after the backoff
+    /// delay.
+    Retrying(u32),
+    /// The operation succeeded with this value.
+    Succeeded(T),
+}
+
+/// Run the future created by `operation` until it succeeds, waiting between the attempts
+/// according to `backoff`, and send the events created by `constructor` to `stream`.
+///
+/// This is useful for flaky network requests or subscriptions:
+///
+/// ```ignore
+/// let feed = relm::retry(relm.stream(), Backoff::new(500).max_attempts(5), || fetch_feed(), Msg::Feed);
+/// ```
+///
+/// where `Msg::Feed` wraps a `RetryEvent<Feed, Error>`.
+/// Cancelling the returned token stops retrying.
+pub fn retry<F, FUTURE, T, E, CALLBACK, MSG>(stream: &StreamHandle<MSG>, backoff: Backoff, mut operation: F,
+    constructor: CALLBACK) -> CancellationToken
+    where F: FnMut() -> FUTURE + 'static,
+          FUTURE: Future<Output=Result<T, E>> + 'static,
+          CALLBACK: Fn(RetryEvent<T, E>) -> MSG + 'static,
+          MSG: 'static,
+{
+    let events = stream.clone();
+    stream.spawn_local(async move {
+        let mut attempt = 1;
+        loop {
+            match operation().await {
+                Ok(value) => return constructor(Succeeded(value)),
+                Err(error) => {
+                    if backoff.gives_up(attempt) {
+                        return constructor(GaveUp(error));
+                    }
+                    events.emit(constructor(Retrying(attempt)));
+                    glib::timeout_future(backoff.delay(attempt)).await;
+                    attempt += 1;
+                },
+            }
+        }
+    })
+}
@@ -0,0 +1,214 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! List filtered by a search entry as the user types.
+//!
+//! ```ignore
+//! let fruits = relm::create_component::<SearchableList<String>>(vec!["Apple".to_string(), "Banana".to_string()]);
+//! connect!(fruits@Activated(index), relm, FruitChosen(index));
+//! ```
+//!
+//! The items are shown and matched, ignoring the case, with their `Display` implementation.
+//! The filtering is debounced by the `search-changed` signal of `gtk::SearchEntry`.
+//! Pressing Enter in the search entry activates the first visible item.
+
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::rc::Rc;
+
+use gtk::{
+    BoxExt,
+    ContainerExt,
+    EntryExt,
+    LabelExt,
+    ListBoxExt,
+    ListBoxRowExt,
+    ScrolledWindowExt,
+    SearchEntryExt,
+    WidgetExt,
+};
+
+use crate::core::StreamHandle;
+use crate::state::{DisplayVariant, Relm, Update};
+use crate::widget::Widget;
+
+use self::SearchableListMsg::*;
+
+/// Messages of the [`SearchableList`](struct.SearchableList.html) component.
+///
+/// The first messages are commands sent to the component, the others are events sent by the
+/// component.
+/// The indexes are the indexes of the items, not of the visible rows.
+pub enum SearchableListMsg<ITEM> {
+    #[doc(hidden)]
+    ActivateFirst,
+    #[doc(hidden)]
+    RowActivated(usize),
+    #[doc(hidden)]
+    RowSelected(Option<usize>),
+    #[doc(hidden)]
+    SearchChanged(String),
+    /// Replace the items of the list.
+    SetItems(Vec<ITEM>),
+
+    /// The item was activated, by a double-click or by pressing Enter.
+    Activated(usize),
+    /// The selected item changed.
+    SelectionChanged(Option<usize>),
+}
+
+impl<ITEM> DisplayVariant for SearchableListMsg<ITEM> {
+    fn display_variant(&self) -> &'static str {
+        match *self {
+            ActivateFirst => "ActivateFirst",
+            RowActivated(_) => "RowActivated",
+            RowSelected(_) => "RowSelected",
+            SearchChanged(_) => "SearchChanged",
+            SetItems(_) => "SetItems",
+            Activated(_) => "Activated",
+            SelectionChanged(_) => "SelectionChanged",
+        }
+    }
+}
+
+#[doc(hidden)]
+pub struct SearchableListModel<ITEM> {
+    items: Vec<ITEM>,
+    // The lowercase labels of the items and the lowercase query, shared with the filter function.
+    labels: Rc<RefCell<Vec<String>>>,
+    query: Rc<RefCell<String>>,
+}
+
+/// A `gtk::SearchEntry` above a list of items, which only shows the items matching the search.
+/// The model parameter is the initial list of items.
+pub struct SearchableList<ITEM: Display + 'static> {
+    list: gtk::ListBox,
+    model: SearchableListModel<ITEM>,
+    root: gtk::Box,
+    stream: StreamHandle<SearchableListMsg<ITEM>>,
+}
+
+impl<ITEM: Display + 'static> SearchableList<ITEM> {
+    fn fill_list(&self) {
+        for row in self.list.get_children() {
+            self.list.remove(&row);
+        }
+        let mut labels = self.model.labels.borrow_mut();
+        labels.clear();
+        for item in &self.model.items {
+            let text = item.to_string();
+            labels.push(text.to_lowercase());
+            let label = gtk::Label::new(Some(&text));
+            label.set_halign(gtk::Align::Start);
+            label.show();
+            self.list.add(&label);
+        }
+    }
+}
+
+impl<ITEM: Display + 'static> Update for SearchableList<ITEM> {
+    type Model = SearchableListModel<ITEM>;
+    type ModelParam = Vec<ITEM>;
+    type Msg = SearchableListMsg<ITEM>;
+
+    fn model(_: &Relm<Self>, items: Vec<ITEM>) -> SearchableListModel<ITEM> {
+        SearchableListModel {
+            items,
+            labels: Rc::new(RefCell::new(vec![])),
+            query: Rc::new(RefCell::new(String::new())),
+        }
+    }
+
+    fn update(&mut self, event: SearchableListMsg<ITEM>) {
+        match event {
+            ActivateFirst => {
+                let first_visible = self.list.get_children().into_iter()
+                    .position(|row| row.get_child_visible());
+                if let Some(index) = first_visible {
+                    self.stream.emit(Activated(index));
+                }
+            },
+            RowActivated(index) => self.stream.emit(Activated(index)),
+            RowSelected(index) => self.stream.emit(SelectionChanged(index)),
+            SearchChanged(query) => {
+                *self.model.query.borrow_mut() = query.to_lowercase();
+                self.list.invalidate_filter();
+            },
+            SetItems(items) => {
+                self.model.items = items;
+                self.fill_list();
+            },
+            // Events to be listened to by the parent.
+            Activated(_) | SelectionChanged(_) => (),
+        }
+    }
+}
+
+impl<ITEM: Display + 'static> Widget for SearchableList<ITEM> {
+    type Root = gtk::Box;
+
+    fn root(&self) -> Self::Root {
+        self.root.clone()
+    }
+
+    fn view(relm: &Relm<Self>, model: SearchableListModel<ITEM>) -> Self {
+        let root = gtk::Box::new(gtk::Orientation::Vertical, 6);
+        let search_entry = gtk::SearchEntry::new();
+        root.pack_start(&search_entry, false, false, 0);
+        let scrolled_window = gtk::ScrolledWindow::new(None::<&gtk::Adjustment>, None::<&gtk::Adjustment>);
+        scrolled_window.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+        let list = gtk::ListBox::new();
+        scrolled_window.add(&list);
+        root.pack_start(&scrolled_window, true, true, 0);
+
+        let labels = model.labels.clone();
+        let query = model.query.clone();
+        list.set_filter_func(Some(Box::new(move |row| {
+            let query = query.borrow();
+            labels.borrow().get(row.get_index() as usize)
+                .map(|label| label.contains(query.as_str()))
+                .unwrap_or(true)
+        })));
+
+        let stream = relm.stream().clone();
+        search_entry.connect_search_changed(move |entry| {
+            stream.emit(SearchChanged(entry.get_text().to_string()));
+        });
+        let stream = relm.stream().clone();
+        search_entry.connect_activate(move |_| stream.emit(ActivateFirst));
+        let stream = relm.stream().clone();
+        list.connect_row_activated(move |_, row| stream.emit(RowActivated(row.get_index() as usize)));
+        let stream = relm.stream().clone();
+        list.connect_row_selected(move |_, row| {
+            stream.emit(RowSelected(row.map(|row| row.get_index() as usize)));
+        });
+        root.show_all();
+
+        let searchable_list = SearchableList {
+            list,
+            model,
+            root,
+            stream: relm.stream().clone(),
+        };
+        searchable_list.fill_list();
+        searchable_list
+    }
+}
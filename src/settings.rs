@@ -0,0 +1,179 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Model fields backed by `gio::Settings`.
+
+use gio::{Settings, SettingsExt, SettingsSchemaSource, SettingsSchemaSourceExt};
+
+use crate::core::StreamHandle;
+
+/// Type that can be stored in `gio::Settings`.
+pub trait SettingValue: Default + Sized {
+    /// Read the value of `key`.
+    fn load(settings: &Settings, key: &str) -> Self;
+    /// Write the value of `key`.
+    fn store(&self, settings: &Settings, key: &str) -> bool;
+}
+
+impl SettingValue for bool {
+    fn load(settings: &Settings, key: &str) -> Self {
+        settings.get_boolean(key)
+    }
+
+    fn store(&self, settings: &Settings, key: &str) -> bool {
+        settings.set_boolean(key, *self).is_ok()
+    }
+}
+
+impl SettingValue for i32 {
+    fn load(settings: &Settings, key: &str) -> Self {
+        settings.get_int(key)
+    }
+
+    fn store(&self, settings: &Settings, key: &str) -> bool {
+        settings.set_int(key, *self).is_ok()
+    }
+}
+
+impl SettingValue for u32 {
+    fn load(settings: &Settings, key: &str) -> Self {
+        settings.get_uint(key)
+    }
+
+    fn store(&self, settings: &Settings, key: &str) -> bool {
+        settings.set_uint(key, *self).is_ok()
+    }
+}
+
+impl SettingValue for f64 {
+    fn load(settings: &Settings, key: &str) -> Self {
+        settings.get_double(key)
+    }
+
+    fn store(&self, settings: &Settings, key: &str) -> bool {
+        settings.set_double(key, *self).is_ok()
+    }
+}
+
+impl SettingValue for String {
+    fn load(settings: &Settings, key: &str) -> Self {
+        settings.get_string(key)
+            .map(|value| value.to_string())
+            .unwrap_or_default()
+    }
+
+    fn store(&self, settings: &Settings, key: &str) -> bool {
+        settings.set_string(key, self).is_ok()
+    }
+}
+
+/// Model field loaded from a `gio::Settings` key and written back when it is changed.
+///
+/// If the schema is not installed, an error is logged and the field only holds its default value.
+pub struct Setting<T> {
+    key: &'static str,
+    settings: Option<Settings>,
+    value: T,
+}
+
+impl<T: SettingValue> Setting<T> {
+    /// Load the `key` of the settings with the `schema` id.
+    pub fn new(schema: &str, key: &'static str) -> Self {
+        let installed = SettingsSchemaSource::get_default()
+            .and_then(|source| source.lookup(schema, true))
+            .is_some();
+        let settings =
+            if installed {
+                Some(Settings::new(schema))
+            }
+            else {
+                log::error!("GSettings schema {} is not installed", schema);
+                None
+            };
+        let value = settings.as_ref()
+            .map(|settings| T::load(settings, key))
+            .unwrap_or_default();
+        Setting {
+            key,
+            settings,
+            value,
+        }
+    }
+
+    /// Get the current value.
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+
+    /// Get the key of the setting.
+    pub fn key(&self) -> &'static str {
+        self.key
+    }
+
+    /// Change the value and write it to the settings.
+    pub fn set(&mut self, value: T) {
+        if let Some(ref settings) = self.settings {
+            if !value.store(settings, self.key) {
+                log::error!("Cannot write GSettings key {}", self.key);
+            }
+        }
+        self.value = value;
+    }
+
+    /// Read the value from the settings again, e.g. after it was changed externally.
+    pub fn reload(&mut self) {
+        if let Some(ref settings) = self.settings {
+            self.value = T::load(settings, self.key);
+        }
+    }
+
+    /// Emit the message created by `constructor` with the key of this setting whenever it is
+    /// changed, including by another application.
+    pub fn watch<F, MSG>(&self, stream: &StreamHandle<MSG>, constructor: F)
+        where F: Fn(&'static str) -> MSG + 'static,
+              MSG: 'static,
+    {
+        if let Some(ref settings) = self.settings {
+            let key = self.key;
+            let stream = stream.clone();
+            let _ = settings.connect_changed(move |_, changed_key| {
+                if changed_key == key {
+                    stream.emit(constructor(key));
+                }
+            });
+        }
+    }
+}
+
+/// Trait implemented by `#[derive(Settings)]` for the models having fields with the
+/// `#[setting(schema = "org.example.App", key = "window-width")]` attribute.
+pub trait SettingsModel: Sized {
+    /// Create the model by loading the settings fields.
+    /// The other fields are initialized with their default value.
+    fn load_settings() -> Self;
+
+    /// Emit the message created by `constructor` with the key of the setting whenever one of the
+    /// settings fields changes.
+    /// The `update()` method can then call [`Setting::reload()`](struct.Setting.html#method.reload).
+    fn watch_settings<F, MSG>(&self, stream: &StreamHandle<MSG>, constructor: F)
+        where F: Fn(&'static str) -> MSG + Clone + 'static,
+              MSG: 'static;
+}
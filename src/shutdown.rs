@@ -0,0 +1,161 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Coordinated shutdown of the application, letting the components save their state before the
+//! main loop stops.
+
+use std::cell::{Cell, RefCell};
+use std::time::{Duration, Instant};
+
+use glib::Continue;
+
+use crate::core::EventStream;
+use crate::widget::Widget;
+
+const MIN_PRUNE_LEN: usize = 64;
+
+struct Entry {
+    // Send the shutdown message to the component, returning false if it was dropped.
+    notify: Box<dyn Fn() -> bool>,
+    pending_messages: Box<dyn Fn() -> Option<usize>>,
+}
+
+impl Entry {
+    fn is_alive(&self) -> bool {
+        (self.pending_messages)().is_some()
+    }
+}
+
+struct Registry {
+    components: Vec<Entry>,
+    // The dropped components are only removed once the registry reaches this length, to keep the
+    // registration of a component in constant amortized time.
+    prune_len: usize,
+}
+
+thread_local! {
+    static COMPONENTS: RefCell<Registry> = RefCell::new(Registry {
+        components: vec![],
+        prune_len: MIN_PRUNE_LEN,
+    });
+    static DELAYS: Cell<usize> = Cell::new(0);
+    static SHUTTING_DOWN: Cell<bool> = Cell::new(false);
+}
+
+/// Register a newly created component which wants to be notified of the shutdown.
+pub(crate) fn register<WIDGET>(stream: &EventStream<WIDGET::Msg>)
+    where WIDGET: Widget + 'static,
+          WIDGET::Msg: 'static,
+{
+    if WIDGET::shutdown_msg().is_none() {
+        return;
+    }
+    let stream = stream.downgrade();
+    let pending_stream = stream.clone();
+    COMPONENTS.with(|components| {
+        let mut registry = components.borrow_mut();
+        if registry.components.len() >= registry.prune_len {
+            registry.components.retain(Entry::is_alive);
+            registry.prune_len = MIN_PRUNE_LEN.max(registry.components.len() * 2);
+        }
+        registry.components.push(Entry {
+            notify: Box::new(move || {
+                if stream.is_dropped() {
+                    return false;
+                }
+                if let Some(msg) = WIDGET::shutdown_msg() {
+                    stream.emit(msg);
+                }
+                true
+            }),
+            pending_messages: Box::new(move || pending_stream.pending_messages()),
+        });
+    });
+}
+
+/// Guard delaying the end of the shutdown until it is dropped.
+/// See [`delay_shutdown()`](fn.delay_shutdown.html).
+#[must_use]
+pub struct ShutdownDelay {
+    _private: (),
+}
+
+impl Drop for ShutdownDelay {
+    fn drop(&mut self) {
+        DELAYS.with(|delays| delays.set(delays.get().saturating_sub(1)));
+    }
+}
+
+/// Delay the end of the shutdown until the returned guard is dropped, e.g. while a file is
+/// being saved asynchronously.
+/// This is meant to be called when handling the message of
+/// [`Widget::shutdown_msg()`](trait.Widget.html#method.shutdown_msg).
+pub fn delay_shutdown() -> ShutdownDelay {
+    DELAYS.with(|delays| delays.set(delays.get() + 1));
+    ShutdownDelay {
+        _private: (),
+    }
+}
+
+/// Check if [`shutdown()`](fn.shutdown.html) was called and the main loop is not stopped yet.
+pub fn is_shutting_down() -> bool {
+    SHUTTING_DOWN.with(Cell::get)
+}
+
+/// Stop the application gracefully.
+///
+/// The message returned by [`Widget::shutdown_msg()`](trait.Widget.html#method.shutdown_msg) is
+/// sent to every live component. The main loop is then stopped once these messages were handled
+/// and all the guards returned by [`delay_shutdown()`](fn.delay_shutdown.html) were dropped, or
+/// after `timeout` ms, whichever comes first.
+///
+/// Use this instead of `gtk::main_quit()` to give the components a chance to save their state.
+pub fn shutdown(timeout: u32) {
+    if SHUTTING_DOWN.with(|shutting_down| shutting_down.replace(true)) {
+        return;
+    }
+    // Take the entries out of the registry since the components could be created or dropped
+    // while handling the messages.
+    let components = COMPONENTS.with(|components| {
+        let mut registry = components.borrow_mut();
+        registry.prune_len = MIN_PRUNE_LEN;
+        std::mem::replace(&mut registry.components, vec![])
+    });
+    let components: Vec<_> = components.into_iter()
+        .filter(|entry| (entry.notify)())
+        .collect();
+    let start = Instant::now();
+    let timeout = Duration::from_millis(timeout as u64);
+    let _ = glib::timeout_add_local(10, move || {
+        let flushed = components.iter()
+            .all(|entry| (entry.pending_messages)().unwrap_or(0) == 0);
+        let delayed = DELAYS.with(Cell::get) > 0;
+        if (flushed && !delayed) || start.elapsed() >= timeout {
+            if !flushed || delayed {
+                log::warn!("Shutdown timed out before the components were done");
+            }
+            SHUTTING_DOWN.with(|shutting_down| shutting_down.set(false));
+            gtk::main_quit();
+            return Continue(false);
+        }
+        Continue(true)
+    });
+}
@@ -0,0 +1,104 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use glib::{Cast, Continue};
+use gtk::{GtkWindowExt, WidgetExt};
+
+use crate::component::Component;
+use crate::state::InitError;
+use crate::widget::Widget;
+use super::try_init;
+
+/// Create the `SPLASH` widget and show it right away, then create the `WIDGET` widget in the
+/// background and run the main event loops.
+///
+/// The window of `WIDGET` is hidden until it sends a message for which `is_ready` returns
+/// `true`: the splash window is then destroyed and the main window is shown.
+/// Thus, the model of `WIDGET` can load its data asynchronously and send the ready message once
+/// it is done:
+///
+/// ```ignore
+/// relm::run_with_splash::<Splash, Win, _>((), (), |msg| matches!(msg, Msg::Loaded(_)))?;
+/// ```
+pub fn run_with_splash<SPLASH, WIDGET, F>(splash_param: SPLASH::ModelParam, model_param: WIDGET::ModelParam,
+    is_ready: F) -> Result<(), InitError>
+    where SPLASH: Widget + 'static,
+          WIDGET: Widget + 'static,
+          WIDGET::ModelParam: 'static,
+          F: Fn(&WIDGET::Msg) -> bool + 'static,
+{
+    gtk::init()?;
+    let splash = Rc::new(RefCell::new(Some(try_init::<SPLASH>(splash_param)?)));
+    let main_component: Rc<RefCell<Option<Component<WIDGET>>>> = Rc::new(RefCell::new(None));
+
+    {
+        let main_component = main_component.clone();
+        let mut model_param = Some(model_param);
+        // Wait for the splash window to be drawn before doing the expensive initialization.
+        let _ = glib::idle_add_local(move || {
+            if let Some(model_param) = model_param.take() {
+                match try_init::<WIDGET>(model_param) {
+                    Ok(component) => {
+                        let window = main_window(&component);
+                        if let Some(ref window) = window {
+                            window.hide();
+                        }
+                        let splash = splash.clone();
+                        component.stream().observe(move |msg| {
+                            if is_ready(msg) {
+                                if let Some(ref window) = window {
+                                    window.present();
+                                }
+                                // Destroy the splash component once the current message is handled.
+                                let splash = splash.clone();
+                                let _ = glib::idle_add_local(move || {
+                                    if let Some(splash) = splash.borrow_mut().take() {
+                                        // Not closed, since closing the splash window could quit the application.
+                                        splash.widget().destroy();
+                                    }
+                                    Continue(false)
+                                });
+                            }
+                        });
+                        *main_component.borrow_mut() = Some(component);
+                    },
+                    Err(error) => {
+                        log::error!("Cannot initialize widget {}: {}", std::any::type_name::<WIDGET>(), error);
+                        gtk::main_quit();
+                    },
+                }
+            }
+            Continue(false)
+        });
+    }
+
+    gtk::main();
+    // Destroy the main component after the main loop since it might still be used until then.
+    let _ = main_component.borrow_mut().take();
+    Ok(())
+}
+
+fn main_window<WIDGET: Widget>(component: &Component<WIDGET>) -> Option<gtk::Window> {
+    component.widget().clone().upcast::<gtk::Widget>().downcast::<gtk::Window>().ok()
+}
@@ -0,0 +1,201 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Customization of the startup of relm applications.
+
+use std::env;
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+use std::slice;
+
+use glib::Cast;
+use gtk::{GtkWindowExt, IconThemeExt};
+
+use crate::component::Component;
+use crate::state::InitError;
+use crate::widget::Widget;
+use super::try_init;
+
+/// Options to customize how a relm widget is started, created with a builder:
+///
+/// ```ignore
+/// let options = RunOptions::new()
+///     .program_name("notes")
+///     .resource_base_path("/org/example/Notes")
+///     .before_show(|component| component.widget().set_title("Notes"));
+/// Win::run_with(options, ())?;
+/// ```
+pub struct RunOptions<WIDGET: Widget> {
+    pub(crate) args: Option<Vec<String>>,
+    pub(crate) before_show: Option<Box<dyn FnOnce(&Component<WIDGET>)>>,
+    pub(crate) program_name: Option<String>,
+    pub(crate) resource_base_path: Option<String>,
+    pub(crate) startup_msgs: Option<Box<dyn FnOnce(&[String]) -> Vec<WIDGET::Msg>>>,
+}
+
+impl<WIDGET: Widget> RunOptions<WIDGET> {
+    /// Create the default options, i.e. the same initialization as [`run()`](fn.run.html).
+    pub fn new() -> Self {
+        RunOptions {
+            args: None,
+            before_show: None,
+            program_name: None,
+            resource_base_path: None,
+            startup_msgs: None,
+        }
+    }
+
+    /// Use these command line arguments instead of the arguments of the process.
+    /// With [`run_with()`](fn.run_with.html), GTK+ is initialized with them, so that it handles
+    /// its own options like `--display`, and the remaining ones are given to the
+    /// [`startup_msgs()`](#method.startup_msgs) callback.
+    /// They are parsed by the `gtk::Application` with [`run_app_with()`](fn.run_app_with.html).
+    pub fn args(mut self, args: Vec<String>) -> Self {
+        self.args = Some(args);
+        self
+    }
+
+    /// Call `callback` with the component of the widget once it is created, before the main loop
+    /// starts, so before its window is first drawn.
+    /// This is useful to restore the size of the window or to set its application.
+    pub fn before_show<F: FnOnce(&Component<WIDGET>) + 'static>(mut self, callback: F) -> Self {
+        self.before_show = Some(Box::new(callback));
+        self
+    }
+
+    /// Set the program name, which is used, among others, as the `WM_CLASS` of the windows and
+    /// as the directory name of the state files.
+    pub fn program_name(mut self, name: &str) -> Self {
+        self.program_name = Some(name.to_string());
+        self
+    }
+
+    /// Set the path of the resources of the application, under which the icons are looked up in
+    /// the `icons` directory.
+    /// A `gtk::Application` also loads its menus and its CSS from there.
+    pub fn resource_base_path(mut self, path: &str) -> Self {
+        self.resource_base_path = Some(path.to_string());
+        self
+    }
+
+    /// Convert the command line arguments, without the program name, to messages sent to the
+    /// component right after it is created, so that `app file.txt` can open the file by sending
+    /// the same message as the open dialog:
+    ///
+    /// ```ignore
+    /// RunOptions::new().startup_msgs(|args| args.iter().map(|path| Msg::Open(path.into())).collect())
+    /// ```
+    ///
+    /// With a `gtk::Application`, only the command line of the first instance is converted, and it
+    /// is then not sent to the `command_line_msg()` and `open_msg()` methods of the
+    /// `ApplicationWidget`.
+    pub fn startup_msgs<F>(mut self, callback: F) -> Self
+        where F: FnOnce(&[String]) -> Vec<WIDGET::Msg> + 'static,
+    {
+        self.startup_msgs = Some(Box::new(callback));
+        self
+    }
+
+    /// Apply the options which need to be set before GTK+ is initialized.
+    pub(crate) fn before_init(&self) {
+        if let Some(ref name) = self.program_name {
+            glib::set_prgname(Some(name));
+        }
+    }
+
+    pub(crate) fn arguments(&self) -> Vec<String> {
+        self.args.clone().unwrap_or_else(|| env::args().collect())
+    }
+
+    /// Call the hooks to run once the component is created.
+    pub(crate) fn started(&mut self, component: &Component<WIDGET>) {
+        if let Some(callback) = self.before_show.take() {
+            callback(component);
+        }
+        if let Some(callback) = self.startup_msgs.take() {
+            let arguments = self.arguments();
+            for msg in callback(arguments.get(1..).unwrap_or_default()) {
+                component.emit(msg);
+            }
+        }
+    }
+}
+
+impl<WIDGET: Widget> Default for RunOptions<WIDGET> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Same as [`try_run()`](fn.try_run.html), but with custom options.
+pub fn run_with<WIDGET>(mut options: RunOptions<WIDGET>, model_param: WIDGET::ModelParam) -> Result<(), InitError>
+    where WIDGET: Widget + 'static,
+{
+    options.before_init();
+    match options.args.take() {
+        Some(args) => options.args = Some(init_gtk(args)?),
+        None => gtk::init()?,
+    }
+    if let Some(ref path) = options.resource_base_path {
+        if let Some(icon_theme) = gtk::IconTheme::get_default() {
+            icon_theme.add_resource_path(&format!("{}/icons", path));
+        }
+    }
+    let component = try_init::<WIDGET>(model_param)?;
+    options.started(&component);
+    if let Ok(window) = component.widget().clone().upcast::<gtk::Widget>().downcast::<gtk::Window>() {
+        window.present();
+    }
+    gtk::main();
+    Ok(())
+}
+
+/// Initialize GTK+ with the command line arguments `args` and return the ones it did not handle.
+fn init_gtk(args: Vec<String>) -> Result<Vec<String>, InitError> {
+    if gtk::is_initialized_main_thread() {
+        // GTK+ only parses the arguments the first time it is initialized.
+        return Ok(args);
+    }
+    let args = args.into_iter()
+        .map(CString::new)
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut argv: Vec<*mut c_char> = args.iter()
+        .map(|arg| arg.as_ptr() as *mut c_char)
+        .collect();
+    argv.push(ptr::null_mut());
+    let mut argc = args.len() as c_int;
+    let mut argv_ptr = argv.as_mut_ptr();
+    unsafe {
+        if gtk_sys::gtk_init_check(&mut argc, &mut argv_ptr) == glib_sys::GFALSE {
+            return Err("Failed to initialize GTK".into());
+        }
+        if glib_sys::g_main_context_acquire(glib_sys::g_main_context_default()) == glib_sys::GFALSE {
+            return Err("Failed to acquire default main context".into());
+        }
+        gtk::set_initialized();
+        // GTK+ removed the options it handled from argv, which still points to the strings of args.
+        let remaining = slice::from_raw_parts(argv_ptr, argc as usize);
+        Ok(remaining.iter()
+            .map(|&arg| CStr::from_ptr(arg).to_string_lossy().into_owned())
+            .collect())
+    }
+}
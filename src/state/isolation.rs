@@ -0,0 +1,120 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::any::{Any, type_name};
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::core::StreamHandle;
+use super::{DisplayVariant, Update};
+
+/// Notification sent when the [`update()`](trait.Update.html#tymethod.update) method of a
+/// component panicked while panic isolation is enabled.
+#[derive(Clone, Debug)]
+pub struct ComponentPanicked {
+    /// The name of the component type.
+    pub component: &'static str,
+    /// The variant of the message that was being handled.
+    pub message: &'static str,
+    /// The message of the panic.
+    pub panic_message: String,
+}
+
+thread_local! {
+    static PANIC_HANDLER: RefCell<Option<Box<dyn Fn(ComponentPanicked)>>> = RefCell::new(None);
+}
+
+/// Catch the panics happening in the `update()` methods of the components and send a message
+/// built by `constructor` to `stream` instead of aborting the application.
+///
+/// The model of a component that panicked might be left in an inconsistent state, so the
+/// application should consider showing an error or recreating the component.
+pub fn isolate_panics<F, MSG>(stream: &StreamHandle<MSG>, constructor: F)
+    where F: Fn(ComponentPanicked) -> MSG + 'static,
+          MSG: 'static,
+{
+    let stream = stream.clone();
+    PANIC_HANDLER.with(move |handler| {
+        *handler.borrow_mut() = Some(Box::new(move |info: ComponentPanicked| {
+            if stream.pending_messages().is_some() {
+                stream.emit(constructor(info));
+            }
+            else {
+                log::error!("Component {} panicked while handling message {}: {}", info.component, info.message,
+                    info.panic_message);
+            }
+        }));
+    });
+}
+
+/// Stop catching the panics happening in the `update()` methods.
+pub fn stop_isolating_panics() {
+    PANIC_HANDLER.with(|handler| *handler.borrow_mut() = None);
+}
+
+/// Call the `update()` method of `component`, followed by `after_update()` when `refresh` is true.
+pub(crate) fn call_update<COMPONENT: Update>(component: &mut COMPONENT, event: COMPONENT::Msg, refresh: bool) {
+    let message = event.display_variant();
+    isolated::<COMPONENT, _>(message, || {
+        component.update(event);
+        if refresh {
+            component.after_update();
+        }
+    });
+}
+
+pub(crate) fn call_after_update<COMPONENT: Update>(component: &mut COMPONENT) {
+    isolated::<COMPONENT, _>("after_update", || component.after_update());
+}
+
+fn isolated<COMPONENT, F: FnOnce()>(message: &'static str, callback: F) {
+    let isolated = PANIC_HANDLER.with(|handler| handler.borrow().is_some());
+    if isolated {
+        let result = panic::catch_unwind(AssertUnwindSafe(callback));
+        if let Err(payload) = result {
+            let info = ComponentPanicked {
+                component: type_name::<COMPONENT>(),
+                message,
+                panic_message: panic_message(&*payload),
+            };
+            PANIC_HANDLER.with(|handler| {
+                if let Some(ref handler) = *handler.borrow() {
+                    handler(info);
+                }
+            });
+        }
+    }
+    else {
+        callback();
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    }
+    else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    }
+    else {
+        "unknown panic".to_string()
+    }
+}
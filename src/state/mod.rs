@@ -33,13 +33,18 @@
 )]
 
 mod into;
+mod isolation;
 mod macros;
 
+use std::error::Error;
 use std::time::SystemTime;
 
 pub use crate::core::{EventStream, StreamHandle};
+use crate::debug::{check_starvation, is_profiling, record_update, starvation_threshold};
 
 pub use self::into::{IntoOption, IntoPair};
+pub use self::isolation::{ComponentPanicked, isolate_panics, stop_isolating_panics};
+use self::isolation::{call_after_update, call_update};
 
 /// Handle event stream to send messages to the [`update()`](trait.Update.html#tymethod.update) method.
 pub struct Relm<UPDATE: Update> {
@@ -69,6 +74,9 @@ impl<UPDATE: Update> Relm<UPDATE> {
     }
 }
 
+/// Error returned when a component cannot be initialized.
+pub type InitError = Box<dyn Error>;
+
 /// Trait for a basic (non-widget) component.
 /// A component has a model (data) associated with it and can mutate it when it receives a message
 /// (in the `update()` method).
@@ -86,6 +94,13 @@ pub trait Update
     /// Create the initial model.
     fn model(relm: &Relm<Self>, param: Self::ModelParam) -> Self::Model;
 
+    /// Create the initial model, or return an error if it cannot be created.
+    /// The default implementation calls [`model()`](trait.Update.html#tymethod.model).
+    /// This error is returned by the `try_*` functions like [`try_init()`](fn.try_init.html).
+    fn try_model(relm: &Relm<Self>, param: Self::ModelParam) -> Result<Self::Model, InitError> {
+        Ok(Self::model(relm, param))
+    }
+
     /// Connect the subscriptions.
     /// Subscriptions are `Future`/`Stream` that are spawn when the object is created.
     fn subscriptions(&mut self, _relm: &Relm<Self>) {
@@ -93,6 +108,12 @@ pub trait Update
 
     /// Method called when a message is received from an event.
     fn update(&mut self, event: Self::Msg);
+
+    /// Method called after each call to [`update()`](trait.Update.html#tymethod.update).
+    /// The `#[widget]` attribute implements it to re-evaluate the `visible` and `sensitive`
+    /// properties bound to the model.
+    fn after_update(&mut self) {
+    }
 }
 
 /// Trait for an `Update` object that can be created directly.
@@ -111,6 +132,19 @@ pub trait UpdateNew: Update {
 pub trait DisplayVariant {
     /// Formats the current variant of the enum.
     fn display_variant(&self) -> &'static str;
+
+    /// Get the index of the current variant in the declaration of the enum.
+    /// This can be used as a compact identifier of the variant, for instance in traces.
+    fn variant_index(&self) -> usize {
+        0
+    }
+
+    /// Get the names of all the variants of the enum, in declaration order.
+    fn variant_names() -> &'static [&'static str]
+        where Self: Sized,
+    {
+        &[]
+    }
 }
 
 impl DisplayVariant for () {
@@ -146,28 +180,48 @@ pub fn init_component<UPDATE>(stream: &EventStream<UPDATE::Msg>, mut component:
     });
 }
 
-fn update_component<COMPONENT>(component: &mut COMPONENT, event: COMPONENT::Msg)
+pub(crate) fn update_component<COMPONENT>(component: &mut COMPONENT, event: COMPONENT::Msg)
     where COMPONENT: Update,
 {
-    if cfg!(debug_assertions) {
+    handle_message(component, event, true);
+}
+
+/// Handle the messages returned by `next_event` until it returns `None`, only calling
+/// [`after_update()`](trait.Update.html#method.after_update) once after the last one, so that the
+/// view is refreshed once for the whole batch.
+pub(crate) fn update_component_batch<COMPONENT, NEXT>(component: &mut COMPONENT, mut next_event: NEXT)
+    where COMPONENT: Update,
+          NEXT: FnMut() -> Option<COMPONENT::Msg>,
+{
+    let mut updated = false;
+    while let Some(event) = next_event() {
+        handle_message(component, event, false);
+        updated = true;
+    }
+    if updated {
+        call_after_update(component);
+    }
+}
+
+fn handle_message<COMPONENT>(component: &mut COMPONENT, event: COMPONENT::Msg, refresh: bool)
+    where COMPONENT: Update,
+{
+    let profiling = is_profiling();
+    let threshold = starvation_threshold();
+    if profiling || threshold.is_some() {
         let time = SystemTime::now();
-        let debug = event.display_variant();
-        let debug =
-            if debug.len() > 100 {
-                format!("{}…", &debug[..100])
-            }
-            else {
-                debug.to_string()
-            };
-        component.update(event);
+        let variant = event.display_variant();
+        call_update(component, event, refresh);
         if let Ok(duration) = time.elapsed() {
-            let ms = duration.subsec_nanos() as u64 / 1_000_000 + duration.as_secs() * 1000;
-            if ms >= 16 {
-                log::warn!("The update function was slow to execute for message {}: {}ms", debug, ms);
+            if profiling {
+                record_update::<COMPONENT>(variant, duration);
+            }
+            if let Some(threshold) = threshold {
+                check_starvation::<COMPONENT>(variant, duration, threshold);
             }
         }
     }
     else {
-        component.update(event)
+        call_update(component, event, refresh)
     }
 }
@@ -0,0 +1,162 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! CSS styles applied to the whole application or to a component.
+
+use std::path::{Path, PathBuf};
+
+use gio::{FileExt, FileMonitorEvent, FileMonitorExt, FileMonitorFlags};
+use glib::{Cast, IsA};
+use gtk::{
+    ContainerExt,
+    CssProvider,
+    CssProviderExt,
+    StyleContext,
+    StyleContextExt,
+    WidgetExt,
+    STYLE_PROVIDER_PRIORITY_APPLICATION,
+};
+use log::error;
+
+/// Where a [`Style`](struct.Style.html) applies.
+pub enum StyleScope<'a> {
+    /// Every widget of the application.
+    App,
+    /// A widget and the descendants it has when the style is created, like the root of a
+    /// component.
+    Widget(&'a gtk::Widget),
+}
+
+/// CSS provider that is removed when dropped, so keep it for as long as the style must apply.
+pub struct Style {
+    // Only kept to keep the hot-reload alive.
+    _monitor: Option<gio::FileMonitor>,
+    provider: CssProvider,
+    widgets: Option<Vec<gtk::Widget>>,
+}
+
+impl Style {
+    /// Apply the CSS `data` to `scope`.
+    pub fn new(scope: StyleScope, data: &str) -> Result<Self, glib::Error> {
+        let provider = CssProvider::new();
+        provider.load_from_data(data.as_bytes())?;
+        Ok(Self::apply(scope, provider, None))
+    }
+
+    /// Apply the CSS file at `path` to `scope`.
+    ///
+    /// In debug builds, the file is watched and reloaded when it changes, so that the style can be
+    /// tweaked without restarting the application. A file that fails to parse on reload is logged
+    /// and the previous style is kept.
+    pub fn from_file<P: AsRef<Path>>(scope: StyleScope, path: P) -> Result<Self, glib::Error> {
+        let path = path.as_ref().to_path_buf();
+        let provider = CssProvider::new();
+        provider.load_from_path(&path.to_string_lossy())?;
+        let monitor =
+            if cfg!(debug_assertions) {
+                watch(&provider, path)
+            }
+            else {
+                None
+            };
+        Ok(Self::apply(scope, provider, monitor))
+    }
+
+    /// Get the underlying `gtk::CssProvider`.
+    pub fn provider(&self) -> &CssProvider {
+        &self.provider
+    }
+
+    fn apply(scope: StyleScope, provider: CssProvider, monitor: Option<gio::FileMonitor>) -> Self {
+        let widgets =
+            match scope {
+                StyleScope::App => {
+                    match gdk::Screen::get_default() {
+                        Some(screen) =>
+                            StyleContext::add_provider_for_screen(&screen, &provider,
+                                STYLE_PROVIDER_PRIORITY_APPLICATION),
+                        None => error!("Cannot apply the style: there's no default screen"),
+                    }
+                    None
+                },
+                StyleScope::Widget(widget) => {
+                    let mut widgets = vec![];
+                    collect_descendants(widget, &mut widgets);
+                    for widget in &widgets {
+                        widget.get_style_context().add_provider(&provider, STYLE_PROVIDER_PRIORITY_APPLICATION);
+                    }
+                    Some(widgets)
+                },
+            };
+        Style {
+            _monitor: monitor,
+            provider,
+            widgets,
+        }
+    }
+}
+
+impl Drop for Style {
+    fn drop(&mut self) {
+        match self.widgets {
+            Some(ref widgets) => {
+                for widget in widgets {
+                    widget.get_style_context().remove_provider(&self.provider);
+                }
+            },
+            None => {
+                if let Some(screen) = gdk::Screen::get_default() {
+                    StyleContext::remove_provider_for_screen(&screen, &self.provider);
+                }
+            },
+        }
+    }
+}
+
+fn collect_descendants<WIDGET: IsA<gtk::Widget>>(widget: &WIDGET, widgets: &mut Vec<gtk::Widget>) {
+    widgets.push(widget.clone().upcast());
+    if let Some(container) = widget.dynamic_cast_ref::<gtk::Container>() {
+        for child in container.get_children() {
+            collect_descendants(&child, widgets);
+        }
+    }
+}
+
+fn watch(provider: &CssProvider, path: PathBuf) -> Option<gio::FileMonitor> {
+    let file = gio::File::new_for_path(&path);
+    let monitor =
+        match file.monitor_file(FileMonitorFlags::NONE, None::<&gio::Cancellable>) {
+            Ok(monitor) => monitor,
+            Err(err) => {
+                error!("Cannot watch {} for changes: {}", path.display(), err);
+                return None;
+            },
+        };
+    let provider = provider.clone();
+    monitor.connect_changed(move |_, _, _, event| {
+        if event == FileMonitorEvent::ChangesDoneHint {
+            if let Err(err) = provider.load_from_path(&path.to_string_lossy()) {
+                error!("Cannot reload {}: {}", path.display(), err);
+            }
+        }
+    });
+    Some(monitor)
+}
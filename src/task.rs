@@ -0,0 +1,234 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::{Rc, Weak};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Context, Poll};
+use std::thread;
+
+use glib::{Continue, MainContext, SourceId};
+
+use crate::cancellation::CancellationToken;
+use crate::core::StreamHandle;
+
+type Tasks = RefCell<Vec<(u64, Box<dyn FnOnce()>)>>;
+
+#[derive(Default)]
+struct Inner {
+    next_id: Cell<u64>,
+    // The functions cancelling the tasks which are still running.
+    tasks: Tasks,
+}
+
+impl Inner {
+    fn add<F: FnOnce() + 'static>(&self, cancel: F) -> u64 {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        self.tasks.borrow_mut().push((id, Box::new(cancel)));
+        id
+    }
+
+    fn remove(&self, id: u64) {
+        self.tasks.borrow_mut().retain(|&(task_id, _)| task_id != id);
+    }
+}
+
+/// Set of tasks (futures, timers and worker threads) started by a component, which are cancelled
+/// when the scope is dropped.
+///
+/// Keep the scope in the model of the component so that its tasks do not outlive it and never
+/// emit to the stream of a destroyed component:
+///
+/// ```ignore
+/// fn model(relm: &Relm<Self>, _: ()) -> Model {
+///     let tasks = TaskScope::new();
+///     tasks.interval(relm.stream(), 1000, || Tick);
+///     tasks.spawn_local(relm.stream(), fetch_feed());
+///     Model {
+///         tasks,
+///     }
+/// }
+/// ```
+#[derive(Default)]
+pub struct TaskScope {
+    inner: Rc<Inner>,
+}
+
+impl TaskScope {
+    /// Create an empty scope.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel all the running tasks of the scope.
+    /// The scope can still be used to start new tasks afterwards.
+    pub fn cancel(&self) {
+        // Take the tasks first since cancelling them could reenter the scope.
+        let tasks = std::mem::replace(&mut *self.inner.tasks.borrow_mut(), vec![]);
+        for (_, cancel) in tasks {
+            cancel();
+        }
+    }
+
+    /// Get the number of tasks still running.
+    pub fn len(&self) -> usize {
+        self.inner.tasks.borrow().len()
+    }
+
+    /// Check if there's no task running.
+    pub fn is_empty(&self) -> bool {
+        self.inner.tasks.borrow().is_empty()
+    }
+
+    /// Call `cancel` when the scope is cancelled or dropped, to stop a task started by other means.
+    pub fn on_cancel<F: FnOnce() + 'static>(&self, cancel: F) {
+        self.inner.add(cancel);
+    }
+
+    /// Emit the message created by `constructor` to `stream` every `duration` ms, until the scope is
+    /// cancelled.
+    pub fn interval<F, MSG>(&self, stream: &StreamHandle<MSG>, duration: u32, constructor: F)
+        where F: Fn() -> MSG + 'static,
+              MSG: 'static,
+    {
+        self.add_source(stream, duration, constructor, true);
+    }
+
+    /// After `duration` ms, emit the message created by `constructor` to `stream`, unless the
+    /// scope was cancelled.
+    pub fn timeout<F, MSG>(&self, stream: &StreamHandle<MSG>, duration: u32, constructor: F)
+        where F: Fn() -> MSG + 'static,
+              MSG: 'static,
+    {
+        self.add_source(stream, duration, constructor, false);
+    }
+
+    fn add_source<F, MSG>(&self, stream: &StreamHandle<MSG>, duration: u32, constructor: F, repeat: bool)
+        where F: Fn() -> MSG + 'static,
+              MSG: 'static,
+    {
+        // The source is taken by the first of the callback or the cancellation to end, so that
+        // an already removed source is never removed again.
+        let source_id: Rc<RefCell<Option<SourceId>>> = Rc::new(RefCell::new(None));
+        let cancelled_source = source_id.clone();
+        let id = self.inner.add(move || {
+            if let Some(source_id) = cancelled_source.borrow_mut().take() {
+                glib::source_remove(source_id);
+            }
+        });
+        let inner = Rc::downgrade(&self.inner);
+        let stream = stream.clone();
+        let callback_source = source_id.clone();
+        *source_id.borrow_mut() = Some(glib::timeout_add_local(duration, move || {
+            if !stream.is_dropped() {
+                stream.emit(constructor());
+            }
+            if repeat && !stream.is_dropped() {
+                return Continue(true);
+            }
+            callback_source.borrow_mut().take();
+            if let Some(inner) = inner.upgrade() {
+                inner.remove(id);
+            }
+            Continue(false)
+        }));
+    }
+
+    /// Spawn `future` on the default main context and emit the message it resolves to, unless the
+    /// scope was cancelled before.
+    /// The future is dropped when the scope or the returned token is cancelled.
+    pub fn spawn_local<FUTURE, MSG>(&self, stream: &StreamHandle<MSG>, future: FUTURE) -> CancellationToken
+        where FUTURE: Future<Output=MSG> + 'static,
+              MSG: 'static,
+    {
+        let token = CancellationToken::new();
+        let cancelled_token = token.clone();
+        let id = self.inner.add(move || cancelled_token.cancel());
+        MainContext::default().spawn_local(ScopedFuture {
+            future: Box::pin(future),
+            id,
+            scope: Rc::downgrade(&self.inner),
+            stream: stream.clone(),
+            token: token.clone(),
+        });
+        token
+    }
+
+    /// Run `work` in a new thread.
+    /// The flag given to `work` is set when the scope is cancelled: the worker should check it
+    /// regularly and return when it is set.
+    pub fn spawn_worker<F>(&self, work: F)
+        where F: FnOnce(Arc<AtomicBool>) + Send + 'static,
+    {
+        let stop = Arc::new(AtomicBool::new(false));
+        let worker_stop = stop.clone();
+        self.inner.add(move || stop.store(true, Ordering::SeqCst));
+        thread::spawn(move || work(worker_stop));
+    }
+}
+
+impl Drop for TaskScope {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+/// Future emitting the message of `future` to `stream`, unless its token was cancelled.
+struct ScopedFuture<MSG> {
+    future: Pin<Box<dyn Future<Output=MSG>>>,
+    id: u64,
+    scope: Weak<Inner>,
+    stream: StreamHandle<MSG>,
+    token: CancellationToken,
+}
+
+impl<MSG> ScopedFuture<MSG> {
+    fn finish(&self) -> Poll<()> {
+        if let Some(scope) = self.scope.upgrade() {
+            scope.remove(self.id);
+        }
+        Poll::Ready(())
+    }
+}
+
+impl<MSG> Future for ScopedFuture<MSG> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<()> {
+        if self.token.is_cancelled() || self.stream.is_dropped() {
+            return self.finish();
+        }
+        match self.future.as_mut().poll(context) {
+            Poll::Ready(msg) => {
+                self.stream.emit(msg);
+                self.finish()
+            },
+            Poll::Pending => {
+                self.token.register(context.waker());
+                Poll::Pending
+            },
+        }
+    }
+}
@@ -0,0 +1,155 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Subscription to the frame clock of a widget, for games and continuous animations.
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
+
+use glib::{Cast, Continue, IsA, ObjectExt, SignalHandlerId};
+use gtk::{TickCallbackId, WidgetExt};
+
+use crate::core::StreamHandle;
+
+/// Frame of a [`FrameTicker`](struct.FrameTicker.html).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrameTick {
+    /// The time elapsed since the previous frame, or zero for the first frame after the ticker was
+    /// enabled or the widget was mapped.
+    pub delta: Duration,
+    /// The monotonic time of the frame, as given by the frame clock.
+    pub time: Duration,
+}
+
+struct State<MSG> {
+    constructor: Box<dyn Fn(FrameTick) -> MSG>,
+    enabled: Cell<bool>,
+    last_time: Cell<Option<i64>>,
+    stream: StreamHandle<MSG>,
+    tick_callback: RefCell<Option<TickCallbackId>>,
+    widget: gtk::Widget,
+}
+
+impl<MSG: 'static> State<MSG> {
+    fn start(state: &Rc<Self>) {
+        if !state.enabled.get() || !state.widget.get_mapped() || state.tick_callback.borrow().is_some() {
+            return;
+        }
+        state.last_time.set(None);
+        let weak_state = Rc::downgrade(state);
+        let tick_callback = state.widget.add_tick_callback(move |_, frame_clock| {
+            let state =
+                match weak_state.upgrade() {
+                    Some(state) => state,
+                    None => return Continue(false),
+                };
+            let time = frame_clock.get_frame_time();
+            let delta = state.last_time.replace(Some(time)).map_or(0, |last_time| time - last_time);
+            if !state.stream.is_dropped() {
+                state.stream.emit((state.constructor)(FrameTick {
+                    delta: Duration::from_micros(delta.max(0) as u64),
+                    time: Duration::from_micros(time.max(0) as u64),
+                }));
+            }
+            Continue(true)
+        });
+        *state.tick_callback.borrow_mut() = Some(tick_callback);
+    }
+
+    fn stop(&self) {
+        if let Some(tick_callback) = self.tick_callback.borrow_mut().take() {
+            tick_callback.remove();
+        }
+    }
+}
+
+/// Subscription sending a message at each frame of a widget while it is enabled.
+///
+/// The ticks stop automatically while the widget is unmapped, for instance when it is in a hidden
+/// page of a notebook, and resume when it is mapped again.
+/// The ticker is disabled when it is dropped, so it should be stored in the model.
+pub struct FrameTicker<MSG> {
+    map_handlers: Vec<SignalHandlerId>,
+    state: Rc<State<MSG>>,
+}
+
+impl<MSG: 'static> FrameTicker<MSG> {
+    /// Create a disabled ticker sending the message created by `constructor` to `stream` at each
+    /// frame of `widget`.
+    pub fn new<W, F>(widget: &W, stream: &StreamHandle<MSG>, constructor: F) -> Self
+        where W: IsA<gtk::Widget>,
+              F: Fn(FrameTick) -> MSG + 'static,
+    {
+        let state = Rc::new(State {
+            constructor: Box::new(constructor),
+            enabled: Cell::new(false),
+            last_time: Cell::new(None),
+            stream: stream.clone(),
+            tick_callback: RefCell::new(None),
+            widget: widget.clone().upcast(),
+        });
+        let weak_state = Rc::downgrade(&state);
+        let map_handler = state.widget.connect_map(move |_| {
+            if let Some(state) = weak_state.upgrade() {
+                State::start(&state);
+            }
+        });
+        let weak_state = Rc::downgrade(&state);
+        let unmap_handler = state.widget.connect_unmap(move |_| {
+            if let Some(state) = weak_state.upgrade() {
+                state.stop();
+            }
+        });
+        FrameTicker {
+            map_handlers: vec![map_handler, unmap_handler],
+            state,
+        }
+    }
+
+    /// Check if the ticker is enabled. It only ticks if the widget is also mapped.
+    pub fn is_enabled(&self) -> bool {
+        self.state.enabled.get()
+    }
+
+    /// Start or stop sending the ticks. Call it from `update()` according to the model, for
+    /// instance when a game is paused.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.state.enabled.set(enabled);
+        if enabled {
+            State::start(&self.state);
+        }
+        else {
+            self.state.stop();
+        }
+    }
+}
+
+impl<MSG> Drop for FrameTicker<MSG> {
+    fn drop(&mut self) {
+        if let Some(tick_callback) = self.state.tick_callback.borrow_mut().take() {
+            tick_callback.remove();
+        }
+        for handler in self.map_handlers.drain(..) {
+            self.state.widget.disconnect(handler);
+        }
+    }
+}
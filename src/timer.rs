@@ -0,0 +1,200 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Timer component for stopwatches, countdowns and timeouts which can be paused.
+//!
+//! ```ignore
+//! let timer = relm::execute::<Timer>((1000, Some(Duration::from_secs(25 * 60))));
+//! connect_stream!(timer@Tick(elapsed), relm.stream(), UpdateClock(elapsed));
+//! connect_stream!(timer@Finished, relm.stream(), Break);
+//! timer.emit(TimerMsg::Start);
+//! ```
+
+use std::time::{Duration, Instant};
+
+use glib::{Continue, SourceId};
+
+use crate::core::StreamHandle;
+use crate::state::{DisplayVariant, Relm, Update, UpdateNew};
+
+use self::TimerMsg::*;
+
+/// Messages of the [`Timer`](struct.Timer.html) component.
+///
+/// The first messages are commands sent to the component, the others are events sent by the
+/// component.
+#[derive(Clone, Debug)]
+pub enum TimerMsg {
+    #[doc(hidden)]
+    Beat,
+    /// Pause the timer, keeping the elapsed time.
+    Pause,
+    /// Stop the timer and set the elapsed time to zero.
+    Reset,
+    /// Continue a paused timer.
+    Resume,
+    /// Change the duration after which the timer finishes, or remove it with `None`.
+    SetDuration(Option<Duration>),
+    /// Start the timer from zero.
+    Start,
+
+    /// The duration was reached: the timer is stopped.
+    Finished,
+    /// Sent at each interval, when the timer is reset and when it finishes, with the elapsed time.
+    Tick(Duration),
+}
+
+impl DisplayVariant for TimerMsg {
+    fn display_variant(&self) -> &'static str {
+        match *self {
+            Beat => "Beat",
+            Pause => "Pause",
+            Reset => "Reset",
+            Resume => "Resume",
+            SetDuration(_) => "SetDuration",
+            Start => "Start",
+            Finished => "Finished",
+            Tick(_) => "Tick",
+        }
+    }
+}
+
+#[doc(hidden)]
+pub struct TimerModel {
+    // The time elapsed before the last pause.
+    accumulated: Duration,
+    duration: Option<Duration>,
+    interval: u32,
+    source: Option<SourceId>,
+    started: Option<Instant>,
+}
+
+/// Component measuring the time elapsed while it is running.
+///
+/// The model parameter is the interval between the `Tick` messages, in milliseconds, and the
+/// optional duration after which the timer finishes, for countdowns.
+/// The elapsed time is measured with a monotonic clock, so the ticks do not drift.
+///
+/// It has no widget: create it with [`relm::execute()`](../fn.execute.html).
+pub struct Timer {
+    model: TimerModel,
+    stream: StreamHandle<TimerMsg>,
+}
+
+impl Timer {
+    fn elapsed(&self) -> Duration {
+        let running = self.model.started.map(|started| started.elapsed()).unwrap_or_default();
+        self.model.accumulated + running
+    }
+
+    /// Check if the timer is running.
+    pub fn is_running(&self) -> bool {
+        self.model.started.is_some()
+    }
+
+    fn run(&mut self) {
+        if self.is_running() {
+            return;
+        }
+        self.model.started = Some(Instant::now());
+        let stream = self.stream.clone();
+        self.model.source = Some(glib::timeout_add_local(self.model.interval, move || {
+            if !stream.is_dropped() {
+                stream.emit(Beat);
+            }
+            Continue(true)
+        }));
+    }
+
+    fn stop(&mut self) {
+        self.model.accumulated = self.elapsed();
+        self.model.started = None;
+        if let Some(source) = self.model.source.take() {
+            glib::source_remove(source);
+        }
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+impl Update for Timer {
+    type Model = TimerModel;
+    type ModelParam = (u32, Option<Duration>);
+    type Msg = TimerMsg;
+
+    fn model(_: &Relm<Self>, (interval, duration): (u32, Option<Duration>)) -> TimerModel {
+        TimerModel {
+            accumulated: Duration::default(),
+            duration,
+            interval,
+            source: None,
+            started: None,
+        }
+    }
+
+    fn update(&mut self, event: TimerMsg) {
+        match event {
+            Beat => {
+                // Ignore the beat queued before a pause.
+                if !self.is_running() {
+                    return;
+                }
+                let elapsed = self.elapsed();
+                match self.model.duration {
+                    Some(duration) if elapsed >= duration => {
+                        self.stop();
+                        self.model.accumulated = duration;
+                        self.stream.emit(Tick(duration));
+                        self.stream.emit(Finished);
+                    },
+                    _ => self.stream.emit(Tick(elapsed)),
+                }
+            },
+            Pause => self.stop(),
+            Reset => {
+                self.stop();
+                self.model.accumulated = Duration::default();
+                self.stream.emit(Tick(Duration::default()));
+            },
+            Resume => self.run(),
+            SetDuration(duration) => self.model.duration = duration,
+            Start => {
+                self.stop();
+                self.model.accumulated = Duration::default();
+                self.run();
+            },
+            Finished | Tick(_) => (),
+        }
+    }
+}
+
+impl UpdateNew for Timer {
+    fn new(relm: &Relm<Self>, model: TimerModel) -> Self {
+        Timer {
+            model,
+            stream: relm.stream().clone(),
+        }
+    }
+}
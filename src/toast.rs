@@ -0,0 +1,219 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Stacked, auto-dismissing toasts shown over the content of a window.
+//!
+//! The [`Toaster`](struct.Toaster.html) component is a `gtk::Overlay` wrapping the main content
+//! of the window:
+//!
+//! ```ignore
+//! let toaster = relm::create_component::<Toaster>(());
+//! toaster.widget().add(&content);
+//! window.add(toaster.widget());
+//! // Give this handle to any component which needs to show toasts.
+//! let toasts = toaster.handle();
+//! toasts.show("File saved", ToastLevel::Success, 3000);
+//! ```
+//!
+//! Each toast has the `toast` style class and the style class of its level (`info`, `success`,
+//! `warning` or `error`), so that they can be styled with CSS.
+
+use gtk::{BoxExt, ButtonExt, ContainerExt, LabelExt, OverlayExt, StyleContextExt, WidgetExt};
+
+use crate::component::Component;
+use crate::core::StreamHandle;
+use crate::state::{DisplayVariant, Relm, Update};
+use crate::timeout;
+use crate::widget::Widget;
+
+use self::ToasterMsg::*;
+
+/// The importance of a toast.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ToastLevel {
+    /// Something failed.
+    Error,
+    /// Neutral information.
+    Info,
+    /// Something succeeded.
+    Success,
+    /// Something might need the attention of the user.
+    Warning,
+}
+
+impl ToastLevel {
+    fn style_class(self) -> &'static str {
+        match self {
+            ToastLevel::Error => "error",
+            ToastLevel::Info => "info",
+            ToastLevel::Success => "success",
+            ToastLevel::Warning => "warning",
+        }
+    }
+}
+
+/// Messages of the [`Toaster`](struct.Toaster.html) component.
+pub enum ToasterMsg {
+    #[doc(hidden)]
+    Dismiss(u64),
+    /// Remove all the toasts.
+    DismissAll,
+    /// Show a toast with this text and level for this number of milliseconds.
+    /// A timeout of 0 shows the toast until the user closes it.
+    ShowToast(String, ToastLevel, u32),
+}
+
+impl DisplayVariant for ToasterMsg {
+    fn display_variant(&self) -> &'static str {
+        match *self {
+            Dismiss(_) => "Dismiss",
+            DismissAll => "DismissAll",
+            ShowToast(_, _, _) => "ShowToast",
+        }
+    }
+}
+
+/// Cloneable handle to show toasts in a [`Toaster`](struct.Toaster.html) from any component.
+#[derive(Clone)]
+pub struct ToastHandle {
+    stream: StreamHandle<ToasterMsg>,
+}
+
+impl ToastHandle {
+    /// Show a toast with this text and level for `timeout` milliseconds.
+    /// A timeout of 0 shows the toast until the user closes it.
+    pub fn show(&self, text: &str, level: ToastLevel, timeout: u32) {
+        self.stream.emit(ShowToast(text.to_string(), level, timeout));
+    }
+
+    /// Remove all the toasts.
+    pub fn dismiss_all(&self) {
+        self.stream.emit(DismissAll);
+    }
+}
+
+impl Component<Toaster> {
+    /// Get a handle to show toasts in this toaster.
+    pub fn handle(&self) -> ToastHandle {
+        ToastHandle {
+            stream: self.stream(),
+        }
+    }
+}
+
+#[doc(hidden)]
+pub struct ToasterModel {
+    next_id: u64,
+    toasts: Vec<(u64, gtk::Box)>,
+}
+
+/// Overlay showing the toasts above its child, the newest at the bottom.
+pub struct Toaster {
+    model: ToasterModel,
+    overlay: gtk::Overlay,
+    stream: StreamHandle<ToasterMsg>,
+    toast_box: gtk::Box,
+}
+
+impl Toaster {
+    fn create_toast(&self, id: u64, text: &str, level: ToastLevel) -> gtk::Box {
+        let toast = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+        let style_context = toast.get_style_context();
+        style_context.add_class("toast");
+        style_context.add_class("app-notification");
+        style_context.add_class(level.style_class());
+        let label = gtk::Label::new(Some(text));
+        label.set_line_wrap(true);
+        toast.pack_start(&label, true, true, 0);
+        let close_button = gtk::Button::from_icon_name(Some("window-close-symbolic"), gtk::IconSize::Button);
+        close_button.set_relief(gtk::ReliefStyle::None);
+        let stream = self.stream.clone();
+        close_button.connect_clicked(move |_| stream.emit(Dismiss(id)));
+        toast.pack_start(&close_button, false, false, 0);
+        toast.show_all();
+        toast
+    }
+}
+
+impl Update for Toaster {
+    type Model = ToasterModel;
+    type ModelParam = ();
+    type Msg = ToasterMsg;
+
+    fn model(_: &Relm<Self>, _: ()) -> ToasterModel {
+        ToasterModel {
+            next_id: 0,
+            toasts: vec![],
+        }
+    }
+
+    fn update(&mut self, event: ToasterMsg) {
+        match event {
+            Dismiss(id) => {
+                if let Some(index) = self.model.toasts.iter().position(|&(toast_id, _)| toast_id == id) {
+                    let (_, toast) = self.model.toasts.remove(index);
+                    self.toast_box.remove(&toast);
+                }
+            },
+            DismissAll => {
+                for (_, toast) in self.model.toasts.drain(..) {
+                    self.toast_box.remove(&toast);
+                }
+            },
+            ShowToast(text, level, duration) => {
+                let id = self.model.next_id;
+                self.model.next_id += 1;
+                let toast = self.create_toast(id, &text, level);
+                self.toast_box.pack_start(&toast, false, false, 0);
+                self.model.toasts.push((id, toast));
+                if duration > 0 {
+                    timeout(&self.stream, duration, move || Dismiss(id));
+                }
+            },
+        }
+    }
+}
+
+impl Widget for Toaster {
+    type Root = gtk::Overlay;
+
+    fn root(&self) -> Self::Root {
+        self.overlay.clone()
+    }
+
+    fn view(relm: &Relm<Self>, model: ToasterModel) -> Self {
+        let overlay = gtk::Overlay::new();
+        // Aligned this way, the toasts do not cover the whole content and block its events.
+        let toast_box = gtk::Box::new(gtk::Orientation::Vertical, 6);
+        toast_box.set_halign(gtk::Align::Center);
+        toast_box.set_valign(gtk::Align::End);
+        toast_box.set_margin_bottom(12);
+        overlay.add_overlay(&toast_box);
+        overlay.show_all();
+
+        Toaster {
+            model,
+            overlay,
+            stream: relm.stream().clone(),
+            toast_box,
+        }
+    }
+}
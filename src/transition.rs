@@ -0,0 +1,80 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Notification of the end of the transitions of `gtk::Revealer` and `gtk::Stack`.
+
+use std::rc::Rc;
+
+use gtk::{RevealerExt, StackExt};
+
+/// Widget animating its changes, whose end can be observed.
+///
+/// With this trait in scope, the end of the transition can be connected in the `view!` macro,
+/// while the model drives the transition through the properties:
+///
+/// ```ignore
+/// gtk::Revealer {
+///     reveal_child: self.model.details_visible,
+///     transition_duration: 200,
+///     transition_type: gtk::RevealerTransitionType::SlideDown,
+///     transition_finished(revealer) => TransitionFinished(revealer.get_reveal_child()),
+///     ...
+/// },
+/// gtk::Stack {
+///     transition_type: gtk::StackTransitionType::SlideLeftRight,
+///     visible_child_name: self.model.page.name(),
+///     transition_finished(_) => PageShown,
+///     ...
+/// },
+/// ```
+pub trait TransitionWidget {
+    /// Call `callback` when a transition finished: when the child of a revealer is fully revealed
+    /// or hidden, or when the new visible child of a stack is fully shown.
+    /// The callback is also called when the change happens without animation, for instance when
+    /// the animations are disabled in the settings or the widget is not mapped.
+    fn connect_transition_finished<F: Fn(&Self) + 'static>(&self, callback: F);
+}
+
+impl TransitionWidget for gtk::Revealer {
+    fn connect_transition_finished<F: Fn(&Self) + 'static>(&self, callback: F) {
+        // child-revealed is only updated at the end of the transition.
+        let _ = self.connect_property_child_revealed_notify(callback);
+    }
+}
+
+impl TransitionWidget for gtk::Stack {
+    fn connect_transition_finished<F: Fn(&Self) + 'static>(&self, callback: F) {
+        let callback = Rc::new(callback);
+        let visible_child_callback = callback.clone();
+        // The transition is started before the visible child is notified, so when it is not
+        // running, the change happened without animation.
+        let _ = self.connect_property_visible_child_notify(move |stack| {
+            if !stack.get_transition_running() && stack.get_visible_child().is_some() {
+                visible_child_callback(stack);
+            }
+        });
+        let _ = self.connect_property_transition_running_notify(move |stack| {
+            if !stack.get_transition_running() {
+                callback(stack);
+            }
+        });
+    }
+}
@@ -0,0 +1,119 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Status icon in the system tray, for background applications.
+//!
+//! This uses `gtk::StatusIcon`, which is deprecated in GTK 3 and only shown by the desktops
+//! still supporting the XEmbed system tray: the AppIndicator protocol is not supported.
+
+#![allow(deprecated)]
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk::{
+    GtkMenuExt,
+    GtkMenuItemExt,
+    Menu,
+    MenuItem,
+    MenuShellExt,
+    StatusIconExt,
+    WidgetExt,
+};
+
+use crate::core::StreamHandle;
+
+/// Status icon with a menu whose items send messages.
+///
+/// Keep the `TrayIcon` in the model and call [`set_visible()`](#method.set_visible) and
+/// [`set_menu()`](#method.set_menu) from `update()` to drive it from the model.
+/// The icon is removed from the tray when it is dropped.
+pub struct TrayIcon<MSG> {
+    icon: gtk::StatusIcon,
+    menu: Rc<RefCell<Option<Menu>>>,
+    stream: StreamHandle<MSG>,
+}
+
+impl<MSG: Clone + 'static> TrayIcon<MSG> {
+    /// Create a visible status icon showing the icon named `icon_name`.
+    /// When the icon is clicked, the `activate` message, if any, is sent to `stream`.
+    pub fn new(icon_name: &str, stream: &StreamHandle<MSG>, activate: Option<MSG>) -> Self {
+        let icon = gtk::StatusIcon::from_icon_name(icon_name);
+        if let Some(msg) = activate {
+            let stream = stream.clone();
+            icon.connect_activate(move |_| {
+                stream.emit(msg.clone());
+            });
+        }
+        let menu: Rc<RefCell<Option<Menu>>> = Rc::new(RefCell::new(None));
+        {
+            let menu = menu.clone();
+            icon.connect_popup_menu(move |_, button, activate_time| {
+                if let Some(ref menu) = *menu.borrow() {
+                    menu.popup_easy(button, activate_time);
+                }
+            });
+        }
+        TrayIcon {
+            icon,
+            menu,
+            stream: stream.clone(),
+        }
+    }
+
+    /// Set the items of the menu shown on right click.
+    /// Each item is a label with the message sent when it is activated.
+    pub fn set_menu(&self, items: &[(&str, MSG)]) {
+        let menu = Menu::new();
+        for &(label, ref msg) in items {
+            let item = MenuItem::with_label(label);
+            let stream = self.stream.clone();
+            let msg = msg.clone();
+            item.connect_activate(move |_| {
+                stream.emit(msg.clone());
+            });
+            menu.append(&item);
+        }
+        menu.show_all();
+        *self.menu.borrow_mut() = Some(menu);
+    }
+
+    /// Set the tooltip of the icon.
+    pub fn set_tooltip(&self, text: &str) {
+        self.icon.set_tooltip_text(Some(text));
+    }
+
+    /// Show or hide the icon.
+    pub fn set_visible(&self, visible: bool) {
+        self.icon.set_visible(visible);
+    }
+
+    /// Get the underlying `gtk::StatusIcon`.
+    pub fn icon(&self) -> &gtk::StatusIcon {
+        &self.icon
+    }
+}
+
+impl<MSG> Drop for TrayIcon<MSG> {
+    fn drop(&mut self) {
+        self.icon.set_visible(false);
+    }
+}
@@ -0,0 +1,108 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Subscriptions converting the Unix signals received by the process into messages.
+
+use glib::{Continue, SourceId};
+
+use crate::core::StreamHandle;
+
+/// Unix signal which can be converted into a message.
+///
+/// These are the signals supported by `g_unix_signal_add()`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UnixSignal {
+    /// `SIGHUP`, sent when the terminal is closed.
+    Hangup,
+    /// `SIGINT`, sent on Ctrl-C.
+    Interrupt,
+    /// `SIGTERM`, the default signal of `kill`.
+    Terminate,
+    /// `SIGUSR1`.
+    User1,
+    /// `SIGUSR2`.
+    User2,
+    /// `SIGWINCH`, sent when the terminal is resized.
+    WindowChange,
+}
+
+impl UnixSignal {
+    fn number(self) -> i32 {
+        match self {
+            UnixSignal::Hangup => libc::SIGHUP,
+            UnixSignal::Interrupt => libc::SIGINT,
+            UnixSignal::Terminate => libc::SIGTERM,
+            UnixSignal::User1 => libc::SIGUSR1,
+            UnixSignal::User2 => libc::SIGUSR2,
+            UnixSignal::WindowChange => libc::SIGWINCH,
+        }
+    }
+}
+
+/// Subscription sending a message when the process receives one of the watched signals.
+///
+/// The handlers are dispatched from the main loop, so `update()` can save the state and call
+/// [`relm::shutdown()`](fn.shutdown.html) instead of the process dying abruptly.
+/// The default behavior of the signals is restored when this value is dropped, so it should be
+/// stored in the model.
+pub struct UnixSignals {
+    sources: Vec<SourceId>,
+}
+
+impl UnixSignals {
+    /// Send the message built by `constructor` to `stream` whenever the process receives one of
+    /// the `signals`.
+    pub fn new<F, MSG>(stream: &StreamHandle<MSG>, signals: &[UnixSignal], constructor: F) -> Self
+        where F: Fn(UnixSignal) -> MSG + Clone + 'static,
+              MSG: 'static,
+    {
+        let sources = signals.iter()
+            .map(|&signal| {
+                let stream = stream.clone();
+                let constructor = constructor.clone();
+                glib::unix_signal_add_local(signal.number(), move || {
+                    stream.emit(constructor(signal));
+                    Continue(true)
+                })
+            })
+            .collect();
+        UnixSignals {
+            sources,
+        }
+    }
+
+    /// Send the message built by `constructor` when the process receives `SIGINT`, `SIGTERM` or
+    /// `SIGHUP`, i.e. when it is asked to terminate.
+    pub fn termination<F, MSG>(stream: &StreamHandle<MSG>, constructor: F) -> Self
+        where F: Fn(UnixSignal) -> MSG + Clone + 'static,
+              MSG: 'static,
+    {
+        Self::new(stream, &[UnixSignal::Hangup, UnixSignal::Interrupt, UnixSignal::Terminate], constructor)
+    }
+}
+
+impl Drop for UnixSignals {
+    fn drop(&mut self) {
+        for source in self.sources.drain(..) {
+            glib::source_remove(source);
+        }
+    }
+}
@@ -0,0 +1,146 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Validation of the fields of a model.
+//!
+//! `#[derive(Validate)]` checks the fields having a `#[validate(...)]` attribute, with the
+//! validators `length(min = 1, max = 20)`, `range(min = 0, max = 100)`, `email` and
+//! `custom = "function"`, where `function` takes a reference to the field and returns a
+//! `Result<(), String>`. The message of the errors of a field can be replaced with
+//! `message = "..."`.
+//!
+//! Since the properties calling a method on the model are refreshed after each `update()`, the
+//! errors can be shown in the view directly:
+//!
+//! ```ignore
+//! #[derive(Validate)]
+//! pub struct Model {
+//!     #[validate(email, message = "Invalid email address")]
+//!     email: String,
+//! }
+//!
+//! view! {
+//!     gtk::Box {
+//!         gtk::Entry {
+//!             changed(entry) => EmailChanged(entry.get_text().to_string()),
+//!         },
+//!         gtk::Label {
+//!             text: &self.model.validate().error("email"),
+//!             visible: self.model.validate().has_error("email"),
+//!         },
+//!         gtk::Button {
+//!             label: "Submit",
+//!             sensitive: self.model.validate().is_valid(),
+//!         },
+//!     }
+//! }
+//! ```
+
+use std::fmt::Display;
+
+/// A model whose fields can be validated.
+/// This is implemented by `#[derive(Validate)]`.
+pub trait Validate {
+    /// Check the fields and return the errors.
+    fn validate(&self) -> ValidationErrors;
+}
+
+/// The errors found by [`Validate::validate()`](trait.Validate.html#tymethod.validate), in the
+/// order of the fields.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ValidationErrors {
+    errors: Vec<(&'static str, String)>,
+}
+
+impl ValidationErrors {
+    /// Create an empty list of errors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an error for `field`.
+    pub fn add(&mut self, field: &'static str, message: String) {
+        self.errors.push((field, message));
+    }
+
+    /// Get the message of the first error of `field`, or an empty string if it is valid.
+    pub fn error(&self, field: &str) -> String {
+        self.errors.iter()
+            .find(|&&(name, _)| name == field)
+            .map(|&(_, ref message)| message.clone())
+            .unwrap_or_default()
+    }
+
+    /// Check whether `field` has an error.
+    pub fn has_error(&self, field: &str) -> bool {
+        self.errors.iter().any(|&(name, _)| name == field)
+    }
+
+    /// Check whether all the fields are valid.
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Iterate over the fields in error and their messages.
+    pub fn iter(&self) -> impl Iterator<Item=(&'static str, &str)> {
+        self.errors.iter().map(|&(field, ref message)| (field, message.as_str()))
+    }
+}
+
+/// Check that the number of characters of `value` is between `min` and `max`.
+pub fn length<T: AsRef<str> + ?Sized>(value: &T, min: Option<usize>, max: Option<usize>) -> Result<(), String> {
+    let length = value.as_ref().chars().count();
+    match (min, max) {
+        (Some(min), _) if length < min && min == 1 => Err("This field is required".to_string()),
+        (Some(min), _) if length < min => Err(format!("Must be at least {} characters long", min)),
+        (_, Some(max)) if length > max => Err(format!("Must be at most {} characters long", max)),
+        _ => Ok(()),
+    }
+}
+
+/// Check that `value` is between `min` and `max`.
+pub fn range<T: Display + PartialOrd>(value: &T, min: Option<T>, max: Option<T>) -> Result<(), String> {
+    match (min, max) {
+        (Some(min), _) if *value < min => Err(format!("Must be at least {}", min)),
+        (_, Some(max)) if *value > max => Err(format!("Must be at most {}", max)),
+        _ => Ok(()),
+    }
+}
+
+/// Check that `value` looks like an email address.
+pub fn email<T: AsRef<str> + ?Sized>(value: &T) -> Result<(), String> {
+    let value = value.as_ref();
+    let valid =
+        match value.find('@') {
+            Some(index) => {
+                let (user, domain) = (&value[..index], &value[index + 1..]);
+                !user.is_empty() && !domain.contains('@') && !value.contains(char::is_whitespace) &&
+                    domain.find('.').map(|dot| dot > 0 && !domain.ends_with('.')).unwrap_or(false)
+            },
+            None => false,
+        };
+    if valid {
+        Ok(())
+    }
+    else {
+        Err("Invalid email address".to_string())
+    }
+}
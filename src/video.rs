@@ -0,0 +1,219 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Video player component based on GStreamer and its `gtksink` element.
+//!
+//! ```ignore
+//! view! {
+//!     gtk::Window {
+//!         #[name="video"]
+//!         relm::video::Video("file:///home/user/video.webm".to_string()) {
+//!             Eos => Finished,
+//!             VideoError(ref message) => ShowError(message.clone()),
+//!         },
+//!     }
+//! }
+//! ```
+
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer::{ClockTime, MessageView, SeekFlags, State};
+use gtk::{BoxExt, WidgetExt};
+use log::error;
+
+use crate::core::StreamHandle;
+use crate::state::{DisplayVariant, InitError, Relm, Update};
+use crate::widget::Widget;
+
+use self::VideoMsg::*;
+
+/// Messages of the [`Video`](struct.Video.html) component.
+///
+/// The first messages are commands sent to the component, the others are events sent by the
+/// component.
+#[derive(Clone, Debug)]
+pub enum VideoMsg {
+    /// Pause the video.
+    Pause,
+    /// Play the video.
+    Play,
+    /// Seek to the position, in seconds.
+    Seek(u64),
+    /// Play another video, from its URI.
+    SetUri(String),
+    /// Stop the video and release the resources used to play it.
+    Stop,
+
+    /// The end of the video was reached.
+    Eos,
+    /// The video was paused.
+    Paused,
+    /// The video started playing.
+    Playing,
+    /// An error occurred while playing the video.
+    VideoError(String),
+}
+
+impl DisplayVariant for VideoMsg {
+    fn display_variant(&self) -> &'static str {
+        match *self {
+            Pause => "Pause",
+            Play => "Play",
+            Seek(_) => "Seek",
+            SetUri(_) => "SetUri",
+            Stop => "Stop",
+            Eos => "Eos",
+            Paused => "Paused",
+            Playing => "Playing",
+            VideoError(_) => "VideoError",
+        }
+    }
+}
+
+#[doc(hidden)]
+pub struct VideoModel {
+    playbin: gst::Element,
+    video_widget: gtk::Widget,
+}
+
+/// Component playing a video, whose URI is the model parameter.
+/// The video starts paused.
+pub struct Video {
+    model: VideoModel,
+    root: gtk::Box,
+    stream: StreamHandle<VideoMsg>,
+}
+
+impl Video {
+    fn set_state(&self, state: State) {
+        if let Err(err) = self.model.playbin.set_state(state) {
+            self.stream.emit(VideoError(err.to_string()));
+        }
+    }
+}
+
+impl Update for Video {
+    type Model = VideoModel;
+    type ModelParam = String;
+    type Msg = VideoMsg;
+
+    fn model(relm: &Relm<Self>, uri: String) -> VideoModel {
+        match Self::try_model(relm, uri) {
+            Ok(model) => model,
+            Err(error) => panic!("Cannot create the video player: {}", error),
+        }
+    }
+
+    fn try_model(_: &Relm<Self>, uri: String) -> Result<VideoModel, InitError> {
+        gst::init()?;
+        let playbin = gst::ElementFactory::make("playbin", None)?;
+        let sink = gst::ElementFactory::make("gtksink", None)?;
+        let video_widget = sink.get_property("widget")?
+            .get::<gtk::Widget>()?
+            .ok_or("gtksink has no widget")?;
+        playbin.set_property("video-sink", &sink)?;
+        playbin.set_property("uri", &uri)?;
+        Ok(VideoModel {
+            playbin,
+            video_widget,
+        })
+    }
+
+    fn update(&mut self, event: VideoMsg) {
+        match event {
+            Pause => self.set_state(State::Paused),
+            Play => self.set_state(State::Playing),
+            Seek(seconds) => {
+                let flags = SeekFlags::FLUSH | SeekFlags::KEY_UNIT;
+                if let Err(err) = self.model.playbin.seek_simple(flags, ClockTime::from_seconds(seconds)) {
+                    self.stream.emit(VideoError(err.to_string()));
+                }
+            },
+            SetUri(uri) => {
+                self.set_state(State::Null);
+                if let Err(err) = self.model.playbin.set_property("uri", &uri) {
+                    self.stream.emit(VideoError(err.to_string()));
+                }
+                self.set_state(State::Paused);
+            },
+            Stop => self.set_state(State::Null),
+            // Events to be listened to by the parent.
+            Eos | Paused | Playing | VideoError(_) => (),
+        }
+    }
+}
+
+impl Widget for Video {
+    type Root = gtk::Box;
+
+    fn root(&self) -> Self::Root {
+        self.root.clone()
+    }
+
+    fn view(relm: &Relm<Self>, model: VideoModel) -> Self {
+        let root = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        root.pack_start(&model.video_widget, true, true, 0);
+        root.show_all();
+
+        if let Some(bus) = model.playbin.get_bus() {
+            let stream = relm.stream().clone();
+            let playbin = model.playbin.clone();
+            let result = bus.add_watch_local(move |_, message| {
+                match message.view() {
+                    MessageView::Eos(_) => stream.emit(Eos),
+                    MessageView::Error(err) => stream.emit(VideoError(err.get_error().to_string())),
+                    MessageView::StateChanged(state_changed) => {
+                        // Only report the state of the whole pipeline, not of its elements.
+                        if state_changed.get_src().as_ref() == Some(playbin.upcast_ref()) {
+                            match state_changed.get_current() {
+                                State::Paused => stream.emit(Paused),
+                                State::Playing => stream.emit(Playing),
+                                _ => (),
+                            }
+                        }
+                    },
+                    _ => (),
+                }
+                glib::Continue(true)
+            });
+            if let Err(err) = result {
+                error!("Cannot watch the GStreamer bus: {}", err);
+            }
+        }
+
+        let video = Video {
+            model,
+            root,
+            stream: relm.stream().clone(),
+        };
+        video.set_state(State::Paused);
+        video
+    }
+}
+
+impl Drop for Video {
+    fn drop(&mut self) {
+        if let Some(bus) = self.model.playbin.get_bus() {
+            let _ = bus.remove_watch();
+        }
+        let _ = self.model.playbin.set_state(State::Null);
+    }
+}
@@ -0,0 +1,166 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Web view component.
+//!
+//! ```ignore
+//! view! {
+//!     gtk::Window {
+//!         #[name="web_view"]
+//!         relm::webkit::WebView {
+//!             LoadUri: "https://crates.io/".to_string(),
+//!             TitleChanged(ref title) => SetTitle(title.clone()),
+//!             JsResult(ref result) => ShowResult(result.clone()),
+//!         },
+//!     }
+//! }
+//! ```
+
+use gtk::WidgetExt;
+use webkit2gtk::{LoadEvent, WebViewExt};
+
+use crate::core::StreamHandle;
+use crate::state::{DisplayVariant, Relm, Update};
+use crate::widget::Widget;
+
+use self::WebViewMsg::*;
+
+/// Messages of the [`WebView`](struct.WebView.html) component.
+///
+/// The first messages are commands sent to the component, the others are events sent by the
+/// component.
+#[derive(Clone, Debug)]
+pub enum WebViewMsg {
+    /// Run the JavaScript code. Its result is sent with a `JsResult` message.
+    EvaluateJs(String),
+    /// Go to the previous page of the history.
+    GoBack,
+    /// Go to the next page of the history.
+    GoForward,
+    /// Load the URI.
+    LoadUri(String),
+    /// Reload the current page.
+    Reload,
+    /// Stop loading the current page.
+    StopLoading,
+
+    /// Result of an `EvaluateJs` command, either the value converted to a string or the error
+    /// message.
+    JsResult(Result<String, String>),
+    /// The loading of the page changed.
+    LoadChanged(LoadEvent),
+    /// The title of the page changed.
+    TitleChanged(String),
+    /// The URI of the page changed.
+    UriChanged(String),
+}
+
+impl DisplayVariant for WebViewMsg {
+    fn display_variant(&self) -> &'static str {
+        match *self {
+            EvaluateJs(_) => "EvaluateJs",
+            GoBack => "GoBack",
+            GoForward => "GoForward",
+            LoadUri(_) => "LoadUri",
+            Reload => "Reload",
+            StopLoading => "StopLoading",
+            JsResult(_) => "JsResult",
+            LoadChanged(_) => "LoadChanged",
+            TitleChanged(_) => "TitleChanged",
+            UriChanged(_) => "UriChanged",
+        }
+    }
+}
+
+/// Component wrapping a `webkit2gtk::WebView`.
+pub struct WebView {
+    stream: StreamHandle<WebViewMsg>,
+    view: webkit2gtk::WebView,
+}
+
+impl Update for WebView {
+    type Model = ();
+    type ModelParam = ();
+    type Msg = WebViewMsg;
+
+    fn model(_: &Relm<Self>, _: ()) {
+    }
+
+    fn update(&mut self, event: WebViewMsg) {
+        match event {
+            EvaluateJs(script) => {
+                let stream = self.stream.clone();
+                self.view.run_javascript(&script, None::<&gio::Cancellable>, move |result| {
+                    let result = result
+                        .map_err(|error| error.to_string())
+                        .map(|result| {
+                            match (result.get_global_context(), result.get_value()) {
+                                (Some(context), Some(value)) => value.to_string(&context).unwrap_or_default(),
+                                _ => String::new(),
+                            }
+                        });
+                    stream.emit(JsResult(result));
+                });
+            },
+            GoBack => self.view.go_back(),
+            GoForward => self.view.go_forward(),
+            LoadUri(uri) => self.view.load_uri(&uri),
+            Reload => self.view.reload(),
+            StopLoading => self.view.stop_loading(),
+            // Events to be listened to by the parent.
+            JsResult(_) | LoadChanged(_) | TitleChanged(_) | UriChanged(_) => (),
+        }
+    }
+}
+
+impl Widget for WebView {
+    type Root = webkit2gtk::WebView;
+
+    fn root(&self) -> Self::Root {
+        self.view.clone()
+    }
+
+    fn view(relm: &Relm<Self>, _model: ()) -> Self {
+        let view = webkit2gtk::WebView::new();
+        let stream = relm.stream().clone();
+        {
+            let stream = stream.clone();
+            view.connect_load_changed(move |_, event| stream.emit(LoadChanged(event)));
+        }
+        {
+            let stream = stream.clone();
+            view.connect_property_title_notify(move |view| {
+                stream.emit(TitleChanged(view.get_title().map(|title| title.to_string()).unwrap_or_default()));
+            });
+        }
+        {
+            let stream = stream.clone();
+            view.connect_property_uri_notify(move |view| {
+                stream.emit(UriChanged(view.get_uri().map(|uri| uri.to_string()).unwrap_or_default()));
+            });
+        }
+        view.show();
+        WebView {
+            stream,
+            view,
+        }
+    }
+}
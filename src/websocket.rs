@@ -0,0 +1,261 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! WebSocket client component reconnecting automatically.
+//!
+//! ```ignore
+//! let socket = relm::execute::<WebSocket>(("wss://example.com/chat".to_string(), Backoff::new(500)));
+//! connect_stream!(socket@Message(ref frame), relm.stream(), Received(frame.clone()));
+//! socket.emit(WebSocketMsg::Send(Frame::Text("hello".to_string())));
+//! ```
+
+use std::io::ErrorKind;
+use std::net::TcpStream;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::Duration;
+
+use glib::{Continue, MainContext, PRIORITY_DEFAULT};
+use tungstenite::Message as RawMessage;
+use tungstenite::client::AutoStream;
+use tungstenite::stream::Stream;
+
+use crate::core::StreamHandle;
+use crate::retry::Backoff;
+use crate::state::{DisplayVariant, Relm, Update, UpdateNew};
+
+use self::WebSocketMsg::*;
+
+// How often the worker checks the frames to send and the close requests.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A data frame sent or received on a [`WebSocket`](struct.WebSocket.html).
+#[derive(Clone, Debug, PartialEq)]
+pub enum Frame {
+    /// A binary frame.
+    Binary(Vec<u8>),
+    /// A text frame.
+    Text(String),
+}
+
+/// Messages of the [`WebSocket`](struct.WebSocket.html) component.
+///
+/// The first messages are commands sent to the component, the others are events sent by the
+/// component.
+#[derive(Clone, Debug)]
+pub enum WebSocketMsg {
+    /// Close the connection and stop reconnecting.
+    Close,
+    /// Send a frame. The frames sent while disconnected are sent after the reconnection.
+    Send(Frame),
+
+    /// The connection was closed, by the server or because of an error, with the reason.
+    Closed(String),
+    /// The connection is established.
+    Connected,
+    /// The reconnection was abandoned after the maximum number of attempts of the backoff.
+    GaveUp,
+    /// A frame was received.
+    Message(Frame),
+    /// The connection will be attempted again after the backoff delay. This is the number of the
+    /// failed attempts.
+    Reconnecting(u32),
+}
+
+impl DisplayVariant for WebSocketMsg {
+    fn display_variant(&self) -> &'static str {
+        match *self {
+            Close => "Close",
+            Send(_) => "Send",
+            Closed(_) => "Closed",
+            Connected => "Connected",
+            GaveUp => "GaveUp",
+            Message(_) => "Message",
+            Reconnecting(_) => "Reconnecting",
+        }
+    }
+}
+
+#[doc(hidden)]
+pub struct WebSocketModel {
+    frames: mpsc::Sender<Frame>,
+    stop: Arc<AtomicBool>,
+}
+
+impl Drop for WebSocketModel {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Component maintaining a WebSocket connection to the URL of the model parameter, reconnecting
+/// with the backoff of the model parameter when the connection is lost.
+///
+/// It has no widget: create it with [`relm::execute()`](../fn.execute.html).
+/// The connection is closed when the component is dropped.
+pub struct WebSocket {
+    model: WebSocketModel,
+}
+
+impl Update for WebSocket {
+    type Model = WebSocketModel;
+    type ModelParam = (String, Backoff);
+    type Msg = WebSocketMsg;
+
+    fn model(relm: &Relm<Self>, (url, backoff): (String, Backoff)) -> WebSocketModel {
+        let (frames, frame_receiver) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let (sender, receiver) = MainContext::channel(PRIORITY_DEFAULT);
+        let stream = relm.stream().clone();
+        receiver.attach(None, move |msg| {
+            if stream.is_dropped() {
+                return Continue(false);
+            }
+            stream.emit(msg);
+            Continue(true)
+        });
+        let worker_stop = stop.clone();
+        thread::spawn(move || {
+            run(&url, backoff, &frame_receiver, &worker_stop, |msg| sender.send(msg).is_ok());
+        });
+        WebSocketModel {
+            frames,
+            stop,
+        }
+    }
+
+    fn update(&mut self, event: WebSocketMsg) {
+        match event {
+            Close => self.model.stop.store(true, Ordering::SeqCst),
+            Send(frame) => {
+                let _ = self.model.frames.send(frame);
+            },
+            Closed(_) | Connected | GaveUp | Message(_) | Reconnecting(_) => (),
+        }
+    }
+}
+
+impl UpdateNew for WebSocket {
+    fn new(_relm: &Relm<Self>, model: WebSocketModel) -> Self {
+        WebSocket {
+            model,
+        }
+    }
+}
+
+// Connect until stopped. `emit` returns false when the component is dropped.
+fn run<F>(url: &str, backoff: Backoff, frames: &Receiver<Frame>, stop: &AtomicBool, emit: F)
+    where F: Fn(WebSocketMsg) -> bool,
+{
+    let mut attempt = 0;
+    while !stop.load(Ordering::SeqCst) {
+        let reason =
+            match tungstenite::connect(url) {
+                Ok((mut socket, _)) => {
+                    attempt = 0;
+                    if let Err(error) = set_read_timeout(socket.get_mut()) {
+                        error.to_string()
+                    }
+                    else if !emit(Connected) {
+                        return;
+                    }
+                    else {
+                        session(&mut socket, frames, stop, &emit)
+                    }
+                },
+                Err(error) => error.to_string(),
+            };
+        attempt += 1;
+        if !emit(Closed(reason)) || stop.load(Ordering::SeqCst) {
+            return;
+        }
+        if backoff.gives_up(attempt) {
+            emit(GaveUp);
+            return;
+        }
+        if !emit(Reconnecting(attempt)) {
+            return;
+        }
+        let mut remaining = Duration::from_millis(backoff.delay(attempt) as u64);
+        while remaining > Duration::from_millis(0) && !stop.load(Ordering::SeqCst) {
+            let delay = remaining.min(POLL_INTERVAL);
+            thread::sleep(delay);
+            remaining -= delay;
+        }
+    }
+}
+
+// Send and receive the frames until the connection is closed, and return the reason.
+fn session<F>(socket: &mut tungstenite::WebSocket<AutoStream>, frames: &Receiver<Frame>, stop: &AtomicBool, emit: &F)
+    -> String
+    where F: Fn(WebSocketMsg) -> bool,
+{
+    loop {
+        if stop.load(Ordering::SeqCst) {
+            let _ = socket.close(None);
+            let _ = socket.write_pending();
+            return "closed by the client".to_string();
+        }
+        loop {
+            let frame =
+                match frames.try_recv() {
+                    Ok(Frame::Binary(data)) => RawMessage::Binary(data),
+                    Ok(Frame::Text(text)) => RawMessage::Text(text),
+                    Err(TryRecvError::Empty) | Err(TryRecvError::Disconnected) => break,
+                };
+            if let Err(error) = socket.write_message(frame) {
+                return error.to_string();
+            }
+        }
+        let frame =
+            match socket.read_message() {
+                Ok(RawMessage::Binary(data)) => Frame::Binary(data),
+                Ok(RawMessage::Text(text)) => Frame::Text(text),
+                Ok(RawMessage::Close(frame)) => {
+                    return frame.map(|frame| frame.reason.into_owned()).unwrap_or_default();
+                },
+                Ok(RawMessage::Ping(_)) | Ok(RawMessage::Pong(_)) => continue,
+                Err(tungstenite::Error::Io(ref error))
+                    if error.kind() == ErrorKind::WouldBlock || error.kind() == ErrorKind::TimedOut =>
+                {
+                    // Send the pongs queued by tungstenite.
+                    let _ = socket.write_pending();
+                    continue;
+                },
+                Err(error) => return error.to_string(),
+            };
+        if !emit(Message(frame)) {
+            return "component dropped".to_string();
+        }
+    }
+}
+
+// Wake up the worker regularly to send the frames.
+fn set_read_timeout(stream: &mut AutoStream) -> std::io::Result<()> {
+    let tcp_stream: &TcpStream =
+        match *stream {
+            Stream::Plain(ref stream) => stream,
+            Stream::Tls(ref stream) => stream.get_ref(),
+        };
+    tcp_stream.set_read_timeout(Some(POLL_INTERVAL))
+}
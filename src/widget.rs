@@ -19,11 +19,11 @@
  * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
  */
 
-use glib::{IsA, Object};
+use glib::{IsA, Object, PRIORITY_DEFAULT, Priority};
 use gtk;
 
-use super::{Relm, run};
-use crate::state::Update;
+use super::{Relm, RunOptions, run, run_with, try_run};
+use crate::state::{InitError, Update};
 
 /// Trait to implement to manage widget's events.
 pub trait Widget
@@ -39,6 +39,13 @@ pub trait Widget
     fn init_view(&mut self) {
     }
 
+    /// Messages to handle right after the view is created, like a message loading the initial data.
+    /// They are sent to [`update()`](trait.Update.html#tymethod.update) before returning to the
+    /// main loop, so before the window is first drawn.
+    fn init_messages(&self) -> Vec<Self::Msg> {
+        vec![]
+    }
+
     /// Method called when the widget is added to its parent.
     /// This is currently only used to set the child properties of a widget as relm widget could
     /// have child properties and we don't know its parent when it is defined. Thus, we call
@@ -54,6 +61,30 @@ pub trait Widget
         None
     }
 
+    /// Whether the messages of this component are buffered and handled right before the next frame
+    /// of its root widget, calling [`after_update()`](trait.Update.html#method.after_update) only
+    /// once after the whole batch instead of after every message.
+    /// With the `#[widget]` attribute, the properties bound to the model are then updated in
+    /// `after_update()`, so that a burst of messages only causes one relayout, like in streaming
+    /// data UIs.
+    fn frame_synchronized() -> bool {
+        false
+    }
+
+    /// The priority of the processing of the messages of this component in the main loop.
+    /// A component receiving many messages, like a log viewer, can use a low priority, e.g.
+    /// `glib::PRIORITY_LOW`, so that it does not delay the components handling the user input.
+    fn priority() -> Priority {
+        PRIORITY_DEFAULT
+    }
+
+    /// Create the message sent to this component by [`relm::shutdown()`](fn.shutdown.html), to let
+    /// it save its state before the main loop stops.
+    /// Components returning `None`, the default, are not notified.
+    fn shutdown_msg() -> Option<Self::Msg> {
+        None
+    }
+
     // TODO: ajouter une méthode param() pour déterminer des paramètres qui seront pris en compte à
     // l’ajout du widget.
 
@@ -67,8 +98,30 @@ pub trait Widget
         run::<Self>(model_param)
     }
 
+    /// Create the window from this widget and start the main loop, returning the initialization
+    /// error if any.
+    fn try_run(model_param: Self::ModelParam) -> Result<(), InitError>
+        where Self: 'static,
+    {
+        try_run::<Self>(model_param)
+    }
+
+    /// Create the window from this widget with custom startup options and start the main loop.
+    fn run_with(options: RunOptions<Self>, model_param: Self::ModelParam) -> Result<(), InitError>
+        where Self: 'static,
+    {
+        run_with::<Self>(options, model_param)
+    }
+
     /// Create the initial view.
     fn view(relm: &Relm<Self>, model: Self::Model) -> Self;
+
+    /// Create the initial view, or return an error if it cannot be created.
+    /// The default implementation calls [`view()`](trait.Widget.html#tymethod.view).
+    /// This error is returned by the `try_*` functions like [`try_init()`](fn.try_init.html).
+    fn try_view(relm: &Relm<Self>, model: Self::Model) -> Result<Self, InitError> {
+        Ok(Self::view(relm, model))
+    }
 }
 
 /// Trait implemented by the generator to ease the creation of tests of relm widgets using the
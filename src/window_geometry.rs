@@ -0,0 +1,158 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::cell::RefCell;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use gdk::{Rectangle, WindowState};
+use glib::{Cast, IsA, KeyFile, KeyFileFlags};
+use gtk::{GtkWindowExt, Inhibit, WidgetExt};
+
+use crate::xdg::state_dir;
+
+const FILE_NAME: &str = "window-geometry.ini";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Geometry {
+    height: i32,
+    maximized: bool,
+    width: i32,
+    x: i32,
+    y: i32,
+}
+
+impl Geometry {
+    fn load(name: &str) -> Option<Self> {
+        let key_file = KeyFile::new();
+        key_file.load_from_file(geometry_path(), KeyFileFlags::NONE).ok()?;
+        Some(Geometry {
+            height: key_file.get_integer(name, "height").ok()?,
+            maximized: key_file.get_boolean(name, "maximized").unwrap_or(false),
+            width: key_file.get_integer(name, "width").ok()?,
+            x: key_file.get_integer(name, "x").ok()?,
+            y: key_file.get_integer(name, "y").ok()?,
+        })
+    }
+
+    fn save(&self, name: &str) -> io::Result<()> {
+        let path = geometry_path();
+        let key_file = KeyFile::new();
+        // Keep the geometry of the other windows.
+        let _ = key_file.load_from_file(&path, KeyFileFlags::KEEP_COMMENTS);
+        key_file.set_integer(name, "height", self.height);
+        key_file.set_boolean(name, "maximized", self.maximized);
+        key_file.set_integer(name, "width", self.width);
+        key_file.set_integer(name, "x", self.x);
+        key_file.set_integer(name, "y", self.y);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, key_file.to_data().as_str())
+    }
+}
+
+fn geometry_path() -> PathBuf {
+    state_dir().join(FILE_NAME)
+}
+
+/// Restore the size, position and maximized state of `window` saved under `name`, and save them
+/// when the window is closed.
+///
+/// The geometries are saved in the `window-geometry.ini` file of the state directory of the
+/// application.
+/// When the window would not be visible on any of the current monitors, e.g. when it was on a
+/// monitor which is now disconnected, only its size is restored, reduced to fit in the primary
+/// monitor, and the window manager chooses its position.
+///
+/// This is what `#[widget(persist_geometry = "name")]` calls after the view is created.
+pub fn persist_geometry<WINDOW: IsA<gtk::Window>>(window: &WINDOW, name: &str) {
+    let window: gtk::Window = window.clone().upcast();
+    let geometry = Geometry::load(name);
+    if let Some(geometry) = geometry {
+        restore(&window, &geometry);
+    }
+    let current = Rc::new(RefCell::new(geometry.unwrap_or_default()));
+
+    {
+        let current = current.clone();
+        let _ = window.connect_configure_event(move |window, _| {
+            let mut current = current.borrow_mut();
+            // Keep the size before the maximization to restore it when unmaximizing.
+            if !current.maximized {
+                let (width, height) = window.get_size();
+                let (x, y) = window.get_position();
+                *current = Geometry { height, maximized: false, width, x, y };
+            }
+            false
+        });
+    }
+    {
+        let current = current.clone();
+        let _ = window.connect_window_state_event(move |_, event| {
+            current.borrow_mut().maximized = event.get_new_window_state().contains(WindowState::MAXIMIZED);
+            Inhibit(false)
+        });
+    }
+    let name = name.to_string();
+    let _ = window.connect_delete_event(move |_, _| {
+        if let Err(error) = current.borrow().save(&name) {
+            log::error!("Cannot save the geometry of window {}: {}", name, error);
+        }
+        Inhibit(false)
+    });
+}
+
+fn restore(window: &gtk::Window, geometry: &Geometry) {
+    let mut width = geometry.width;
+    let mut height = geometry.height;
+    let title_bar = Rectangle { x: geometry.x, y: geometry.y, width, height: 50.min(height) };
+    match window.get_screen() {
+        Some(screen) => {
+            let monitors: Vec<_> = (0..screen.get_n_monitors())
+                .map(|monitor| screen.get_monitor_geometry(monitor))
+                .collect();
+            // The title bar must be visible so that the user can move the window.
+            if monitors.iter().any(|monitor| intersects(monitor, &title_bar)) {
+                window.move_(geometry.x, geometry.y);
+            }
+            else {
+                let primary = screen.get_monitor_geometry(screen.get_primary_monitor());
+                width = width.min(primary.width);
+                height = height.min(primary.height);
+            }
+        },
+        None => window.move_(geometry.x, geometry.y),
+    }
+    if width > 0 && height > 0 {
+        window.resize(width, height);
+    }
+    if geometry.maximized {
+        window.maximize();
+    }
+}
+
+fn intersects(rectangle1: &Rectangle, rectangle2: &Rectangle) -> bool {
+    rectangle1.x < rectangle2.x + rectangle2.width && rectangle2.x < rectangle1.x + rectangle1.width &&
+        rectangle1.y < rectangle2.y + rectangle2.height && rectangle2.y < rectangle1.y + rectangle1.height
+}
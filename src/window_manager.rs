@@ -0,0 +1,181 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::rc::{Rc, Weak};
+
+use glib::{Cast, IsA};
+use gtk::{GtkWindowExt, Inhibit, WidgetExt};
+
+use crate::component::Component;
+use crate::core::StreamHandle;
+use crate::state::{DisplayVariant, InitError};
+use crate::widget::Widget;
+use crate::try_init;
+
+struct Entry {
+    component: Box<dyn Any>,
+    window: gtk::Window,
+}
+
+type Windows<KEY> = Rc<RefCell<HashMap<KEY, Entry>>>;
+
+/// Owner of the top-level window components of a multi-window application, identified by a key.
+///
+/// When the user closes a window, its component is dropped and the message created by the
+/// `closed` constructor given to [`new()`](#method.new) is sent to the owner.
+/// The windows still open are closed when the manager is dropped.
+pub struct WindowManager<KEY, MSG> {
+    closed: Rc<dyn Fn(KEY) -> MSG>,
+    stream: StreamHandle<MSG>,
+    windows: Windows<KEY>,
+}
+
+impl<KEY, MSG> WindowManager<KEY, MSG>
+    where KEY: Clone + Eq + Hash + 'static,
+          MSG: 'static,
+{
+    /// Create a window manager sending the message created by `closed` to `stream` when the user
+    /// closes a window.
+    pub fn new<F>(stream: &StreamHandle<MSG>, closed: F) -> Self
+        where F: Fn(KEY) -> MSG + 'static,
+    {
+        WindowManager {
+            closed: Rc::new(closed),
+            stream: stream.clone(),
+            windows: Rc::new(RefCell::new(HashMap::new())),
+        }
+    }
+
+    /// Open a window of type `WIDGET` identified by `key` and show it.
+    /// If a window with this key is already open, it is presented instead.
+    pub fn open<WIDGET>(&mut self, key: KEY, model_param: WIDGET::ModelParam) -> Result<(), InitError>
+        where WIDGET: Widget + 'static,
+              WIDGET::Msg: DisplayVariant + 'static,
+              WIDGET::Root: IsA<gtk::Window>,
+    {
+        if self.present(&key) {
+            return Ok(());
+        }
+        let component = try_init::<WIDGET>(model_param)?;
+        let window: gtk::Window = component.widget().clone().upcast();
+        let windows = Rc::downgrade(&self.windows);
+        let closed = self.closed.clone();
+        let stream = self.stream.clone();
+        let closed_key = key.clone();
+        window.connect_delete_event(move |_, _| {
+            user_closed(&windows, &closed_key, &stream, &*closed);
+            Inhibit(false)
+        });
+        window.show_all();
+        self.windows.borrow_mut().insert(key, Entry {
+            component: Box::new(component),
+            window,
+        });
+        Ok(())
+    }
+
+    /// Close the window identified by `key` without sending the `closed` message.
+    /// Returns `false` if there's no such window.
+    pub fn close(&mut self, key: &KEY) -> bool {
+        let entry = self.windows.borrow_mut().remove(key);
+        match entry {
+            Some(entry) => {
+                entry.window.close();
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Bring the window identified by `key` to the front.
+    /// Returns `false` if there's no such window.
+    pub fn present(&self, key: &KEY) -> bool {
+        match self.windows.borrow().get(key) {
+            Some(entry) => {
+                entry.window.present();
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Send a message to the component of the window identified by `key`.
+    /// Returns `false` if there's no such window or if it is not of type `WIDGET`.
+    pub fn emit<WIDGET>(&self, key: &KEY, msg: WIDGET::Msg) -> bool
+        where WIDGET: Widget + 'static,
+    {
+        let windows = self.windows.borrow();
+        match windows.get(key).and_then(|entry| entry.component.downcast_ref::<Component<WIDGET>>()) {
+            Some(component) => {
+                component.emit(msg);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Check if the window identified by `key` is open.
+    pub fn contains(&self, key: &KEY) -> bool {
+        self.windows.borrow().contains_key(key)
+    }
+
+    /// Get the keys of the open windows.
+    pub fn keys(&self) -> Vec<KEY> {
+        self.windows.borrow().keys().cloned().collect()
+    }
+
+    /// Get the number of open windows.
+    pub fn len(&self) -> usize {
+        self.windows.borrow().len()
+    }
+
+    /// Check if there's no open window.
+    pub fn is_empty(&self) -> bool {
+        self.windows.borrow().is_empty()
+    }
+}
+
+impl<KEY, MSG> Drop for WindowManager<KEY, MSG> {
+    fn drop(&mut self) {
+        let windows: Vec<_> = self.windows.borrow_mut().drain().map(|(_, entry)| entry).collect();
+        for entry in windows {
+            entry.window.close();
+        }
+    }
+}
+
+fn user_closed<KEY, MSG>(windows: &Weak<RefCell<HashMap<KEY, Entry>>>, key: &KEY, stream: &StreamHandle<MSG>,
+    closed: &dyn Fn(KEY) -> MSG)
+    where KEY: Clone + Eq + Hash,
+{
+    if let Some(windows) = windows.upgrade() {
+        // Drop the component after releasing the borrow since its drop could reenter the manager.
+        let entry = windows.borrow_mut().remove(key);
+        if entry.is_some() {
+            stream.emit(closed(key.clone()));
+        }
+        drop(entry);
+    }
+}
@@ -0,0 +1,334 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+//! Assistant whose steps are relm components.
+//!
+//! Each step implements [`WizardStep`](trait.WizardStep.html) to store the data of its messages
+//! in the result of the wizard and to tell whether it is complete, which enables the Next and
+//! Finish buttons. The result is sent to the parent in the `Finished` message.
+//!
+//! ```ignore
+//! impl WizardStep<Signup> for NameStep {
+//!     fn step_update(msg: &NameMsg, signup: &mut Signup) -> Option<bool> {
+//!         match *msg {
+//!             NameChanged(ref name) => {
+//!                 signup.name = name.clone();
+//!                 Some(!name.is_empty())
+//!             },
+//!         }
+//!     }
+//! }
+//!
+//! let wizard = relm::create_component::<Wizard<Signup>>(Signup::default());
+//! wizard.add_step::<NameStep>("Name", ());
+//! wizard.add_step::<EmailStep>("Email", ());
+//! connect!(wizard@Finished(ref signup), relm, SignedUp(signup.clone()));
+//! ```
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use glib::Cast;
+use gtk::{
+    BoxExt,
+    ButtonExt,
+    ContainerExt,
+    LabelExt,
+    StackExt,
+    WidgetExt,
+};
+
+use crate::component::Component;
+use crate::core::StreamHandle;
+use crate::state::{DisplayVariant, Relm, Update};
+use crate::widget::Widget;
+
+use self::WizardMsg::*;
+
+/// A step of a [`Wizard`](struct.Wizard.html) producing a `RESULT`.
+pub trait WizardStep<RESULT>: Widget {
+    /// Whether the step is complete before it emits any message.
+    fn initially_complete() -> bool {
+        true
+    }
+
+    /// Store the data of `msg`, emitted by the step, in `result`.
+    /// Return whether the step is complete after this message, or `None` if it did not change.
+    fn step_update(msg: &Self::Msg, result: &mut RESULT) -> Option<bool>;
+}
+
+#[doc(hidden)]
+pub trait AnyStep<RESULT> {
+    fn connect(&self, index: usize, result: Rc<RefCell<RESULT>>, stream: StreamHandle<WizardMsg<RESULT>>);
+    fn initially_complete(&self) -> bool;
+    fn title(&self) -> &str;
+    fn widget(&self) -> gtk::Widget;
+}
+
+struct StepComponent<STEP: Widget> {
+    component: Component<STEP>,
+    title: String,
+}
+
+impl<RESULT: 'static, STEP: WizardStep<RESULT>> AnyStep<RESULT> for StepComponent<STEP>
+    where STEP::Msg: 'static,
+{
+    fn connect(&self, index: usize, result: Rc<RefCell<RESULT>>, stream: StreamHandle<WizardMsg<RESULT>>) {
+        self.component.stream().observe(move |msg| {
+            let complete = STEP::step_update(msg, &mut result.borrow_mut());
+            if let Some(complete) = complete {
+                stream.emit(StepCompleted(index, complete));
+            }
+        });
+    }
+
+    fn initially_complete(&self) -> bool {
+        STEP::initially_complete()
+    }
+
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn widget(&self) -> gtk::Widget {
+        self.component.widget().clone().upcast()
+    }
+}
+
+/// Messages of the [`Wizard`](struct.Wizard.html) component.
+///
+/// The first messages are commands sent to the component, the others are events sent by the
+/// component.
+pub enum WizardMsg<RESULT> {
+    #[doc(hidden)]
+    AddStep(Box<dyn AnyStep<RESULT>>),
+    /// Go to the previous step.
+    Back,
+    /// Cancel the wizard.
+    Cancel,
+    /// Finish the wizard, if the last step is complete.
+    Finish,
+    /// Go to the next step, if the current step is complete.
+    Next,
+    #[doc(hidden)]
+    StepCompleted(usize, bool),
+
+    /// The wizard was cancelled.
+    Cancelled,
+    /// The wizard was finished, with the data collected by the steps.
+    Finished(RESULT),
+    /// The step at this index is now shown.
+    StepChanged(usize),
+}
+
+impl<RESULT> DisplayVariant for WizardMsg<RESULT> {
+    fn display_variant(&self) -> &'static str {
+        match *self {
+            AddStep(_) => "AddStep",
+            Back => "Back",
+            Cancel => "Cancel",
+            Finish => "Finish",
+            Next => "Next",
+            StepCompleted(_, _) => "StepCompleted",
+            Cancelled => "Cancelled",
+            Finished(_) => "Finished",
+            StepChanged(_) => "StepChanged",
+        }
+    }
+}
+
+#[doc(hidden)]
+pub struct WizardModel<RESULT> {
+    completed: Vec<bool>,
+    current: usize,
+    result: Rc<RefCell<RESULT>>,
+    steps: Vec<Box<dyn AnyStep<RESULT>>>,
+}
+
+/// Container component showing its steps one at a time, with Back, Next, Finish and Cancel
+/// buttons.
+/// The model parameter is the initial result, which is updated by the steps.
+pub struct Wizard<RESULT: Clone + 'static> {
+    back_button: gtk::Button,
+    finish_button: gtk::Button,
+    model: WizardModel<RESULT>,
+    next_button: gtk::Button,
+    root: gtk::Box,
+    stack: gtk::Stack,
+    stream: StreamHandle<WizardMsg<RESULT>>,
+    title: gtk::Label,
+}
+
+impl<RESULT: Clone + 'static> Component<Wizard<RESULT>> {
+    /// Create the step component `STEP` and add it after the other steps, with `title` shown
+    /// above it.
+    pub fn add_step<STEP>(&self, title: &str, model_param: STEP::ModelParam)
+        where STEP: WizardStep<RESULT> + 'static,
+              STEP::Msg: DisplayVariant + 'static,
+    {
+        let component = crate::create_component::<STEP>(model_param);
+        self.emit(AddStep(Box::new(StepComponent {
+            component,
+            title: title.to_string(),
+        })));
+    }
+}
+
+impl<RESULT: Clone + 'static> Wizard<RESULT> {
+    fn can_leave_step(&self) -> bool {
+        self.model.completed.get(self.model.current).cloned().unwrap_or(false)
+    }
+
+    fn is_last_step(&self) -> bool {
+        self.model.current + 1 >= self.model.steps.len()
+    }
+
+    fn show_step(&mut self, index: usize) {
+        self.model.current = index;
+        if let Some(step) = self.model.steps.get(index) {
+            self.stack.set_visible_child(&step.widget());
+            self.title.set_text(&format!("Step {} of {}: {}", index + 1, self.model.steps.len(), step.title()));
+        }
+        self.refresh_buttons();
+    }
+
+    fn refresh_buttons(&self) {
+        let is_last_step = self.is_last_step();
+        self.back_button.set_sensitive(self.model.current > 0);
+        self.next_button.set_visible(!is_last_step);
+        self.next_button.set_sensitive(!is_last_step && self.can_leave_step());
+        self.finish_button.set_visible(is_last_step);
+        self.finish_button.set_sensitive(is_last_step && self.can_leave_step());
+    }
+}
+
+impl<RESULT: Clone + 'static> Update for Wizard<RESULT> {
+    type Model = WizardModel<RESULT>;
+    type ModelParam = RESULT;
+    type Msg = WizardMsg<RESULT>;
+
+    fn model(_: &Relm<Self>, result: RESULT) -> WizardModel<RESULT> {
+        WizardModel {
+            completed: vec![],
+            current: 0,
+            result: Rc::new(RefCell::new(result)),
+            steps: vec![],
+        }
+    }
+
+    fn update(&mut self, event: WizardMsg<RESULT>) {
+        match event {
+            AddStep(step) => {
+                let index = self.model.steps.len();
+                step.connect(index, self.model.result.clone(), self.stream.clone());
+                let widget = step.widget();
+                widget.show();
+                self.stack.add(&widget);
+                self.model.completed.push(step.initially_complete());
+                self.model.steps.push(step);
+                let current = self.model.current;
+                self.show_step(current);
+            },
+            Back => {
+                if self.model.current > 0 {
+                    let previous = self.model.current - 1;
+                    self.show_step(previous);
+                    self.stream.emit(StepChanged(previous));
+                }
+            },
+            Cancel => self.stream.emit(Cancelled),
+            Finish => {
+                if self.is_last_step() && self.can_leave_step() {
+                    let result = self.model.result.borrow().clone();
+                    self.stream.emit(Finished(result));
+                }
+            },
+            Next => {
+                if !self.is_last_step() && self.can_leave_step() {
+                    let next = self.model.current + 1;
+                    self.show_step(next);
+                    self.stream.emit(StepChanged(next));
+                }
+            },
+            StepCompleted(index, complete) => {
+                if let Some(completed) = self.model.completed.get_mut(index) {
+                    *completed = complete;
+                }
+                self.refresh_buttons();
+            },
+            // Events to be listened to by the parent.
+            Cancelled | Finished(_) | StepChanged(_) => (),
+        }
+    }
+}
+
+impl<RESULT: Clone + 'static> Widget for Wizard<RESULT> {
+    type Root = gtk::Box;
+
+    fn root(&self) -> Self::Root {
+        self.root.clone()
+    }
+
+    fn view(relm: &Relm<Self>, model: WizardModel<RESULT>) -> Self {
+        let root = gtk::Box::new(gtk::Orientation::Vertical, 6);
+        let title = gtk::Label::new(None);
+        title.set_halign(gtk::Align::Start);
+        root.pack_start(&title, false, false, 0);
+
+        let stack = gtk::Stack::new();
+        stack.set_transition_type(gtk::StackTransitionType::SlideLeftRight);
+        root.pack_start(&stack, true, true, 0);
+
+        let buttons = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+        buttons.set_halign(gtk::Align::End);
+        let cancel_button = gtk::Button::with_label("Cancel");
+        let back_button = gtk::Button::with_label("Back");
+        let next_button = gtk::Button::with_label("Next");
+        let finish_button = gtk::Button::with_label("Finish");
+        connect_button(&buttons, &cancel_button, relm.stream(), || Cancel);
+        connect_button(&buttons, &back_button, relm.stream(), || Back);
+        connect_button(&buttons, &next_button, relm.stream(), || Next);
+        connect_button(&buttons, &finish_button, relm.stream(), || Finish);
+        root.pack_start(&buttons, false, false, 0);
+        root.show_all();
+
+        let wizard = Wizard {
+            back_button,
+            finish_button,
+            model,
+            next_button,
+            root,
+            stack,
+            stream: relm.stream().clone(),
+            title,
+        };
+        wizard.refresh_buttons();
+        wizard
+    }
+}
+
+fn connect_button<RESULT: 'static>(buttons: &gtk::Box, button: &gtk::Button, stream: &StreamHandle<WizardMsg<RESULT>>,
+    msg: fn() -> WizardMsg<RESULT>)
+{
+    buttons.pack_start(button, false, false, 0);
+    let stream = stream.clone();
+    button.connect_clicked(move |_| stream.emit(msg()));
+}
@@ -0,0 +1,51 @@
+/*
+ * Copyright (c) 2020 Boucher, Antoni <bouanto@zoho.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of
+ * this software and associated documentation files (the "Software"), to deal in
+ * the Software without restriction, including without limitation the rights to
+ * use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+ * the Software, and to permit persons to whom the Software is furnished to do so,
+ * subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+ * FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+ * COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+ * IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+ * CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ */
+
+use std::cell::RefCell;
+use std::env;
+use std::path::{Path, PathBuf};
+
+thread_local! {
+    static STATE_DIR: RefCell<Option<PathBuf>> = RefCell::new(None);
+}
+
+/// Use `dir` instead of the XDG state directory of the application, e.g. in tests.
+pub fn set_state_dir<P: AsRef<Path>>(dir: P) {
+    STATE_DIR.with(|state_dir| *state_dir.borrow_mut() = Some(dir.as_ref().to_path_buf()));
+}
+
+/// Get the directory where the state of the application, like its models and the geometry of
+/// its windows, is saved.
+pub fn state_dir() -> PathBuf {
+    if let Some(dir) = STATE_DIR.with(|state_dir| state_dir.borrow().clone()) {
+        return dir;
+    }
+    let base_dir = env::var_os("XDG_STATE_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .unwrap_or_else(|| glib::get_home_dir()
+            .unwrap_or_else(env::temp_dir)
+            .join(".local/state"));
+    let app_name = glib::get_prgname()
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| "relm".to_string());
+    base_dir.join(app_name)
+}